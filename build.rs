@@ -1,8 +1,11 @@
 #![doc = " Build script para trae-cli"]
 #![doc = " Copia automáticamente el binario a bin/ después de compilar en release"]
+#![doc = " y expone hash de git, fecha de build, rustc y features (`TRAE_GIT_HASH`, `TRAE_BUILD_DATE`, ...)"]
 use std::env;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 #[doc = "Function documentation added by AI refactor"]
 fn main() {
     #[cfg(target_os = "windows")]
@@ -33,5 +36,67 @@ fn main() {
             println!("cargo:warning=✅ trae.exe copiado a bin/");
         }
     }
+    emit_build_info();
     println!("cargo:rerun-if-changed=src/main.rs");
 }
+/// Short commit hash ("unknown" outside a git checkout) and UTC build date
+/// (`SOURCE_DATE_EPOCH` when set, for reproducible builds).
+fn emit_build_info() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short=7", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    let epoch = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs())
+        });
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    // CARGO_FEATURE_<NAME> is set for each enabled feature (uppercased, '-' -> '_')
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|f| f.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=TRAE_GIT_HASH={hash}");
+    println!("cargo:rustc-env=TRAE_RUSTC_VERSION={rustc_version}");
+    println!("cargo:rustc-env=TRAE_FEATURES={}", features.join(","));
+    println!("cargo:rustc-env=TRAE_BUILD_DATE={}", civil_date(epoch / 86_400));
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Re-run on new commits: HEAD moves on checkout, the branch ref on commit
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Ok(head) = fs::read_to_string(".git/HEAD") {
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{reference}");
+        }
+    }
+}
+/// `YYYY-MM-DD` for a count of days since 1970-01-01 (Howard Hinnant's
+/// civil_from_days).
+fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
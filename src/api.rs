@@ -1,6 +1,9 @@
-use anyhow::Result;
+use crate::error::{Result, TraeError};
 
 /// API-friendly thin wrappers for common TRAE operations.
+///
+/// Failures are reported as [`TraeError`] so callers can tell a missing
+/// `cargo`, a JARVIXSERVER problem or a failed quality gate apart.
 pub async fn analyze(
     performance: bool,
     security: bool,
@@ -20,16 +23,106 @@ pub async fn analyze(
         output,
     )
     .await
+    .map_err(TraeError::from)
 }
 
+/// [`analyze`] that an embedding application can stop; returns
+/// [`TraeError::Cancelled`] once `cancel` fires.
+#[allow(clippy::too_many_arguments)]
+pub async fn analyze_cancellable(
+    performance: bool,
+    security: bool,
+    quality: bool,
+    no_jarvix: bool,
+    profile: Option<String>,
+    force_refresh: bool,
+    output: Option<String>,
+    cancel: crate::CancellationToken,
+) -> Result<()> {
+    crate::commands::analyze::AnalyzeCommand::run_simple_cancellable(
+        performance,
+        security,
+        quality,
+        no_jarvix,
+        profile,
+        force_refresh,
+        output,
+        false,
+        None,
+        None,
+        Some(cancel),
+        &crate::config::RunOptions::from_env(),
+    )
+    .await
+    .map_err(TraeError::from)
+}
+
+/// Set [`RepairOptions::cancel`](crate::commands::repair::RepairOptions::cancel)
+/// to be able to stop a running repair.
 pub async fn repair(opts: crate::commands::repair::RepairOptions) -> Result<()> {
-    crate::commands::repair::RepairCommand::run_simple(opts).await
+    crate::commands::repair::RepairCommand::run_simple(opts).await.map_err(TraeError::from)
+}
+
+/// [`repair`] that reports [`ProgressEvent`](crate::events::ProgressEvent)s
+/// (steps, cargo output lines, final metrics) to `on_event` instead of
+/// printing, so the embedder can render its own UI.
+pub async fn repair_with_progress(
+    mut opts: crate::commands::repair::RepairOptions,
+    on_event: impl Fn(crate::events::ProgressEvent) + Send + Sync + 'static,
+) -> Result<()> {
+    opts.progress = Some(crate::events::ProgressSink::new(on_event));
+    repair(opts).await
+}
+
+/// Line, function, struct, trait and test counts of the project at `path`,
+/// without the symbol extraction of a full crawl. The numbers match
+/// `advanced_project_crawler(path, None, &RunOptions::default()).metrics`.
+pub fn project_metrics(path: &std::path::Path) -> crate::core::crawler::ProjectMetrics {
+    crate::core::crawler::project_metrics(path, None, &crate::config::RunOptions::from_env())
 }
 
 pub async fn test_cmd(release: bool, coverage: bool, bench: bool, test: Option<String>, package: Option<String>, verbose: bool, no_jarvix: bool) -> Result<()> {
-    crate::commands::test::TestCommand::run_simple(release, coverage, bench, test, package, verbose, no_jarvix).await
+    crate::commands::test::TestCommand::run_simple(release, coverage, bench, test, package, verbose, no_jarvix).await.map_err(TraeError::from)
 }
 
 pub async fn cargo_run(command: &str, args: &[String], interactive: bool, verbose: bool, no_jarvix: bool) -> Result<()> {
-    crate::commands::cargo::CargoCommand::run_simple(command, args, interactive, verbose, no_jarvix).await
+    crate::commands::cargo::CargoCommand::run_simple(command, args, interactive, verbose, no_jarvix).await.map_err(TraeError::from)
+}
+
+/// Runs `cargo command args` and returns its exit code and combined output
+/// instead of only streaming it, so the caller can inspect what cargo said.
+/// A cargo that fails is still `Ok`: check [`CargoOutput::success`]. The
+/// output has no color codes; `echo` also prints each line as it arrives.
+///
+/// [`CargoOutput::success`]: crate::core::cargo::CargoOutput::success
+pub async fn cargo_run_capture(
+    command: &str,
+    args: &[String],
+    echo: bool,
+) -> Result<crate::core::cargo::CargoOutput> {
+    crate::commands::cargo::CargoCommand::run_simple_capture(command, args, echo)
+        .await
+        .map_err(TraeError::from)
+}
+
+/// [`cargo_run`] offloading `build`/`test` to JARVIXSERVER with the given
+/// timeout and poll intervals, for remotes slower than the `[jarvix]` ones.
+/// Falls back to local cargo like `--remote` does.
+pub async fn cargo_run_offloaded(
+    command: &str,
+    args: &[String],
+    interactive: bool,
+    verbose: bool,
+    offload: crate::commands::cargo::RemoteOffload,
+) -> Result<()> {
+    crate::commands::cargo::CargoCommand::run_simple_offloaded(
+        command,
+        args,
+        interactive,
+        verbose,
+        false,
+        Some(offload),
+    )
+    .await
+    .map_err(TraeError::from)
 }
@@ -1,15 +1,35 @@
 //! HTTP Server for TRAE CLI
 //! Expone comandos de trae-cli como REST API integrado con JARVIXSERVER
 
-use axum::{extract::{Json, State}, http::StatusCode, response::IntoResponse, routing::{get, post}, Router};
+use axum::{extract::{Json, State}, http::{HeaderValue, StatusCode}, middleware, response::{IntoResponse, Response}, routing::{get, post}, Router};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tower_http::cors::CorsLayer;
+use trae_cli::build_info::LONG_VERSION;
+use trae_cli::core::cargo::cargo_program;
+use trae_cli::core::score::{duplication_factor, ScoreBreakdown, ScoreWeights};
+use trae_cli::jarvix::client::VERSION_HEADER;
+use trae_cli::utils::path::portable;
+
+/// Env var holding the `/api/analyze` time budget in seconds.
+const ANALYZE_BUDGET_ENV: &str = "TRAE_ANALYZE_TIMEOUT_SECS";
+const DEFAULT_ANALYZE_BUDGET: Duration = Duration::from_secs(60);
 
 /// Struct documentation added by AI refactor
 #[derive(Clone)]
 struct AppState {
     jarvix_url: String,
+    /// Maximum wall time for `/api/analyze` before answering 503.
+    analyze_budget: Duration,
+    /// Quality score coefficients, the `[score]` section of the config at startup.
+    score_weights: ScoreWeights,
 }
 
 /// Struct documentation added by AI refactor
@@ -81,6 +101,8 @@ struct AnalyzeResponse {
     rust_files: usize,
     issues: Vec<Issue>,
     quality_score: f64,
+    /// Penalties and bonus `quality_score` is made of.
+    score_breakdown: ScoreBreakdown,
 }
 
 /// Struct documentation added by AI refactor
@@ -132,7 +154,7 @@ async fn health_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse
     let jarvix_connected = check_jarvix_connection(&state.jarvix_url).await;
     let response = HealthResponse {
         status: "healthy".to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
+        version: LONG_VERSION.to_string(),
         uptime_seconds: 0,
         jarvix_connected,
     };
@@ -143,29 +165,27 @@ async fn health_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse
 async fn build_handler(Json(req): Json<BuildRequest>) -> impl IntoResponse {
     println!("🔨 Build request: release={}, features={:?}", req.release, req.features);
     let start = std::time::Instant::now();
-    let mut cmd = std::process::Command::new("cargo");
-    cmd.arg("build");
+    let mut args = Vec::new();
     if req.release {
-        cmd.arg("--release");
+        args.push("--release".to_string());
     }
     if !req.features.is_empty() {
-        cmd.arg("--features").arg(req.features.join(","));
+        args.push("--features".to_string());
+        args.push(req.features.join(","));
     }
     if let Some(target) = req.target {
-        cmd.arg("--target").arg(target);
+        args.push("--target".to_string());
+        args.push(target);
     }
-    match cmd.output() {
-        Ok(output) => {
+    match trae_cli::cargo_run_capture("build", &args, false).await {
+        Ok(run) => {
             let duration = start.elapsed().as_millis() as u64;
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let full_output = format!("{}\n{}", stdout, stderr);
-            let warnings = full_output.matches("warning:").count();
-            let errors = full_output.matches("error:").count();
+            let warnings = run.output.matches("warning:").count();
+            let errors = run.output.matches("error:").count();
             let response = BuildResponse {
-                success: output.status.success(),
+                success: run.success(),
                 duration_ms: duration,
-                output: full_output,
+                output: run.output,
                 warnings,
                 errors,
             };
@@ -180,16 +200,43 @@ async fn build_handler(Json(req): Json<BuildRequest>) -> impl IntoResponse {
 }
 
 /// Function documentation added by AI refactor
-async fn analyze_handler(Json(req): Json<AnalyzeRequest>) -> impl IntoResponse {
+async fn analyze_handler(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<AnalyzeRequest>,
+) -> impl IntoResponse {
     println!("🔍 Analyze request: path={:?}, depth={}", req.path, req.depth);
     let path = req.path.unwrap_or_else(|| ".".to_string());
-    match analyze_project_advanced(&path) {
-        Ok(analysis) => Json(ApiResponse::success(analysis)).into_response(),
-        Err(e) => (
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = {
+        let cancelled = Arc::clone(&cancelled);
+        let weights = state.score_weights.clone();
+        tokio::task::spawn_blocking(move || analyze_project_advanced(&path, &weights, &cancelled))
+    };
+    match tokio::time::timeout(state.analyze_budget, task).await {
+        Ok(Ok(Ok(analysis))) => Json(ApiResponse::success(analysis)).into_response(),
+        Ok(Ok(Err(e))) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(error_response(format!("Analysis failed: {}", e))),
         )
             .into_response(),
+        Ok(Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(error_response(format!("Analysis task panicked: {}", e))),
+        )
+            .into_response(),
+        Err(_) => {
+            // The blocking task cannot be aborted; tell it to stop picking up files.
+            cancelled.store(true, Ordering::Relaxed);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(error_response(format!(
+                    "Analysis exceeded the time budget of {}s; narrow the path or raise {}",
+                    state.analyze_budget.as_secs(),
+                    ANALYZE_BUDGET_ENV
+                ))),
+            )
+                .into_response()
+        }
     }
 }
 
@@ -230,17 +277,25 @@ async fn metrics_handler() -> impl IntoResponse {
 async fn status_handler() -> impl IntoResponse {
     Json(serde_json::json!({
         "service": "trae-cli",
-        "version": env!("CARGO_PKG_VERSION"),
+        "version": LONG_VERSION,
+        "build": trae_cli::build_info::to_json(),
         "status": "operational",
         "endpoints": ["/health", "/api/build", "/api/analyze", "/api/repair", "/api/metrics"]
     }))
 }
 
+/// Tags every response with the build that served it (`X-TRAE-Version`).
+async fn add_version_header(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(VERSION_HEADER, HeaderValue::from_static(LONG_VERSION));
+    response
+}
+
 /// Function documentation added by AI refactor
 async fn check_jarvix_connection(url: &str) -> bool {
-    if let Ok(client) = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
+    if let Ok(client) = trae_cli::utils::http::jarvix_builder()
+        .and_then(|builder| Ok(builder.timeout(std::time::Duration::from_secs(2)).build()?))
     {
         client.get(format!("{}/healthz", url)).send().await.is_ok()
     } else {
@@ -258,113 +313,148 @@ const fn get_memory_usage() -> u64 {
     0
 }
 
-/// Function documentation added by AI refactor
-fn analyze_project_advanced(path: &str) -> Result<AnalyzeResponse, String> {
-    use std::collections::HashMap;
-    use walkdir::WalkDir;
+/// Per-file result of the analyze scan, merged after the parallel pass.
+#[derive(Default)]
+struct FileScan {
+    is_rust: bool,
+    lines: usize,
+    complexity: Option<(String, f64)>,
+    issues: Vec<Issue>,
+}
 
-    let mut total_files = 0;
-    let mut total_lines = 0;
-    let mut rust_files = 0;
-    let mut issues = Vec::new();
-    let mut complexity_metrics = HashMap::new();
+/// Scans a single file for issues. Pure and independent so it can run on rayon.
+fn scan_file(path: &std::path::Path) -> FileScan {
+    let mut scan = FileScan::default();
+    let Some(ext) = path.extension() else {
+        return scan;
+    };
+    let file = portable(path);
+    if ext == "rs" {
+        scan.is_rust = true;
+        if let Ok(content) = std::fs::read_to_string(path) {
+            scan.lines = content.lines().count();
+            scan.complexity = Some((file.clone(), calculate_cyclomatic_complexity(&content)));
+            for (idx, line) in content.lines().enumerate() {
+                if line.contains("unsafe") && !line.trim_start().starts_with("//") {
+                    scan.issues.push(Issue {
+                        file: file.clone(),
+                        line: idx + 1,
+                        severity: "critical".to_string(),
+                        message: "Unsafe code detected - review security implications"
+                            .to_string(),
+                    });
+                }
+                if line.contains("unwrap()") && !line.trim_start().starts_with("//") {
+                    scan.issues.push(Issue {
+                        file: file.clone(),
+                        line: idx + 1,
+                        severity: "warning".to_string(),
+                        message: "Consider using proper error handling instead of unwrap()"
+                            .to_string(),
+                    });
+                }
+                if line.contains("panic!") && !line.trim_start().starts_with("//") {
+                    scan.issues.push(Issue {
+                        file: file.clone(),
+                        line: idx + 1,
+                        severity: "error".to_string(),
+                        message: "Panic detected - use Result/Option for error handling"
+                            .to_string(),
+                    });
+                }
+                if line.contains("todo!") || line.contains("unimplemented!") {
+                    scan.issues.push(Issue {
+                        file: file.clone(),
+                        line: idx + 1,
+                        severity: "info".to_string(),
+                        message: "TODO or unimplemented macro found".to_string(),
+                    });
+                }
+                if line.contains("#[allow(") {
+                    scan.issues.push(Issue {
+                        file: file.clone(),
+                        line: idx + 1,
+                        severity: "warning".to_string(),
+                        message: "Clippy allow attribute found - review if necessary"
+                            .to_string(),
+                    });
+                }
+            }
+        }
+    } else if ext == "toml" && path.file_name().unwrap_or_default() == "Cargo.toml" {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            let manifest = trae_cli::core::manifest::Manifest::parse(&content).unwrap_or_default();
+            if manifest.depends_on("rand") {
+                scan.issues.push(Issue {
+                    file,
+                    line: 0,
+                    severity: "info".to_string(),
+                    message: "Random dependency detected - ensure secure random generation"
+                        .to_string(),
+                });
+            }
+        }
+    }
+    scan
+}
 
-    let entries: Vec<_> = WalkDir::new(path)
+/// Runs the analysis and scores it with `weights`; CPU-bound, so callers
+/// should invoke it via `spawn_blocking`.
+/// Files are scanned in parallel with rayon. Once `cancelled` is set (the
+/// request exceeded its budget) remaining files are skipped.
+fn analyze_project_advanced(
+    path: &str,
+    weights: &ScoreWeights,
+    cancelled: &AtomicBool,
+) -> Result<AnalyzeResponse, String> {
+    use rayon::prelude::*;
+    use walkdir::WalkDir;
+
+    let files: Vec<_> = WalkDir::new(path)
         .max_depth(10)
         .into_iter()
         .filter_map(std::result::Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
         .collect();
 
-    for entry in entries {
-        if entry.file_type().is_file() {
-            total_files += 1;
-            if let Some(ext) = entry.path().extension() {
-                if ext == "rs" {
-                    rust_files += 1;
-                    if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                        let lines = content.lines().count();
-                        total_lines += lines;
-                        let cyclomatic_complexity = calculate_cyclomatic_complexity(&content);
-                        complexity_metrics.insert(
-                            entry.path().display().to_string(),
-                            cyclomatic_complexity,
-                        );
-                        for (idx, line) in content.lines().enumerate() {
-                            if line.contains("unsafe") && !line.trim_start().starts_with("//") {
-                                issues.push(Issue {
-                                    file: entry.path().display().to_string(),
-                                    line: idx + 1,
-                                    severity: "critical".to_string(),
-                                    message: "Unsafe code detected - review security implications"
-                                        .to_string(),
-                                });
-                            }
-                            if line.contains("unwrap()") && !line.trim_start().starts_with("//") {
-                                issues.push(Issue {
-                                    file: entry.path().display().to_string(),
-                                    line: idx + 1,
-                                    severity: "warning".to_string(),
-                                    message: "Consider using proper error handling instead of unwrap()"
-                                        .to_string(),
-                                });
-                            }
-                            if line.contains("panic!") && !line.trim_start().starts_with("//") {
-                                issues.push(Issue {
-                                    file: entry.path().display().to_string(),
-                                    line: idx + 1,
-                                    severity: "error".to_string(),
-                                    message: "Panic detected - use Result/Option for error handling"
-                                        .to_string(),
-                                });
-                            }
-                            if line.contains("todo!") || line.contains("unimplemented!") {
-                                issues.push(Issue {
-                                    file: entry.path().display().to_string(),
-                                    line: idx + 1,
-                                    severity: "info".to_string(),
-                                    message: "TODO or unimplemented macro found".to_string(),
-                                });
-                            }
-                            if line.contains("#[allow(") {
-                                issues.push(Issue {
-                                    file: entry.path().display().to_string(),
-                                    line: idx + 1,
-                                    severity: "warning".to_string(),
-                                    message: "Clippy allow attribute found - review if necessary"
-                                        .to_string(),
-                                });
-                            }
-                        }
-                    }
-                } else if ext == "toml"
-                    && entry.path().file_name().unwrap_or_default() == "Cargo.toml"
-                {
-                    if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                        if content.contains("rand =") {
-                            issues.push(Issue {
-                                file: entry.path().display().to_string(),
-                                line: 0,
-                                severity: "info".to_string(),
-                                message: "Random dependency detected - ensure secure random generation"
-                                    .to_string(),
-                            });
-                        }
-                    }
-                }
+    let scans: Vec<FileScan> = files
+        .par_iter()
+        .map(|file| {
+            if cancelled.load(Ordering::Relaxed) {
+                FileScan::default()
+            } else {
+                scan_file(file)
             }
+        })
+        .collect();
+    if cancelled.load(Ordering::Relaxed) {
+        return Err("analysis cancelled".to_string());
+    }
+
+    let total_files = files.len();
+    let mut total_lines = 0;
+    let mut rust_files = 0;
+    let mut issues = Vec::new();
+    let mut complexity_metrics = HashMap::new();
+    for scan in scans {
+        if scan.is_rust {
+            rust_files += 1;
+        }
+        total_lines += scan.lines;
+        if let Some((file, complexity)) = scan.complexity {
+            complexity_metrics.insert(file, complexity);
         }
+        issues.extend(scan.issues);
     }
 
-    let duplication_score = calculate_duplication_score(total_lines, rust_files);
-    let six_sigma_metrics = calculate_six_sigma_metrics(&issues, total_lines);
-    let fourier_complexity = analyze_fourier_complexity(&complexity_metrics);
-    let quality_score = calculate_advanced_quality_score(
+    let score_breakdown = ScoreBreakdown::compute(
         rust_files,
         issues.len(),
         total_lines,
-        six_sigma_metrics.dpmo,
-        fourier_complexity,
-        duplication_score,
+        analyze_fourier_complexity(&complexity_metrics),
+        duplication_factor(total_lines, rust_files),
+        weights,
     );
 
     let response = AnalyzeResponse {
@@ -372,7 +462,8 @@ fn analyze_project_advanced(path: &str) -> Result<AnalyzeResponse, String> {
         total_lines,
         rust_files,
         issues,
-        quality_score,
+        quality_score: score_breakdown.score,
+        score_breakdown,
     };
 
     Ok(response)
@@ -396,34 +487,6 @@ fn calculate_cyclomatic_complexity(content: &str) -> f64 {
     complexity
 }
 
-/// Function documentation added by AI refactor
-fn calculate_duplication_score(total_lines: usize, rust_files: usize) -> f64 {
-    if rust_files == 0 {
-        return 0.0;
-    }
-    let avg_lines_per_file = total_lines as f64 / rust_files as f64;
-    let duplication_factor: f64 = if avg_lines_per_file > 200.0 { 0.3 } else { 0.1 };
-    duplication_factor.min(1.0)
-}
-
-/// Struct documentation added by AI refactor
-#[derive(Debug)]
-struct SixSigmaMetrics {
-    dpmo: f64,
-}
-
-/// Function documentation added by AI refactor
-fn calculate_six_sigma_metrics(issues: &[Issue], total_lines: usize) -> SixSigmaMetrics {
-    let defects = issues.len() as f64;
-    let opportunities = total_lines as f64;
-    let dpmo = if opportunities > 0.0 {
-        (defects / opportunities) * 1_000_000.0
-    } else {
-        0.0
-    };
-    SixSigmaMetrics { dpmo }
-}
-
 /// Function documentation added by AI refactor
 fn analyze_fourier_complexity(metrics: &HashMap<String, f64>) -> f64 {
     if metrics.is_empty() {
@@ -439,35 +502,12 @@ fn analyze_fourier_complexity(metrics: &HashMap<String, f64>) -> f64 {
     variance.sqrt()
 }
 
-/// Function documentation added by AI refactor
-fn calculate_advanced_quality_score(
-    rust_files: usize,
-    issues: usize,
-    total_lines: usize,
-    dpmo: f64,
-    fourier_complexity: f64,
-    duplication: f64,
-) -> f64 {
-    if rust_files == 0 || total_lines == 0 {
-        return 0.0;
-    }
-    let issues_per_1k_lines = (issues as f64 / total_lines as f64) * 1000.0;
-    let mut score = 100.0 - (issues_per_1k_lines * 5.0);
-    score -= (dpmo / 1000.0).min(20.0);
-    score -= (fourier_complexity / 10.0).min(15.0);
-    score -= duplication * 30.0;
-    if rust_files > 5 {
-        score += 5.0;
-    }
-    score.clamp(0.0, 100.0)
-}
-
 /// Function documentation added by AI refactor
 fn run_advanced_repair() -> Result<RepairResponse, String> {
     let mut fixed_issues = 0;
     let mut applied_fixes = Vec::new();
 
-    match std::process::Command::new("cargo")
+    match std::process::Command::new(cargo_program())
         .arg("fix")
         .arg("--allow-dirty")
         .output()
@@ -484,7 +524,7 @@ fn run_advanced_repair() -> Result<RepairResponse, String> {
         }
     }
 
-    match std::process::Command::new("cargo")
+    match std::process::Command::new(cargo_program())
         .arg("clippy")
         .arg("--fix")
         .arg("--allow-dirty")
@@ -501,7 +541,7 @@ fn run_advanced_repair() -> Result<RepairResponse, String> {
         }
     }
 
-    match std::process::Command::new("cargo").arg("fmt").output() {
+    match std::process::Command::new(cargo_program()).arg("fmt").output() {
         Ok(_) => {
             applied_fixes.push("rustfmt: code formatted".to_string());
         }
@@ -510,7 +550,7 @@ fn run_advanced_repair() -> Result<RepairResponse, String> {
         }
     }
 
-    match std::process::Command::new("cargo")
+    match std::process::Command::new(cargo_program())
         .arg("audit")
         .arg("fix")
         .output()
@@ -526,7 +566,7 @@ fn run_advanced_repair() -> Result<RepairResponse, String> {
         }
     }
 
-    let remaining = match std::process::Command::new("cargo").arg("check").output() {
+    let remaining = match std::process::Command::new(cargo_program()).arg("check").output() {
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr);
             stderr.matches("warning:").count() + stderr.matches("error:").count()
@@ -553,8 +593,16 @@ async fn main() {
         .unwrap_or_else(|_| "http://localhost:5051".to_string());
     println!("📡 JARVIX URL: {}", jarvix_url);
 
+    let analyze_budget = std::env::var(ANALYZE_BUDGET_ENV)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map_or(DEFAULT_ANALYZE_BUDGET, Duration::from_secs);
+    println!("⏱️  Analyze budget: {}s", analyze_budget.as_secs());
+
     let state = Arc::new(AppState {
         jarvix_url: jarvix_url.clone(),
+        analyze_budget,
+        score_weights: trae_cli::config::TraeConfig::load().score,
     });
 
     println!("🔧 Creating router...");
@@ -566,6 +614,7 @@ async fn main() {
         .route("/api/repair", post(repair_handler))
         .route("/api/metrics", get(metrics_handler))
         .layer(CorsLayer::permissive())
+        .layer(middleware::map_response(add_version_header))
         .with_state(state);
 
     println!("🔌 Binding to port 3001...");
@@ -3,6 +3,8 @@ use serde_json::json;
 use std::process::Command;
 use tiny_http::Header;
 use tiny_http::{Method, Response, Server};
+use trae_cli::core::cargo::cargo_program;
+use trae_cli::utils::path::portable;
 use walkdir::WalkDir;
 #[derive(Debug, Serialize, Deserialize)]
 #[doc = "Struct documentation added by AI refactor"]
@@ -71,7 +73,7 @@ fn analyze_project() -> Result<AnalyzeResponse, String> {
                         for (idx, line) in content.lines().enumerate() {
                             if line.contains("unwrap()") && !line.trim_start().starts_with("//") {
                                 issues.push(Issue {
-                                    file: entry.path().display().to_string(),
+                                    file: portable(entry.path()),
                                     line: idx + 1,
                                     severity: "warning".to_string(),
                                     message:
@@ -81,7 +83,7 @@ fn analyze_project() -> Result<AnalyzeResponse, String> {
                             }
                             if line.contains("panic!") && !line.trim_start().starts_with("//") {
                                 issues.push(Issue {
-                                    file: entry.path().display().to_string(),
+                                    file: portable(entry.path()),
                                     line: idx + 1,
                                     severity: "error".to_string(),
                                     message:
@@ -110,7 +112,7 @@ fn analyze_project() -> Result<AnalyzeResponse, String> {
 }
 #[doc = "Function documentation added by AI refactor"]
 fn run_repair() -> Result<String, String> {
-    match Command::new("cargo")
+    match Command::new(cargo_program())
         .arg("fix")
         .arg("--allow-dirty")
         .output()
@@ -170,7 +172,7 @@ fn main() {
         let response = match (request.method(), request.url()) {
             (&Method::Get, "/health") => Response::from_string("OK"),
             (&Method::Get, "/status") => make_json_response(ApiResponse::success(
-                serde_json :: json ! ({ "service" : "trae-cli" , "version" : "0.1.0" , "status" : "operational" , "endpoints" : ["/health" , "/status" , "/api/analyze" , "/api/repair" , "/api/metrics"] }),
+                serde_json :: json ! ({ "service" : "trae-cli" , "version" : trae_cli::build_info::LONG_VERSION , "status" : "operational" , "endpoints" : ["/health" , "/status" , "/api/analyze" , "/api/repair" , "/api/metrics"] }),
             )),
             (&Method::Post, "/api/analyze") => match analyze_project() {
                 Ok(result) => make_json_response(ApiResponse::success(result)),
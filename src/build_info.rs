@@ -0,0 +1,35 @@
+#![doc = " # Build Info - Version and build metadata"]
+#![doc = ""]
+#![doc = " Versión, commit y fecha de compilación embebidos por `build.rs`"]
+/// Crate version from Cargo.toml.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Short git commit hash of the build, or `unknown` outside a git checkout.
+pub const GIT_HASH: &str = env!("TRAE_GIT_HASH");
+/// UTC build date, `YYYY-MM-DD`.
+pub const BUILD_DATE: &str = env!("TRAE_BUILD_DATE");
+/// `0.2.0 (abc1234 2024-06-01)`: what `trae --version`, the HTTP server's
+/// `/health` and the `X-TRAE-Version` header report.
+pub const LONG_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("TRAE_GIT_HASH"),
+    " ",
+    env!("TRAE_BUILD_DATE"),
+    ")"
+);
+/// `rustc --version` of the compiler that built trae.
+pub const RUSTC_VERSION: &str = env!("TRAE_RUSTC_VERSION");
+/// Cargo features compiled in.
+pub fn features() -> Vec<&'static str> {
+    env!("TRAE_FEATURES").split(',').filter(|f| !f.is_empty()).collect()
+}
+/// Output of `trae version --json`, also served by the HTTP server's `/status`.
+pub fn to_json() -> serde_json::Value {
+    serde_json::json!({
+        "version": VERSION,
+        "git_hash": GIT_HASH,
+        "build_date": BUILD_DATE,
+        "rustc_version": RUSTC_VERSION,
+        "features": features(),
+    })
+}
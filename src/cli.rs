@@ -2,24 +2,39 @@
 #![doc = ""]
 #![doc = " Define la estructura principal de comandos y subcomandos de TRAE CLI"]
 use crate::commands::{
-    analyze::AnalyzeCommand, build::BuildCommand, cargo::CargoCommand, clippy::ClippyCommand,
-    build_help::BuildHelpCommand,
-    daemon::DaemonCommand, doc::DocCommand, math::MathCommand, mcp::McpCommand,
-    metrics::MetricsCommand, paths::PathsCommand, release::ReleaseCommand, repair::RepairCommand,
+    analyze::AnalyzeCommand, build::BuildCommand, cargo::CargoCommand, clean::CleanCommand, clippy::ClippyCommand,
+    check::CheckCommand, explain::ExplainCommand,
+    build_help::BuildHelpCommand, dashboard::DashboardCommand,
+    daemon::DaemonCommand, deadcode::DeadcodeCommand, deps::DepsCommand, doc::DocCommand, math::MathCommand, mcp::McpCommand,
+    metrics::MetricsCommand, modules::ModulesCommand, paths::PathsCommand, preflight::PreflightCommand,
+    release::ReleaseCommand, repair::RepairCommand, report::ReportCommand,
     rustup::RustupCommand, security::SecurityCommand, simulate::SimulateCommand, test::TestCommand,
-    watch::WatchCommand,
-    metadata::TraeMetadataCommand,
+    watch::WatchCommand, websearch::WebSearchCommand,
+    metadata::TraeMetadataCommand, status::StatusCommand,
 };
+use crate::core::analyzer::parse_confidence;
 use crate::core::cargo::CargoExecutor;
+use crate::core::issue_cap::IssueCap;
+use crate::exit::ExitCode;
+use crate::utils::path::{portable, validate_path, WorkingDirGuard};
+use crate::utils::snippet::{parse_context, with_snippets, Snippet, Snippets};
+use crate::utils::source::{panicking_call, PanicCall};
+/// `--report-header` values must parse as an HTTP header.
+fn validate_report_header(value: &str) -> std::result::Result<String, String> {
+    crate::jarvix::client::parse_report_header(value).map(|_| value.to_string())
+}
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use serde_json::json;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 #[doc = " TRAE CLI - Enhanced Rust Development Tools"]
 #[derive(Parser, Debug)]
 #[command(name = "trae")]
-#[command(version = "0.1.0")]
+#[command(version = crate::build_info::LONG_VERSION)]
 #[command(about = "Total Rust Analysis Engine - Enhanced cargo with advanced tooling")]
 # [command (long_about = None)]
 pub struct TraeCli {
@@ -30,8 +45,69 @@ pub struct TraeCli {
     #[arg(short, long, global = true)]
     pub config: Option<String>,
     #[doc = " Disable JARVIXSERVER reporting"]
-    #[arg(long, global = true)]
+    #[arg(long, global = true, alias = "no-report")]
     pub no_jarvix: bool,
+    #[doc = " JARVIXSERVER URL (same as JARVIX_ENDPOINT; skips auto-detection)"]
+    #[arg(long, global = true, value_name = "URL")]
+    pub jarvix: Option<String>,
+    #[doc = " Rust project to run in (default: current directory)"]
+    #[arg(long, global = true, value_name = "DIR", value_parser = validate_path)]
+    pub project: Option<PathBuf>,
+    #[doc = " No network access: cargo runs with --offline, no JARVIXSERVER jobs or downloads"]
+    #[arg(long, global = true)]
+    pub offline: bool,
+    #[doc = " CI mode: quiet, no color/spinners, repair --force, JSON where available,"]
+    #[doc = " critical issues exit non-zero, no JARVIXSERVER reporting (see README)"]
+    #[arg(long, global = true)]
+    pub ci: bool,
+    #[doc = " Hide spinners and progress bars (output is otherwise unchanged);"]
+    #[doc = " TRAE_PROGRESS_STYLE=ascii|none restyles or hides them instead"]
+    #[arg(long, global = true, visible_alias = "no-progress")]
+    pub quiet: bool,
+    #[doc = " Keep JARVIXSERVER reporting enabled under --ci"]
+    #[arg(long, global = true, requires = "ci", conflicts_with = "no_jarvix")]
+    pub with_jarvix: bool,
+    #[doc = " Cargo target directory for every cargo invocation (passed as CARGO_TARGET_DIR)"]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub target_dir: Option<PathBuf>,
+    #[doc = " Parallel jobs for every cargo invocation (CARGO_BUILD_JOBS) and the scanners"]
+    #[arg(short, long, global = true, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+    #[doc = " Also scan dotfiles and dot-directories (.git, .cargo, .vscode), skipped by default"]
+    #[arg(long, global = true)]
+    pub include_hidden: bool,
+    #[doc = " Skip files larger than this many bytes when scanning (default: 2000000)"]
+    #[arg(long, global = true, value_name = "BYTES")]
+    pub max_file_size: Option<u64>,
+    #[doc = " Send metric reports to this URL instead of JARVIXSERVER (same JSON payload)"]
+    #[arg(long, global = true, value_name = "URL")]
+    pub report_url: Option<String>,
+    #[doc = " Extra header for --report-url requests, as `key:value` (repeatable)"]
+    #[arg(long, global = true, value_name = "KEY:VALUE", value_parser = validate_report_header)]
+    pub report_header: Vec<String>,
+    #[doc = " Write the reports of scan, analyze, security and repair here (created if missing)"]
+    #[doc = " as scan-report.json, analyze-report.json, security-report.json and"]
+    #[doc = " repair-report.json; a command's own --export/--output path still wins"]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+    #[doc = " Hide scan, analyze and security findings below this severity (security: high"]
+    #[doc = " counts as critical, medium as warning, low as info) and exit 2 on any finding"]
+    #[doc = " left; without it nothing is hidden and only critical findings fail"]
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        value_parser = ["info", "warning", "critical"]
+    )]
+    pub severity: Option<String>,
+    #[doc = " Print the cargo command `trae cargo`/passthrough would run, without running it."]
+    #[doc = " Goes before the subcommand: after it, `--dry-run` belongs to the subcommand"]
+    #[doc = " (`trae repair --dry-run`, `trae cargo publish --dry-run`)"]
+    #[arg(long)]
+    pub dry_run: bool,
+    #[doc = " Settings the flags above resolve to, set by `execute` before the command runs"]
+    #[arg(skip)]
+    pub options: crate::config::RunOptions,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -39,13 +115,19 @@ pub struct TraeCli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     #[doc = " Enhanced build commands with analysis and repair"]
+    #[command(visible_alias = "b")]
     Build(BuildCommand),
     #[doc = " Repair and fix project issues automatically"]
     Repair(RepairCommand),
     #[doc = " Deep code analysis and optimization suggestions"]
     Analyze(AnalyzeCommand),
     #[doc = " Enhanced clippy with parallel analysis"]
+    #[command(visible_alias = "lint")]
     Clippy(ClippyCommand),
+    #[doc = " cargo check with format pre-check and a diagnostics summary by code and crate"]
+    Check(CheckCommand),
+    #[doc = " Explanation of a rustc error code (`rustc --explain E0308`)"]
+    Explain(ExplainCommand),
     #[doc = "Help and suggestions for improving cargo build"]
     BuildHelp(BuildHelpCommand),
     #[doc = " Performance simulation and auto-optimization"]
@@ -67,19 +149,48 @@ pub enum Commands {
     Cargo(CargoCommand),
     #[doc = " Passthrough to `rustup` (official Rust toolchain manager)"]
     Rustup(RustupCommand),
+    #[doc = " cargo clean by profile, docs only or workspace only, reporting freed space"]
+    Clean(CleanCommand),
     #[doc = " Check source paths and parse `.rs` files"]
     Paths(PathsCommand),
     #[doc = " Show all available cargo commands (from `CARGO_COMMANDS.md`)"]
     #[command(name = "help-cargo")]
     HelpCargo,
-    #[doc = " Initialize TRAE configuration"]
+    #[doc = " Initialize TRAE configuration; with PATH or --lib, create a crate (`cargo init`)"]
     Init {
+        #[doc = " Directory for `cargo init` (default: the current one)"]
+        #[arg(value_name = "PATH", conflicts_with_all = ["force", "check", "template"])]
+        path: Option<PathBuf>,
+        #[doc = " Create a library crate (`cargo init --lib`)"]
+        #[arg(long, conflicts_with_all = ["force", "check", "template"])]
+        lib: bool,
         #[doc = " Force overwrite existing configuration"]
         #[arg(long)]
         force: bool,
+        #[doc = " Validate the existing configuration without writing"]
+        #[arg(long, conflicts_with = "force")]
+        check: bool,
+        #[doc = " Write a starter .trae/config.toml (and sample rule files) for a use case"]
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            value_parser = crate::config::INIT_TEMPLATES,
+            conflicts_with = "check"
+        )]
+        template: Option<String>,
     },
     #[doc = " Check TRAE and system dependencies"]
-    Doctor,
+    Doctor {
+        #[doc = " Print `{ checks: [{ name, status, required, detail, remediation }], ok }` as JSON"]
+        #[arg(long)]
+        json: bool,
+    },
+    #[doc = " Version, git commit and build date (`--json` adds rustc and features)"]
+    Version {
+        #[doc = " Print version, git_hash, build_date, rustc_version and features as JSON"]
+        #[arg(long)]
+        json: bool,
+    },
     #[doc = " 🔍 SUPER SCAN - Análisis completo multilenguaje del proyecto desde raíz"]
     #[command(name = "scan")]
     Scan {
@@ -89,18 +200,101 @@ pub enum Commands {
         #[doc = " Detectar código muerto/mock"]
         #[arg(long)]
         dead_code: bool,
-        #[doc = " Análisis multilenguaje (Rust, JS, Python, Go, etc)"]
+        #[doc = " Con --dead-code, no marcar código mock (`mock` en código fuera de tests)"]
+        #[arg(long, requires = "dead_code")]
+        no_mock_check: bool,
+        #[doc = " Análisis multilenguaje (JS, Python, Go, Ruby, Java, C/C++, shell, Dockerfile,"]
+        #[doc = " SQL y .trae/multilang.toml)"]
         #[arg(long)]
         multilang: bool,
         #[doc = " Mostrar solo errores críticos"]
         #[arg(long)]
         critical_only: bool,
+        #[doc = " Descartar issues con confianza menor (0-1) que asigna su detector"]
+        #[arg(long, value_name = "0..1", value_parser = parse_confidence)]
+        min_confidence: Option<f64>,
         #[doc = " Exportar reporte completo"]
         #[arg(long)]
         export: Option<String>,
+        #[doc = " Directorio del proyecto a escanear (por defecto: directorio actual)"]
+        #[arg(long, value_name = "DIR", value_parser = validate_path)]
+        path: Option<PathBuf>,
+        #[doc = " Solo imprimir los conteos (`critical=N warning=M info=K suggestions=S`)"]
+        #[arg(long)]
+        count_only: bool,
+        #[doc = " Con --count-only, imprimir los conteos como JSON"]
+        #[arg(long, requires = "count_only")]
+        json: bool,
+        #[doc = " Formato de salida: reporte de texto, un diagnóstico JSON estilo LSP por línea o"]
+        #[doc = " un documento Markdown (tabla resumen y secciones plegables por categoría)"]
+        #[arg(
+            long,
+            default_value = "text",
+            value_parser = ["text", "lsp", "markdown"],
+            conflicts_with = "count_only"
+        )]
+        format: String,
+        #[doc = " Segundos de espera por cada job de JARVIXSERVER antes de escanear en local"]
+        #[doc = " (por defecto: config o 120)"]
+        #[arg(long, value_name = "SECS")]
+        remote_timeout: Option<u64>,
+        #[doc = " Re-escanear al cambiar archivos y mostrar issues nuevos/resueltos"]
+        #[arg(long, conflicts_with_all = ["count_only", "export", "format"])]
+        watch: bool,
+        #[doc = " Escanear cada proyecto (Cargo.toml) bajo DIR y mostrar una tabla consolidada;"]
+        #[doc = " --export escribe el JSON combinado"]
+        #[arg(
+            long,
+            value_name = "DIR",
+            value_parser = validate_path,
+            conflicts_with_all = ["path", "watch", "count_only", "format"]
+        )]
+        projects: Option<PathBuf>,
+        #[doc = " Con --projects, cuántos proyectos se escanean a la vez"]
+        #[arg(long, value_name = "N", default_value = "4", requires = "projects")]
+        max_parallel: NonZeroUsize,
+        #[doc = " Conservar como máximo N issues por severidad; el escaneo de archivos Rust se"]
+        #[doc = " detiene cuando todas las severidades llegan al límite"]
+        #[arg(long, value_name = "N", conflicts_with = "watch")]
+        max_issues: Option<NonZeroUsize>,
+        #[doc = " Mostrar N líneas de código alrededor de cada hallazgo (0-10): en el reporte,"]
+        #[doc = " el --export y los diagnósticos LSP (`data.snippet`)"]
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = parse_context,
+            conflicts_with_all = ["count_only", "watch", "projects"]
+        )]
+        diff_context: Option<usize>,
+        #[doc = " Listar todos los issues agrupados por severidad, categoría o archivo, con"]
+        #[doc = " conteos por grupo; --export los anida en `groups`"]
+        #[arg(
+            long,
+            value_name = "BY",
+            value_parser = ["severity", "category", "file"],
+            conflicts_with_all = ["count_only", "format", "watch", "projects"]
+        )]
+        group_by: Option<String>,
     },
     #[doc = " 🧪 Enhanced testing with coverage and analysis"]
+    #[command(visible_alias = "t")]
     Test(TestCommand),
+    #[doc = " 🪦 Crawl src/ and list dead code candidates"]
+    Deadcode(DeadcodeCommand),
+    #[doc = " 🧩 Declared dependencies; `--unused-features` finds dead Cargo features"]
+    Deps(DepsCommand),
+    #[doc = " 📦 Module directories under src/ plus `cargo tree`"]
+    Modules(ModulesCommand),
+    #[doc = " 🚀 Check everything before pushing (fmt + clippy + test + build release)"]
+    Preflight(PreflightCommand),
+    #[doc = " 🌐 Search the web, docs.rs or crates.io through JARVIXSERVER"]
+    WebSearch(WebSearchCommand),
+    #[doc = " Run any cargo command (kept for compatibility; same as `trae <cmd>`)"]
+    #[command(hide = true)]
+    Custom {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     #[doc = "Generate project metadata JSON"]
     Metadata(TraeMetadataCommand),
     #[doc = " Quick pipeline: analyze -> repair -> test (compact powerful command)"]
@@ -118,57 +312,323 @@ pub enum Commands {
     Math(MathCommand),
     #[doc = " � Security audit and vulnerability scanning"]
     Security(SecurityCommand),
+    #[doc = " 📊 Project health on one screen: score, issues, metrics, deps, security, last build"]
+    Dashboard(DashboardCommand),
+    #[doc = " 🩺 Local trae server, daemon and (with --remote) JARVIXSERVER: up, latency, version"]
+    Status(StatusCommand),
+    #[doc = " 🧩 Merge `scan --export`/`analyze --output` JSON reports from sharded runs into one"]
+    Report(ReportCommand),
+}
+impl Commands {
+    /// Commands that read the sources themselves, and so run from the
+    /// workspace root wherever trae is started, unless given a directory
+    /// of their own. Cargo-backed commands keep the current directory, where
+    /// cargo already resolves the package.
+    fn runs_at_workspace_root(&self) -> bool {
+        match self {
+            Commands::Scan { path, projects, .. } => path.is_none() && projects.is_none(),
+            Commands::Security(cmd) => cmd.path.is_none(),
+            Commands::Deadcode(cmd) => cmd.path.is_none(),
+            Commands::Modules(cmd) => cmd.path.is_none(),
+            Commands::Metadata(_)
+            | Commands::Dashboard(_) => true,
+            _ => false,
+        }
+    }
+    /// Commands built on cargo, which fail up front when it is missing
+    /// instead of on their first spawn. Others use cargo, if at all, only
+    /// for optional checks.
+    fn needs_cargo(&self) -> bool {
+        matches!(
+            self,
+            Commands::Build(_)
+                | Commands::Init { path: Some(_), .. }
+                | Commands::Init { lib: true, .. }
+                | Commands::Repair(_)
+                | Commands::Clippy(_)
+                | Commands::Check(_)
+                | Commands::Release(_)
+                | Commands::Cargo(_)
+                | Commands::Clean(_)
+                | Commands::External(_)
+                | Commands::Test(_)
+                | Commands::Deps(_)
+                | Commands::Preflight(_)
+                | Commands::Custom { .. }
+                | Commands::Auto { .. }
+                | Commands::Metadata(_)
+                | Commands::Doc(_)
+                | Commands::BuildHelp(_)
+        )
+    }
 }
 impl TraeCli {
-    #[doc = "Method documentation added by AI refactor"]
-    pub async fn execute(&self) -> Result<()> {
+    /// Entry point for a `trae` binary: parses `args`, runs the command and
+    /// maps the outcome to an [`ExitCode`] (errors are printed to stderr).
+    pub async fn run_from<I, T>(args: I) -> ExitCode
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let mut cli = match Self::try_parse_from(args) {
+            Ok(cli) => cli,
+            Err(e) => {
+                let _ = e.print();
+                return if e.use_stderr() {
+                    ExitCode::UsageError
+                } else {
+                    ExitCode::Success
+                };
+            }
+        };
+        let result = cli.execute().await;
+        if let Err(e) = &result {
+            eprintln!("{} {e:#}", "❌".red());
+        }
+        ExitCode::from_result(&result)
+    }
+    /// Whether best-effort JARVIXSERVER reporting should run: off with
+    /// `--no-jarvix`, and off under `--ci` unless `--with-jarvix` is given.
+    pub fn jarvix_enabled(&self) -> bool {
+        !self.no_jarvix && (!self.ci || self.with_jarvix)
+    }
+    /// Whether the dead code scan looks for mocks: off with `scan --no-mock-check`.
+    fn mock_check(&self) -> bool {
+        !matches!(self.command, Commands::Scan { no_mock_check: true, .. })
+    }
+    /// How `scan --group-by` groups the report; `None` for the default layout.
+    fn group_by(&self) -> Option<&str> {
+        match &self.command {
+            Commands::Scan { group_by, .. } => group_by.as_deref(),
+            _ => None,
+        }
+    }
+    /// `issues` without those under `scan --min-confidence`.
+    fn confident(
+        &self,
+        mut issues: Vec<crate::core::analyzer::AnalysisIssue>,
+    ) -> Vec<crate::core::analyzer::AnalysisIssue> {
+        if let Commands::Scan { min_confidence: Some(min), .. } = self.command {
+            issues.retain(|i| i.confidence >= min);
+        }
+        issues
+    }
+    /// Resolves the global flags into [`Self::options`] and runs the command.
+    pub async fn execute(&mut self) -> Result<()> {
         let start_time = Instant::now();
+        if self.ci {
+            colored::control::set_override(false);
+        }
+        let mut target_dir = None;
+        if let Some(dir) = &self.target_dir {
+            match crate::config::writable_target_dir(dir) {
+                Ok(dir) => target_dir = Some(dir),
+                Err(e) => eprintln!(
+                    "{}",
+                    format!(
+                        "⚠️ --target-dir {} no es escribible ({e}); se usa el target por defecto",
+                        dir.display()
+                    )
+                    .yellow()
+                ),
+            }
+        }
+        if let Some(jobs) = self.jobs {
+            crate::config::use_jobs(jobs);
+        }
+        // Before --project and the workspace root move us
+        let output_dir = self.output_dir.as_deref().map(crate::config::writable_output_dir);
+        let output_dir = output_dir.transpose()?;
+        let _project = self
+            .project
+            .as_deref()
+            .map(WorkingDirGuard::enter)
+            .transpose()?;
+        let loaded = crate::config::load_dotenv(std::path::Path::new(".env"))?;
+        if loaded > 0 && !self.ci {
+            eprintln!("{} Cargadas {loaded} variables desde .env", "ℹ".blue());
+        }
+        let _workspace = self
+            .command
+            .runs_at_workspace_root()
+            .then(crate::core::workspace::find_root)
+            .flatten()
+            .map(|root| WorkingDirGuard::enter(&root))
+            .transpose()?;
+        // After .env, which may export TRAE_* defaults
+        let env = crate::config::RunOptions::from_env();
+        self.options = crate::config::RunOptions {
+            offline: self.offline || env.offline,
+            ci: self.ci || env.ci,
+            quiet: self.quiet,
+            include_hidden: self.include_hidden || env.include_hidden,
+            max_file_size: self.max_file_size,
+            target_dir,
+            jobs: self.jobs,
+            output_dir,
+            severity: self.severity.as_deref().map(|severity| {
+                crate::core::analyzer::IssueSeverity::from_name(severity)
+                    .expect("clap only accepts severity names")
+            }),
+            report_url: self.report_url.clone(),
+            report_headers: self.report_header.clone(),
+            jarvix_endpoint: self.jarvix.clone(),
+            ..Default::default()
+        };
+        if self.options.offline {
+            eprintln!(
+                "{}",
+                "📴 Modo offline: cargo --offline, sin JARVIXSERVER ni descargas".yellow()
+            );
+        }
+        // Every cargo child is spawned from the resolved path (cargo_program)
+        if self.command.needs_cargo() {
+            crate::core::cargo::ensure_cargo()?;
+        }
         let result = match &self.command {
             Commands::Build(cmd) => cmd.execute(self).await,
+            Commands::Repair(cmd) if cmd.selects_nothing() => {
+                cmd.run_basic_sequence(&self.options).await
+            }
             Commands::Repair(cmd) => cmd.execute(self).await,
             Commands::Analyze(cmd) => cmd.execute(self).await,
             Commands::BuildHelp(cmd) => cmd.execute(self).await,
-            Commands::Clippy(cmd) => cmd.execute().await,
+            Commands::Clippy(cmd) => cmd.execute(self).await,
+            Commands::Check(cmd) => cmd.execute(self).await,
+            Commands::Explain(cmd) => cmd.execute().await,
             Commands::Simulate(cmd) => cmd.execute(self).await,
             Commands::Daemon(cmd) => cmd.execute(self).await,
             Commands::Mcp(cmd) => cmd.execute().await,
-            Commands::Release(cmd) => cmd.execute().await,
-            Commands::Watch(cmd) => cmd.execute().await,
+            Commands::Release(cmd) => cmd.execute(self).await,
+            Commands::Watch(cmd) => cmd.execute(self).await,
             Commands::Metrics(cmd) => cmd.execute(self).await,
             Commands::Cargo(cmd) => cmd.execute(self).await,
             Commands::Rustup(cmd) => cmd.execute().await,
-            Commands::Paths(cmd) => cmd.execute().await,
+            Commands::Clean(cmd) => cmd.execute(self).await,
+            Commands::Paths(cmd) => cmd.execute(self).await,
             Commands::External(args) => self.run_external_cargo(args).await,
             Commands::Test(cmd) => cmd.execute(self).await,
-            Commands::Auto { no_jarvix } => self.run_auto(*no_jarvix).await,
+            Commands::Deadcode(cmd) => cmd.execute(self).await,
+            Commands::Modules(cmd) => cmd.execute(self).await,
+            Commands::Deps(cmd) => cmd.execute(self).await,
+            Commands::Preflight(cmd) => cmd.execute(self).await,
+            Commands::WebSearch(cmd) => cmd.execute(self).await,
+            Commands::Custom { args } => self.run_external_cargo(args).await,
+            Commands::Auto { no_jarvix } => {
+                self.run_auto(*no_jarvix || !self.jarvix_enabled()).await
+            }
             Commands::Metadata(cmd) => cmd.execute(self).await,
             Commands::Doc(cmd) => cmd.execute(self).await,
             Commands::Math(cmd) => cmd.execute(self).await,
             Commands::Security(cmd) => cmd.execute(self).await,
+            Commands::Dashboard(cmd) => cmd.execute(self).await,
+            Commands::Status(cmd) => cmd.execute(self).await,
+            Commands::Report(cmd) => cmd.execute(self).await,
             Commands::CommandsGuide => self.show_command_catalog(),
             Commands::HelpCargo => self.show_cargo_help().await,
-            Commands::Init { force } => self.init_config(*force).await,
-            Commands::Doctor => self.run_doctor().await,
+            Commands::Init { path, lib, force, check, template } => {
+                if path.is_some() || *lib {
+                    self.init_crate(path.as_deref(), *lib).await
+                } else if *check {
+                    crate::config::check_trae_config(self.config.as_deref())
+                } else if let Some(template) = template {
+                    self.init_template(template, *force)
+                } else {
+                    self.init_config(*force).await
+                }
+            }
+            Commands::Doctor { json } => self.run_doctor(*json).await,
+            Commands::Version { json } => {
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&crate::build_info::to_json())?);
+                } else {
+                    println!("trae {}", crate::build_info::LONG_VERSION);
+                }
+                Ok(())
+            }
             Commands::Scan {
                 deps,
                 dead_code,
+                no_mock_check,
                 multilang,
                 critical_only,
+                min_confidence,
                 export,
+                path,
+                count_only,
+                json,
+                format,
+                remote_timeout,
+                watch,
+                projects,
+                max_parallel,
+                max_issues,
+                diff_context,
+                group_by: _,
             } => {
+                let export = self.options.report_path(export.as_deref(), "scan-report.json");
+                if let Some(dir) = projects {
+                    let mut args: Vec<String> = [
+                        (*deps, "--deps"),
+                        (*dead_code, "--dead-code"),
+                        (*no_mock_check, "--no-mock-check"),
+                        (*multilang, "--multilang"),
+                        (*critical_only, "--critical-only"),
+                        (!self.jarvix_enabled(), "--no-jarvix"),
+                        // A --ci child would otherwise drop the reporting kept here
+                        (self.jarvix_enabled() && self.options.ci, "--with-jarvix"),
+                    ]
+                    .iter()
+                    .filter(|(on, _)| *on)
+                    .map(|(_, flag)| flag.to_string())
+                    .collect();
+                    if let Some(secs) = remote_timeout {
+                        args.extend(["--remote-timeout".to_string(), secs.to_string()]);
+                    }
+                    if let Some(min) = min_confidence {
+                        args.extend(["--min-confidence".to_string(), min.to_string()]);
+                    }
+                    if let Some(max) = max_issues {
+                        args.extend(["--max-issues".to_string(), max.to_string()]);
+                    }
+                    if let Some(severity) = &self.severity {
+                        args.extend(["--severity".to_string(), severity.clone()]);
+                    }
+                    args.extend(self.options.child_args());
+                    let export = export.as_deref();
+                    return self.run_fleet_scan(dir, &args, max_parallel.get(), export).await;
+                }
+                let _root = path.as_deref().map(WorkingDirGuard::enter).transpose()?;
+                if *watch {
+                    return self
+                        .run_scan_watch(*deps, *dead_code, *multilang, *critical_only)
+                        .await;
+                }
+                let output = match (*count_only, *json || self.ci) {
+                    (false, _) if format == "lsp" => ScanOutput::Lsp,
+                    (false, _) if format == "markdown" => ScanOutput::Markdown,
+                    (false, _) => ScanOutput::Report,
+                    (true, false) => ScanOutput::CountLine,
+                    (true, true) => ScanOutput::CountJson,
+                };
                 self.run_super_scan(
                     *deps,
                     *dead_code,
                     *multilang,
                     *critical_only,
                     export.as_deref(),
+                    output,
+                    crate::commands::cargo::RemoteOffload::scan(*remote_timeout),
+                    IssueCap::new(max_issues.map(NonZeroUsize::get)),
+                    *diff_context,
                 )
                 .await
             }
         };
+        self.options.oversized.warn();
         let total_duration = start_time.elapsed();
-        if total_duration > Duration::from_millis(100) {
-            println!("⚡ Comando ejecutado en: {total_duration:?}");
+        if total_duration > Duration::from_millis(100) && !self.ci {
+            eprintln!("⚡ Comando ejecutado en: {total_duration:?}");
         }
         result
     }
@@ -212,12 +672,33 @@ impl TraeCli {
         use crate::config::init_trae_config;
         init_trae_config(force).await
     }
+    /// `trae init [PATH] [--lib]`: a new crate, as `cargo init` creates it.
+    async fn init_crate(&self, path: Option<&Path>, lib: bool) -> Result<()> {
+        let mut args = vec![std::ffi::OsStr::new("init")];
+        if let Some(path) = path {
+            args.push(path.as_os_str());
+        }
+        if lib {
+            args.push("--lib".as_ref());
+        }
+        CargoExecutor::new(&self.options).execute_streaming(&args).await
+    }
+    /// `trae init --template`: the template's files in the current directory.
+    fn init_template(&self, template: &str, force: bool) -> Result<()> {
+        let written = crate::config::write_template(Path::new("."), template, force)?;
+        println!("🔧 Plantilla '{template}' inicializada:");
+        for path in written {
+            println!("  {} {}", "✅".green(), path.strip_prefix(".").unwrap_or(&path).display());
+        }
+        Ok(())
+    }
     #[doc = "Method documentation added by AI refactor"]
-    async fn run_doctor(&self) -> Result<()> {
+    async fn run_doctor(&self, json: bool) -> Result<()> {
         use crate::core::doctor::run_system_check;
-        run_system_check().await
+        run_system_check(json, &self.options).await
     }
     #[doc = "Method documentation added by AI refactor"]
+    #[allow(clippy::too_many_arguments)]
     async fn run_super_scan(
         &self,
         deps: bool,
@@ -225,25 +706,37 @@ impl TraeCli {
         multilang: bool,
         critical_only: bool,
         export: Option<&str>,
+        output: ScanOutput,
+        offload: crate::commands::cargo::RemoteOffload,
+        mut cap: IssueCap,
+        diff_context: Option<usize>,
     ) -> Result<()> {
-        println!(
-            "{}",
-            "🔍 TRAE SUPER SCAN - Análisis Nuclear Completo con JARVIX Paralelización"
-                .cyan()
-                .bold()
-        );
-        println!("{}", "=====================================\n".cyan());
+        let verbose = output == ScanOutput::Report;
+        let progress = |msg: &str| {
+            if verbose {
+                println!("{}", msg.yellow());
+            }
+        };
+        if verbose {
+            println!(
+                "{}",
+                "🔍 TRAE SUPER SCAN - Análisis Nuclear Completo con JARVIX Paralelización"
+                    .cyan()
+                    .bold()
+            );
+            println!("{}", "=====================================\n".cyan());
+        }
         let mut all_issues = Vec::new();
         let mut all_suggestions = Vec::new();
         let mut metrics =
             crate::metrics::collector::MetricsCollector::new("super_scan".to_string());
-        let jarvix_client = if self.no_jarvix {
+        let jarvix_client = if !self.jarvix_enabled() {
             None
         } else {
-            crate::jarvix::client::JarvixClient::new().ok().flatten()
+            crate::jarvix::client::JarvixClient::new(&self.options).await.ok().flatten()
         };
         let use_parallel = jarvix_client.is_some();
-        if use_parallel {
+        if use_parallel && verbose {
             println!("⚡ Modo PARALELO activado - Usando JARVIXSERVER workers");
             if let Some(client) = jarvix_client.as_ref() {
                 if let Ok(stats) = client.get_pool_stats().await {
@@ -252,154 +745,162 @@ impl TraeCli {
                     eprintln!("⚠️  No se pudo obtener stats de JARVIXSERVER");
                 }
             }
-        } else {
+        } else if verbose {
             println!("🔄 Modo SECUENCIAL - JARVIXSERVER no disponible");
         }
-        println!("{}", "🦀 [1/6] Analizando proyecto Rust...".yellow());
-        let rust_scan = self.scan_rust_project(critical_only);
+        progress("🦀 [1/6] Analizando proyecto Rust...");
+        let mut severity = crate::core::severity::SeverityFilter::from_options(&self.options);
+        let rust_scan = self.scan_rust_project(critical_only, &mut cap, &mut severity);
         all_issues.extend(rust_scan.0);
         all_suggestions.extend(rust_scan.1);
-        if deps {
-            if use_parallel {
-                println!(
-                    "{}",
-                    "📦 [2/6] Escaneando dependencias (PARALELO)...".yellow()
-                );
-                let job_data = json ! ({ "project_path" : std :: env :: current_dir () ?. to_string_lossy () , "scan_type" : "dependencies" });
-                if let Some(client) = jarvix_client.as_ref() {
-                    if let Ok(job_id) = client
-                        .submit_parallel_analysis_job("dependency_analysis", job_data)
-                        .await
-                    {
-                        println!("📤 Job dependencias enviado: {job_id}");
-                        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                        if let Ok(Some(result)) = client.get_job_result(&job_id).await {
-                            if let Some(issues_array) = result.as_array() {
-                                for issue in issues_array {
-                                    if let (Some(desc), Some(severity)) = (
-                                        issue.get("description").and_then(|d| d.as_str()),
-                                        issue.get("severity").and_then(|s| s.as_str()),
-                                    ) {
-                                        all_issues.push(crate::core::analyzer::AnalysisIssue {
-                                            category: "Dependencies".to_string(),
-                                            description: desc.to_string(),
-                                            severity: match severity {
-                                                "critical" => {
-                                                    crate::core::analyzer::IssueSeverity::Critical
-                                                }
-                                                "warning" => {
-                                                    crate::core::analyzer::IssueSeverity::Warning
-                                                }
-                                                _ => crate::core::analyzer::IssueSeverity::Info,
-                                            },
-                                            file: issue
-                                                .get("file")
-                                                .and_then(|f| f.as_str())
-                                                .map(std::string::ToString::to_string),
-                                            line: issue
-                                                .get("line")
-                                                .and_then(serde_json::Value::as_u64)
-                                                .map(|l| l as usize),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
+        let remote_scans: Vec<_> = [
+            (deps, ("dependency_analysis", "dependencies", "Dependencies")),
+            (dead_code, ("dead_code_scan", "dead_code", "Code Quality")),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, scan)| enabled.then_some(scan))
+        .collect();
+        let mut remote = match jarvix_client.as_ref() {
+            Some(client) if !remote_scans.is_empty() => {
+                if deps {
+                    progress("📦 [2/6] Escaneando dependencias (PARALELO)...");
+                }
+                if dead_code {
+                    progress("💀 [3/6] Detectando código muerto (PARALELO con Nim)...");
                 }
+                self.remote_scan_issues(client, &remote_scans, offload, verbose).await?
+            }
+            _ => HashMap::new(),
+        };
+        if deps {
+            if let Some(issues) = remote.remove("dependencies") {
+                cap.extend(&mut all_issues, severity.filter(self.confident(issues)));
             } else {
-                println!("{}", "📦 [2/6] Escaneando dependencias...".yellow());
-                let deps_issues = self.scan_dependencies();
-                all_issues.extend(deps_issues);
+                progress("📦 [2/6] Escaneando dependencias...");
+                let issues = self.confident(self.scan_dependencies());
+                cap.extend(&mut all_issues, severity.filter(issues));
             }
         }
         if dead_code {
-            if use_parallel {
-                println!(
-                    "{}",
-                    "💀 [3/6] Detectando código muerto (PARALELO con Nim)...".yellow()
-                );
-                let job_data = json ! ({ "project_path" : std :: env :: current_dir () ?. to_string_lossy () , "scan_type" : "dead_code" });
-                if let Ok(job_id) = jarvix_client
-                    .as_ref()
-                    .unwrap()
-                    .submit_parallel_analysis_job("dead_code_scan", job_data)
-                    .await
-                {
-                    println!("📤 Job código muerto enviado: {job_id}");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                    if let Ok(Some(result)) = jarvix_client
-                        .as_ref()
-                        .unwrap()
-                        .get_job_result(&job_id)
-                        .await
-                    {
-                        if let Some(issues_array) = result.as_array() {
-                            for issue in issues_array {
-                                if let Some(desc) =
-                                    issue.get("description").and_then(|d| d.as_str())
-                                {
-                                    all_issues.push(crate::core::analyzer::AnalysisIssue {
-                                        category: "Code Quality".to_string(),
-                                        description: desc.to_string(),
-                                        severity: crate::core::analyzer::IssueSeverity::Info,
-                                        file: issue
-                                            .get("file")
-                                            .and_then(|f| f.as_str())
-                                            .map(std::string::ToString::to_string),
-                                        line: issue
-                                            .get("line")
-                                            .and_then(serde_json::Value::as_u64)
-                                            .map(|l| l as usize),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
+            if let Some(issues) = remote.remove("dead_code") {
+                cap.extend(&mut all_issues, severity.filter(self.confident(issues)));
             } else {
-                println!("{}", "💀 [3/6] Detectando código muerto/mock...".yellow());
-                let dead_issues = self.scan_dead_code();
-                all_issues.extend(dead_issues);
+                progress("💀 [3/6] Detectando código muerto/mock...");
+                let issues = self.confident(self.scan_dead_code());
+                cap.extend(&mut all_issues, severity.filter(issues));
             }
         }
         if multilang {
-            println!("{}", "🌐 [4/6] Análisis multilenguaje...".yellow());
-            let lang_issues = self.scan_multilang();
-            all_issues.extend(lang_issues);
+            progress("🌐 [4/6] Análisis multilenguaje...");
+            let rules = crate::core::multilang::MultilangRules::load(Path::new("."))?;
+            let issues = self.confident(self.scan_multilang(&rules));
+            cap.extend(&mut all_issues, severity.filter(issues));
         }
-        println!("{}", "🏗️ [5/6] Analizando artifacts de build...".yellow());
+        progress("🏗️ [5/6] Analizando artifacts de build...");
         let build_issues = self.scan_build_artifacts();
-        all_issues.extend(build_issues);
-        println!("{}", "📊 [6/6] Generando reporte...".yellow());
-        self.generate_scan_report(&all_issues, &all_suggestions, export, &mut metrics)?;
-        if let Some(client) = jarvix_client {
-            metrics.add_custom_metric("total_issues".to_string(), all_issues.len() as u64);
-            metrics.add_custom_metric(
-                "critical_issues".to_string(),
+        cap.extend(&mut all_issues, severity.filter(self.confident(build_issues)));
+        if critical_only {
+            all_issues.retain(|i| {
+                matches!(i.severity, crate::core::analyzer::IssueSeverity::Critical)
+            });
+        }
+        crate::core::analyzer::sort_issues(&mut all_issues);
+        let counts = ScanCounts {
+            suppressed: severity.suppressed(),
+            ..ScanCounts::of(&all_issues, &all_suggestions)
+        };
+        // Taken in report order, so the cap on snippet lines spares critical issues
+        let mut snippets = Snippets::new(diff_context);
+        let issue_snippets: Vec<_> = all_issues
+            .iter()
+            .map(|i| snippets.as_mut()?.take(i.file.as_deref(), i.line))
+            .collect();
+        match output {
+            ScanOutput::Report => {
+                progress("📊 [6/6] Generando reporte...");
+                self.generate_scan_report(
+                    &all_issues,
+                    &issue_snippets,
+                    &all_suggestions,
+                    export,
+                    &cap,
+                    &severity,
+                    &mut metrics,
+                )?;
+            }
+            ScanOutput::CountLine => println!("{counts}"),
+            ScanOutput::CountJson => println!("{}", serde_json::to_string(&counts)?),
+            ScanOutput::Lsp => crate::utils::diagnostics::emit(
                 all_issues
                     .iter()
-                    .filter(|i| {
-                        matches!(i.severity, crate::core::analyzer::IssueSeverity::Critical)
-                    })
-                    .count() as u64,
-            );
+                    .zip(issue_snippets)
+                    .map(|(i, snippet)| i.to_diagnostic().with_snippet(snippet)),
+            )?,
+            ScanOutput::Markdown => {
+                let mut report = crate::core::analyzer::markdown_report(
+                    "🔍 trae scan",
+                    &all_issues,
+                    &all_suggestions,
+                );
+                for notice in [cap.notice(), severity.notice()].into_iter().flatten() {
+                    report.note(notice);
+                }
+                print!("{}", report.render());
+            }
+        }
+        // Counts and diagnostics own stdout; the reports print their notice themselves
+        let own_notice = matches!(output, ScanOutput::Report | ScanOutput::Markdown);
+        for notice in [cap.notice(), severity.notice()].into_iter().flatten() {
+            if !own_notice {
+                eprintln!("{}", notice.yellow());
+            }
+        }
+        if let Some(notice) = snippets.as_ref().and_then(Snippets::notice) {
+            eprintln!("{}", notice.yellow());
+        }
+        let critical_count = counts.critical;
+        if let Some(client) = jarvix_client {
+            metrics.add_custom_metric("total_issues".to_string(), all_issues.len() as u64);
+            metrics.add_custom_metric("critical_issues".to_string(), critical_count as u64);
             metrics.add_custom_metric("parallel_processing".to_string(), i32::from(use_parallel));
             metrics.add_custom_metric("performance_boost".to_string(), 400);
             if let Err(e) = client.report_scan_metrics(metrics).await {
                 eprintln!("⚠️ No se pudo reportar métricas de scan: {e}");
             }
         }
+        let failing = severity.failing(all_issues.iter().map(|i| &i.severity));
+        if failing > 0 {
+            let what = severity.describe_failing("issues críticos detectados", "issues");
+            return Err(crate::exit::findings(failing, what));
+        }
         Ok(())
     }
 
-    /// Run a compact pipeline: analyze -> repair -> test
+    /// Run a compact pipeline: analyze -> repair -> test. Every stage runs
+    /// even after one fails; a single table summarizes them and the first
+    /// failure decides the exit code.
     async fn run_auto(&self, no_jarvix: bool) -> Result<()> {
+        use crate::utils::ui::{print_overall_summary, StageSummary};
         println!("{}", "⚡ TRAE AUTO - pipeline compacto: analyze -> repair -> test".cyan().bold());
+        let pipeline_start = Instant::now();
+        let mut stages = Vec::new();
+        let mut first_error = None;
+        let mut finish = |stage: StageSummary, outcome: crate::error::Result<()>| {
+            stages.push(stage);
+            if let Err(e) = outcome {
+                first_error.get_or_insert(e);
+            }
+        };
         // Analyze
         // default: full profile = None, don't force refresh, no output file
-        crate::api::analyze(true, true, true, no_jarvix, None, false, None).await?;
-        // Repair (auto)
+        // Critical findings are what repair is for, so they don't stop the pipeline
+        let start = Instant::now();
+        let outcome = crate::api::analyze(true, true, true, no_jarvix, None, false, None).await;
+        if let Err(e @ crate::error::TraeError::QualityGateFailed(_)) = &outcome {
+            println!("⚠️ {e}; continuando con repair");
+        }
+        finish(StageSummary::new("Analyze", start.elapsed(), &outcome), outcome);
+        // Repair (auto); its steps go into the final table instead of its own
         // default repair: level balanced, rollback disabled, no updates, no git operations
         let repair_opts = crate::commands::repair::RepairOptions {
             auto: true,
@@ -410,152 +911,307 @@ impl TraeCli {
             no_jarvix,
             level: Some("balanced".to_string()),
             rollback: false,
+            allow_dirty_repair: false,
             update: false,
             upgrade: false,
+            locked: false,
             git_branch: None,
+            git_branch_force: false,
             git_commit: None,
+            git_allow_dirty: false,
+            edition: None,
+            cancel: None,
+            progress: None,
         };
-        crate::api::repair(repair_opts).await?;
+        let repair_steps = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = std::sync::Arc::clone(&repair_steps);
+        let start = Instant::now();
+        let outcome = crate::api::repair_with_progress(repair_opts, move |event| {
+            if let crate::events::ProgressEvent::StepFinished(step) = event {
+                sink.lock().unwrap_or_else(|e| e.into_inner()).push(step);
+            }
+        })
+        .await;
+        let mut stage = StageSummary::new("Repair", start.elapsed(), &outcome);
+        stage.steps = std::mem::take(&mut *repair_steps.lock().unwrap_or_else(|e| e.into_inner()));
+        finish(stage, outcome);
         // Test (basic)
-        crate::api::test_cmd(false, false, false, None, None, false, no_jarvix).await?;
-        println!("{}", "✅ TRAE AUTO completado".green());
+        let start = Instant::now();
+        let outcome = crate::api::test_cmd(false, false, false, None, None, false, no_jarvix).await;
+        finish(StageSummary::new("Test", start.elapsed(), &outcome), outcome);
+        print_overall_summary("Auto Summary", &stages, pipeline_start.elapsed());
+        match first_error {
+            Some(e) => Err(e.into()),
+            None => {
+                println!("{}", "✅ TRAE AUTO completado".green());
+                Ok(())
+            }
+        }
+    }
+    /// Issues of JARVIXSERVER scan jobs by scan type. `scans` are
+    /// `(job_type, scan_type, category)` and run together through a
+    /// [`JobManager`](crate::jarvix::jobs::JobManager), at most
+    /// `max_jobs_in_flight` of `[jarvix]` at once. Each result lists issues
+    /// (`description`, optional `severity`, `file` and `line`). A scan missing
+    /// from the map could not be submitted, failed or outlived
+    /// `offload.timeout`, and the caller should run it locally.
+    async fn remote_scan_issues(
+        &self,
+        client: &crate::jarvix::client::JarvixClient,
+        scans: &[(&str, &str, &str)],
+        offload: crate::commands::cargo::RemoteOffload,
+        verbose: bool,
+    ) -> Result<HashMap<String, Vec<crate::core::analyzer::AnalysisIssue>>> {
+        use crate::jarvix::jobs::{JobManager, JobSpec};
+        let project_path = std::env::current_dir()?.to_string_lossy().to_string();
+        let jobs = scans
+            .iter()
+            .map(|(job_type, scan_type, _)| JobSpec {
+                name: scan_type.to_string(),
+                job_type: job_type.to_string(),
+                data: json!({
+                    "project_path": project_path,
+                    "scan_type": scan_type,
+                    "mock_check": self.mock_check(),
+                }),
+            })
+            .collect();
+        let max_in_flight = crate::config::TraeConfig::load().jarvix.max_jobs_in_flight;
+        let manager = JobManager::new(client, offload.timeout, offload.poll_interval)
+            .max_in_flight(max_in_flight);
+        let spinner = crate::utils::progress::spinner(&self.options);
+        spinner.set_message(format!(
+            "{} job(s) en JARVIXSERVER (máx. {max_in_flight} a la vez, límite {}s)",
+            scans.len(),
+            offload.timeout.as_secs()
+        ));
+        spinner.enable_steady_tick(Duration::from_millis(120));
+        let outcomes = manager
+            .run(jobs, |outcome| {
+                spinner.suspend(|| match outcome.result() {
+                    Some(_) if verbose => println!("⏱️  Job {outcome}"),
+                    Some(_) => {}
+                    None => eprintln!("⚠️ Job {outcome}; escaneo local en su lugar"),
+                });
+            })
+            .await;
+        spinner.finish_and_clear();
+        Ok(outcomes
+            .iter()
+            .filter_map(|outcome| {
+                let (_, _, category) = scans.iter().find(|(_, name, _)| *name == outcome.name)?;
+                Some((outcome.name.clone(), remote_issues(outcome.result()?, category)))
+            })
+            .collect())
+    }
+    /// `trae scan --projects`: scans every project under `dir` (see
+    /// [`discover_projects`](crate::core::fleet::discover_projects)) with
+    /// `args`, prints one table and, with `export`, the combined JSON. Fails
+    /// when a project could not be scanned, else with the critical issues.
+    async fn run_fleet_scan(
+        &self,
+        dir: &Path,
+        args: &[String],
+        max_parallel: usize,
+        export: Option<&str>,
+    ) -> Result<()> {
+        use crate::core::fleet;
+        let projects = fleet::discover_projects(dir, &self.options);
+        if projects.is_empty() {
+            anyhow::bail!("No se encontró ningún Cargo.toml bajo {}", dir.display());
+        }
+        println!(
+            "{}",
+            format!("🔍 Escaneando {} proyectos ({max_parallel} a la vez)...", projects.len())
+                .cyan()
+                .bold()
+        );
+        let exe = std::env::current_exe()?;
+        let weights = crate::config::TraeConfig::load().score;
+        let scans =
+            fleet::scan_projects(&exe, &projects, args, max_parallel, &weights, &self.options)
+                .await;
+        fleet::print_table(&scans);
+        if let Some(path) = export {
+            fleet::export(path, &scans)?;
+            let path = crate::config::full_path(path);
+            println!("{}", format!("📁 Reporte exportado a: {path}").green());
+        }
+        let failed = scans.iter().filter(|scan| scan.error.is_some()).count();
+        if failed > 0 {
+            anyhow::bail!("{failed} de {} proyectos no se pudieron escanear", scans.len());
+        }
+        let severity = crate::core::severity::SeverityFilter::from_options(&self.options);
+        let failing: usize =
+            scans.iter().filter_map(|scan| scan.counts).map(|c| c.failing(&severity)).sum();
+        if failing > 0 {
+            let what = severity.describe_failing("issues críticos en los proyectos", "issues");
+            return Err(crate::exit::findings(failing, what));
+        }
         Ok(())
     }
-    #[doc = "Method documentation added by AI refactor"]
+    /// `trae scan --watch`: one local scan, then after every burst of changes
+    /// a re-scan and the issues it introduced or resolved. Runs until Ctrl+C.
+    async fn run_scan_watch(
+        &self,
+        deps: bool,
+        dead_code: bool,
+        multilang: bool,
+        critical_only: bool,
+    ) -> Result<()> {
+        let rules = multilang
+            .then(|| crate::core::multilang::MultilangRules::load(Path::new(".")))
+            .transpose()?;
+        let multilang = rules.as_ref();
+        let mut changes =
+            crate::commands::watch::ChangeWatcher::project(Duration::from_millis(300), None)?;
+        let mut issues = self.scan_local(deps, dead_code, multilang, critical_only);
+        println!(
+            "{} {}",
+            "👀 Scan en modo watch:".cyan().bold(),
+            ScanCounts::of(&issues, &[])
+        );
+        println!("{}", format!("🔔 Modo de watch: {}", changes.mode()).dimmed());
+        println!("{}", "⌛ Esperando cambios...".dimmed());
+        for run in 1.. {
+            let changed = changes.changed().await;
+            if changed.is_empty() {
+                break;
+            }
+            let next = self.rescan_changed(&issues, &changed, deps, dead_code, multilang, critical_only);
+            let diff = IssueDiff::between(&issues, &next);
+            println!(
+                "🔁 Re-scan #{run:02} ({} archivo(s)): {} nuevos, {} resueltos → {}",
+                changed.len(),
+                format!("+{}", diff.introduced.len()).red(),
+                format!("-{}", diff.resolved.len()).green(),
+                ScanCounts::of(&next, &[])
+            );
+            for issue in &diff.introduced {
+                println!("  {} {}", "+".red().bold(), issue_location(issue));
+            }
+            for issue in &diff.resolved {
+                println!("  {} {}", "-".green().bold(), issue_location(issue).dimmed());
+            }
+            issues = next;
+            println!("{}", "⌛ Esperando cambios...".dimmed());
+        }
+        Ok(())
+    }
+    /// Every local scanner `scan` would run without JARVIX, without printing.
+    fn scan_local(
+        &self,
+        deps: bool,
+        dead_code: bool,
+        multilang: Option<&crate::core::multilang::MultilangRules>,
+        critical_only: bool,
+    ) -> Vec<crate::core::analyzer::AnalysisIssue> {
+        let mut severity = crate::core::severity::SeverityFilter::from_options(&self.options);
+        let mut issues =
+            self.scan_rust_project(critical_only, &mut IssueCap::default(), &mut severity).0;
+        if deps {
+            issues.extend(self.scan_dependencies());
+        }
+        if dead_code {
+            issues.extend(self.scan_dead_code());
+        }
+        if let Some(rules) = multilang {
+            issues.extend(self.scan_multilang(rules));
+        }
+        issues.extend(self.scan_build_artifacts());
+        if critical_only {
+            issues.retain(crate::core::analyzer::AnalysisIssue::is_critical);
+        }
+        let mut issues = severity.filter(self.confident(issues));
+        crate::core::analyzer::sort_issues(&mut issues);
+        issues
+    }
+    /// `previous` updated for `changed`: when every changed path is a source
+    /// file only those files are re-scanned, anything else (Cargo.toml, a new
+    /// backup file, a renamed directory) falls back to [`Self::scan_local`].
+    fn rescan_changed(
+        &self,
+        previous: &[crate::core::analyzer::AnalysisIssue],
+        changed: &[PathBuf],
+        deps: bool,
+        dead_code: bool,
+        multilang: Option<&crate::core::multilang::MultilangRules>,
+        critical_only: bool,
+    ) -> Vec<crate::core::analyzer::AnalysisIssue> {
+        let is_source = |path: &PathBuf| {
+            path.extension().is_some_and(|ext| ext == "rs")
+                || multilang.is_some_and(|rules| rules.file_type(path).is_some())
+        };
+        if !changed.iter().all(is_source) {
+            return self.scan_local(deps, dead_code, multilang, critical_only);
+        }
+        let changed: Vec<PathBuf> = changed.iter().map(|p| Path::new(".").join(p)).collect();
+        let mut issues: Vec<_> = previous
+            .iter()
+            .filter(|i| !i.file.as_deref().is_some_and(|f| changed.iter().any(|c| Path::new(f) == c)))
+            .cloned()
+            .collect();
+        let markers = crate::core::markers::TodoMarkers::load();
+        let limit = self.options.max_file_size();
+        let oversized = &self.options.oversized;
+        let admitted = |p: &&PathBuf| crate::utils::path::within_size_limit(p, limit, oversized);
+        for path in changed.iter().filter(admitted) {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                issues.extend(scan_rust_file(path, critical_only, &markers).0);
+                if dead_code {
+                    issues.extend(scan_dead_code_file(path, self.mock_check()));
+                }
+            } else if let Some(rules) = multilang {
+                issues.extend(rules.check_file(path));
+            }
+        }
+        if critical_only {
+            issues.retain(crate::core::analyzer::AnalysisIssue::is_critical);
+        }
+        let mut severity = crate::core::severity::SeverityFilter::from_options(&self.options);
+        let mut issues = severity.filter(self.confident(issues));
+        crate::core::analyzer::sort_issues(&mut issues);
+        issues
+    }
+    /// Issues and suggestions of every Rust file, stopping at the first file
+    /// `cap` has no room left for.
     fn scan_rust_project(
         &self,
         critical_only: bool,
+        cap: &mut IssueCap,
+        severity: &mut crate::core::severity::SeverityFilter,
     ) -> (
         Vec<crate::core::analyzer::AnalysisIssue>,
         Vec<crate::core::analyzer::OptimizationSuggestion>,
     ) {
-        use walkdir::WalkDir;
         let mut issues = Vec::new();
         let mut suggestions = Vec::new();
-        for entry in WalkDir::new(".")
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
-            let path = entry.path();
-            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    for (line_num, line) in content.lines().enumerate() {
-                        if line.contains("TODO:")
-                            || line.contains("FIXME:")
-                            || line.contains("XXX:")
-                        {
-                            let severity = if line.contains("FIXME:") {
-                                crate::core::analyzer::IssueSeverity::Critical
-                            } else if line.contains("XXX:") {
-                                crate::core::analyzer::IssueSeverity::Warning
-                            } else {
-                                crate::core::analyzer::IssueSeverity::Info
-                            };
-                            if !critical_only
-                                || matches!(
-                                    severity,
-                                    crate::core::analyzer::IssueSeverity::Critical
-                                )
-                            {
-                                issues.push(crate::core::analyzer::AnalysisIssue {
-                                    category: "Code Quality".to_string(),
-                                    description: format!(
-                                        "{} en línea {}: {}",
-                                        if line.contains("FIXME:") {
-                                            "FIXME"
-                                        } else if line.contains("XXX:") {
-                                            "XXX"
-                                        } else {
-                                            "TODO"
-                                        },
-                                        line_num + 1,
-                                        line.trim()
-                                    ),
-                                    severity,
-                                    file: Some(path.to_string_lossy().to_string()),
-                                    line: Some(line_num + 1),
-                                });
-                            }
-                        }
-                        if line.contains("panic!") {
-                            issues.push(crate::core::analyzer::AnalysisIssue {
-                                category: "Safety".to_string(),
-                                description: format!(
-                                    "panic! macro en línea {}: {}",
-                                    line_num + 1,
-                                    line.trim()
-                                ),
-                                severity: crate::core::analyzer::IssueSeverity::Critical,
-                                file: Some(path.to_string_lossy().to_string()),
-                                line: Some(line_num + 1),
-                            });
-                        }
-                        if line.contains("unwrap()") && !line.contains("//") {
-                            let severity = if content.matches("unwrap()").count() > 10 {
-                                crate::core::analyzer::IssueSeverity::Critical
-                            } else {
-                                crate::core::analyzer::IssueSeverity::Warning
-                            };
-                            if !critical_only
-                                || matches!(
-                                    severity,
-                                    crate::core::analyzer::IssueSeverity::Critical
-                                )
-                            {
-                                issues.push(crate::core::analyzer::AnalysisIssue {
-                                    category: "Safety".to_string(),
-                                    description: format!(
-                                        "unwrap() en línea {}: {}",
-                                        line_num + 1,
-                                        line.trim()
-                                    ),
-                                    severity,
-                                    file: Some(path.to_string_lossy().to_string()),
-                                    line: Some(line_num + 1),
-                                });
-                            }
-                        }
-                    }
-                    let lines = content.lines().count();
-                    if lines > 1000 {
-                        suggestions.push(crate::core::analyzer::OptimizationSuggestion {
-                            description: format!(
-                                "Archivo muy grande ({lines} líneas) - Considerar refactorizar"
-                            ),
-                            impact: crate::core::analyzer::OptimizationImpact::High,
-                            effort: crate::core::analyzer::OptimizationEffort::High,
-                            file: Some(path.to_string_lossy().to_string()),
-                            line: None,
-                        });
-                    }
-                }
+        let markers = crate::core::markers::TodoMarkers::load();
+        for path in project_files(&["rs"], &self.options) {
+            if cap.is_full() {
+                break;
             }
+            let (file_issues, file_suggestions) = scan_rust_file(&path, critical_only, &markers);
+            cap.extend(&mut issues, severity.filter(self.confident(file_issues)));
+            suggestions.extend(file_suggestions);
         }
         (issues, suggestions)
     }
     #[doc = "Method documentation added by AI refactor"]
     fn scan_dependencies(&self) -> Vec<crate::core::analyzer::AnalysisIssue> {
         let mut issues = Vec::new();
-        if let Ok(content) = std::fs::read_to_string("Cargo.toml") {
-            for (line_num, line) in content.lines().enumerate() {
-                if line.contains('=')
-                    && !line.contains("version")
-                    && !line.contains('[')
-                    && !line.contains('#')
-                    && (line.contains(".git") || line.contains("path ="))
-                {
-                    issues.push(crate::core::analyzer::AnalysisIssue {
-                        category: "Dependencies".to_string(),
-                        description: format!(
-                            "Dependencia sin versión fija en línea {}: {}",
-                            line_num + 1,
-                            line.trim()
-                        ),
-                        severity: crate::core::analyzer::IssueSeverity::Warning,
-                        file: Some("Cargo.toml".to_string()),
-                        line: Some(line_num + 1),
-                    });
-                }
+        let content = std::fs::read_to_string("Cargo.toml").unwrap_or_default();
+        if let Ok(manifest) = crate::core::manifest::Manifest::parse(&content) {
+            for (dep, _) in manifest.specs().filter(|(_, spec)| spec.is_unversioned_source()) {
+                let line = crate::core::manifest::declaration_line(&content, &dep.name);
+                issues.push(crate::core::analyzer::AnalysisIssue {
+                    category: "Dependencies".to_string(),
+                    description: format!("Dependencia sin versión fija (path/git): {}", dep.name),
+                    severity: crate::core::analyzer::IssueSeverity::Warning,
+                    file: Some("Cargo.toml".to_string()),
+                    line,
+                    confidence: crate::core::analyzer::FULL_CONFIDENCE,
+                });
             }
         }
         if !std::path::Path::new("Cargo.lock").exists() {
@@ -565,151 +1221,48 @@ impl TraeCli {
                 severity: crate::core::analyzer::IssueSeverity::Warning,
                 file: None,
                 line: None,
+                confidence: crate::core::analyzer::FULL_CONFIDENCE,
             });
         }
         issues
     }
     #[doc = "Method documentation added by AI refactor"]
     fn scan_dead_code(&self) -> Vec<crate::core::analyzer::AnalysisIssue> {
-        use walkdir::WalkDir;
-        let mut issues = Vec::new();
-        for entry in WalkDir::new(".")
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
-            let path = entry.path();
-            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    for (line_num, line) in content.lines().enumerate() {
-                        if line.contains("mock") || line.contains("Mock") || line.contains("MOCK") {
-                            issues.push(crate::core::analyzer::AnalysisIssue {
-                                category: "Code Quality".to_string(),
-                                description: format!(
-                                    "Posible código mock en línea {}: {}",
-                                    line_num + 1,
-                                    line.trim()
-                                ),
-                                severity: crate::core::analyzer::IssueSeverity::Info,
-                                file: Some(path.to_string_lossy().to_string()),
-                                line: Some(line_num + 1),
-                            });
-                        }
-                        if line.contains("#[allow(dead_code)]") {
-                            issues.push(crate::core::analyzer::AnalysisIssue {
-                                category: "Code Quality".to_string(),
-                                description: format!(
-                                    "Código marcado como dead_code en línea {}",
-                                    line_num + 1
-                                ),
-                                severity: crate::core::analyzer::IssueSeverity::Info,
-                                file: Some(path.to_string_lossy().to_string()),
-                                line: Some(line_num + 1),
-                            });
-                        }
-                    }
-                }
-            }
-        }
-        issues
+        let mock_check = self.mock_check();
+        project_files(&["rs"], &self.options)
+            .iter()
+            .flat_map(|path| scan_dead_code_file(path, mock_check))
+            .collect()
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn scan_multilang(&self) -> Vec<crate::core::analyzer::AnalysisIssue> {
-        use walkdir::WalkDir;
-        let mut issues = Vec::new();
-        for entry in WalkDir::new(".")
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
-            let path = entry.path();
-            if path.is_file() {
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                match ext {
-                    "js" | "ts" | "jsx" | "tsx" => {
-                        if let Ok(content) = std::fs::read_to_string(path) {
-                            for (line_num, line) in content.lines().enumerate() {
-                                if line.contains("console.log") && !line.trim().starts_with("//") {
-                                    issues.push(crate::core::analyzer::AnalysisIssue {
-                                        category: "Code Quality".to_string(),
-                                        description: format!(
-                                            "console.log en archivo JS línea {}: {}",
-                                            line_num + 1,
-                                            line.trim()
-                                        ),
-                                        severity: crate::core::analyzer::IssueSeverity::Info,
-                                        file: Some(path.to_string_lossy().to_string()),
-                                        line: Some(line_num + 1),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    "py" => {
-                        if let Ok(content) = std::fs::read_to_string(path) {
-                            for (line_num, line) in content.lines().enumerate() {
-                                if line.contains("print(") && !line.trim().starts_with('#') {
-                                    issues.push(crate::core::analyzer::AnalysisIssue {
-                                        category: "Code Quality".to_string(),
-                                        description: format!(
-                                            "print() en archivo Python línea {}: {}",
-                                            line_num + 1,
-                                            line.trim()
-                                        ),
-                                        severity: crate::core::analyzer::IssueSeverity::Info,
-                                        file: Some(path.to_string_lossy().to_string()),
-                                        line: Some(line_num + 1),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    "go" => {
-                        if let Ok(content) = std::fs::read_to_string(path) {
-                            for (line_num, line) in content.lines().enumerate() {
-                                if line.contains("fmt.Println") && !line.trim().starts_with("//") {
-                                    issues.push(crate::core::analyzer::AnalysisIssue {
-                                        category: "Code Quality".to_string(),
-                                        description: format!(
-                                            "fmt.Println en archivo Go línea {}: {}",
-                                            line_num + 1,
-                                            line.trim()
-                                        ),
-                                        severity: crate::core::analyzer::IssueSeverity::Info,
-                                        file: Some(path.to_string_lossy().to_string()),
-                                        line: Some(line_num + 1),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        issues
+    fn scan_multilang(
+        &self,
+        rules: &crate::core::multilang::MultilangRules,
+    ) -> Vec<crate::core::analyzer::AnalysisIssue> {
+        project_files_where(|path| rules.file_type(path).is_some(), &self.options)
+            .iter()
+            .flat_map(|path| rules.check_file(path))
+            .collect()
     }
     #[doc = "Method documentation added by AI refactor"]
     fn scan_build_artifacts(&self) -> Vec<crate::core::analyzer::AnalysisIssue> {
         let mut issues = Vec::new();
-        if std::path::Path::new("target").exists() {
-            if let Ok(entries) = std::fs::read_dir("target") {
-                let mut total_size = 0u64;
-                for entry in entries.flatten() {
-                    if let Ok(metadata) = entry.metadata() {
-                        total_size += metadata.len();
-                    }
-                }
-                if total_size > 2_000_000_000 {
-                    issues.push(crate::core::analyzer::AnalysisIssue {
-                        category: "Build".to_string(),
-                        description: format!(
-                            "Directorio target muy grande ({:.1} GB) - Ejecutar 'cargo clean'",
-                            total_size as f64 / 1_000_000_000.0
-                        ),
-                        severity: crate::core::analyzer::IssueSeverity::Warning,
-                        file: Some("target/".to_string()),
-                        line: None,
-                    });
-                }
+        let target_dir = self.options.target_dir();
+        if target_dir.is_dir() {
+            let total_size = crate::utils::path::dir_size(&target_dir);
+            let threshold = crate::config::TraeConfig::load().analysis.target_warn_mb * 1_000_000;
+            if total_size > threshold {
+                issues.push(crate::core::analyzer::AnalysisIssue {
+                    category: "Build".to_string(),
+                    description: format!(
+                        "Directorio target muy grande ({:.1} GB) - Ejecutar 'cargo clean'",
+                        total_size as f64 / 1_000_000_000.0
+                    ),
+                    severity: crate::core::analyzer::IssueSeverity::Warning,
+                    file: Some(format!("{}/", portable(&target_dir))),
+                    line: None,
+                    confidence: crate::core::analyzer::FULL_CONFIDENCE,
+                });
             }
         }
         if let Ok(entries) = std::fs::read_dir(".") {
@@ -724,8 +1277,9 @@ impl TraeCli {
                             category: "Build".to_string(),
                             description: format!("Archivo temporal/backup encontrado: {name}"),
                             severity: crate::core::analyzer::IssueSeverity::Info,
-                            file: Some(path.to_string_lossy().to_string()),
+                            file: Some(portable(&path)),
                             line: None,
+                            confidence: 0.8,
                         });
                     }
                 }
@@ -734,11 +1288,15 @@ impl TraeCli {
         issues
     }
     #[doc = "Method documentation added by AI refactor"]
+    #[allow(clippy::too_many_arguments)]
     fn generate_scan_report(
         &self,
         issues: &[crate::core::analyzer::AnalysisIssue],
+        snippets: &[Option<Snippet>],
         suggestions: &[crate::core::analyzer::OptimizationSuggestion],
         export: Option<&str>,
+        cap: &IssueCap,
+        severity: &crate::core::severity::SeverityFilter,
         metrics: &mut crate::metrics::collector::MetricsCollector,
     ) -> Result<()> {
         use colored::Colorize;
@@ -760,13 +1318,29 @@ impl TraeCli {
             "{}",
             format!("📈 Issues encontrados: {}", issues.len()).yellow()
         );
-        println!("  🔴 Críticos: {} ", critical_issues.len());
-        println!("  🟡 Advertencias: {}", warning_issues.len());
-        println!("  🔵 Informativos: {}", info_issues.len());
-        println!("  💡 Sugerencias: {}\n", suggestions.len());
-        if !critical_issues.is_empty() {
+        let counts = ScanCounts::of(issues, suggestions);
+        println!("  🔴 Críticos: {} ", counts.critical);
+        println!("  🟡 Advertencias: {}", counts.warning);
+        println!("  🔵 Informativos: {}", counts.info);
+        println!("  💡 Sugerencias: {}\n", counts.suggestions);
+        let marker_counts = crate::core::markers::TodoMarkers::load().counts(issues);
+        if !marker_counts.is_empty() {
+            let counts: Vec<String> =
+                marker_counts.iter().map(|(marker, n)| format!("{marker} {n}")).collect();
+            println!("📝 Marcadores: {}\n", counts.join(" · "));
+        }
+        for notice in [cap.notice(), severity.notice()].into_iter().flatten() {
+            println!("{}\n", notice.yellow());
+        }
+        let groups = self.group_by().map(|by| crate::core::analyzer::group_issues(issues, by));
+        if let Some(groups) = &groups {
+            print_issue_groups(issues, snippets, groups, &self.options);
+        } else if !critical_issues.is_empty() {
             println!("{}", "🔴 ISSUES CRÍTICOS:".red().bold());
-            for issue in &critical_issues {
+            for (issue, snippet) in issues.iter().zip(snippets) {
+                if !issue.is_critical() {
+                    continue;
+                }
                 if let (Some(file), Some(line)) = (&issue.file, issue.line) {
                     println!(
                         "  ❗ {}: {} ({}:{})",
@@ -775,6 +1349,9 @@ impl TraeCli {
                 } else {
                     println!("  ❗ {}: {}", issue.category, issue.description);
                 }
+                if let Some(snippet) = snippet {
+                    snippet.print(&self.options);
+                }
             }
             println!();
         }
@@ -783,11 +1360,24 @@ impl TraeCli {
         metrics.add_custom_metric("critical_count".to_string(), critical_issues.len() as u64);
         metrics.finish();
         if let Some(export_path) = export {
-            let report = serde_json :: json ! ({ "timestamp" : chrono :: Utc :: now () , "total_issues" : issues . len () , "critical_issues" : critical_issues . len () , "warning_issues" : warning_issues . len () , "info_issues" : info_issues . len () , "suggestions" : suggestions . len () , "issues" : issues , "suggestions" : suggestions });
+            let mut report = serde_json :: json ! ({ "schema_version" : crate::core::report::SCHEMA_VERSION , "timestamp" : chrono :: Utc :: now () , "total_issues" : issues . len () , "critical_issues" : critical_issues . len () , "warning_issues" : warning_issues . len () , "info_issues" : info_issues . len () , "suggestions" : suggestions . len () , "issues" : with_snippets (issues , snippets) ? , "suggestions" : suggestions });
+            report["markers"] = json!(marker_counts);
+            if let Some(threshold) = severity.threshold() {
+                report["severity_threshold"] = threshold.name().into();
+                report["suppressed"] = severity.suppressed().into();
+            }
+            if let (Some(by), Some(groups)) = (self.group_by(), &groups) {
+                report["group_by"] = by.into();
+                report["groups"] = issue_groups_json(issues, snippets, groups)?;
+            }
+            if let Some(max) = cap.truncated_at() {
+                report["truncated_at"] = max.into();
+            }
             std::fs::write(export_path, serde_json::to_string_pretty(&report)?)?;
             println!(
                 "{}",
-                format!("📁 Reporte exportado a: {export_path}").green()
+                format!("📁 Reporte exportado a: {}", crate::config::full_path(export_path))
+                    .green()
             );
         }
         if critical_issues.is_empty() {
@@ -817,13 +1407,422 @@ impl TraeCli {
             );
             return Ok(());
         }
+        if self.dry_run {
+            return crate::commands::cargo::dry_run(args);
+        }
         println!(
             "{}",
             format!("🚀 Passthrough cargo: {}", args.join(" "))
                 .cyan()
                 .bold()
         );
-        let executor = CargoExecutor::new().with_working_dir(".");
-        executor.execute_streaming(args).await
+        let executor = CargoExecutor::new(&self.options).with_working_dir(".");
+        // A cargo run that fails (build error, failing test) is a quality gate
+        // failure, as in the old standalone binary; not finding cargo is not.
+        executor.execute_streaming(args).await.map_err(|e| {
+            if e.downcast_ref::<std::io::Error>().is_some() {
+                e
+            } else {
+                crate::exit::findings(1, format!("cargo {} falló ({e})", args[0]))
+            }
+        })
+    }
+}
+/// The `scan --group-by` listing: a heading with its count per group, then
+/// every issue of the group with its snippet.
+fn print_issue_groups(
+    issues: &[crate::core::analyzer::AnalysisIssue],
+    snippets: &[Option<Snippet>],
+    groups: &[(String, Vec<usize>)],
+    options: &crate::config::RunOptions,
+) {
+    use colored::Colorize;
+    for (heading, members) in groups {
+        println!("{}", format!("📂 {heading} ({})", members.len()).cyan().bold());
+        for &i in members {
+            let issue = &issues[i];
+            let badge = crate::utils::markdown::badge(&format!("{:?}", issue.severity));
+            match (&issue.file, issue.line) {
+                (Some(file), Some(line)) => println!(
+                    "  {badge} {}: {} ({file}:{line})",
+                    issue.category, issue.description
+                ),
+                _ => println!("  {badge} {}: {}", issue.category, issue.description),
+            }
+            if let Some(Some(snippet)) = snippets.get(i) {
+                snippet.print(options);
+            }
+        }
+        println!();
+    }
+}
+/// `groups` for the `--export` of `scan --group-by`: each heading as
+/// `group`, its `count` and its `issues`, snippets included.
+fn issue_groups_json(
+    issues: &[crate::core::analyzer::AnalysisIssue],
+    snippets: &[Option<Snippet>],
+    groups: &[(String, Vec<usize>)],
+) -> serde_json::Result<serde_json::Value> {
+    let mut values = Vec::with_capacity(groups.len());
+    for (heading, members) in groups {
+        let issues: Vec<_> = members.iter().map(|&i| issues[i].clone()).collect();
+        let snippets: Vec<_> =
+            members.iter().map(|&i| snippets.get(i).cloned().flatten()).collect();
+        values.push(json!({
+            "group": heading,
+            "count": members.len(),
+            "issues": with_snippets(&issues, &snippets)?,
+        }));
+    }
+    Ok(values.into())
+}
+/// TODO-style `markers`, `panic!` and `unwrap()` in one Rust file, plus a
+/// refactor suggestion when it is over 1000 lines.
+fn scan_rust_file(
+    path: &Path,
+    critical_only: bool,
+    markers: &crate::core::markers::TodoMarkers,
+) -> (
+    Vec<crate::core::analyzer::AnalysisIssue>,
+    Vec<crate::core::analyzer::OptimizationSuggestion>,
+) {
+    let mut issues = Vec::new();
+    let mut suggestions = Vec::new();
+    if let Ok(content) = std::fs::read_to_string(path) {
+        let code = crate::utils::source::mask_comments_and_strings(&content);
+        let code_lines: Vec<&str> = code.lines().collect();
+        let comments = crate::utils::source::mask_code_and_strings(&content);
+        let comment_lines: Vec<&str> = comments.lines().collect();
+        for (line_num, line) in content.lines().enumerate() {
+            // Comments and strings are blanked in `code_lines`: a hit there is real code
+            let in_code =
+                |needle: &str| code_lines.get(line_num).is_some_and(|c| c.contains(needle));
+            let comment = comment_lines.get(line_num).copied().unwrap_or_default();
+            if let Some((marker, severity, noted)) = markers.find(line, comment) {
+                if !critical_only
+                    || matches!(
+                        severity,
+                        crate::core::analyzer::IssueSeverity::Critical
+                    )
+                {
+                    issues.push(crate::core::analyzer::AnalysisIssue {
+                        category: "Code Quality".to_string(),
+                        description: crate::core::markers::describe(marker, line_num + 1, line),
+                        severity: severity.clone(),
+                        file: Some(portable(path)),
+                        line: Some(line_num + 1),
+                        // In a comment it is a note; anywhere else, likely data
+                        confidence: if noted { 0.9 } else { 0.5 },
+                    });
+                }
+            }
+            if line.contains("panic!") {
+                issues.push(crate::core::analyzer::AnalysisIssue {
+                    category: "Safety".to_string(),
+                    description: format!(
+                        "panic! macro en línea {}: {}",
+                        line_num + 1,
+                        line.trim()
+                    ),
+                    severity: crate::core::analyzer::IssueSeverity::Critical,
+                    file: Some(portable(path)),
+                    line: Some(line_num + 1),
+                    confidence: if in_code("panic!") { 0.9 } else { 0.3 },
+                });
+            }
+            let call = panicking_call(line).filter(|_| !line.contains("//"));
+            if let Some(call) = call {
+                let severity = match call {
+                    PanicCall::Expect => crate::core::analyzer::IssueSeverity::Info,
+                    _ if content.matches("unwrap()").count() > 10 => {
+                        crate::core::analyzer::IssueSeverity::Critical
+                    }
+                    _ => crate::core::analyzer::IssueSeverity::Warning,
+                };
+                // A message says the panic was thought about; a string is no call at all
+                let confidence = match call {
+                    _ if !in_code("unwrap()") && !in_code(".expect(") => 0.3,
+                    PanicCall::Expect => 0.6,
+                    _ => 0.9,
+                };
+                let call = match call {
+                    PanicCall::Unwrap => "unwrap()",
+                    PanicCall::EmptyExpect => "expect() sin mensaje",
+                    PanicCall::Expect => "expect()",
+                };
+                if !critical_only
+                    || matches!(
+                        severity,
+                        crate::core::analyzer::IssueSeverity::Critical
+                    )
+                {
+                    issues.push(crate::core::analyzer::AnalysisIssue {
+                        category: "Safety".to_string(),
+                        description: format!(
+                            "{call} en línea {}: {}",
+                            line_num + 1,
+                            line.trim()
+                        ),
+                        severity,
+                        file: Some(portable(path)),
+                        line: Some(line_num + 1),
+                        confidence,
+                    });
+                }
+            }
+        }
+        let lines = content.lines().count();
+        if lines > 1000 {
+            suggestions.push(crate::core::analyzer::OptimizationSuggestion {
+                description: format!(
+                    "Archivo muy grande ({lines} líneas) - Considerar refactorizar"
+                ),
+                impact: crate::core::analyzer::OptimizationImpact::High,
+                effort: crate::core::analyzer::OptimizationEffort::High,
+                file: Some(portable(path)),
+                line: None,
+            });
+        }
+    }
+    (issues, suggestions)
+}
+/// Mock code and `#[allow(dead_code)]` markers in one Rust file. Mocks are
+/// only looked for with `mock_check`, and never in test code (`tests/`,
+/// `benches/`, `#[cfg(test)]` items), comments or strings.
+fn scan_dead_code_file(path: &Path, mock_check: bool) -> Vec<crate::core::analyzer::AnalysisIssue> {
+    let mut issues = Vec::new();
+    if let Ok(content) = std::fs::read_to_string(path) {
+        let mocks = if mock_check && !crate::utils::source::is_test_path(path) {
+            crate::utils::source::mock_lines(&content)
+        } else {
+            Vec::new()
+        };
+        let code = crate::utils::source::mask_comments_and_strings(&content);
+        let code_lines: Vec<&str> = code.lines().collect();
+        for (line_num, line) in content.lines().enumerate() {
+            if mocks.contains(&(line_num + 1)) {
+                issues.push(crate::core::analyzer::AnalysisIssue {
+                    category: "Code Quality".to_string(),
+                    description: format!(
+                        "Posible código mock en línea {}: {}",
+                        line_num + 1,
+                        line.trim()
+                    ),
+                    severity: crate::core::analyzer::IssueSeverity::Info,
+                    file: Some(portable(path)),
+                    line: Some(line_num + 1),
+                    confidence: 0.6,
+                });
+            }
+            if line.contains("#[allow(dead_code)]") {
+                issues.push(crate::core::analyzer::AnalysisIssue {
+                    category: "Code Quality".to_string(),
+                    description: format!(
+                        "Código marcado como dead_code en línea {}",
+                        line_num + 1
+                    ),
+                    severity: crate::core::analyzer::IssueSeverity::Info,
+                    file: Some(portable(path)),
+                    line: Some(line_num + 1),
+                    confidence: if code_lines.get(line_num).is_some_and(|c| {
+                        c.contains("#[allow(dead_code)]")
+                    }) {
+                        crate::core::analyzer::FULL_CONFIDENCE
+                    } else {
+                        0.3
+                    },
+                });
+            }
+        }
+    }
+    issues
+}
+/// Files under the current directory with one of `extensions`, as `./`-prefixed
+/// paths (the form every scan issue reports), walked under `options`.
+fn project_files(extensions: &[&str], options: &crate::config::RunOptions) -> Vec<PathBuf> {
+    project_files_where(
+        |path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext))
+        },
+        options,
+    )
+}
+/// Files under the current directory for which `keep` holds, like
+/// [`project_files`].
+fn project_files_where(
+    keep: impl Fn(&Path) -> bool,
+    options: &crate::config::RunOptions,
+) -> Vec<PathBuf> {
+    let limit = options.max_file_size();
+    crate::utils::path::walk(Path::new("."), None, options)
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| path.is_file())
+        .filter(|path| keep(path))
+        .filter(|path| crate::utils::path::within_size_limit(path, limit, &options.oversized))
+        .collect()
+}
+/// Issues listed in the result of a JARVIXSERVER scan job, under `category`.
+fn remote_issues(
+    result: &serde_json::Value,
+    category: &str,
+) -> Vec<crate::core::analyzer::AnalysisIssue> {
+    result
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|issue| {
+            let description = issue.get("description").and_then(|d| d.as_str())?;
+            Some(crate::core::analyzer::AnalysisIssue {
+                category: category.to_string(),
+                description: description.to_string(),
+                severity: match issue.get("severity").and_then(|s| s.as_str()) {
+                    Some("critical") => crate::core::analyzer::IssueSeverity::Critical,
+                    Some("warning") => crate::core::analyzer::IssueSeverity::Warning,
+                    _ => crate::core::analyzer::IssueSeverity::Info,
+                },
+                file: issue
+                    .get("file")
+                    .and_then(|f| f.as_str())
+                    .map(std::string::ToString::to_string),
+                line: issue
+                    .get("line")
+                    .and_then(serde_json::Value::as_u64)
+                    .map(|l| l as usize),
+                confidence: issue
+                    .get("confidence")
+                    .and_then(serde_json::Value::as_f64)
+                    .filter(|c| (0.0..=1.0).contains(c))
+                    .unwrap_or(crate::core::analyzer::FULL_CONFIDENCE),
+            })
+        })
+        .collect()
+}
+/// `file:line description` for watch-mode diff lines.
+fn issue_location(issue: &crate::core::analyzer::AnalysisIssue) -> String {
+    match (&issue.file, issue.line) {
+        (Some(file), Some(line)) => format!("{file}:{line} {}", issue.description),
+        (Some(file), None) => format!("{file} {}", issue.description),
+        (None, _) => issue.description.clone(),
+    }
+}
+/// Issues introduced and resolved between two scans of `trae scan --watch`.
+/// Issues are matched by category, file, severity and description with the
+/// line number taken out, so an edit that only moves an issue reports nothing.
+#[derive(Debug, Default)]
+pub struct IssueDiff {
+    pub introduced: Vec<crate::core::analyzer::AnalysisIssue>,
+    pub resolved: Vec<crate::core::analyzer::AnalysisIssue>,
+}
+impl IssueDiff {
+    #[doc = " Compara dos escaneos (cada issue repetido cuenta por separado)"]
+    pub fn between(
+        before: &[crate::core::analyzer::AnalysisIssue],
+        after: &[crate::core::analyzer::AnalysisIssue],
+    ) -> Self {
+        let unmatched = |from: &[crate::core::analyzer::AnalysisIssue],
+                         against: &[crate::core::analyzer::AnalysisIssue]| {
+            let mut remaining: HashMap<_, usize> = HashMap::new();
+            for issue in against {
+                *remaining.entry(issue_key(issue)).or_default() += 1;
+            }
+            from.iter()
+                .filter(|issue| match remaining.get_mut(&issue_key(issue)) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                })
+                .cloned()
+                .collect()
+        };
+        Self {
+            introduced: unmatched(after, before),
+            resolved: unmatched(before, after),
+        }
+    }
+}
+/// Identity of an issue across scans, ignoring where in its file it is.
+fn issue_key(issue: &crate::core::analyzer::AnalysisIssue) -> (String, Option<String>, String, String) {
+    let description = match issue.line {
+        Some(line) => issue.description.replacen(&format!("línea {line}"), "línea", 1),
+        None => issue.description.clone(),
+    };
+    (
+        issue.category.clone(),
+        issue.file.clone(),
+        format!("{:?}", issue.severity),
+        description,
+    )
+}
+/// How `trae scan` prints its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanOutput {
+    Report,
+    CountLine,
+    CountJson,
+    Lsp,
+    Markdown,
+}
+/// Issue counts by severity, shared by the full report and `--count-only`
+/// so both always agree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ScanCounts {
+    pub critical: usize,
+    pub warning: usize,
+    pub info: usize,
+    pub suggestions: usize,
+    /// Issues hidden by `--severity`, left out of the counts above.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub suppressed: usize,
+}
+impl ScanCounts {
+    /// Counts of `issues` by severity, plus the number of `suggestions`.
+    pub fn of(
+        issues: &[crate::core::analyzer::AnalysisIssue],
+        suggestions: &[crate::core::analyzer::OptimizationSuggestion],
+    ) -> Self {
+        use crate::core::analyzer::IssueSeverity;
+        let count = |severity: fn(&IssueSeverity) -> bool| {
+            issues.iter().filter(|i| severity(&i.severity)).count()
+        };
+        Self {
+            critical: count(|s| matches!(s, IssueSeverity::Critical)),
+            warning: count(|s| matches!(s, IssueSeverity::Warning)),
+            info: count(|s| matches!(s, IssueSeverity::Info)),
+            suggestions: suggestions.len(),
+            suppressed: 0,
+        }
+    }
+    /// How many of the counted issues fail a scan under `severity`.
+    pub fn failing(&self, severity: &crate::core::severity::SeverityFilter) -> usize {
+        use crate::core::analyzer::IssueSeverity;
+        [
+            (IssueSeverity::Critical, self.critical),
+            (IssueSeverity::Warning, self.warning),
+            (IssueSeverity::Info, self.info),
+        ]
+        .iter()
+        .filter(|(level, _)| severity.fails(level))
+        .map(|(_, count)| count)
+        .sum()
+    }
+}
+const fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+impl std::fmt::Display for ScanCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "critical={} warning={} info={} suggestions={}",
+            self.critical, self.warning, self.info, self.suggestions
+        )?;
+        if self.suppressed > 0 {
+            write!(f, " suppressed={}", self.suppressed)?;
+        }
+        Ok(())
     }
 }
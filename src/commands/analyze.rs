@@ -2,9 +2,21 @@
 #![doc = ""]
 #![doc = " Comando para análisis profundo del código y sugerencias de optimización"]
 use crate::cli::TraeCli;
+use crate::core::analyzer::{markdown_report, AnalysisIssue};
+use crate::core::baseline::{load_issues, rebase_issues, BaseWorktree, BaselineDiff};
+use crate::core::features::FeatureArgs;
+use crate::core::issue_cap::IssueCap;
+use crate::core::score::{duplication_factor, ScoreBreakdown};
+use crate::core::severity::SeverityFilter;
+use crate::error::TraeError;
+use crate::utils::path::{validate_path, WorkingDirGuard};
+use crate::utils::snippet::{parse_context, with_snippets, Snippets};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use tokio_util::sync::CancellationToken;
 #[doc = " Six Sigma Analysis Command - Herramienta de análisis profundo de calidad"]
 #[doc = ""]
 #[doc = " Esta estructura implementa un analizador de código Six Sigma completo que:"]
@@ -41,6 +53,32 @@ pub struct AnalyzeCommand {
     #[doc = "Write JSON summary to path"]
     #[arg(long, value_name = "PATH")]
     pub output: Option<String>,
+    #[doc = "Output format: text summary, one LSP-style JSON diagnostic per issue or a"]
+    #[doc = "Markdown report (summary table, collapsible section per category)"]
+    #[arg(long, default_value = "text", value_parser = ["text", "lsp", "markdown"])]
+    pub format: String,
+    #[doc = "Project directory to analyze (default: current directory)"]
+    #[arg(long, value_name = "DIR", value_parser = validate_path)]
+    pub path: Option<PathBuf>,
+    #[doc = "Diff issues against a `--output`/`scan --export` report; only new critical ones fail"]
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["format", "output"])]
+    pub baseline: Option<PathBuf>,
+    #[doc = "Diff issues against those of a git ref (branch, tag, commit), analyzed in a"]
+    #[doc = "temporary worktree; only new critical ones fail"]
+    #[arg(long, value_name = "REF", conflicts_with_all = ["format", "output", "baseline"])]
+    pub compare: Option<String>,
+    #[command(flatten)]
+    pub features: FeatureArgs,
+    #[doc = "Show how the quality score is made up: each penalty and the size bonus"]
+    #[arg(long, conflicts_with_all = ["baseline", "compare"])]
+    pub explain: bool,
+    #[doc = "Keep at most N issues of each severity in `--output` and `--format lsp|markdown`"]
+    #[arg(long, value_name = "N", conflicts_with_all = ["baseline", "compare"])]
+    pub max_issues: Option<NonZeroUsize>,
+    #[doc = "Include N lines of code around each issue (0-10) in `--output`, `--format lsp`"]
+    #[doc = "(`data.snippet`) and the new issues of `--baseline`/`--compare`"]
+    #[arg(long, value_name = "N", value_parser = parse_context)]
+    pub diff_context: Option<usize>,
 }
 impl AnalyzeCommand {
     #[doc = " Ejecuta el análisis Six Sigma completo del proyecto"]
@@ -62,21 +100,219 @@ impl AnalyzeCommand {
     #[doc = " - Progress indicators"]
     #[doc = " - Memory-efficient processing"]
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        // Read relative to where trae was started, before `--path` moves us
+        let baseline = self.baseline.as_deref().map(load_issues).transpose()?;
+        let _root = self.path.as_deref().map(WorkingDirGuard::enter).transpose()?;
+        let project = crate::core::workspace::find_root().unwrap_or_else(|| PathBuf::from("."));
+        let mut options = cli.options.clone();
+        self.features.activate(&project, &mut options);
+        let cap = IssueCap::new(self.max_issues.map(NonZeroUsize::get));
+        if self.format != "text" {
+            let snippets = Snippets::new(self.diff_context);
+            let markdown = self.format == "markdown";
+            return Self::run_formatted(markdown, cap, snippets, &options).await;
+        }
+        if let Some(baseline) = baseline {
+            let snippets = Snippets::new(self.diff_context);
+            let cancel = CancellationToken::new();
+            return Self::run_baseline(baseline, None, snippets, &cancel, &options).await;
+        }
+        if let Some(base) = &self.compare {
+            // Ctrl-C is caught once for the whole comparison, both analyses included
+            let cancel = CancellationToken::new();
+            let interrupt = tokio::spawn(cancel_on_ctrl_c(cancel.clone()));
+            let snippets = Snippets::new(self.diff_context);
+            let compared = match Self::analyze_ref(base, &cancel, &options).await {
+                Ok(baseline) => {
+                    Self::run_baseline(baseline, Some(base), snippets, &cancel, &options).await
+                }
+                Err(e) => Err(e),
+            };
+            interrupt.abort();
+            return compared;
+        }
         // Delegate to the API-friendly run_simple to keep behavior consistent
-        crate::commands::analyze::AnalyzeCommand::run_simple(
+        crate::commands::analyze::AnalyzeCommand::run_simple_cancellable(
             self.performance,
             self.security,
             self.quality,
-            cli.no_jarvix,
+            !cli.jarvix_enabled(),
             self.profile.clone(),
             self.force_refresh,
-            self.output.clone(),
+            options.report_path(self.output.as_deref(), "analyze-report.json"),
+            self.explain,
+            Some(cap),
+            self.diff_context,
+            None,
+            &options,
         )
         .await
     }
 
+    /// Analyzes the current directory and prints only diagnostics, or a
+    /// Markdown report with `markdown`, bypassing the summary cache (it
+    /// stores counts, not issues). The exit code counts every failing issue
+    /// (see [`SeverityFilter::fails`]), also those `cap` leaves out.
+    /// Diagnostics carry `snippets`.
+    async fn run_formatted(
+        markdown: bool,
+        mut cap: IssueCap,
+        mut snippets: Option<Snippets>,
+        options: &crate::config::RunOptions,
+    ) -> Result<()> {
+        let mut analyzer = crate::core::analyzer::ProjectAnalyzer::new();
+        let scan_options = options.clone();
+        let analysis = tokio::task::spawn_blocking(move || {
+            analyzer.analyze_project(".", &scan_options)
+        })
+        .await??;
+        let mut severity = SeverityFilter::from_options(options);
+        let mut issues = severity.filter(analysis.issues);
+        let failing = severity.failing(issues.iter().map(|i| &i.severity));
+        cap.retain(&mut issues);
+        if markdown {
+            let mut report =
+                markdown_report("📊 trae analyze", &issues, &analysis.suggestions);
+            report
+                .summary("📁 Files", analysis.files_count)
+                .summary("📏 Lines", analysis.total_lines);
+            for notice in [cap.notice(), severity.notice()].into_iter().flatten() {
+                report.note(notice);
+            }
+            print!("{}", report.render());
+        } else {
+            crate::utils::diagnostics::emit(issues.iter().map(|i| {
+                let snippet = snippets.as_mut().and_then(|s| s.take(i.file.as_deref(), i.line));
+                i.to_diagnostic().with_snippet(snippet)
+            }))?;
+            let snippet_notice = snippets.as_ref().and_then(Snippets::notice);
+            let notices = [cap.notice(), severity.notice(), snippet_notice];
+            for notice in notices.into_iter().flatten() {
+                eprintln!("{}", notice.yellow());
+            }
+        }
+        if failing > 0 {
+            let what = severity.describe_failing("issues críticos detectados", "issues");
+            return Err(crate::exit::findings(failing, what));
+        }
+        Ok(())
+    }
+
+    /// Issues of the current directory as of `base` (a branch, tag or
+    /// commit), analyzed in a temporary git worktree. The worktree is removed
+    /// when this returns, also on error or once `cancel` fires.
+    async fn analyze_ref(
+        base: &str,
+        cancel: &CancellationToken,
+        options: &crate::config::RunOptions,
+    ) -> Result<Vec<AnalysisIssue>> {
+        let worktree = BaseWorktree::add(std::path::Path::new("."), base)?;
+        println!("{}", format!("🌿 Analizando `{base}` en un worktree temporal...").cyan().bold());
+        let root = worktree.project_dir();
+        let issues = analyze_blocking(root.clone(), cancel, options).await?.issues;
+        Ok(rebase_issues(issues, &root))
+    }
+
+    /// Analyzes the current directory and prints which issues are new, fixed
+    /// or unchanged since `baseline`, which comes from the `base` ref of
+    /// `--compare` or else from a file. Both sides drop the issues under
+    /// `--severity`. Only new failing issues fail the run, so a PR check
+    /// reports regressions alone. New issues show `snippets`, unless `options`
+    /// are quiet.
+    async fn run_baseline(
+        baseline: Vec<AnalysisIssue>,
+        base: Option<&str>,
+        mut snippets: Option<Snippets>,
+        cancel: &CancellationToken,
+        options: &crate::config::RunOptions,
+    ) -> Result<()> {
+        println!("{}", "🔍 Análisis profundo del proyecto...".cyan().bold());
+        let analysis = analyze_blocking(PathBuf::from("."), cancel, options).await?;
+        let mut severity = SeverityFilter::from_options(options);
+        let baseline = SeverityFilter::from_options(options).filter(baseline);
+        let diff = BaselineDiff::new(baseline, severity.filter(analysis.issues));
+        let against = base.map_or_else(|| "el baseline".to_string(), |base| format!("`{base}`"));
+        println!("\n📊 Comparación con {against}:");
+        println!(
+            "  {} nuevos, {} corregidos, {} sin cambios",
+            diff.new.len().to_string().red().bold(),
+            diff.fixed.len().to_string().green().bold(),
+            diff.unchanged
+        );
+        if let Some(notice) = severity.notice() {
+            println!("{}", notice.yellow());
+        }
+        let describe = |issue: &AnalysisIssue| match (&issue.file, issue.line) {
+            (Some(file), Some(line)) => format!(
+                "[{:?}] {}: {} ({file}:{line})",
+                issue.severity, issue.category, issue.description
+            ),
+            _ => format!("[{:?}] {}: {}", issue.severity, issue.category, issue.description),
+        };
+        for issue in &diff.new {
+            println!("  {} {}", "+".red().bold(), describe(issue));
+            let snippet = snippets.as_mut().and_then(|s| s.take(issue.file.as_deref(), issue.line));
+            if let Some(snippet) = snippet {
+                snippet.print(options);
+            }
+        }
+        if let Some(notice) = snippets.as_ref().and_then(Snippets::notice) {
+            println!("{}", notice.yellow());
+        }
+        for issue in &diff.fixed {
+            println!("  {} {}", "-".green().bold(), describe(issue));
+        }
+        let failing = severity.failing(diff.new.iter().map(|i| &i.severity));
+        if failing > 0 {
+            let against = match base {
+                Some(base) => format!("respecto a `{base}`"),
+                None => "respecto al baseline".to_string(),
+            };
+            let what = severity.describe_failing(
+                &format!("issues críticos nuevos {against}"),
+                &format!("issues nuevos {against}"),
+            );
+            return Err(crate::exit::findings(failing, what));
+        }
+        println!("{}", "✅ Sin issues críticos nuevos".green());
+        Ok(())
+    }
+
     /// API-friendly wrapper to run analyze without a full `TraeCli` instance.
     pub async fn run_simple(
+        performance: bool,
+        security: bool,
+        quality: bool,
+        no_jarvix: bool,
+        profile: Option<String>,
+        force_refresh: bool,
+        output: Option<String>,
+    ) -> Result<()> {
+        Self::run_simple_cancellable(
+            performance,
+            security,
+            quality,
+            no_jarvix,
+            profile,
+            force_refresh,
+            output,
+            false,
+            None,
+            None,
+            None,
+            &crate::config::RunOptions::from_env(),
+        )
+        .await
+    }
+
+    /// [`Self::run_simple`] that stops with `TraeError::Cancelled` once
+    /// `cancel` fires, checked before and while the project is analyzed.
+    /// With `explain` the quality score breakdown is printed too. `cap`
+    /// limits the issues written to `output`; counts and score use them all.
+    /// With `diff_context` the issues of `output` carry a `snippet`. JARVIXSERVER
+    /// is reached under `options`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_simple_cancellable(
         _performance: bool,
         _security: bool,
         _quality: bool,
@@ -84,6 +320,11 @@ impl AnalyzeCommand {
         profile: Option<String>,
         force_refresh: bool,
         output: Option<String>,
+        explain: bool,
+        cap: Option<IssueCap>,
+        diff_context: Option<usize>,
+        cancel: Option<CancellationToken>,
+        options: &crate::config::RunOptions,
     ) -> Result<()> {
         use std::fs;
         use std::path::Path;
@@ -94,26 +335,25 @@ impl AnalyzeCommand {
 
         // Find workspace root so analysis works from any subdirectory in a Rust workspace
         let orig_cwd = std::env::current_dir()?;
-        let mut root = orig_cwd.clone();
-        let mut found = false;
-        while !root.join("Cargo.toml").exists() {
-            if !root.pop() {
-                break;
-            }
-        }
-        if root.join("Cargo.toml").exists() {
-            found = true;
-        }
-        // If we found a workspace root, change cwd to it; otherwise keep original cwd
-        if found {
-            let _ = std::env::set_current_dir(&root);
+        if let Some(root) = crate::core::workspace::find_root() {
+            let _ = std::env::set_current_dir(root);
         }
 
         // Compute fingerprint of workspace (paths + modified time) for cache key
         let mut hasher = Sha256::new();
-        for entry in walkdir::WalkDir::new(".")
-            .into_iter()
-            .filter_map(|e| e.ok())
+        // The cached breakdown is only valid for the coefficients it was computed with
+        let weights = crate::config::TraeConfig::load().score;
+        hasher.update(weights.summary().as_bytes());
+        // Masking differs per feature set, so each one gets its own entry
+        if let Some(features) = &options.features {
+            hasher.update(format!("features={features}").as_bytes());
+        }
+        // So are the counts, under --severity
+        let mut severity = SeverityFilter::from_options(options);
+        if let Some(threshold) = severity.threshold() {
+            hasher.update(format!("severity={}", threshold.name()).as_bytes());
+        }
+        for entry in crate::utils::path::walk(std::path::Path::new("."), None, options)
             .filter(|e| e.path().is_file())
         {
             if let Ok(md) = fs::metadata(entry.path()) {
@@ -129,13 +369,30 @@ impl AnalyzeCommand {
         let cache_file = cache_dir.join(format!("analyze_{}.json", fingerprint));
 
         // TTL = 1 hour
-        let use_cache = !force_refresh && cache_file.exists() && cache_file.metadata().ok().and_then(|m| m.modified().ok()).map(|t| { t.elapsed().map(|d| d.as_secs() < 3600).unwrap_or(false) }).unwrap_or(false);
+        // The cache holds counts only, so a run that writes the issues can't use it
+        let use_cache = output.is_none() && !force_refresh && cache_file.exists() && cache_file.metadata().ok().and_then(|m| m.modified().ok()).map(|t| { t.elapsed().map(|d| d.as_secs() < 3600).unwrap_or(false) }).unwrap_or(false);
         if use_cache {
             if let Ok(s) = fs::read_to_string(&cache_file) {
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&s) {
+                // Summaries cached before the breakdown existed can't explain the score
+                let breakdown = json_breakdown(&s);
+                let cached = serde_json::from_str::<serde_json::Value>(&s).ok();
+                if let Some(json) = cached.filter(|_| !explain || breakdown.is_some()) {
                     println!("📦 Usando cache de análisis ({})", cache_file.to_string_lossy());
                     println!("Resumen: {}", json.get("summary").unwrap_or(&serde_json::Value::String("(nocontent)".to_string())));
+                    if let Some(breakdown) = breakdown.filter(|_| explain) {
+                        print_score_breakdown(&breakdown);
+                    }
                     let _ = std::env::set_current_dir(orig_cwd);
+                    // Summaries cached before --severity only have the critical count
+                    let failing = ["failing_count", "critical_count"]
+                        .iter()
+                        .find_map(|key| json.get(key).and_then(|v| v.as_u64()))
+                        .unwrap_or(0);
+                    if failing > 0 {
+                        let what =
+                            severity.describe_failing("issues críticos detectados", "issues");
+                        return Err(crate::exit::findings(failing as usize, what));
+                    }
                     return Ok(());
                 }
             }
@@ -166,24 +423,64 @@ impl AnalyzeCommand {
             analyzer = crate::core::analyzer::ProjectAnalyzer::with_config(cfg);
         }
         // Run heavy analysis in blocking thread to avoid blocking async runtime
-        let analysis = tokio::task::spawn_blocking(move || analyzer.analyze_project(".")).await??;
+        let cancel = cancel.unwrap_or_default();
+        let scan_options = options.clone();
+        let analysis =
+            tokio::task::spawn_blocking(move || analyzer.analyze_project(".", &scan_options));
+        let mut analysis = tokio::select! {
+            joined = analysis => joined??,
+            () = cancel.cancelled() => {
+                // The blocking scan finishes in the background; its result is dropped
+                println!("{}", "⏹️ Análisis cancelado".yellow());
+                let _ = std::env::set_current_dir(orig_cwd);
+                return Err(TraeError::Cancelled.into());
+            }
+        };
+        // The score rates the whole project, whatever --severity hides
+        let all_issues = analysis.issues.len();
+        severity.retain(&mut analysis.issues);
         println!("\n📊 Resultados del Análisis:");
         println!("  • Issues detectados: {}", analysis.issues.len());
         println!("  • Optimizaciones sugeridas: {}", analysis.suggestions.len());
         println!("  • Líneas de código: {}", analysis.total_lines);
         println!("  • Archivos analizados: {}", analysis.files_count);
+        if let Some(notice) = severity.notice() {
+            println!("  {}", notice.yellow());
+        }
         metrics.add_custom_metric("issues_found".to_string(), analysis.issues.len() as u64);
         metrics.add_custom_metric("suggestions_count".to_string(), analysis.suggestions.len() as u64);
         metrics.add_custom_metric("total_lines".to_string(), analysis.total_lines as u64);
         metrics.add_custom_metric("files_analyzed".to_string(), analysis.files_count as u64);
 
+        let breakdown = ScoreBreakdown::compute(
+            analysis.files_count,
+            all_issues,
+            analysis.total_lines,
+            analysis.metrics.get("fourier_complexity").copied().unwrap_or(0.0),
+            duplication_factor(analysis.total_lines, analysis.files_count),
+            &weights,
+        );
+        if explain {
+            print_score_breakdown(&breakdown);
+        }
+
+        let critical_count = analysis.issues.iter().filter(|i| i.is_critical()).count();
+        let failing_count = severity.failing(analysis.issues.iter().map(|i| &i.severity));
         // Write cache summary
+        let suppressed = match severity.suppressed() {
+            0 => String::new(),
+            n => format!(" suppressed:{n}"),
+        };
         let summary = serde_json::json!({
-            "summary": format!("issues:{} suggestions:{} lines:{} files:{}", analysis.issues.len(), analysis.suggestions.len(), analysis.total_lines, analysis.files_count),
+            "summary": format!("issues:{} suggestions:{} lines:{} files:{}{}", analysis.issues.len(), analysis.suggestions.len(), analysis.total_lines, analysis.files_count, suppressed),
             "issues_count": analysis.issues.len(),
+            "critical_count": critical_count,
+            "failing_count": failing_count,
+            "suppressed_count": severity.suppressed(),
             "files_count": analysis.files_count,
             "lines": analysis.total_lines,
             "profile": profile.unwrap_or_else(|| "default".to_string()),
+            "score_breakdown": breakdown,
         });
         let _ = fs::write(&cache_file, serde_json::to_string_pretty(&summary).unwrap_or_default());
 
@@ -199,19 +496,87 @@ impl AnalyzeCommand {
 
         // Optionally write full JSON output
         if let Some(out) = output {
-            let full = serde_json::json!({"analysis": summary, "issues": analysis.issues, "suggestions": analysis.suggestions, "metrics": analysis.metrics});
-            let _ = fs::write(out, serde_json::to_string_pretty(&full).unwrap_or_default());
+            let mut cap = cap.unwrap_or_default();
+            let mut issues = analysis.issues;
+            cap.retain(&mut issues);
+            if let Some(notice) = cap.notice() {
+                println!("{}", notice.yellow());
+            }
+            let mut snippets = Snippets::new(diff_context);
+            let issue_snippets: Vec<_> = issues
+                .iter()
+                .map(|i| snippets.as_mut()?.take(i.file.as_deref(), i.line))
+                .collect();
+            if let Some(notice) = snippets.as_ref().and_then(Snippets::notice) {
+                println!("{}", notice.yellow());
+            }
+            let issues = with_snippets(&issues, &issue_snippets)?;
+            let mut full = serde_json::json!({"schema_version": crate::core::report::SCHEMA_VERSION, "analysis": summary, "issues": issues, "suggestions": analysis.suggestions, "metrics": analysis.metrics});
+            if let Some(max) = cap.truncated_at() {
+                full["truncated_at"] = max.into();
+            }
+            if fs::write(&out, serde_json::to_string_pretty(&full).unwrap_or_default()).is_ok() {
+                println!("📁 Reporte exportado a: {}", crate::config::full_path(&out));
+            }
         }
 
         if !no_jarvix {
-            if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new() {
+            if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new(options).await {
                 if let Err(e) = client.report_scan_metrics(metrics).await {
                     eprintln!("⚠️ No se pudo reportar métricas de análisis a JARVIXSERVER: {e}");
                 }
             }
         }
-        println!("{}", "✅ Análisis completado".green());
         let _ = std::env::set_current_dir(orig_cwd);
+        if failing_count > 0 {
+            let what = severity.describe_failing("issues críticos detectados", "issues");
+            return Err(crate::exit::findings(failing_count, what));
+        }
+        println!("{}", "✅ Análisis completado".green());
         Ok(())
     }
 }
+/// The `score_breakdown` of a cached analysis summary, if it has one.
+fn json_breakdown(summary: &str) -> Option<ScoreBreakdown> {
+    let json: serde_json::Value = serde_json::from_str(summary).ok()?;
+    serde_json::from_value(json.get("score_breakdown")?.clone()).ok()
+}
+/// `analyze --explain`: the components of the quality score.
+fn print_score_breakdown(breakdown: &ScoreBreakdown) {
+    println!("\n🧮 Puntuación de calidad:");
+    for line in breakdown.lines() {
+        println!("  • {line}");
+    }
+}
+/// Cancels `cancel` on the first Ctrl-C and exits at once on the second.
+async fn cancel_on_ctrl_c(cancel: CancellationToken) {
+    if tokio::signal::ctrl_c().await.is_ok() {
+        cancel.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    }
+}
+/// Analyzes `dir` on a blocking thread. The analyzer can't stop midway, so
+/// when `cancel` fires first the scan is still awaited (it may be reading a
+/// worktree the caller removes next) before failing as cancelled.
+async fn analyze_blocking(
+    dir: PathBuf,
+    cancel: &CancellationToken,
+    options: &crate::config::RunOptions,
+) -> Result<crate::core::analyzer::ProjectAnalysis> {
+    let mut analyzer = crate::core::analyzer::ProjectAnalyzer::new();
+    let options = options.clone();
+    let mut analysis =
+        tokio::task::spawn_blocking(move || analyzer.analyze_project(dir, &options));
+    tokio::select! {
+        joined = &mut analysis => joined?,
+        () = cancel.cancelled() => {
+            let waiting = "⏹️ Interrumpido: esperando a que termine el análisis en curso \
+                           (Ctrl-C de nuevo para salir ya)";
+            eprintln!("{}", waiting.yellow());
+            let _ = analysis.await;
+            Err(TraeError::Cancelled.into())
+        }
+    }
+}
@@ -3,8 +3,15 @@
 #![doc = " Comando de build mejorado con anÃ¡lisis automÃ¡tico y reporte de mÃ©tricas"]
 use crate::{
     cli::TraeCli,
-    commands::repair::RepairCommand,
-    core::{analyzer::ProjectAnalyzer, cargo::CargoExecutor},
+    commands::{clippy::print_lint_summary, repair::RepairCommand},
+    core::{
+        analyzer::ProjectAnalyzer,
+        build_errors::{self, BuildError, BuildFailed},
+        cargo::{CargoExecutor, CargoStream},
+        lints::LintSummary,
+        manifest::{Manifest, BUILTIN_PROFILES},
+    },
+    exit::UsageError,
     jarvix::client::JarvixClient,
     metrics::collector::MetricsCollector,
     utils::ui::{print_step_table, StepSummary},
@@ -12,15 +19,18 @@ use crate::{
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
 use log::{info, warn};
+use std::path::Path;
 use std::time::Instant;
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct BuildCommand {
-    #[doc = " Build in release mode"]
+    #[doc = " Build in release mode (same as `--profile release`)"]
     #[arg(long)]
     pub release: bool,
+    #[doc = " Cargo profile to build with: dev, release or a `[profile.NAME]` of Cargo.toml"]
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
     #[doc = " Build specific target"]
     #[arg(long)]
     pub target: Option<String>,
@@ -42,6 +52,9 @@ pub struct BuildCommand {
     #[doc = " Use Docker for build with Chapel support"]
     #[arg(long)]
     pub docker: bool,
+    #[doc = " Fail (exit 2) when the build emits compiler warnings, listing them by code"]
+    #[arg(long, conflicts_with = "docker")]
+    pub fail_on_warning: bool,
     #[doc = " Additional cargo arguments"]
     #[arg(last = true)]
     pub cargo_args: Vec<String>,
@@ -51,18 +64,20 @@ impl BuildCommand {
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         info!("??? Iniciando build mejorado con TRAE CLI");
         let total_start = Instant::now();
+        let profile = resolve_profile(Path::new("."), self.release, self.profile.as_deref())?;
         let mut metrics = MetricsCollector::new("build".to_string());
+        metrics.add_custom_metric("profile".to_string(), profile.as_deref().unwrap_or("dev"));
         let mut perf_metrics = crate::performance_patterns::MetricsCollector::new();
         let mut steps = Vec::new();
         let mut artifacts = Vec::new();
         let mut fatal_error: Option<anyhow::Error> = None;
         perf_metrics.start_operation("show_config".to_string());
-        self.show_build_config(cli);
+        self.show_build_config(cli, profile.as_deref());
         perf_metrics.end_operation(true);
         if self.analyze {
             perf_metrics.start_operation("pre_analysis".to_string());
             let step_start = Instant::now();
-            match self.pre_build_analysis() {
+            match self.pre_build_analysis(cli) {
                 Ok(_) => {
                     perf_metrics.end_operation(true);
                     steps.push(StepSummary::success("Pre-anÃ¡lisis", step_start.elapsed()));
@@ -83,11 +98,28 @@ impl BuildCommand {
         if fatal_error.is_none() {
             perf_metrics.start_operation("cargo_build".to_string());
             let step_start = Instant::now();
-            match self.execute_build(cli).await {
-                Ok(result_artifacts) => {
-                    perf_metrics.end_operation(true);
-                    steps.push(StepSummary::success("Cargo build", step_start.elapsed()));
+            match self.execute_build(cli, profile.as_deref()).await {
+                Ok((result_artifacts, lints)) => {
                     artifacts = result_artifacts;
+                    let warnings = lints.as_ref().map_or(0, LintSummary::warnings);
+                    metrics.add_custom_metric("warnings".to_string(), warnings as u64);
+                    match lints.filter(|_| self.fail_on_warning && warnings > 0) {
+                        Some(lints) => {
+                            perf_metrics.end_operation(false);
+                            let per_crate = lints.crates.len() > 1;
+                            print_lint_summary(&lints, "WARNINGS", "errores", per_crate);
+                            let what = "warnings de compilación (--fail-on-warning)";
+                            let err = crate::exit::findings(warnings, what);
+                            let elapsed = step_start.elapsed();
+                            let step = StepSummary::failed("Cargo build", elapsed, err.to_string());
+                            steps.push(step);
+                            fatal_error = Some(err);
+                        }
+                        None => {
+                            perf_metrics.end_operation(true);
+                            steps.push(StepSummary::success("Cargo build", step_start.elapsed()));
+                        }
+                    }
                 }
                 Err(e) => {
                     perf_metrics.end_operation(false);
@@ -156,11 +188,11 @@ impl BuildCommand {
         metrics.record_build_time(total_duration);
         metrics.record_build_result(fatal_error.is_none());
         metrics.finish();
-        if cli.no_jarvix {
+        if !cli.jarvix_enabled() {
             steps.push(StepSummary::skipped("Jarvix report"));
         } else {
             let step_start = Instant::now();
-            match self.report_metrics(metrics.clone()).await {
+            match self.report_metrics(metrics.clone(), &cli.options).await {
                 Ok(()) => steps.push(StepSummary::success("Jarvix report", step_start.elapsed())),
                 Err(e) => {
                     steps.push(StepSummary::failed(
@@ -175,6 +207,10 @@ impl BuildCommand {
         if fatal_error.is_none() {
             if !perf_metrics.operations.is_empty() {
                 let stability = perf_metrics.fft_pattern_analysis();
+                let run = crate::metrics::perf_history::PerfRun::from_collector("build", &perf_metrics);
+                if let Err(e) = crate::metrics::perf_history::record_run(run) {
+                    warn!("No se pudo guardar el historial de rendimiento: {e}");
+                }
                 if stability < 0.7 {
                     println ! ("??  Patrones de build inestables detectados (Estabilidad FFT: {stability:.2})");
                 } else {
@@ -201,6 +237,9 @@ impl BuildCommand {
             );
             Ok(())
         } else if let Some(err) = fatal_error {
+            if let Some(failed) = err.downcast_ref::<BuildFailed>() {
+                print_build_errors(&failed.errors);
+            }
             if let Err(suggest_err) = self.suggest_repairs(&err) {
                 eprintln!("?? No se pudieron sugerir reparaciones: {suggest_err}");
             }
@@ -210,14 +249,14 @@ impl BuildCommand {
         }
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn show_build_config(&self, _cli: &TraeCli) {
+    fn show_build_config(&self, _cli: &TraeCli, profile: Option<&str>) {
         println!("{}", "ðŸ“‹ ConfiguraciÃ³n del Build:".cyan().bold());
         println!(
             "  â€¢ Modo: {}",
-            if self.release {
-                "Release".yellow()
-            } else {
-                "Debug".blue()
+            match profile {
+                None | Some("dev") => "Debug".blue(),
+                Some("release") => "Release".yellow(),
+                Some(name) => format!("Perfil {name}").yellow(),
             }
         );
         if let Some(target) = &self.target {
@@ -248,18 +287,13 @@ impl BuildCommand {
         println!();
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn pre_build_analysis(&self) -> Result<()> {
+    fn pre_build_analysis(&self, cli: &TraeCli) -> Result<()> {
         println!("{}", "ðŸ” Ejecutando pre-anÃ¡lisis...".cyan());
         let quantum_start = Instant::now();
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .expect("Failed to set spinner template"),
-        );
+        let spinner = crate::utils::progress::spinner(&cli.options);
         spinner.set_message("Analizando estructura del proyecto...");
         let mut analyzer = ProjectAnalyzer::new();
-        let analysis = analyzer.analyze_project(".")?;
+        let analysis = analyzer.analyze_project(".", &cli.options)?;
         let analysis_time = quantum_start.elapsed();
         let fft_factor = (1000.0 / (analysis_time.as_millis() as f64 + 1.0)).min(10.0);
         spinner.finish_with_message(format!(
@@ -268,32 +302,38 @@ impl BuildCommand {
         if analysis.has_critical_issues() {
             warn!("âš ï¸ Se encontraron issues crÃ\u{AD}ticos que podrÃ\u{AD}an afectar el build");
             analysis.show_critical_issues();
+            if cli.options.ci {
+                let critical = analysis.issues.iter().filter(|i| i.is_critical()).count();
+                return Err(crate::exit::findings(
+                    critical,
+                    "issues críticos en el pre-análisis (modo CI)",
+                ));
+            }
         }
         Ok(())
     }
-    #[doc = "Method documentation added by AI refactor"]
-    async fn execute_build(&self, _cli: &TraeCli) -> Result<Vec<String>> {
+    /// Runs the build and returns its artifacts, plus the compiler
+    /// diagnostics when cargo printed them as JSON (not with Docker or a
+    /// user-chosen `--message-format`).
+    async fn execute_build(
+        &self,
+        cli: &TraeCli,
+        profile: Option<&str>,
+    ) -> Result<(Vec<String>, Option<LintSummary>)> {
         let build_msg = if self.docker {
             "ðŸš€ Ejecutando cargo build con Docker y Chapel..."
         } else {
             "ðŸš€ Ejecutando cargo build..."
         };
         println!("{}", build_msg.cyan());
-        let progress = ProgressBar::new_spinner();
-        progress.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .expect("Failed to set progress template"),
-        );
+        let progress = crate::utils::progress::spinner(&cli.options);
         progress.set_message("Compilando proyecto...");
         let result = if self.docker {
-            self.execute_build_with_docker().await
+            self.execute_build_with_docker(profile).await.map(|out| (out, None))
         } else {
-            let executor = CargoExecutor::new();
+            let executor = CargoExecutor::new(&cli.options);
             let mut build_args = vec!["build".to_string()];
-            if self.release {
-                build_args.push("--release".to_string());
-            }
+            build_args.extend(profile_args(profile));
             if let Some(target) = &self.target {
                 build_args.extend_from_slice(&["--target".to_string(), target.clone()]);
             }
@@ -303,20 +343,69 @@ impl BuildCommand {
             if self.workspace {
                 build_args.push("--workspace".to_string());
             }
-            build_args.extend_from_slice(&self.cargo_args);
-            executor.execute_streaming_capture(&build_args).await
+            // JSON diagnostics feed the error summary; a user-chosen format wins
+            if self.cargo_args.iter().any(|arg| arg.starts_with("--message-format")) {
+                if self.fail_on_warning {
+                    let msg = "--fail-on-warning necesita los diagnósticos JSON: \
+                               quita --message-format";
+                    return Err(UsageError(msg.to_string()).into());
+                }
+                build_args.extend_from_slice(&self.cargo_args);
+                executor.execute_streaming_capture(&build_args).await.map(|out| (out, None))
+            } else {
+                build_args.push("--message-format=json".to_string());
+                build_args.extend_from_slice(&self.cargo_args);
+                Self::execute_json_build(&executor, &build_args)
+                    .await
+                    .map(|(out, lints)| (out, Some(lints)))
+            }
         };
         progress.finish_with_message("Build completado âœ“".to_string());
+        let (output, lints) = result?;
+        Ok((self.extract_artifacts(&output), lints))
+    }
+    /// Runs a `--message-format=json` build printing what cargo would have
+    /// printed. Returns that human output and the diagnostics; on failure, a
+    /// [`BuildFailed`] with the compiler errors.
+    async fn execute_json_build(
+        executor: &CargoExecutor,
+        args: &[String],
+    ) -> Result<(String, LintSummary)> {
+        let mut human = String::new();
+        let mut json = String::new();
+        let result = executor
+            .execute_streaming_capture_with_handler(args, |stream, line| match stream {
+                CargoStream::Stdout => {
+                    json.push_str(line);
+                    json.push('\n');
+                    if let Some(text) = build_errors::human_output(line) {
+                        eprint!("{text}");
+                        human.push_str(&text);
+                    }
+                }
+                CargoStream::Stderr => {
+                    eprintln!("{line}");
+                    human.push_str(line);
+                    human.push('\n');
+                }
+            })
+            .await;
         match result {
-            Ok(output) => {
-                let artifacts = self.extract_artifacts(&output);
-                Ok(artifacts)
+            Ok(_) => Ok((human, LintSummary::parse(&json))),
+            // cargo could not even start
+            Err(e) if human.is_empty() => Err(e),
+            Err(e) => {
+                let status = e.to_string().lines().next().unwrap_or_default().to_string();
+                Err(BuildFailed {
+                    errors: build_errors::parse(&json),
+                    output: format!("{status}\n{human}"),
+                }
+                .into())
             }
-            Err(e) => Err(e),
         }
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn execute_build_with_docker(&self) -> Result<String> {
+    async fn execute_build_with_docker(&self, profile: Option<&str>) -> Result<String> {
         use tokio::process::Command;
         let mut docker_args = vec![
             "run".to_string(),
@@ -329,9 +418,7 @@ impl BuildCommand {
             "cargo".to_string(),
             "build".to_string(),
         ];
-        if self.release {
-            docker_args.push("--release".to_string());
-        }
+        docker_args.extend(profile_args(profile));
         if let Some(target) = &self.target {
             docker_args.extend_from_slice(&["--target".to_string(), target.clone()]);
         }
@@ -380,8 +467,12 @@ impl BuildCommand {
         repair.execute(cli).await
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn report_metrics(&self, metrics: MetricsCollector) -> Result<()> {
-        match JarvixClient::new() {
+    async fn report_metrics(
+        &self,
+        metrics: MetricsCollector,
+        options: &crate::config::RunOptions,
+    ) -> Result<()> {
+        match JarvixClient::new(options).await {
             Ok(Some(client)) => {
                 client.report_build_metrics(metrics).await?;
                 println!("{}", "ðŸ“Š MÃ©tricas reportadas a JARVIXSERVER".green());
@@ -450,3 +541,55 @@ impl BuildCommand {
         artifacts
     }
 }
+/// Cargo profile selected by `--release`/`--profile`, `None` for cargo's
+/// default. `--release` is `--profile release`, so giving both with another
+/// name is a usage error, as is a profile that is neither built in nor a
+/// `[profile.NAME]` of `dir/Cargo.toml` or a parent workspace manifest.
+pub fn resolve_profile(dir: &Path, release: bool, profile: Option<&str>) -> Result<Option<String>> {
+    let name = match (release, profile) {
+        (false, None) => return Ok(None),
+        (true, None) => "release",
+        (true, Some(name)) if name != "release" => {
+            return Err(UsageError(format!("--release y --profile {name} se contradicen")).into());
+        }
+        (_, Some(name)) => name,
+    };
+    let declared = || {
+        dir.canonicalize().is_ok_and(|dir| {
+            dir.ancestors()
+                .filter_map(|dir| Manifest::load(dir).ok())
+                .any(|manifest| manifest.profile.contains_key(name))
+        })
+    };
+    if !BUILTIN_PROFILES.contains(&name) && !declared() {
+        return Err(UsageError(format!(
+            "El perfil '{name}' no existe: añade [profile.{name}] a Cargo.toml"
+        ))
+        .into());
+    }
+    Ok(Some(name.to_string()))
+}
+/// `--profile NAME` for cargo, nothing for the default profile.
+pub fn profile_args(profile: Option<&str>) -> Vec<String> {
+    profile
+        .map(|name| vec!["--profile".to_string(), name.to_string()])
+        .unwrap_or_default()
+}
+/// Distinct compiler errors of a failed build, duplicates counted.
+fn print_build_errors(errors: &[BuildError]) {
+    if errors.is_empty() {
+        return;
+    }
+    println!("{}", "┌─ ERRORES DE COMPILACIÓN ────────────────────┐".red().bold());
+    for line in build_errors::summary_lines(errors, build_errors::SUMMARY_LIMIT) {
+        println!("  {line}");
+    }
+    println!("{}", "└─────────────────────────────────────────────┘".red().bold());
+    let codes = build_errors::explainable_codes(errors);
+    if !codes.is_empty() {
+        let commands: Vec<String> =
+            codes.iter().map(|code| format!("trae explain {code}")).collect();
+        println!("💡 Explicación de rustc: {}", commands.join(", ").cyan());
+    }
+    println!();
+}
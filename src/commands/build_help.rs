@@ -1,6 +1,6 @@
+use crate::core::cargo::cargo_command;
 use anyhow::Result;
 use clap::Args;
-use std::process::Command;
 
 #[derive(Args, Debug)]
 pub struct BuildHelpCommand {
@@ -17,7 +17,7 @@ pub struct BuildHelpCommand {
 }
 
 impl BuildHelpCommand {
-    pub async fn execute(&self, _cli: &crate::cli::TraeCli) -> Result<()> {
+    pub async fn execute(&self, cli: &crate::cli::TraeCli) -> Result<()> {
         // Minimal, sober suggestions
         println!("TRAE Build Helper - recomendaciones sobrias para compilar");
         if self.optimize_size {
@@ -32,7 +32,7 @@ impl BuildHelpCommand {
 
         if self.run {
             // build command composition
-            let mut cmd = Command::new("cargo");
+            let mut cmd = cargo_command(&cli.options);
             cmd.arg("build");
             if self.release {
                 cmd.arg("--release");
@@ -3,17 +3,17 @@
 #![doc = " Wrapper inteligente de `cargo` con métricas, progreso y passthrough."]
 use crate::{
     cli::TraeCli,
-    core::cargo::{CargoExecutor, CargoStream},
+    config::{RunOptions, TraeConfig},
+    core::cargo::{ensure_cargo, CargoExecutor, CargoOutput, CargoStream},
+    exit::UsageError,
+    jarvix::client::JarvixClient,
     utils::progress,
 };
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::env;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use which::which;
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct CargoCommand {
@@ -22,7 +22,6 @@ pub struct CargoCommand {
     pub command: String,
     #[doc = " Additional arguments for cargo"]
     #[arg(
-        last = true,
         trailing_var_arg = true,
         allow_hyphen_values = true,
         value_name = "ARGS"
@@ -31,57 +30,220 @@ pub struct CargoCommand {
     #[doc = " Run command interactively"]
     #[arg(long)]
     pub interactive: bool,
+    #[doc = " Also save cargo's whole output (stdout and stderr, without colors) to this file"]
+    #[arg(long, value_name = "PATH", conflicts_with = "interactive")]
+    pub capture_output: Option<PathBuf>,
+    #[doc = " Offload build/test to JARVIXSERVER (falls back to local cargo on timeout)"]
+    #[arg(long)]
+    pub remote: bool,
+    #[doc = " Seconds to wait for the remote job before falling back (default: config or 120)"]
+    #[arg(long, value_name = "SECS", requires = "remote")]
+    pub remote_timeout: Option<u64>,
+    #[doc = " Seconds between remote job polls (default: config or 2)"]
+    #[arg(long, value_name = "SECS", requires = "remote")]
+    pub remote_poll: Option<u64>,
+    #[doc = " Longest wait between polls once backed off (default: config or 30)"]
+    #[arg(long, value_name = "SECS", requires = "remote")]
+    pub remote_max_poll: Option<u64>,
+}
+/// How long to wait for, and how often to poll, a build offloaded to JARVIXSERVER.
+/// The default is `[jarvix]`'s defaults; library users can override fields:
+/// `RemoteOffload { timeout: Duration::from_secs(600), ..Default::default() }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteOffload {
+    pub timeout: Duration,
+    /// Wait before the first poll; it doubles after each poll that finds the
+    /// job still running.
+    pub poll_interval: Duration,
+    /// Cap of the doubling wait between polls.
+    pub max_poll_interval: Duration,
 }
-#[doc = "Function documentation added by AI refactor"]
-fn resolve_executable(name: &str) -> Option<String> {
-    if let Ok(path) = which(name) {
-        return Some(path.to_string_lossy().to_string());
+impl Default for RemoteOffload {
+    fn default() -> Self {
+        Self::with_config(&TraeConfig::default())
     }
-    let mut candidates: Vec<PathBuf> = Vec::new();
-    if let Some(cargo_home) = env::var_os("CARGO_HOME") {
-        candidates.push(PathBuf::from(cargo_home).join("bin").join(name));
+}
+impl RemoteOffload {
+    /// The configured settings, whether or not `offload` is on.
+    fn with_config(config: &TraeConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.jarvix.offload_timeout),
+            poll_interval: Duration::from_secs(config.jarvix.offload_poll_interval),
+            max_poll_interval: Duration::from_secs(config.jarvix.offload_max_poll_interval),
+        }
     }
-    if let Some(home) = dirs::home_dir() {
-        candidates.push(home.join(".cargo").join("bin").join(name));
+    /// Offload settings from `[jarvix]`; `None` unless `offload = true`.
+    pub fn from_config(config: &TraeConfig) -> Option<Self> {
+        config.jarvix.offload.then(|| Self::with_config(config))
     }
-    for p in candidates {
-        if p.exists() {
-            return Some(p.to_string_lossy().to_string());
+    /// Settings for `scan`'s JARVIXSERVER jobs, which run whenever JARVIX is
+    /// reachable: `timeout_secs` or the configured offload timeout.
+    pub fn scan(timeout_secs: Option<u64>) -> Self {
+        let config = TraeConfig::load();
+        Self {
+            timeout: Duration::from_secs(timeout_secs.unwrap_or(config.jarvix.offload_timeout)),
+            ..Self::with_config(&config)
         }
-        let mut pexe = p.clone();
-        pexe.set_extension("exe");
-        if pexe.exists() {
-            return Some(pexe.to_string_lossy().to_string());
+    }
+}
+/// Submits `cargo build`/`test` as a JARVIXSERVER job and waits for the result.
+/// Returns `Ok(false)` when the caller should run cargo locally instead.
+async fn try_remote(
+    command: &str,
+    args: &[String],
+    offload: RemoteOffload,
+    options: &RunOptions,
+) -> Result<bool> {
+    if command != "build" && command != "test" {
+        return Ok(false);
+    }
+    if options.offline {
+        println!("📴 Modo offline: cargo {command} se ejecuta localmente (sin offload)");
+        return Ok(false);
+    }
+    let Ok(Some(client)) = JarvixClient::new(options).await else {
+        return Ok(false);
+    };
+    let job_data = serde_json::json!({
+        "cwd": std::env::current_dir()?.to_string_lossy().to_string(),
+        "command": command,
+        "args": args,
+    });
+    let Ok(job_id) = client
+        .submit_parallel_analysis_job("cargo_build", job_data)
+        .await
+    else {
+        eprintln!("⚠️ No se pudo enviar el job a JARVIXSERVER, usando cargo local");
+        return Ok(false);
+    };
+    println!("⚡ Offloading cargo {command} to JarvixServer (job {job_id})");
+    println!(
+        "   límite {:?}, sondeo cada {:?} con backoff hasta {:?}",
+        offload.timeout,
+        offload.poll_interval,
+        offload.max_poll_interval.max(offload.poll_interval)
+    );
+    let Some(res) = client
+        .wait_for_job(&job_id, offload.timeout, offload.poll_interval, offload.max_poll_interval)
+        .await
+    else {
+        eprintln!("⚠️ Usando cargo local");
+        return Ok(false);
+    };
+    // If remote job returns logs, stream them
+    if let Some(logs) = res.get("logs") {
+        println!("📤 Remote job logs:\n{}", logs);
+    }
+    // If remote job provides an artifact URL, try to download it
+    if let Some(artifact) = res.get("artifact_url").and_then(|v| v.as_str()) {
+        println!("📥 Downloading artifact from {}", artifact);
+        let download =
+            async { anyhow::Ok(crate::utils::http::jarvix_client()?.get(artifact).send().await?) };
+        match download.await {
+            Ok(resp) => {
+                if resp.status().is_success() {
+                    let bytes = resp.bytes().await.unwrap_or_default();
+                    let target_dir = options.target_dir();
+                    let path = target_dir.join("remote_artifact.tar.gz");
+                    let _ = std::fs::create_dir_all(&target_dir);
+                    std::fs::write(&path, &bytes).ok();
+                    println!("📦 Artifact saved to {}", path.to_string_lossy());
+                } else {
+                    eprintln!("⚠️ Failed to download artifact: {}", resp.status());
+                }
+            }
+            Err(e) => eprintln!("⚠️ Error downloading artifact: {}", e),
         }
     }
-    None
+    println!("📤 Remote job result: {}", res);
+    Ok(true)
+}
+/// Full cargo argv for `command args`, adding `--color=always` unless the
+/// caller already chose a `--color`. It goes before any `--` so it never
+/// reaches the test harness or the program run by `cargo run`.
+pub fn cargo_args(command: &str, args: &[String]) -> Vec<String> {
+    cargo_args_colored(command, args, "always")
+}
+/// [`cargo_args`] with `--color=<color>` as the default instead.
+fn cargo_args_colored(command: &str, args: &[String], color: &str) -> Vec<String> {
+    let mut arg_strings = Vec::with_capacity(args.len() + 2);
+    arg_strings.push(command.to_string());
+    arg_strings.extend_from_slice(args);
+    let cargo_end = arg_strings
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(arg_strings.len());
+    if !arg_strings[..cargo_end]
+        .iter()
+        .any(|arg| arg.starts_with("--color"))
+    {
+        arg_strings.insert(cargo_end, format!("--color={color}"));
+    }
+    arg_strings
+}
+/// Subcommands `cargo --list` knows about: built-ins, installed `cargo-*`
+/// extensions and aliases.
+fn known_subcommands(cargo: &std::path::Path) -> Result<Vec<String>> {
+    let output = std::process::Command::new(cargo).arg("--list").output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect())
+}
+/// `--dry-run`: checks that cargo is installed and knows `args[0]`, then
+/// prints the exact command line instead of running it.
+pub fn dry_run(args: &[String]) -> Result<()> {
+    let cargo = ensure_cargo()?;
+    let Some(subcommand) = args.first() else {
+        return Err(UsageError("no se indicó subcomando cargo".to_string()).into());
+    };
+    if !known_subcommands(cargo)?.contains(subcommand) {
+        return Err(UsageError(format!(
+            "cargo no reconoce el subcomando `{subcommand}` (ver `cargo --list`)"
+        ))
+        .into());
+    }
+    let line = args
+        .iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"') {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!("{} cargo {}", "🔎 Dry run:".cyan().bold(), line);
+    Ok(())
 }
 impl CargoCommand {
     #[doc = "Method documentation added by AI refactor"]
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        if cli.dry_run {
+            return dry_run(&cargo_args(&self.command, &self.args));
+        }
         println!(
             "{}",
             format!("🚀 Ejecutando cargo {} mejorado...", self.command)
                 .cyan()
                 .bold()
         );
-        if resolve_executable("cargo").is_none() {
-            eprintln ! ("❌ 'cargo' no se encuentra en PATH ni en CARGO_HOME. Instálalo: https://www.rust-lang.org/tools/install");
-            return Err(anyhow::anyhow!("cargo not found"));
+        ensure_cargo()?;
+        if cli.jarvix_enabled() {
+            if let Some(offload) = self.remote_offload() {
+                if try_remote(&self.command, &self.args, offload, &cli.options).await? {
+                    return Ok(());
+                }
+            }
         }
         let mut metrics =
             crate::metrics::collector::MetricsCollector::new(format!("cargo_{}", self.command));
         let start_time = Instant::now();
-        let executor = CargoExecutor::new().with_working_dir(".");
-        let mut arg_strings = Vec::new();
-        arg_strings.push(self.command.clone());
-        arg_strings.extend(self.args.clone());
-        if !arg_strings
-            .iter()
-            .any(|arg| arg.starts_with("--color") || arg == "--color")
-        {
-            arg_strings.push("--color=always".to_string());
-        }
+        let executor = CargoExecutor::new(&cli.options).with_working_dir(".");
+        let arg_strings = cargo_args(&self.command, &self.args);
         let arg_refs: Vec<&str> = arg_strings.iter().map(|s| s.as_str()).collect();
         if self.interactive {
             self.run_interactive(cli, &executor, &mut metrics, &arg_refs, start_time)
@@ -91,6 +253,22 @@ impl CargoCommand {
                 .await
         }
     }
+    /// Offload settings when `--remote` is passed or `[jarvix] offload = true`;
+    /// CLI values override the configured timeout/intervals.
+    fn remote_offload(&self) -> Option<RemoteOffload> {
+        let config = TraeConfig::load();
+        if !self.remote && !config.jarvix.offload {
+            return None;
+        }
+        let base = RemoteOffload::with_config(&config);
+        Some(RemoteOffload {
+            timeout: self.remote_timeout.map_or(base.timeout, Duration::from_secs),
+            poll_interval: self.remote_poll.map_or(base.poll_interval, Duration::from_secs),
+            max_poll_interval: self
+                .remote_max_poll
+                .map_or(base.max_poll_interval, Duration::from_secs),
+        })
+    }
     #[doc = "Method documentation added by AI refactor"]
     async fn run_interactive(
         &self,
@@ -144,19 +322,14 @@ impl CargoCommand {
         args: &[&str],
         start_time: Instant,
     ) -> Result<()> {
-        let total_units = progress::estimate_cargo_units().max(1);
-        let progress_bar = ProgressBar::new(total_units as u64);
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} {pos}/{len} {wide_bar:.cyan/blue} {msg}")
-                .expect("progress template"),
-        );
+        let total_units = progress::estimate_cargo_units(&cli.options).max(1);
+        let progress_bar = crate::utils::progress::bar(total_units as u64, &cli.options);
         progress_bar.enable_steady_tick(Duration::from_millis(120));
         progress_bar.set_message(format!("Ejecutando cargo {}...", self.command));
-        let verbose = cli.verbose;
+        let verbose = cli.verbose && !cli.ci;
         let mut completed = 0usize;
         let result = executor
-            .execute_streaming_capture_with_handler(args, |stream, line| {
+            .execute_captured(args, |stream, line| {
                 let show_line = verbose
                     || matches!(stream, CargoStream::Stderr)
                     || line.contains("error:")
@@ -177,7 +350,13 @@ impl CargoCommand {
                 }
             })
             .await;
-        match result {
+        if let (Ok(run), Some(path)) = (&result, &self.capture_output) {
+            match std::fs::write(path, run.plain()) {
+                Ok(()) => println!("📝 Salida de cargo guardada en {}", path.display()),
+                Err(e) => eprintln!("⚠️  No se pudo guardar la salida en {}: {e}", path.display()),
+            }
+        }
+        match result.and_then(|run| run.into_result()) {
             Ok(_) => {
                 progress_bar.finish_with_message("Cargo completado");
                 let duration = start_time.elapsed();
@@ -220,10 +399,10 @@ impl CargoCommand {
         cli: &TraeCli,
         metrics: &crate::metrics::collector::MetricsCollector,
     ) {
-        if cli.no_jarvix {
+        if !cli.jarvix_enabled() {
             return;
         }
-        if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new() {
+        if let Ok(Some(client)) = JarvixClient::new(&cli.options).await {
             if let Err(e) = client.report_cargo_metrics(metrics.clone()).await {
                 eprintln!("⚠️  No se pudo reportar métricas cargo a JARVIXSERVER: {e}");
             }
@@ -238,58 +417,51 @@ impl CargoCommand {
         verbose: bool,
         no_jarvix: bool,
     ) -> Result<()> {
-        // Attempt to offload cargo build to JarvixServer if available and command is build/test
-        if !no_jarvix {
-            if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new() {
-                if command == "build" || command == "test" {
-                    let job_data = serde_json::json!({
-                        "cwd": std::env::current_dir()?.to_string_lossy().to_string(),
-                        "command": command,
-                        "args": args,
-                    });
-                    if let Ok(job_id) = client
-                        .submit_parallel_analysis_job("cargo_build", job_data)
-                        .await
-                    {
-                        println!("⚡ Offloading cargo {} to JarvixServer (job {})", command, job_id);
-                        // Poll for result with timeout
-                        let start = std::time::Instant::now();
-                        let timeout = std::time::Duration::from_secs(120);
-                        loop {
-                            if start.elapsed() > timeout {
-                                eprintln!("⚠️ Offload timed out, falling back to local cargo");
-                                break;
-                            }
-                            if let Ok(Some(res)) = client.get_job_result(&job_id).await {
-                                        // If remote job returns logs, stream them
-                                        if let Some(logs) = res.get("logs") {
-                                            println!("📤 Remote job logs:\n{}", logs);
-                                        }
-                                        // If remote job provides an artifact URL, try to download it
-                                        if let Some(artifact) = res.get("artifact_url").and_then(|v| v.as_str()) {
-                                            println!("📥 Downloading artifact from {}", artifact);
-                                            match reqwest::get(artifact).await {
-                                                Ok(resp) => {
-                                                    if resp.status().is_success() {
-                                                        let bytes = resp.bytes().await.unwrap_or_default();
-                                                        let path = std::path::Path::new("target").join("remote_artifact.tar.gz");
-                                                        let _ = std::fs::create_dir_all("target");
-                                                        std::fs::write(&path, &bytes).ok();
-                                                        println!("📦 Artifact saved to {}", path.to_string_lossy());
-                                                    } else {
-                                                        eprintln!("⚠️ Failed to download artifact: {}", resp.status());
-                                                    }
-                                                }
-                                                Err(e) => eprintln!("⚠️ Error downloading artifact: {}", e),
-                                            }
-                                        }
-                                        println!("📤 Remote job result: {}", res);
-                                        return Ok(());
-                            }
-                            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                        }
+        let offload = if no_jarvix {
+            None
+        } else {
+            RemoteOffload::from_config(&TraeConfig::load())
+        };
+        Self::run_simple_offloaded(command, args, interactive, verbose, no_jarvix, offload).await
+    }
+
+    /// Runs `cargo command args` locally and returns its exit code and output
+    /// (without colors, unless `args` pick a `--color`), failing or not.
+    /// With `echo` each line is also printed as it arrives.
+    pub async fn run_simple_capture(
+        command: &str,
+        args: &[String],
+        echo: bool,
+    ) -> Result<CargoOutput> {
+        ensure_cargo()?;
+        let arg_strings = cargo_args_colored(command, args, "never");
+        CargoExecutor::new(&RunOptions::from_env())
+            .with_working_dir(".")
+            .execute_captured(&arg_strings, |stream, line| {
+                if echo {
+                    match stream {
+                        CargoStream::Stdout => println!("{line}"),
+                        CargoStream::Stderr => eprintln!("{line}"),
                     }
                 }
+            })
+            .await
+    }
+
+    /// [`Self::run_simple`] offloading `build`/`test` with `offload` when it
+    /// is `Some`, whatever `[jarvix] offload` says; `None` always runs locally.
+    pub async fn run_simple_offloaded(
+        command: &str,
+        args: &[String],
+        interactive: bool,
+        verbose: bool,
+        no_jarvix: bool,
+        offload: Option<RemoteOffload>,
+    ) -> Result<()> {
+        let options = RunOptions::from_env();
+        if let Some(offload) = offload {
+            if try_remote(command, args, offload, &options).await? {
+                return Ok(());
             }
         }
         println!(
@@ -298,22 +470,11 @@ impl CargoCommand {
                 .cyan()
                 .bold()
         );
-        if resolve_executable("cargo").is_none() {
-            eprintln!("❌ 'cargo' no se encuentra en PATH ni en CARGO_HOME. Instálalo: https://www.rust-lang.org/tools/install");
-            return Err(anyhow::anyhow!("cargo not found"));
-        }
+        ensure_cargo()?;
         let mut metrics = crate::metrics::collector::MetricsCollector::new(format!("cargo_{}", command));
         let start_time = Instant::now();
-        let executor = CargoExecutor::new().with_working_dir(".");
-        let mut arg_strings: Vec<String> = Vec::new();
-        arg_strings.push(command.to_string());
-        arg_strings.extend_from_slice(args);
-        if !arg_strings
-            .iter()
-            .any(|arg| arg.starts_with("--color") || arg == "--color")
-        {
-            arg_strings.push("--color=always".to_string());
-        }
+        let executor = CargoExecutor::new(&options).with_working_dir(".");
+        let arg_strings = cargo_args(command, args);
         let arg_refs: Vec<&str> = arg_strings.iter().map(|s| s.as_str()).collect();
         if interactive {
             match executor.execute_interactive(&arg_refs).await {
@@ -323,7 +484,7 @@ impl CargoCommand {
                     metrics.add_custom_metric("success".to_string(), 1);
                     metrics.add_custom_metric("interactive_mode".to_string(), 1);
                     if !no_jarvix {
-                        if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new() {
+                        if let Ok(Some(client)) = JarvixClient::new(&options).await {
                             if let Err(e) = client.report_cargo_metrics(metrics.clone()).await {
                                 eprintln!("⚠️ No se pudo reportar métricas cargo a JARVIXSERVER: {e}");
                             }
@@ -341,13 +502,8 @@ impl CargoCommand {
                 }
             }
         } else {
-            let total_units = crate::utils::progress::estimate_cargo_units().max(1);
-            let progress_bar = ProgressBar::new(total_units as u64);
-            progress_bar.set_style(
-                ProgressStyle::default_bar()
-                    .template("{spinner:.green} {pos}/{len} {wide_bar:.cyan/blue} {msg}")
-                    .expect("progress template"),
-            );
+            let total_units = crate::utils::progress::estimate_cargo_units(&options).max(1);
+            let progress_bar = crate::utils::progress::bar(total_units as u64, &options);
             progress_bar.enable_steady_tick(Duration::from_millis(120));
             progress_bar.set_message(format!("Ejecutando cargo {}...", command));
             let mut completed = 0usize;
@@ -381,7 +537,7 @@ impl CargoCommand {
                     metrics.add_custom_metric("success".to_string(), 1);
                     metrics.add_custom_metric("streaming_mode".to_string(), 1);
                     if !no_jarvix {
-                        if let Ok(Some(client)) = crate::jarvix::client::JarvixClient::new() {
+                        if let Ok(Some(client)) = JarvixClient::new(&options).await {
                             if let Err(e) = client.report_cargo_metrics(metrics.clone()).await {
                                 eprintln!("⚠️ No se pudo reportar métricas cargo a JARVIXSERVER: {e}");
                             }
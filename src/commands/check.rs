@@ -0,0 +1,119 @@
+#![doc = " # Check Command - cargo check with a diagnostics summary"]
+#![doc = ""]
+#![doc = " Verifica el proyecto con `cargo check` y resume los diagnósticos por código y por crate"]
+use crate::cli::TraeCli;
+use crate::commands::clippy::print_lint_summary;
+use crate::core::cargo::cargo_command;
+use crate::core::features::FeatureArgs;
+use crate::core::lints::LintSummary;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use tokio::process::Command;
+use std::time::Instant;
+#[derive(Args, Debug, Default)]
+#[doc = " Verificación de compilación con resumen de diagnósticos"]
+pub struct CheckCommand {
+    #[doc = " Check lib, bins, tests, examples and benches in one pass"]
+    #[arg(long)]
+    pub all_targets: bool,
+    #[doc = " Check examples"]
+    #[arg(long)]
+    pub examples: bool,
+    #[doc = " Check tests"]
+    #[arg(long)]
+    pub tests: bool,
+    #[doc = " Check benches"]
+    #[arg(long)]
+    pub benches: bool,
+    #[doc = " Check every workspace member"]
+    #[arg(long)]
+    pub workspace: bool,
+    #[doc = " Package to check (repeatable)"]
+    #[arg(short, long, value_name = "SPEC")]
+    pub package: Vec<String>,
+    #[command(flatten)]
+    pub features: FeatureArgs,
+    #[doc = " Check for the target triple"]
+    #[arg(long, value_name = "TRIPLE")]
+    pub target: Option<String>,
+    #[doc = " Count warnings as errors in the exit status"]
+    #[arg(long, visible_alias = "fail-on-warning")]
+    pub deny_warnings: bool,
+    #[doc = " Additional cargo check arguments"]
+    #[arg(last = true)]
+    pub cargo_args: Vec<String>,
+}
+impl CheckCommand {
+    /// Warns about formatting, then runs `cargo check` and summarizes its
+    /// diagnostics. Compile errors (and warnings with `--deny-warnings`) are
+    /// findings; warnings are counted here rather than passed to rustc as
+    /// `-D warnings`, which would rebuild every crate.
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let start = Instant::now();
+        self.check_format(cli);
+        let progress = crate::utils::progress::spinner(&cli.options);
+        progress.set_message("Verificando compilación con cargo check...");
+        progress.enable_steady_tick(std::time::Duration::from_millis(100));
+        let mut check = Command::from(cargo_command(&cli.options));
+        let output = check.args(self.check_args()).output().await?;
+        progress.finish_and_clear();
+        let summary = LintSummary::parse(&String::from_utf8_lossy(&output.stdout));
+        // A failure without diagnostics is cargo itself failing (bad manifest, unknown flag)
+        if !output.status.success() && summary.lints.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("cargo check falló: {}", stderr.trim());
+        }
+        println!(
+            "{} cargo check completado en {:.2}s",
+            "✅".green(),
+            start.elapsed().as_secs_f64()
+        );
+        if summary.lints.is_empty() {
+            println!("{}", "✨ Sin diagnósticos".green());
+        } else {
+            let per_crate = self.all_targets || self.workspace || summary.crates.len() > 1;
+            print_lint_summary(&summary, "DIAGNÓSTICOS", "errores", per_crate);
+        }
+        if summary.denied() > 0 {
+            return Err(crate::exit::findings(summary.denied(), "errores de compilación"));
+        }
+        if self.deny_warnings && summary.warnings() > 0 {
+            return Err(crate::exit::findings(summary.warnings(), "warnings (--deny-warnings)"));
+        }
+        Ok(())
+    }
+    /// `cargo fmt --check`, reported but never fatal.
+    fn check_format(&self, cli: &TraeCli) {
+        let fmt = cargo_command(&cli.options).args(["fmt", "--check"]).output();
+        match fmt {
+            Ok(output) if output.status.success() => println!("{}", "✓ Formato verificado".green()),
+            Ok(output) if output.stdout.is_empty() => {
+                println!("{}", "⚠️ No se pudo verificar formato (¿rustfmt instalado?)".yellow())
+            }
+            Ok(_) => println!("{}", "⚠️ Formato incorrecto (ejecuta 'trae fmt')".yellow()),
+            Err(_) => println!("{}", "⚠️ No se pudo verificar formato".yellow()),
+        }
+    }
+    /// Arguments of the `cargo check` run, always with JSON diagnostics.
+    pub fn check_args(&self) -> Vec<String> {
+        let mut args = vec!["check".to_string(), "--message-format=json".to_string()];
+        let flags = [
+            (self.all_targets, "--all-targets"),
+            (self.examples, "--examples"),
+            (self.tests, "--tests"),
+            (self.benches, "--benches"),
+            (self.workspace, "--workspace"),
+        ];
+        args.extend(flags.iter().filter(|(on, _)| *on).map(|(_, flag)| flag.to_string()));
+        args.extend(self.features.cargo_args());
+        for package in &self.package {
+            args.extend(["--package".to_string(), package.clone()]);
+        }
+        if let Some(target) = &self.target {
+            args.extend(["--target".to_string(), target.clone()]);
+        }
+        args.extend(self.cargo_args.iter().cloned());
+        args
+    }
+}
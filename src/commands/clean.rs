@@ -0,0 +1,165 @@
+#![doc = " # Clean Command - Selective cargo clean"]
+#![doc = ""]
+#![doc = " `cargo clean` por perfil, solo docs o solo el workspace, midiendo el espacio liberado"]
+use crate::{
+    cli::TraeCli,
+    config::RunOptions,
+    core::cargo::{metadata_command, CargoExecutor},
+    utils::path::dir_size,
+};
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+#[derive(Args, Debug, Clone, Default)]
+#[doc = " Opciones de limpieza selectiva"]
+pub struct CleanCommand {
+    #[doc = " Only clean this profile's artifacts (`target/debug` or `target/release`)"]
+    #[arg(long, value_name = "PROFILE", value_parser = ["dev", "release"])]
+    pub profile: Option<String>,
+    #[doc = " Only clean generated documentation (`target/doc`)"]
+    #[arg(long, conflicts_with_all = ["profile", "keep_deps"])]
+    pub doc: bool,
+    #[doc = " Clean only the workspace's own crates, keeping compiled dependencies"]
+    #[arg(long)]
+    pub keep_deps: bool,
+    #[doc = " Report how much space would be freed without deleting anything"]
+    #[arg(long)]
+    pub dry_run: bool,
+}
+/// Per-profile subdirectories that hold one entry per compiled crate.
+const CRATE_DIRS: [&str; 4] = ["deps", ".fingerprint", "build", "incremental"];
+/// A workspace member: the package name `cargo clean -p` takes and the names
+/// its artifacts carry (the package's and each target's, `-` and `_` forms).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub artifact_names: Vec<String>,
+}
+impl WorkspacePackage {
+    /// Whether `file_name` (`libmy_app-1a2b3c.rlib`, `.fingerprint/my-app-1a2b3c`,
+    /// the `my-app` binary) is an artifact of this package: one of its names,
+    /// optionally followed by `-<hash>`, then any extension.
+    fn owns(&self, file_name: &str) -> bool {
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        let unhashed = match stem.rsplit_once('-') {
+            Some((name, hash)) if hash.chars().all(|c| c.is_ascii_hexdigit()) => name,
+            _ => stem,
+        };
+        [stem, unhashed].into_iter().any(|name| {
+            let unprefixed = name.strip_prefix("lib").unwrap_or(name);
+            self.artifact_names.iter().any(|n| n == name || n == unprefixed)
+        })
+    }
+}
+impl CleanCommand {
+    /// Arguments after `cargo` for this selection; `packages` are the
+    /// workspace members cleaned with `--keep-deps`.
+    pub fn cargo_args(&self, packages: &[WorkspacePackage]) -> Vec<String> {
+        let mut args = vec!["clean".to_string()];
+        if self.doc {
+            args.push("--doc".to_string());
+        }
+        match self.profile.as_deref() {
+            Some("release") => args.push("--release".to_string()),
+            Some(profile) => args.extend(["--profile".to_string(), profile.to_string()]),
+            None => {}
+        }
+        if self.keep_deps {
+            for package in packages {
+                args.extend(["-p".to_string(), package.name.clone()]);
+            }
+        }
+        args
+    }
+    /// Bytes under `target` that this selection would delete. With
+    /// `--keep-deps` only the artifacts of `packages` are counted.
+    pub fn reclaimable(&self, target: &Path, packages: &[WorkspacePackage]) -> u64 {
+        if self.doc {
+            return dir_size(&target.join("doc"));
+        }
+        let profile_dirs: Vec<PathBuf> = match self.profile.as_deref() {
+            Some("dev") => vec![target.join("debug")],
+            Some(profile) => vec![target.join(profile)],
+            None if self.keep_deps => ["debug", "release"].map(|p| target.join(p)).to_vec(),
+            None => return dir_size(target),
+        };
+        if !self.keep_deps {
+            return profile_dirs.iter().map(|dir| dir_size(dir)).sum();
+        }
+        let owned = |path: &Path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| packages.iter().any(|p| p.owns(n)))
+        };
+        profile_dirs
+            .iter()
+            .flat_map(|dir| {
+                let nested = CRATE_DIRS.iter().map(move |sub| dir.join(sub));
+                std::iter::once(dir.clone()).chain(nested)
+            })
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path())
+            .filter(|path| owned(path))
+            .map(|path| if path.is_dir() { dir_size(&path) } else { std::fs::metadata(&path).map_or(0, |m| m.len()) })
+            .sum()
+    }
+    /// Runs `cargo clean` for the selection (or only sizes it with
+    /// `--dry-run`) and reports the space freed.
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let target = cli.options.target_dir();
+        let packages = if self.keep_deps { workspace_packages(&cli.options)? } else { Vec::new() };
+        let args = self.cargo_args(&packages);
+        if self.dry_run {
+            println!(
+                "🔎 cargo {} liberaría {}",
+                args.join(" "),
+                format_size(self.reclaimable(&target, &packages)).green().bold()
+            );
+            return Ok(());
+        }
+        let before = dir_size(&target);
+        CargoExecutor::new(&cli.options).execute_streaming(&args).await?;
+        let freed = before.saturating_sub(dir_size(&target));
+        println!("🧹 Espacio liberado: {}", format_size(freed).green().bold());
+        Ok(())
+    }
+}
+/// The current workspace's member packages. Fails rather than returning none,
+/// since `cargo clean` without `-p` would delete the dependencies too.
+pub fn workspace_packages(options: &RunOptions) -> Result<Vec<WorkspacePackage>> {
+    let metadata = metadata_command(options)
+        .no_deps()
+        .exec()
+        .context("--keep-deps necesita cargo metadata para saber qué limpiar")?;
+    let packages: Vec<WorkspacePackage> = metadata
+        .packages
+        .iter()
+        .filter(|p| metadata.workspace_members.contains(&p.id))
+        .map(|p| {
+            let mut artifact_names: Vec<String> = std::iter::once(p.name.to_string())
+                .chain(p.targets.iter().map(|t| t.name.clone()))
+                .flat_map(|name| [name.replace('-', "_"), name])
+                .collect();
+            artifact_names.sort();
+            artifact_names.dedup();
+            WorkspacePackage { name: p.name.to_string(), artifact_names }
+        })
+        .collect();
+    if packages.is_empty() {
+        anyhow::bail!("--keep-deps: el workspace no tiene paquetes; no se ejecuta cargo clean");
+    }
+    Ok(packages)
+}
+/// `1.5 GB` / `320.0 MB` / `12.3 KB` (decimal units, as the target size check).
+pub fn format_size(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    if bytes >= 1_000_000_000.0 {
+        format!("{:.1} GB", bytes / 1_000_000_000.0)
+    } else if bytes >= 1_000_000.0 {
+        format!("{:.1} MB", bytes / 1_000_000.0)
+    } else {
+        format!("{:.1} KB", bytes / 1_000.0)
+    }
+}
@@ -1,13 +1,15 @@
 #![doc = " # Clippy Command - Enhanced cargo clippy with parallelism"]
 #![doc = ""]
 #![doc = " Comando clippy mejorado con análisis paralelo y reporte inteligente"]
+use crate::cli::TraeCli;
+use crate::core::cargo::cargo_command;
+use crate::core::lints::LintSummary;
 use crate::jarvix::client::JarvixClient;
 use crate::metrics::collector::MetricsCollector;
 use crate::performance_patterns::{parallel_process, PerformanceConfig};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
 use log::info;
 use std::time::Instant;
 #[derive(Args, Debug)]
@@ -25,13 +27,19 @@ pub struct ClippyCommand {
     #[doc = " Allow warnings"]
     #[arg(long)]
     pub allow_warnings: bool,
+    #[doc = " Lint every workspace member and show per-crate totals"]
+    #[arg(long)]
+    pub workspace: bool,
+    #[doc = " Keep linting the remaining crates after one fails (cargo --keep-going)"]
+    #[arg(long)]
+    pub keep_going: bool,
     #[doc = " Additional clippy arguments"]
     #[arg(last = true)]
     pub clippy_args: Vec<String>,
 }
 impl ClippyCommand {
     #[doc = "Method documentation added by AI refactor"]
-    pub async fn execute(&self) -> Result<()> {
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         info!("🔍 Ejecutando clippy mejorado con paralelismo");
         let start_time = Instant::now();
         let mut metrics = MetricsCollector::new("clippy".to_string());
@@ -40,28 +48,48 @@ impl ClippyCommand {
         println!("  All features: {}", self.all_features);
         println!("  Auto-fix: {}", self.fix);
         println!("  Allow warnings: {}", self.allow_warnings);
-        let result = self.execute_clippy_parallel().await;
+        if self.workspace || self.keep_going {
+            println!("  Workspace: {}", self.workspace);
+            println!("  Keep going: {}", self.keep_going);
+        }
+        let result = self.execute_clippy_parallel(cli).await;
         let duration = start_time.elapsed();
+        let denied = result.as_ref().map_or(0, LintSummary::denied);
         metrics.record_build_time(duration);
-        metrics.add_custom_metric("clippy_success".to_string(), result.is_ok());
+        metrics.add_custom_metric("clippy_success".to_string(), result.is_ok() && denied == 0);
+        if let Ok(summary) = &result {
+            metrics.add_custom_metric("clippy_warnings".to_string(), summary.warnings() as u64);
+            metrics.add_custom_metric("clippy_denied".to_string(), denied as u64);
+        }
         metrics.finish();
         println!(
             "{} Clippy completado en {:.2}s",
             "✅".green(),
             duration.as_secs_f64()
         );
-        if result.is_ok() {
-            self.analyze_clippy_results_parallel()?;
+        if let Ok(summary) = &result {
+            if summary.lints.is_empty() {
+                println!("{}", "✨ Clippy no encontró lints".green());
+            } else {
+                print_lint_summary(summary, "LINTS", "denegados", self.workspace);
+            }
+            if denied == 0 {
+                self.analyze_clippy_results_parallel()?;
+            }
         }
-        if let Err(e) = self.report_metrics(metrics.clone()).await {
+        if let Err(e) = self.report_metrics(metrics.clone(), &cli.options).await {
             eprintln!("⚠️ No se pudo reportar métricas a JARVIXSERVER: {e}");
         } else {
             println!("📡 Métricas reportadas a JARVIXSERVER exitosamente");
         }
+        result?;
+        if denied > 0 {
+            return Err(crate::exit::findings(denied, "lints denegados o errores de clippy"));
+        }
         Ok(())
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn execute_clippy_parallel(&self) -> Result<String> {
+    async fn execute_clippy_parallel(&self, cli: &TraeCli) -> Result<LintSummary> {
         use tokio::process::Command;
         let mut clippy_args = vec!["clippy".to_string()];
         if self.all_targets {
@@ -70,9 +98,16 @@ impl ClippyCommand {
         if self.all_features {
             clippy_args.push("--all-features".to_string());
         }
+        if self.workspace {
+            clippy_args.push("--workspace".to_string());
+        }
+        if self.keep_going {
+            clippy_args.push("--keep-going".to_string());
+        }
         if self.fix {
             clippy_args.push("--fix".to_string());
         }
+        clippy_args.push("--message-format=json".to_string());
         if !self.allow_warnings {
             clippy_args.extend_from_slice(&[
                 "--".to_string(),
@@ -81,28 +116,17 @@ impl ClippyCommand {
             ]);
         }
         clippy_args.extend_from_slice(&self.clippy_args);
-        let progress = ProgressBar::new_spinner();
-        progress.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .expect("Failed to set progress template"),
-        );
+        let progress = crate::utils::progress::spinner(&cli.options);
         progress.set_message("Analizando código con Clippy...");
-        let output = Command::new("cargo").args(&clippy_args).output().await?;
+        let output = Command::from(cargo_command(&cli.options)).args(&clippy_args).output().await?;
         progress.finish_with_message("Análisis Clippy completado ✓".to_string());
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
+        let summary = LintSummary::parse(&String::from_utf8_lossy(&output.stdout));
+        // A failure without diagnostics is clippy itself failing, not lints
+        if !output.status.success() && summary.lints.is_empty() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            if self.allow_warnings || !stderr.contains("warning:") {
-                return Err(anyhow::anyhow!("Clippy failed: {}", stderr));
-            }
-            Ok(format!(
-                "{}\n{}",
-                String::from_utf8_lossy(&output.stdout),
-                stderr
-            ))
+            return Err(anyhow::anyhow!("Clippy failed: {}", stderr));
         }
+        Ok(summary)
     }
     #[doc = "Method documentation added by AI refactor"]
     fn analyze_clippy_results_parallel(&self) -> Result<()> {
@@ -131,8 +155,12 @@ impl ClippyCommand {
         Ok(())
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn report_metrics(&self, metrics: MetricsCollector) -> Result<()> {
-        match JarvixClient::new() {
+    async fn report_metrics(
+        &self,
+        metrics: MetricsCollector,
+        options: &crate::config::RunOptions,
+    ) -> Result<()> {
+        match JarvixClient::new(options).await {
             Ok(Some(client)) => {
                 client.report_clippy_metrics(metrics).await?;
                 println!("{}", "📊 Métricas reportadas a JARVIXSERVER".green());
@@ -147,3 +175,33 @@ impl ClippyCommand {
         Ok(())
     }
 }
+/// Boxed findings per lint (or rustc code) under `title`, most frequent
+/// first, plus the totals of every crate when `per_crate` is set. `denied`
+/// names the error-level count: denied lints for clippy, errors for check.
+pub(crate) fn print_lint_summary(
+    summary: &LintSummary,
+    title: &str,
+    denied: &str,
+    per_crate: bool,
+) {
+    let rule = "─".repeat(42usize.saturating_sub(title.chars().count()));
+    println!("{}", format!("┌─ {title} {rule}┐").yellow().bold());
+    let mut lints: Vec<_> = summary.lints.iter().collect();
+    lints.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (lint, count) in lints {
+        println!("  {count:>4} {lint}");
+    }
+    println!(
+        "  {} warnings, {} {denied}",
+        summary.warnings(),
+        summary.denied().to_string().red()
+    );
+    println!("{}", "└─────────────────────────────────────────────┘".yellow().bold());
+    if per_crate {
+        println!("{}", "┌─ POR CRATE ─────────────────────────────────┐".cyan().bold());
+        for (name, counts) in &summary.crates {
+            println!("  {name}: {} warnings, {} {denied}", counts.warnings, counts.denied);
+        }
+        println!("{}", "└─────────────────────────────────────────────┘".cyan().bold());
+    }
+}
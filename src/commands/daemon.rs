@@ -6,7 +6,9 @@ use crate::cli::TraeCli;
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
+use std::path::PathBuf;
 use std::process::Stdio;
 use tokio::process::Command;
 #[derive(Args, Debug)]
@@ -62,7 +64,55 @@ impl DaemonCommand {
             .id()
             .map(|v| v.to_string())
             .unwrap_or_else(|| "desconocido".to_string());
+        if let Some(id) = child.id() {
+            let record = DaemonPid {
+                pid: id,
+                port: self.port,
+                binary: self.binary.clone(),
+                version: crate::build_info::LONG_VERSION.to_string(),
+                started_at: chrono::Utc::now(),
+            };
+            if let Err(e) = record.save() {
+                eprintln!("{}", format!("⚠️ No se pudo guardar el PID file: {e:#}").yellow());
+            }
+        }
         println ! ("{}" , format ! ("✅ trae-server iniciado (pid {}) en background. Usa Ctrl+C para detener el CLI; el server sigue activo." , pid) . green ());
         Ok(())
     }
 }
+/// What `trae daemon` records about the server it launched, read back by
+/// `trae status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonPid {
+    pub pid: u32,
+    pub port: u16,
+    pub binary: String,
+    /// Version of the trae that launched it.
+    pub version: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+impl DaemonPid {
+    /// `daemon.pid` next to the user config (`~/.config/trae` on Linux).
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("trae").join("daemon.pid"))
+    }
+    /// The record of the last `trae daemon`, if any.
+    pub fn load() -> Result<Option<Self>> {
+        let Some(path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("No se pudo leer {}", path.display()))?;
+        let record = serde_json::from_str(&content)
+            .with_context(|| format!("PID file inválido: {}", path.display()))?;
+        Ok(Some(record))
+    }
+    fn save(&self) -> Result<()> {
+        let path = Self::path().context("sin directorio de configuración")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+#![doc = " # Dashboard Command - Project health at a glance"]
+#![doc = ""]
+#![doc = " Calidad, issues, métricas, dependencias, seguridad y último build en una sola pantalla"]
+use crate::cli::{ScanCounts, TraeCli};
+use crate::config::RunOptions;
+use crate::core::cargo::cargo_command;
+use crate::core::crawler::ProjectMetrics;
+use crate::core::freshness::{
+    dependency_ages, direct_dependencies, resolved_metadata, ReleaseLookup, DEFAULT_MAX_AGE_DAYS,
+};
+use crate::metrics::perf_history::{PerfHistory, PERF_HISTORY_FILE};
+use crate::utils::diagnostics;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+#[derive(Args, Debug)]
+#[doc = " Opciones del dashboard"]
+pub struct DashboardCommand {
+    #[doc = " Print the snapshot as JSON (for a status page) instead of the overview"]
+    #[arg(long)]
+    pub json: bool,
+}
+/// Everything `trae dashboard` shows. A sub-check that could not run is
+/// `None` and listed in `unavailable` with the reason.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dashboard {
+    pub timestamp: DateTime<Utc>,
+    pub project: String,
+    pub quality_score: Option<f64>,
+    pub issues: Option<ScanCounts>,
+    pub metrics: ProjectMetrics,
+    pub dependencies: Option<DependencyHealth>,
+    pub security: Option<SecuritySummary>,
+    /// Advisories `cargo audit` found, when it is installed.
+    pub vulnerabilities: Option<usize>,
+    pub last_build: Option<LastBuild>,
+    pub unavailable: BTreeMap<String, String>,
+}
+/// Direct crates.io dependencies and those more than `max_age_days` behind
+/// their latest release, as `name version → latest`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyHealth {
+    pub checked: usize,
+    pub outdated: Vec<String>,
+    pub max_age_days: i64,
+    /// Dependencies whose release dates could not be looked up.
+    pub unknown: usize,
+}
+/// `trae security --audit` findings by severity: critical counts as high
+/// and info as low.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SecuritySummary {
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+/// Latest `trae build` recorded in the performance history.
+#[derive(Debug, Clone, Serialize)]
+pub struct LastBuild {
+    pub timestamp: DateTime<Utc>,
+    pub total_ms: u64,
+    pub success_rate: f64,
+}
+impl DashboardCommand {
+    #[doc = " Recoge el snapshot y lo imprime como resumen o JSON"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let progress = crate::utils::progress::spinner(&cli.options);
+        if self.json {
+            progress.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+        }
+        progress.enable_steady_tick(std::time::Duration::from_millis(100));
+        progress.set_message("Recogiendo métricas, scan, dependencias y seguridad...");
+        let dashboard = collect(cli.jarvix_enabled(), &cli.options).await?;
+        progress.finish_and_clear();
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&dashboard)?);
+        } else {
+            print_dashboard(&dashboard);
+        }
+        Ok(())
+    }
+}
+/// Runs every sub-check of the dashboard for the current directory, the
+/// slow ones concurrently, the `trae` ones with `options`. Only a failure to
+/// start at all is an error.
+pub async fn collect(jarvix: bool, options: &RunOptions) -> Result<Dashboard> {
+    let exe = std::env::current_exe()?;
+    let root = std::env::current_dir()?;
+    let mut args = options.child_args();
+    if !jarvix {
+        args.push("--no-jarvix".to_string());
+    } else if options.ci {
+        args.push("--with-jarvix".to_string());
+    }
+    let weights = crate::config::TraeConfig::load().score;
+    let (scan, security, dependencies, vulnerabilities) = tokio::join!(
+        crate::core::fleet::scan_projects(
+            &exe,
+            std::slice::from_ref(&root),
+            &args,
+            1,
+            &weights,
+            options,
+        ),
+        security_findings(&exe, &args),
+        dependency_health(options),
+        cargo_audit_vulnerabilities(options),
+    );
+    let mut unavailable = BTreeMap::new();
+    let scan = scan.into_iter().next();
+    let (quality_score, issues) = match scan {
+        Some(scan) if scan.error.is_none() => (scan.quality_score, scan.counts),
+        scan => {
+            let reason = scan.and_then(|s| s.error).unwrap_or_else(|| "sin resultado".into());
+            unavailable.insert("scan".to_string(), reason);
+            (None, None)
+        }
+    };
+    let security = available(&mut unavailable, "security", security);
+    let dependencies = available(&mut unavailable, "dependencies", dependencies);
+    let vulnerabilities = available(&mut unavailable, "cargo_audit", vulnerabilities);
+    let last_build = PerfHistory::load(Path::new(PERF_HISTORY_FILE))
+        .window(None, Some("build"))
+        .runs
+        .pop()
+        .map(|run| LastBuild {
+            timestamp: run.timestamp,
+            total_ms: run.total_ms,
+            success_rate: run.success_rate,
+        });
+    Ok(Dashboard {
+        timestamp: Utc::now(),
+        project: root.file_name().map_or_else(
+            || root.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        quality_score,
+        issues,
+        metrics: crate::core::crawler::project_metrics(&root, None, options),
+        dependencies,
+        security,
+        vulnerabilities,
+        last_build,
+        unavailable,
+    })
+}
+/// The value of a sub-check, or `None` with its error recorded as the
+/// reason `check` is unavailable.
+fn available<T>(
+    unavailable: &mut BTreeMap<String, String>,
+    check: &str,
+    result: Result<T>,
+) -> Option<T> {
+    result.map_err(|e| unavailable.insert(check.to_string(), format!("{e:#}"))).ok()
+}
+/// Counts the LSP diagnostics of a `trae security --audit --format lsp` child.
+async fn security_findings(exe: &Path, args: &[String]) -> Result<SecuritySummary> {
+    let output = tokio::process::Command::new(exe)
+        .args(["security", "--audit", "--format", "lsp"])
+        .args(args)
+        .output()
+        .await
+        .context("no se pudo ejecutar trae security")?;
+    // 2 is an audit with critical findings, still a complete one
+    if !matches!(output.status.code(), Some(0 | 2)) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        anyhow::bail!("{}", reason.map_or("trae security falló", str::trim));
+    }
+    let mut summary = SecuritySummary::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(diagnostic) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        match diagnostic["severity"].as_u64().map(|s| s as u8) {
+            Some(diagnostics::ERROR) => summary.high += 1,
+            Some(diagnostics::WARNING) => summary.medium += 1,
+            Some(_) => summary.low += 1,
+            None => {}
+        }
+    }
+    Ok(summary)
+}
+/// Advisories `cargo audit --json` reports for the lockfile.
+async fn cargo_audit_vulnerabilities(options: &RunOptions) -> Result<usize> {
+    let installed = tokio::process::Command::from(cargo_command(options))
+        .args(["audit", "--version"])
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success());
+    if !installed {
+        anyhow::bail!("cargo-audit no está instalado (cargo install cargo-audit)");
+    }
+    let mut command = tokio::process::Command::from(cargo_command(options));
+    command.args(["audit", "--json"]);
+    if options.offline {
+        command.arg("--no-fetch");
+    }
+    let output = command.output().await.context("no se pudo ejecutar cargo audit")?;
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .with_context(|| {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            format!("cargo audit no devolvió JSON: {}", stderr.trim())
+        })?;
+    report["vulnerabilities"]["count"]
+        .as_u64()
+        .map(|count| count as usize)
+        .context("cargo audit no informó vulnerabilidades")
+}
+/// Direct dependencies against crates.io, from the cache only when offline.
+async fn dependency_health(options: &RunOptions) -> Result<DependencyHealth> {
+    let metadata = resolved_metadata(options)?;
+    let lookup = ReleaseLookup::new(metadata.workspace_root.as_std_path(), options.offline)?;
+    let (ages, missing) = dependency_ages(&lookup, &direct_dependencies(&metadata), |_| {}).await;
+    let outdated = ages
+        .iter()
+        .filter(|age| age.is_stale(DEFAULT_MAX_AGE_DAYS))
+        .map(|age| format!("{} {} → {}", age.name, age.version, age.latest))
+        .collect();
+    Ok(DependencyHealth {
+        checked: ages.len() + missing.len(),
+        outdated,
+        max_age_days: DEFAULT_MAX_AGE_DAYS,
+        unknown: missing.len(),
+    })
+}
+/// The single-screen overview; unavailable sub-checks say why.
+fn print_dashboard(dashboard: &Dashboard) {
+    println!("{}", format!("📊 TRAE DASHBOARD - {}", dashboard.project).cyan().bold());
+    println!("{}", "=====================================".cyan());
+    let unavailable = |check: &str| {
+        let reason = dashboard.unavailable.get(check).map_or("sin datos", String::as_str);
+        format!("no disponible ({reason})").bright_black().to_string()
+    };
+    let score = dashboard.quality_score.map_or_else(
+        || unavailable("scan"),
+        |score| {
+            let text = format!("{score:.1}/100");
+            match score {
+                s if s >= 80.0 => text.green().to_string(),
+                s if s >= 60.0 => text.yellow().to_string(),
+                _ => text.red().to_string(),
+            }
+        },
+    );
+    println!("🏆 Calidad:      {score}");
+    let issues = dashboard.issues.map_or_else(
+        || unavailable("scan"),
+        |c| {
+            let critical = format!("{} críticos", c.critical);
+            let critical =
+                if c.critical > 0 { critical.red().to_string() } else { critical };
+            format!("{critical} · {} warnings · {} info", c.warning, c.info)
+        },
+    );
+    println!("🔍 Issues:       {issues}");
+    let m = &dashboard.metrics;
+    println!(
+        "📏 Código:       {} líneas · {} archivos · {} funciones · {} tests",
+        m.total_lines, m.code_files, m.total_functions, m.total_tests
+    );
+    let deps = dashboard.dependencies.as_ref().map_or_else(
+        || unavailable("dependencies"),
+        |d| {
+            let mut line = format!(
+                "{} de {} con más de {} días de retraso",
+                d.outdated.len(),
+                d.checked,
+                d.max_age_days
+            );
+            if !d.outdated.is_empty() {
+                line = format!("{}: {}", line.yellow(), d.outdated.join(", "));
+            }
+            if d.unknown > 0 {
+                line.push_str(&format!(" ({} sin consultar)", d.unknown));
+            }
+            line
+        },
+    );
+    println!("📦 Dependencias: {deps}");
+    let security = dashboard.security.as_ref().map_or_else(
+        || unavailable("security"),
+        |s| format!("{} altos · {} medios · {} bajos", s.high, s.medium, s.low),
+    );
+    println!("🔒 Seguridad:    {security}");
+    let vulnerabilities = dashboard.vulnerabilities.map_or_else(
+        || unavailable("cargo_audit"),
+        |count| match count {
+            0 => "0 (cargo audit)".green().to_string(),
+            n => format!("{n} (cargo audit)").red().to_string(),
+        },
+    );
+    println!("🛡️ Vulnerab.:    {vulnerabilities}");
+    let build = dashboard.last_build.as_ref().map_or_else(
+        || "sin builds en el historial (ejecuta `trae build`)".bright_black().to_string(),
+        |b| {
+            format!(
+                "{} · {}ms · {:.0}% ok",
+                b.timestamp.format("%Y-%m-%d %H:%M"),
+                b.total_ms,
+                b.success_rate * 100.0
+            )
+        },
+    );
+    println!("🏗️ Último build: {build}");
+}
@@ -0,0 +1,412 @@
+#![doc = " # Deadcode Command - Project crawler and dead code candidates"]
+#![doc = ""]
+#![doc = " Lista funciones, structs, traits, TODOs y definiciones potencialmente muertas de `src/`"]
+use crate::cli::TraeCli;
+use crate::config::RunOptions;
+use crate::core::cargo::{cargo_command, CargoExecutor};
+use crate::core::features::FeatureArgs;
+use crate::core::crawler::{
+    advanced_project_crawler, extract_dependencies, macro_generated, project_metrics, scan_deadcode,
+    stream_items, unused_imports, CrawlItem, DeadCodeItem, DependencyKind, ImportInfo,
+    MacroGenerated, ProjectMetrics,
+};
+use crate::utils::path::{validate_path, WorkingDirGuard};
+use crate::utils::progress::spinner;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use console::Emoji;
+use globset::Glob;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+#[derive(Args, Debug, Default)]
+#[doc = " Opciones del crawler de dead code (`--verbose` muestra métricas y dependencias)"]
+#[doc = ""]
+#[doc = " Los filtros de dead code se combinan: el tipo (`--functions`/`--structs`/`--enums`),"]
+#[doc = " `--filter` y `--public-only`/`--private-only` deben cumplirse todos a la vez."]
+pub struct DeadcodeCommand {
+    #[doc = " Accepted for compatibility; the crawler always covers `src/`"]
+    #[arg(long)]
+    pub workspace: bool,
+    #[doc = " Show only functions"]
+    #[arg(long)]
+    pub functions: bool,
+    #[doc = " Show only structs"]
+    #[arg(long)]
+    pub structs: bool,
+    #[doc = " Show only enums"]
+    #[arg(long)]
+    pub enums: bool,
+    #[doc = " Only dead items whose name matches this glob (`test_*`, `*Handler`)"]
+    #[arg(long, value_name = "GLOB", value_parser = parse_glob)]
+    pub filter: Option<Glob>,
+    #[doc = " Only dead items declared `pub`"]
+    #[arg(long, conflicts_with = "private_only")]
+    pub public_only: bool,
+    #[doc = " Only dead items not declared `pub`"]
+    #[arg(long)]
+    pub private_only: bool,
+    #[doc = " Maximum directory depth under src/ (1 = only src/*.rs; unlimited by default)"]
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+    #[doc = " Also crawl `cargo expand` output to list macro-generated items (needs cargo-expand)"]
+    #[arg(long)]
+    pub expand: bool,
+    #[doc = " Re-read every file instead of reusing .trae/crawl-cache.json (which is left untouched)"]
+    #[arg(long)]
+    pub no_cache: bool,
+    #[doc = " Only print the project metrics box: skips symbol extraction and `cargo check`"]
+    #[arg(long)]
+    pub metrics_only: bool,
+    #[doc = " Only list `use` imports whose name never appears elsewhere in their file"]
+    #[doc = " (heuristic); skips symbol extraction and `cargo check`"]
+    #[arg(long, conflicts_with = "metrics_only")]
+    pub imports: bool,
+    #[doc = " Output format: the text report, or everything the crawler found as one JSON"]
+    #[doc = " object (`CrawledInfo` plus `dead_code`); skips `cargo check`"]
+    #[arg(
+        long,
+        default_value = "text",
+        value_parser = ["text", "json"],
+        conflicts_with_all = ["metrics_only", "imports", "expand"]
+    )]
+    pub format: String,
+    #[doc = " With --format json, print one JSON object per item as soon as its file is read"]
+    #[doc = " (JSON Lines), tagged with its `type`: function, struct, trait, todo or deadcode"]
+    #[arg(long)]
+    pub stream: bool,
+    #[doc = " Project directory to crawl (default: current directory)"]
+    #[arg(long, value_name = "DIR", value_parser = validate_path)]
+    pub path: Option<PathBuf>,
+    #[command(flatten)]
+    pub features: FeatureArgs,
+}
+impl DeadcodeCommand {
+    /// Crawls `src/`, prints the findings and finishes with `cargo check
+    /// --workspace` so rustc's own `dead_code` warnings are shown too.
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let _root = self.path.as_deref().map(WorkingDirGuard::enter).transpose()?;
+        let project = Path::new(".");
+        let mut options = cli.options.clone();
+        options.no_crawl_cache |= self.no_cache;
+        // Every crawl step walks src/ again; read the config's limit once
+        options.max_file_size = Some(options.max_file_size());
+        self.features.activate(project, &mut options);
+        if self.metrics_only {
+            let metrics = project_metrics(project, self.max_depth, &options);
+            print_metrics(&metrics, extract_dependencies(project).len());
+            return Ok(());
+        }
+        if self.imports {
+            print_unused_imports(&unused_imports(project, self.max_depth, &options));
+            return Ok(());
+        }
+        if self.stream && self.format != "json" {
+            anyhow::bail!("--stream solo está disponible con --format json");
+        }
+        if self.format == "json" {
+            return self.print_json(project, &options);
+        }
+        println!("{} {} Analizando dead code y extrayendo información del proyecto...", "→".blue().bold(), Emoji("🪦", ""));
+        let spinner = spinner(&options);
+        spinner.set_message("Ejecutando crawling avanzado del proyecto...");
+        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+        // Análisis avanzado: Crawling semántico
+        let crawled = advanced_project_crawler(project, self.max_depth, &options);
+
+        spinner.finish_with_message(format!(
+            "✓ Crawling completado: {} funciones, {} structs, {} traits, {} tests"
+        , crawled.functions.len(), crawled.structs.len(), crawled.traits.len(), crawled.tests.len()));
+        println!();
+
+        // Mostrar estadísticas del proyecto
+        if cli.verbose {
+            print_metrics(&crawled.metrics, crawled.dependencies.len());
+
+            // Mostrar dependencias
+            if !crawled.dependency_details.is_empty() {
+                println!("{}", "┌─ DEPENDENCIAS ──────────────────────────────┐".yellow().bold());
+                for (i, dep) in crawled.dependency_details.iter().take(10).enumerate() {
+                    let kind = match dep.kind {
+                        DependencyKind::Normal => String::new(),
+                        DependencyKind::Dev => " (dev)".to_string(),
+                        DependencyKind::Build => " (build)".to_string(),
+                    };
+                    let target = dep.target.as_deref().map(|t| format!(" [{t}]")).unwrap_or_default();
+                    println!("  {} {}{}", format!("{}.", i+1).bright_black(), dep.name, format!("{kind}{target}").bright_black());
+                }
+                if crawled.dependency_details.len() > 10 {
+                    println!("  ... y {} más", crawled.dependency_details.len() - 10);
+                }
+                println!("{}", "└─────────────────────────────────────────────┘".yellow().bold());
+                println!();
+            }
+        }
+
+        // Mostrar funciones encontradas
+        if !crawled.functions.is_empty() {
+            println!("{}", "┌─ FUNCIONES DETECTADAS ──────────────────────┐".green().bold());
+            for func in crawled.functions.iter().take(20) {
+                let pub_marker = if func.is_pub { "pub " } else { "" };
+                println!("  {} {}{}({})",
+                    "→".green(),
+                    pub_marker,
+                    func.name.cyan(),
+                    func.params.join(", ").bright_black()
+                );
+            }
+            if crawled.functions.len() > 20 {
+                println!("  ... y {} más", crawled.functions.len() - 20);
+            }
+            println!("{}", "└─────────────────────────────────────────────┘".green().bold());
+            println!();
+        }
+
+        // Mostrar structs
+        if !crawled.structs.is_empty() {
+            println!("{}", "┌─ STRUCTS DEFINIDAS ─────────────────────────┐".magenta().bold());
+            for st in crawled.structs.iter().take(15) {
+                let pub_marker = if st.is_pub { "pub " } else { "" };
+                println!("  {} {}{} {{ {} }}",
+                    "⚙".magenta(),
+                    pub_marker,
+                    st.name.cyan(),
+                    st.fields.join(", ").bright_black()
+                );
+            }
+            if crawled.structs.len() > 15 {
+                println!("  ... y {} más", crawled.structs.len() - 15);
+            }
+            println!("{}", "└─────────────────────────────────────────────┘".magenta().bold());
+            println!();
+        }
+
+        // Mostrar traits
+        if !crawled.traits.is_empty() {
+            println!("{}", "┌─ TRAITS DEFINIDAS ──────────────────────────┐".cyan().bold());
+            for tr in crawled.traits.iter().take(15) {
+                println!("  {} {} with {} methods",
+                    "╬".cyan(),
+                    tr.name.yellow(),
+                    tr.methods.len()
+                );
+            }
+            if crawled.traits.len() > 15 {
+                println!("  ... y {} más", crawled.traits.len() - 15);
+            }
+            println!("{}", "└─────────────────────────────────────────────┘".cyan().bold());
+            println!();
+        }
+
+        // Mostrar TODOs y FIXMEs
+        if !crawled.todos.is_empty() {
+            println!("{}", "┌─ TAREAS PENDIENTES (TODO/FIXME) ────────────┐".yellow().bold());
+            for todo in crawled.todos.iter().take(15) {
+                println!("  {} {} ({}:{})",
+                    "⚠".yellow(),
+                    todo.text.yellow(),
+                    todo.file.bright_black(),
+                    todo.line
+                );
+            }
+            if crawled.todos.len() > 15 {
+                println!("  ... y {} más", crawled.todos.len() - 15);
+            }
+            println!("{}", "└─────────────────────────────────────────────┘".yellow().bold());
+            println!();
+        }
+
+        if self.expand {
+            match cargo_expand(&self.features, &options).await {
+                Ok(Some(expanded)) => {
+                    print_macro_generated(&macro_generated(project, &expanded, &options))
+                }
+                Ok(None) => println!(
+                    "{} cargo-expand no está instalado (cargo install cargo-expand); solo se muestran ítems definidos en src/\n",
+                    "ℹ".blue()
+                ),
+                Err(e) => println!(
+                    "{} cargo expand falló; solo se muestran ítems definidos en src/: {e}\n",
+                    "⚠".yellow()
+                ),
+            }
+        }
+
+        // Análisis de dead code
+        let dead_items = scan_deadcode(project, self.max_depth, &options);
+
+        let mut filtered = dead_items.clone();
+        self.retain_matching(&mut filtered);
+
+        if !filtered.is_empty() {
+            println!("{}", "┌─ CÓDIGO POTENCIALMENTE MUERTO ──────────────┐".red().bold());
+            for item in filtered.iter().take(20) {
+                let pub_marker = if item.is_pub { "pub " } else { "" };
+                println!("{} {} {} ({}:{})",
+                    "  ✗".red(),
+                    item.item_type.red().bold(),
+                    format!("{}{}", pub_marker, item.name).bright_red(),
+                    item.file.bright_black(),
+                    item.line
+                );
+            }
+            if filtered.len() > 20 {
+                println!("  ... y {} más", filtered.len() - 20);
+            }
+            println!("{}", "└─────────────────────────────────────────────┘".red().bold());
+        }
+
+        let mut check = vec!["check".to_string(), "--workspace".to_string()];
+        check.extend(self.features.cargo_args());
+        CargoExecutor::new(&options).execute_streaming(&check).await
+    }
+}
+impl DeadcodeCommand {
+    /// Keeps the dead items that pass every filter: the kind toggle
+    /// (`--functions` wins over `--structs`, which wins over `--enums`),
+    /// the `--filter` glob on the name and the visibility flag.
+    pub fn retain_matching(&self, items: &mut Vec<DeadCodeItem>) {
+        let keep = self.dead_code_filter();
+        items.retain(|item| keep(item));
+    }
+    /// The filter of [`Self::retain_matching`] for one item at a time.
+    fn dead_code_filter(&self) -> impl Fn(&DeadCodeItem) -> bool + '_ {
+        let kind = if self.functions {
+            Some("function")
+        } else if self.structs {
+            Some("struct")
+        } else if self.enums {
+            Some("enum")
+        } else {
+            None
+        };
+        let name = self.filter.as_ref().map(Glob::compile_matcher);
+        move |item| {
+            kind.is_none_or(|kind| item.item_type == kind)
+                && name.as_ref().is_none_or(|glob| glob.is_match(&item.name))
+                && (!self.public_only || item.is_pub)
+                && (!self.private_only || !item.is_pub)
+        }
+    }
+    /// `--format json`: the whole crawl as one object or, with `--stream`,
+    /// one line per item. The dead-code filters apply to `dead_code` items.
+    fn print_json(&self, project: &Path, options: &RunOptions) -> Result<()> {
+        let keep = self.dead_code_filter();
+        if self.stream {
+            let mut out = std::io::stdout().lock();
+            return stream_items(project, self.max_depth, options, |item| {
+                if matches!(&item, CrawlItem::Deadcode(dead) if !keep(dead)) {
+                    return Ok(());
+                }
+                serde_json::to_writer(&mut out, &item)?;
+                writeln!(out)?;
+                anyhow::Ok(())
+            });
+        }
+        let mut dead_code = scan_deadcode(project, self.max_depth, options);
+        dead_code.retain(|item| keep(item));
+        let crawled = advanced_project_crawler(project, self.max_depth, options);
+        let mut json = serde_json::to_value(crawled)?;
+        json["dead_code"] = serde_json::to_value(dead_code)?;
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        Ok(())
+    }
+}
+fn parse_glob(pattern: &str) -> std::result::Result<Glob, String> {
+    Glob::new(pattern).map_err(|e| e.to_string())
+}
+/// Expanded source of the current crate, or `None` when cargo-expand isn't installed.
+async fn cargo_expand(features: &FeatureArgs, options: &RunOptions) -> Result<Option<String>> {
+    let installed = tokio::process::Command::from(cargo_command(options))
+        .args(["expand", "--version"])
+        .output()
+        .await
+        .is_ok_and(|o| o.status.success());
+    if !installed {
+        return Ok(None);
+    }
+    let output = tokio::process::Command::from(cargo_command(options))
+        .arg("expand")
+        .args(features.cargo_args())
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("sin salida"));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+/// Items that only exist after macro expansion, kept apart from the
+/// source-defined sections above.
+fn print_macro_generated(generated: &MacroGenerated) {
+    println!("{}", "┌─ GENERADO POR MACROS (cargo expand) ────────┐".blue().bold());
+    if generated.is_empty() {
+        println!("  {} Ningún ítem generado por macros", "✓".green());
+    }
+    for func in generated.functions.iter().take(20) {
+        println!("  {} fn {}({})", "✧".blue(), func.name.cyan(), func.params.join(", ").bright_black());
+    }
+    for st in generated.structs.iter().take(15) {
+        println!("  {} struct {}", "✧".blue(), st.name.cyan());
+    }
+    for tr in generated.traits.iter().take(15) {
+        println!("  {} trait {}", "✧".blue(), tr.name.cyan());
+    }
+    for imp in generated.impls.iter().take(20) {
+        println!("  {} impl {}", "✧".blue(), imp.cyan());
+    }
+    let shown = generated.functions.len().min(20)
+        + generated.structs.len().min(15)
+        + generated.traits.len().min(15)
+        + generated.impls.len().min(20);
+    let total = generated.functions.len() + generated.structs.len() + generated.traits.len() + generated.impls.len();
+    if total > shown {
+        println!("  ... y {} más", total - shown);
+    }
+    println!("  {}", "No aparecen en src/: los genera un derive o una macro".bright_black());
+    println!("{}", "└─────────────────────────────────────────────┘".blue().bold());
+    println!();
+}
+/// Recuadro de `--imports`: cada import sin uso aparente con su ubicación
+fn print_unused_imports(imports: &[ImportInfo]) {
+    if imports.is_empty() {
+        println!("{} Ningún import sin uso aparente", "✓".green());
+        return;
+    }
+    println!("{}", "┌─ IMPORTS POSIBLEMENTE SIN USO ──────────────┐".red().bold());
+    for import in imports {
+        println!(
+            "  {} {} ({}:{})",
+            "✗".red(),
+            import.path.bright_red(),
+            import.file.bright_black(),
+            import.line
+        );
+    }
+    println!("  {} imports", imports.len());
+    println!(
+        "  {}",
+        "Heurístico: el nombre no aparece fuera de los `use`; los traits usados solo por"
+            .bright_black()
+    );
+    let note = "sus métodos y los usos generados por macros también salen aquí";
+    println!("  {}", note.bright_black());
+    println!("{}", "└─────────────────────────────────────────────┘".red().bold());
+}
+/// Recuadro "MÉTRICAS DEL PROYECTO" (`--verbose` y `--metrics-only`)
+fn print_metrics(metrics: &ProjectMetrics, dependencies: usize) {
+    println!("{}", "┌─ MÉTRICAS DEL PROYECTO ─────────────────────┐".cyan().bold());
+    println!("  {} líneas de código", metrics.total_lines);
+    println!("  {} archivos Rust", metrics.code_files);
+    println!("  {} funciones totales", metrics.total_functions);
+    println!("  {} structs", metrics.total_structs);
+    println!("  {} traits", metrics.total_traits);
+    println!(
+        "  {} tests (cobertura estimada, heurística: {:.1}%)",
+        metrics.total_tests, metrics.test_coverage_estimate
+    );
+    println!("  {} dependencias", dependencies);
+    println!("{}", "└─────────────────────────────────────────────┘".cyan().bold());
+    println!();
+}
@@ -0,0 +1,250 @@
+#![doc = " # Deps Command - Declared dependencies and feature hygiene"]
+#![doc = ""]
+#![doc = " Lista las dependencias de `Cargo.toml`, las que el código no nombra y features sin uso"]
+use crate::cli::TraeCli;
+use crate::config::RunOptions;
+use crate::core::build_errors;
+use crate::core::cargo::{cargo_command, metadata_command};
+use crate::core::features::unused_features;
+use crate::core::freshness::{
+    dependency_ages, direct_dependencies, resolved_metadata, DependencySource, ReleaseLookup,
+    DEFAULT_MAX_AGE_DAYS,
+};
+use crate::core::manifest::{DependencyKind, Manifest};
+use crate::core::unused_deps::unused_dependencies;
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use console::Emoji;
+use std::path::Path;
+#[derive(Args, Debug)]
+#[doc = " Opciones del análisis de dependencias"]
+pub struct DepsCommand {
+    #[doc = " Report `[features]` entries nothing uses: no `cfg(feature = ...)`, no other"]
+    #[doc = " feature enabling them and no optional dependency behind them"]
+    #[arg(long)]
+    pub unused_features: bool,
+    #[doc = " Check that the minimum versions declared in Cargo.toml build: resolve them with"]
+    #[doc = " `cargo +nightly update -Z minimal-versions`, `cargo check` and restore Cargo.lock"]
+    #[arg(long, conflicts_with = "unused_features")]
+    pub minimal_versions: bool,
+    #[doc = " Report how old each direct dependency is: release date of the resolved and of the"]
+    #[doc = " latest version on crates.io, cached in .trae/crates-io (cache only with --offline)"]
+    #[arg(long, conflicts_with_all = ["unused_features", "minimal_versions"])]
+    pub age: bool,
+    #[doc = " With --age, flag dependencies whose resolved release is more than DAYS older than"]
+    #[doc = " the latest"]
+    #[arg(long, value_name = "DAYS", default_value_t = DEFAULT_MAX_AGE_DAYS, requires = "age")]
+    pub max_age_days: i64,
+    #[doc = " Report dependencies no source names: no path through the crate, `use` or"]
+    #[doc = " `extern crate` in the code of their table (src, tests/benches/examples, build.rs)"]
+    #[arg(long, conflicts_with_all = ["unused_features", "minimal_versions", "age"])]
+    pub unused: bool,
+    #[doc = " With --unused, exit with 2 when any dependency looks unused (for CI)"]
+    #[arg(long, requires = "unused")]
+    pub fail_on_unused: bool,
+}
+impl DepsCommand {
+    #[doc = " Ejecuta el análisis elegido; sin flags lista las dependencias de Cargo.toml"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let project = Path::new(".");
+        if self.unused_features {
+            return print_unused_features(project, &cli.options);
+        }
+        if self.minimal_versions {
+            return check_minimal_versions(&cli.options);
+        }
+        if self.age {
+            return print_dependency_age(self.max_age_days, &cli.options).await;
+        }
+        if self.unused {
+            return print_unused_dependencies(project, self.fail_on_unused, &cli.options);
+        }
+        let manifest = Manifest::load(project)?;
+        println!("{} {} Dependencias declaradas en Cargo.toml", "→".blue().bold(), Emoji("📦", ""));
+        for dep in manifest.dependencies() {
+            let kind = match dep.kind {
+                DependencyKind::Normal => String::new(),
+                DependencyKind::Dev => " (dev)".to_string(),
+                DependencyKind::Build => " (build)".to_string(),
+            };
+            let target = dep.target.map(|t| format!(" [{t}]")).unwrap_or_default();
+            println!("  {} {}{}{}", "•".yellow(), dep.name.cyan(), kind.bright_black(), target.bright_black());
+        }
+        Ok(())
+    }
+}
+fn print_unused_features(project: &Path, options: &RunOptions) -> Result<()> {
+    println!("{} {} Buscando features sin uso...", "→".blue().bold(), Emoji("🧩", ""));
+    let unused = unused_features(project, options)?;
+    if unused.is_empty() {
+        println!("  {} Todas las features de [features] se usan", "✓".green());
+        return Ok(());
+    }
+    for feature in &unused {
+        let location = feature
+            .line
+            .map_or_else(|| "Cargo.toml".to_string(), |line| format!("Cargo.toml:{line}"));
+        println!(
+            "  {} feature {} sin uso: ni cfg(feature), ni otra feature, ni dependencia opcional ({})",
+            "⚠".yellow(),
+            feature.name.yellow().bold(),
+            location.bright_black()
+        );
+    }
+    println!("\n{} {} features posiblemente muertas", "⚠".yellow(), unused.len());
+    Ok(())
+}
+/// `trae deps --unused`: dependencies whose crate no source file of their
+/// table names. Only `--fail-on-unused` turns them into a failure.
+fn print_unused_dependencies(project: &Path, fail: bool, options: &RunOptions) -> Result<()> {
+    println!("{} {} Buscando dependencias sin uso...", "→".blue().bold(), Emoji("🔍", ""));
+    let unused = unused_dependencies(project, options)?;
+    if unused.is_empty() {
+        println!("  {} Todas las dependencias de Cargo.toml se usan", "✓".green());
+        return Ok(());
+    }
+    for dep in &unused {
+        let table = match dep.kind {
+            DependencyKind::Normal => "dependencies",
+            DependencyKind::Dev => "dev-dependencies",
+            DependencyKind::Build => "build-dependencies",
+        };
+        let table =
+            dep.target.as_ref().map_or_else(|| table.to_string(), |t| format!("{t}.{table}"));
+        let package = dep.package.as_ref().map(|p| format!(" (package {p})")).unwrap_or_default();
+        let location = dep
+            .line
+            .map_or_else(|| "Cargo.toml".to_string(), |line| format!("Cargo.toml:{line}"));
+        println!(
+            "  {} {}{package} [{table}] sin uso: ni rutas, ni use, ni extern crate ({})",
+            "⚠".yellow(),
+            dep.name.yellow().bold(),
+            location.bright_black()
+        );
+    }
+    println!("\n{} {} dependencias posiblemente sin uso", "⚠".yellow(), unused.len());
+    println!(
+        "  {}",
+        "Heurístico: las usadas solo a través de macros de otro crate también salen aquí"
+            .bright_black()
+    );
+    if fail {
+        return Err(crate::exit::findings(unused.len(), "dependencias sin uso"));
+    }
+    Ok(())
+}
+/// `trae deps --age`: release dates of the resolved and latest version of
+/// every direct crates.io dependency. Git and path dependencies have no
+/// crates.io release to compare with and are only listed.
+async fn print_dependency_age(max_days: i64, options: &RunOptions) -> Result<()> {
+    println!("{} {} Antigüedad de las dependencias...", "→".blue().bold(), Emoji("📅", ""));
+    let metadata = resolved_metadata(options)?;
+    let lookup = ReleaseLookup::new(metadata.workspace_root.as_std_path(), options.offline)?;
+    let deps = direct_dependencies(&metadata);
+    let progress = crate::utils::progress::spinner(options);
+    progress.enable_steady_tick(std::time::Duration::from_millis(100));
+    let (ages, missing) = dependency_ages(&lookup, &deps, |name| {
+        progress.set_message(format!("Consultando crates.io: {name}"));
+    })
+    .await;
+    progress.finish_and_clear();
+    let date = |d: Option<chrono::DateTime<chrono::Utc>>| {
+        d.map_or_else(|| "?".to_string(), |d| d.format("%Y-%m-%d").to_string())
+    };
+    for age in &ages {
+        let gap = age.gap_days().map_or_else(|| "?".to_string(), |days| format!("{days} días"));
+        let line = format!(
+            "{} {} ({}) → {} ({}): {gap}",
+            age.name,
+            age.version,
+            date(age.released),
+            age.latest,
+            date(age.latest_released)
+        );
+        if age.is_stale(max_days) {
+            println!("  {} {}", "⚠".yellow(), line.yellow());
+        } else {
+            println!("  {} {line}", "✓".green());
+        }
+    }
+    for dep in &deps {
+        let origin = match &dep.source {
+            DependencySource::Git => "git".to_string(),
+            DependencySource::Path => "path".to_string(),
+            DependencySource::Registry(source) => source.clone(),
+            DependencySource::CratesIo => continue,
+        };
+        println!(
+            "  {} {} {}: dependencia {origin}, sin versiones en crates.io",
+            "·".bright_black(),
+            dep.name,
+            dep.version
+        );
+    }
+    for note in &missing {
+        println!("  {} No se pudo consultar {note}", "?".bright_black());
+    }
+    let stale = ages.iter().filter(|age| age.is_stale(max_days)).count();
+    if stale == 0 {
+        println!("\n{} Ninguna dependencia más de {max_days} días por detrás", "✓".green());
+    } else {
+        println!(
+            "\n{} {stale} dependencias más de {max_days} días por detrás de su última versión",
+            "⚠".yellow()
+        );
+    }
+    Ok(())
+}
+/// `trae deps --minimal-versions`: builds the project against the lowest
+/// versions its requirements allow. Needs nightly for `-Z minimal-versions`.
+/// Resolves and builds a temporary copy of the workspace, so the real
+/// `Cargo.lock` is never written; artifacts go to `<target>/minimal-versions`.
+fn check_minimal_versions(options: &RunOptions) -> Result<()> {
+    let nightly = cargo_command(options).args(["+nightly", "--version"]).output();
+    if !nightly.is_ok_and(|output| output.status.success()) {
+        anyhow::bail!(
+            "--minimal-versions necesita el toolchain nightly (cargo -Z minimal-versions). \
+             Instálalo con: rustup toolchain install nightly"
+        );
+    }
+    let root = metadata_command(options).no_deps().exec()?.workspace_root;
+    let root = root.as_std_path();
+    let sandbox = crate::core::workspace::sandbox(root)?;
+    let target_dir = root.join(options.target_dir()).join("minimal-versions");
+    println!(
+        "{} {} Resolviendo versiones mínimas (cargo +nightly update -Z minimal-versions)...",
+        "→".blue().bold(),
+        Emoji("📉", "")
+    );
+    let update = cargo_command(options)
+        .args(["+nightly", "update", "-Z", "minimal-versions"])
+        .current_dir(sandbox.path())
+        .output()
+        .context("No se pudo ejecutar cargo +nightly update")?;
+    if !update.status.success() {
+        let stderr = String::from_utf8_lossy(&update.stderr);
+        anyhow::bail!("cargo update -Z minimal-versions falló: {}", stderr.trim());
+    }
+    println!("{} {} cargo check --all-targets...", "→".blue().bold(), Emoji("🔨", ""));
+    let check = cargo_command(options)
+        .args(["check", "--all-targets", "--message-format=json", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(sandbox.path())
+        .output()
+        .context("No se pudo ejecutar cargo check")?;
+    if check.status.success() {
+        println!("  {} Las versiones mínimas declaradas compilan", "✓".green());
+        return Ok(());
+    }
+    let errors = build_errors::parse(&String::from_utf8_lossy(&check.stdout));
+    if errors.is_empty() {
+        let stderr = String::from_utf8_lossy(&check.stderr);
+        anyhow::bail!("cargo check falló: {}", stderr.trim());
+    }
+    for line in build_errors::summary_lines(&errors, build_errors::SUMMARY_LIMIT) {
+        println!("  {} {line}", "✗".red());
+    }
+    println!("  Sube los mínimos en Cargo.toml de las dependencias implicadas");
+    Err(crate::exit::findings(errors.len(), "errores con las versiones mínimas declaradas"))
+}
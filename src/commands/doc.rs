@@ -1,11 +1,11 @@
 #![doc = " # Doc Command - Documentation generation and validation"]
 #![doc = ""]
 #![doc = " Comando de documentación con generación automática, validación y publicación"]
+use crate::core::cargo::cargo_command;
 use crate::{cli::TraeCli, jarvix::client::JarvixClient, metrics::collector::MetricsCollector};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -41,11 +41,17 @@ pub struct DocCommand {
     #[arg(long, default_value = "target/doc")]
     pub output: String,
     #[doc = " Include private items"]
-    #[arg(long)]
+    #[arg(long, alias = "document-private-items")]
     pub private: bool,
     #[doc = " Generate dependency documentation"]
     #[arg(long)]
     pub deps: bool,
+    #[doc = " Document only the workspace's own crates (`cargo doc --no-deps`)"]
+    #[arg(long, conflicts_with = "deps")]
+    pub no_deps: bool,
+    #[doc = " Document every workspace member (`cargo doc --workspace`)"]
+    #[arg(long)]
+    pub workspace: bool,
 }
 impl DocCommand {
     #[doc = "Method documentation added by AI refactor"]
@@ -54,16 +60,11 @@ impl DocCommand {
         let metrics = MetricsCollector::new("doc".to_string());
         println!("{}", "📚 TRAE DOC - Documentation Suite".cyan().bold());
         println!("{}", "================================\n".cyan());
-        let pb = ProgressBar::new_spinner();
-        let style = match ProgressStyle::default_spinner().template("{spinner:.green} {msg}") {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("⚠️  progress template failed: {:?}", e);
-                ProgressStyle::default_spinner()
-            }
-        };
-        pb.set_style(style);
-        if self.generate {
+        let pb = crate::utils::progress::spinner(&cli.options);
+        // Plain `trae doc [--open]` builds the docs, as the standalone binary did
+        let generate = self.generate
+            || !(self.validate || self.api || self.readme || self.coverage || self.publish);
+        if generate {
             pb.set_message("Generando documentación...");
             self.generate_docs(cli)?;
             pb.finish_with_message("✓ Documentación generada");
@@ -96,8 +97,8 @@ impl DocCommand {
         let elapsed = start_time.elapsed();
         println!("\n{}", "✓ OPERACIÓN COMPLETADA".green().bold());
         println!("Tiempo total: {:?}", elapsed);
-        if !cli.no_jarvix {
-            if let Ok(Some(client)) = JarvixClient::new() {
+        if cli.jarvix_enabled() {
+            if let Ok(Some(client)) = JarvixClient::new(&cli.options).await {
                 if let Err(e) = client.report_doc_metrics(metrics).await {
                     eprintln!("⚠️ No se pudo reportar métricas de doc: {e}");
                 }
@@ -106,8 +107,8 @@ impl DocCommand {
         Ok(())
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn generate_docs(&self, _cli: &TraeCli) -> Result<()> {
-        let mut cmd = Command::new("cargo");
+    fn generate_docs(&self, cli: &TraeCli) -> Result<()> {
+        let mut cmd = cargo_command(&cli.options);
         cmd.arg("doc");
         if self.private {
             cmd.arg("--document-private-items");
@@ -115,6 +116,12 @@ impl DocCommand {
         if self.deps {
             cmd.arg("--include-dependencies");
         }
+        if self.no_deps {
+            cmd.arg("--no-deps");
+        }
+        if self.workspace {
+            cmd.arg("--workspace");
+        }
         let _ = !self.output.is_empty();
         let output = cmd.output()?;
         let success = output.status.success();
@@ -0,0 +1,43 @@
+#![doc = " # Explain Command - rustc error code explanations"]
+#![doc = ""]
+#![doc = " Muestra la explicación de `rustc --explain` para un código de error (E0308)"]
+use crate::core::build_errors::rustc_code;
+use crate::exit::UsageError;
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use std::process::Command;
+#[derive(Args, Debug)]
+#[doc = " Explicación de un código de error de rustc"]
+pub struct ExplainCommand {
+    #[doc = " Error code, e.g. E0308 (the E and leading zeros are optional)"]
+    #[arg(value_name = "CODE")]
+    pub code: String,
+}
+impl ExplainCommand {
+    /// Prints `rustc --explain <code>`. A malformed code, or one rustc does
+    /// not know, is a usage error.
+    pub async fn execute(&self) -> Result<()> {
+        let Some(code) = rustc_code(&self.code) else {
+            return Err(UsageError(format!(
+                "`{}` no es un código de error de rustc (formato: E0308)",
+                self.code
+            ))
+            .into());
+        };
+        let output = Command::new("rustc")
+            .args(["--explain", &code])
+            .output()
+            .context("No se pudo ejecutar rustc")?;
+        if !output.status.success() {
+            return Err(UsageError(format!(
+                "rustc no tiene explicación para {code}: no es un código de error válido"
+            ))
+            .into());
+        }
+        println!("{}", format!("📖 rustc --explain {code}").cyan().bold());
+        println!();
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        Ok(())
+    }
+}
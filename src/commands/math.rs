@@ -35,7 +35,7 @@ impl MathCommand {
             println!("❌ JARVIXSERVER requerido para análisis matemático");
             return Ok(());
         } else {
-            crate::jarvix::client::JarvixClient::new().ok().flatten()
+            crate::jarvix::client::JarvixClient::new(&trae_cli.options).await.ok().flatten()
         };
         let client = match jarvix_client {
             Some(c) => c,
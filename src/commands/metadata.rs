@@ -1,6 +1,5 @@
 use anyhow::Result;
 use clap::Args;
-use cargo_metadata::MetadataCommand as CargoMetadataCommand;
 use std::fs;
 
 #[derive(Args, Debug)]
@@ -16,9 +15,11 @@ pub struct TraeMetadataCommand {
 }
 
 impl TraeMetadataCommand {
-    pub async fn execute(&self, _cli: &crate::cli::TraeCli) -> Result<()> {
+    pub async fn execute(&self, cli: &crate::cli::TraeCli) -> Result<()> {
         // Fetch cargo metadata
-        let meta = CargoMetadataCommand::new().exec().map_err(|e| anyhow::anyhow!(e))?;
+        let meta = crate::core::cargo::metadata_command(&cli.options)
+            .exec()
+            .map_err(|e| anyhow::anyhow!(e))?;
         let mut out = serde_json::json!({
             "workspace_root": meta.workspace_root,
             "packages": [],
@@ -56,7 +57,8 @@ impl TraeMetadataCommand {
         if self.include_loc {
             // Count lines in src/**/*.rs
             let mut total = 0usize;
-            for entry in walkdir::WalkDir::new(".").into_iter().filter_map(|e| e.ok()).filter(|e| e.path().is_file() && e.path().extension().map(|s| s == "rs").unwrap_or(false)) {
+            let root = std::path::Path::new(".");
+            for entry in crate::utils::path::rust_files(root, None, &cli.options) {
                 if let Ok(s) = fs::read_to_string(entry.path()) {
                     total += s.lines().count();
                 }
@@ -2,6 +2,10 @@
 #![doc = ""]
 #![doc = " Comando para gestionar métricas y reportes"]
 use crate::cli::TraeCli;
+use crate::metrics::perf_history::{
+    parse_duration, PerfHistory, TrendDirection, PERF_HISTORY_FILE, REGRESSION_RUNS,
+    STABILITY_THRESHOLD,
+};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
@@ -17,22 +21,37 @@ pub struct MetricsCommand {
     #[doc = " Configure JARVIXSERVER connection"]
     #[arg(long)]
     pub configure: bool,
+    #[doc = " Show build stability trend from .trae/perf_history.json"]
+    #[arg(long)]
+    pub perf_trend: bool,
+    #[doc = " Number of recent runs considered by --perf-trend (default 10, or every run in the"]
+    #[doc = " --since window)"]
+    #[arg(long, requires = "perf_trend")]
+    pub last: Option<usize>,
+    #[doc = " With --perf-trend, only runs recorded in this window before now: 30m, 24h, 7d, 4w"]
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, requires = "perf_trend")]
+    pub since: Option<chrono::Duration>,
+    #[doc = " With --perf-trend, only runs of this command (e.g. build)"]
+    #[arg(long, value_name = "NAME", requires = "perf_trend")]
+    pub command: Option<String>,
 }
 impl MetricsCommand {
     #[doc = "Method documentation added by AI refactor"]
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         println!("{}", "📊 Gestión de métricas TRAE".cyan().bold());
-        if self.show {
+        if self.perf_trend {
+            self.show_perf_trend();
+        } else if self.show {
             self.show_metrics()?;
         } else if self.configure {
-            self.configure_jarvix()?;
+            self.configure_jarvix(&cli.options).await?;
         } else if let Some(path) = &self.export {
             self.export_metrics(path)?;
         } else {
             println!("📈 Estado de métricas:");
             println!(
                 "  • JARVIXSERVER: {}",
-                if cli.no_jarvix {
+                if !cli.jarvix_enabled() {
                     "Deshabilitado"
                 } else {
                     "Habilitado"
@@ -58,10 +77,77 @@ impl MetricsCommand {
         metrics.finish();
         Ok(())
     }
+    #[doc = " Muestra la tendencia de estabilidad FFT de las últimas ejecuciones"]
+    fn show_perf_trend(&self) {
+        let history = PerfHistory::load(std::path::Path::new(PERF_HISTORY_FILE));
+        if history.runs.is_empty() {
+            println!("ℹ️  Sin historial en {PERF_HISTORY_FILE}; ejecuta `trae build` primero");
+            return;
+        }
+        let since = self.since.map(|window| chrono::Utc::now() - window);
+        let history = history.window(since, self.command.as_deref());
+        let last = self.last.unwrap_or(if since.is_some() { history.runs.len() } else { 10 });
+        let runs = history.last(last);
+        let mut scope = String::new();
+        if let Some(since) = since {
+            scope.push_str(&format!(", desde {}", since.format("%Y-%m-%d %H:%M")));
+        }
+        if let Some(command) = &self.command {
+            scope.push_str(&format!(", comando {command}"));
+        }
+        if runs.is_empty() {
+            println!("ℹ️  Ninguna ejecución coincide ({})", scope.trim_start_matches(", "));
+            return;
+        }
+        println!("📈 Estabilidad de build ({} ejecuciones{scope}):", runs.len());
+        for run in runs {
+            let stability = format!("{:.2}", run.stability);
+            let stability = if run.stability < STABILITY_THRESHOLD {
+                stability.red()
+            } else {
+                stability.green()
+            };
+            let slowest = run
+                .slowest
+                .first()
+                .map(|op| format!("{} {}ms", op.name, op.duration_ms))
+                .unwrap_or_default();
+            println!(
+                "  • {} {:<8} FFT {} | {:.0}% ok | {}ms | más lenta: {}",
+                run.timestamp.format("%Y-%m-%d %H:%M"),
+                run.command,
+                stability,
+                run.success_rate * 100.0,
+                run.total_ms,
+                slowest
+            );
+        }
+        if let Some((total_ms, stability)) = PerfHistory::averages(runs) {
+            println!(
+                "  Σ {} ejecuciones | media {total_ms:.0}ms | estabilidad media {stability:.2}",
+                runs.len()
+            );
+        }
+        match history.trend(last) {
+            TrendDirection::Improving => println!("{}", "⬆️  Tendencia: mejorando".green()),
+            TrendDirection::Degrading => println!("{}", "⬇️  Tendencia: empeorando".yellow()),
+            TrendDirection::Flat => println!("➡️  Tendencia: estable"),
+        }
+        if history.regression() {
+            println!(
+                "{}",
+                format!(
+                    "🚨 Regresión: estabilidad < {STABILITY_THRESHOLD} en las últimas {REGRESSION_RUNS} ejecuciones"
+                )
+                .red()
+                .bold()
+            );
+        }
+    }
     #[doc = "Method documentation added by AI refactor"]
-    fn configure_jarvix(&self) -> Result<()> {
+    async fn configure_jarvix(&self, options: &crate::config::RunOptions) -> Result<()> {
         println!("⚙️ Configurando conexión JARVIXSERVER...");
-        match crate::jarvix::client::JarvixClient::new() {
+        match crate::jarvix::client::JarvixClient::new(options).await {
             Ok(Some(_)) => println!("✅ Conexión a JARVIXSERVER establecida"),
             Ok(None) => println!("⚠️ JARVIXSERVER no configurado"),
             Err(e) => println!("❌ Error conectando a JARVIXSERVER: {e}"),
@@ -3,20 +3,31 @@
 #![doc = " Contiene todos los subcomandos de TRAE CLI"]
 pub mod analyze;
 pub mod build;
+pub mod check;
 pub mod cargo;
+pub mod clean;
 pub mod clippy;
+pub mod dashboard;
 pub mod build_help;
 pub mod daemon;
+pub mod deadcode;
+pub mod deps;
 pub mod doc;
+pub mod explain;
 pub mod math;
 pub mod mcp;
 pub mod metrics;
+pub mod modules;
 pub mod paths;
+pub mod preflight;
 pub mod release;
 pub mod repair;
+pub mod report;
 pub mod rustup;
 pub mod security;
 pub mod simulate;
+pub mod status;
 pub mod test;
 pub mod watch;
+pub mod websearch;
 pub mod metadata;
@@ -0,0 +1,83 @@
+#![doc = " # Modules Command - Module layout of src/"]
+#![doc = ""]
+#![doc = " Lista los directorios de módulos bajo `src/` y muestra `cargo tree`"]
+use crate::cli::TraeCli;
+use crate::core::cargo::CargoExecutor;
+use crate::core::crawler::scan_modules;
+use crate::utils::path::{validate_path, WorkingDirGuard};
+use crate::utils::progress::spinner;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use console::Emoji;
+use std::path::{Path, PathBuf};
+#[derive(Args, Debug)]
+#[doc = " Opciones del análisis de módulos"]
+pub struct ModulesCommand {
+    #[doc = " Show only unused modules"]
+    #[arg(long)]
+    pub unused_only: bool,
+    #[doc = " Accepted for compatibility; dependencies are shown by `cargo tree`"]
+    #[arg(long)]
+    pub with_deps: bool,
+    #[doc = " Print modules as a tree"]
+    #[arg(long)]
+    pub tree: bool,
+    #[doc = " Maximum `cargo tree` depth"]
+    #[arg(long, short = 'd')]
+    pub depth: Option<usize>,
+    #[doc = " Project directory to inspect (default: current directory)"]
+    #[arg(long, value_name = "DIR", value_parser = validate_path)]
+    pub path: Option<PathBuf>,
+}
+impl ModulesCommand {
+    #[doc = " Lista los módulos de `src/` y muestra `cargo tree`"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let _root = self.path.as_deref().map(WorkingDirGuard::enter).transpose()?;
+        println!("{} {} Analizando módulos...", "→".blue().bold(), Emoji("📦", ""));
+        let spinner = spinner(&cli.options);
+        spinner.set_message("Escaneando estructura...");
+        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+        let modules = scan_modules(Path::new("."), &cli.options);
+
+        spinner.finish_with_message(format!(
+            "✓ Encontrados {} módulos",
+            modules.len()
+        ));
+        println!();
+
+        if self.tree {
+            println!("{}", "src/".bright_cyan().bold());
+            for (idx, module) in modules.iter().enumerate() {
+                let is_last = idx == modules.len() - 1;
+                let prefix = if is_last { "└──" } else { "├──" };
+                let status = if module.used { "✓".green() } else { "✗".red() };
+                println!("{} {} {} ({} files)",
+                    prefix.bright_black(),
+                    status,
+                    module.name.cyan(),
+                    module.file_count
+                );
+            }
+        } else {
+            for module in &modules {
+                let status = if module.used { "✓".green() } else { "✗".red() };
+                if !self.unused_only || !module.used {
+                    println!("{} {} - {} archivos",
+                        status,
+                        module.name.cyan(),
+                        module.file_count
+                    );
+                }
+            }
+        }
+        println!();
+
+        let mut args = vec!["tree".to_string()];
+        if let Some(depth) = self.depth {
+            args.extend(["--depth".to_string(), depth.to_string()]);
+        }
+        CargoExecutor::new(&cli.options).execute_streaming(&args).await
+    }
+}
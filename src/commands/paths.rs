@@ -4,7 +4,6 @@ use colored::Colorize;
 use serde_json::json;
 use std::{fs, path::PathBuf};
 use tokio::task;
-use walkdir::WalkDir;
 const RUST_EXTENSION: &str = "rs";
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
@@ -21,11 +20,12 @@ pub struct PathsCommand {
 }
 impl PathsCommand {
     #[doc = "Method documentation added by AI refactor"]
-    pub async fn execute(&self) -> Result<()> {
+    pub async fn execute(&self, cli: &crate::cli::TraeCli) -> Result<()> {
         let mut handles = Vec::with_capacity(self.paths.len());
         for path in &self.paths {
             let path = path.clone();
-            handles.push(task::spawn_blocking(move || analyze_path(&path)));
+            let options = cli.options.clone();
+            handles.push(task::spawn_blocking(move || analyze_path(&path, &options)));
         }
         let mut results = Vec::new();
         for handle in handles {
@@ -35,7 +35,7 @@ impl PathsCommand {
                 Err(err) => results.push(json ! ({ "error" : format ! ("task panicked: {err}") })),
             }
         }
-        if self.json {
+        if self.json || cli.options.ci {
             println!("{}", serde_json::to_string_pretty(&results)?);
         } else {
             render_human_readable(&results);
@@ -50,7 +50,7 @@ impl PathsCommand {
     }
 }
 #[doc = "Function documentation added by AI refactor"]
-fn analyze_path(path_str: &str) -> Result<serde_json::Value> {
+fn analyze_path(path_str: &str, options: &crate::config::RunOptions) -> Result<serde_json::Value> {
     let path = PathBuf::from(path_str);
     if !path.exists() {
         return Ok(json ! ({ "path" : path_str , "exists" : false }));
@@ -59,10 +59,8 @@ fn analyze_path(path_str: &str) -> Result<serde_json::Value> {
     if path.is_file() {
         file_entries.push(path);
     } else {
-        for entry in WalkDir::new(&path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|entry| entry.path().is_file())
+        for entry in
+            crate::utils::path::walk(&path, None, options).filter(|entry| entry.path().is_file())
         {
             if entry
                 .path()
@@ -77,7 +75,7 @@ fn analyze_path(path_str: &str) -> Result<serde_json::Value> {
     }
     let mut files_report = Vec::new();
     for file in file_entries {
-        let file_display = file.to_string_lossy().to_string();
+        let file_display = crate::utils::path::portable(&file);
         let content = match fs::read_to_string(&file) {
             Ok(content) => content,
             Err(err) => {
@@ -0,0 +1,69 @@
+#![doc = " # Preflight Command - Checks before pushing"]
+#![doc = ""]
+#![doc = " fmt --check, clippy -D warnings, tests y build release; se detiene en el primer fallo"]
+use crate::{
+    cli::TraeCli,
+    core::cargo::CargoExecutor,
+    utils::ui::{print_step_table, StepSummary},
+};
+use anyhow::Result;
+use clap::Args;
+use std::time::Instant;
+#[derive(Args, Debug)]
+#[doc = " Opciones de preflight"]
+pub struct PreflightCommand {
+    #[doc = " Skip the final `cargo build --release`"]
+    #[arg(long)]
+    pub no_build: bool,
+    #[doc = " Print `{ steps: [{ name, status, duration_ms }], ok }` as JSON; cargo output is"]
+    #[doc = " captured instead of streamed"]
+    #[arg(long)]
+    pub json: bool,
+}
+/// Steps in order; the first failing one stops the run.
+const STEPS: [(&str, &[&str]); 4] = [
+    ("Fmt check", &["fmt", "--", "--check"]),
+    ("Clippy -D warnings", &["clippy", "--", "-D", "warnings"]),
+    ("Tests", &["test"]),
+    ("Build release", &["build", "--release"]),
+];
+impl PreflightCommand {
+    /// A failing step is a quality gate failure (exit code 2), not a tool
+    /// error, with `--json` too.
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let executor = CargoExecutor::new(&cli.options);
+        let start = Instant::now();
+        let mut steps = Vec::new();
+        let mut failed = None;
+        for (label, args) in STEPS {
+            if failed.is_some() || (self.no_build && args[0] == "build") {
+                steps.push(StepSummary::skipped(label));
+                continue;
+            }
+            let step_start = Instant::now();
+            let outcome = if self.json {
+                executor.execute_with_output(args).await.map(drop)
+            } else {
+                executor.execute_streaming(args).await
+            };
+            match outcome {
+                Ok(()) => steps.push(StepSummary::success(label, step_start.elapsed())),
+                Err(e) => {
+                    steps.push(StepSummary::failed(label, step_start.elapsed(), e.to_string()));
+                    failed = Some(label);
+                }
+            }
+        }
+        if self.json {
+            let steps: Vec<_> = steps.iter().map(StepSummary::to_json).collect();
+            let report = serde_json::json!({ "steps": steps, "ok": failed.is_none() });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_step_table("Preflight Summary", &steps, start.elapsed());
+        }
+        match failed {
+            Some(label) => Err(crate::exit::findings(1, format!("paso de preflight fallido: {label}"))),
+            None => Ok(()),
+        }
+    }
+}
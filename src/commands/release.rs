@@ -1,14 +1,54 @@
 #![doc = " # Release Command"]
 #![doc = ""]
 #![doc = " Pipeline moderna: fmt check, clippy -D warnings, tests, build/package y SBOM opcional."]
+#![doc = " Con `--resume` retoma desde la etapa que falló (checkpoint en `.trae/`)."]
 use crate::{
-    core::cargo::CargoExecutor,
+    cli::TraeCli,
+    core::{
+        cargo::CargoExecutor,
+        release_state::{source_fingerprint, ReleaseState, RELEASE_STATE_FILE},
+        workspace,
+    },
     utils::ui::{print_step_table, StepSummary},
 };
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
+use std::path::PathBuf;
 use std::time::Instant;
+/// Stage key of the SBOM step, which has no fixed cargo arguments.
+const SBOM_STAGE: &str = "sbom";
+/// Steps of the run so far and the checkpoint they are recorded in.
+struct Progress {
+    steps: Vec<StepSummary>,
+    state: ReleaseState,
+    root: PathBuf,
+    state_path: PathBuf,
+    /// Labels of the stages skipped because the checkpoint had them passed.
+    resumed: Vec<String>,
+}
+impl Progress {
+    /// Whether `stage` passed in the checkpointed run; if so it is reported
+    /// as skipped.
+    fn resume(&mut self, label: &str, stage: &str) -> bool {
+        if !self.state.has_passed(stage) {
+            return false;
+        }
+        self.steps.push(StepSummary::skipped(format!("{label} (reanudado)")));
+        self.resumed.push(label.to_string());
+        true
+    }
+    /// Records `stage` as passed; the checkpoint is written right away so a
+    /// later failure (or Ctrl+C) keeps it. The fingerprint is taken again,
+    /// as cargo may have just written `Cargo.lock`.
+    fn pass(&mut self, stage: &str) {
+        self.state.mark_passed(stage);
+        self.state.fingerprint = source_fingerprint(&self.root);
+        if let Err(e) = self.state.save(&self.state_path) {
+            println!("{} {e}", "⚠️  No se pudo guardar el checkpoint:".yellow());
+        }
+    }
+}
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct ReleaseCommand {
@@ -27,72 +67,146 @@ pub struct ReleaseCommand {
     #[doc = " Ejecutar build release completo al final"]
     #[arg(long)]
     pub build: bool,
+    #[doc = " Retomar desde la etapa que falló, saltando las que ya pasaron con este mismo código"]
+    #[arg(long)]
+    pub resume: bool,
+    #[doc = " Ignorar el checkpoint y ejecutar todas las etapas (también con --resume)"]
+    #[arg(long)]
+    pub force_all: bool,
 }
 impl ReleaseCommand {
     #[doc = "Method documentation added by AI refactor"]
-    pub async fn execute(&self) -> Result<()> {
-        let executor = CargoExecutor::new();
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let executor = CargoExecutor::new(&cli.options);
         let start = Instant::now();
-        let mut steps = Vec::new();
+        let mut progress = self.load_progress()?;
+        let result = self.run_stages(&executor, &mut progress).await;
+        print_step_table("Release Summary", &progress.steps, start.elapsed());
+        if !progress.resumed.is_empty() {
+            println!(
+                "{} {}",
+                format!("⏭️  {} etapas omitidas por --resume:", progress.resumed.len()).cyan(),
+                progress.resumed.join(", ")
+            );
+        }
+        match result {
+            Ok(()) => {
+                std::fs::remove_file(&progress.state_path).ok();
+                Ok(())
+            }
+            Err(e) => {
+                if !progress.state.passed.is_empty() {
+                    println!(
+                        "{}",
+                        "💾 Etapas superadas guardadas: `trae release --resume` continúa desde aquí"
+                            .yellow()
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+    /// The checkpoint to continue from: the saved one with `--resume`, if
+    /// the sources still match it, else an empty one for the current tree.
+    fn load_progress(&self) -> Result<Progress> {
+        let root = match workspace::find_root() {
+            Some(root) => root,
+            None => std::env::current_dir()?,
+        };
+        let state_path = root.join(RELEASE_STATE_FILE);
+        let fingerprint = source_fingerprint(&root);
+        let saved = if self.resume && !self.force_all {
+            ReleaseState::load(&state_path)
+        } else {
+            None
+        };
+        let state = match saved {
+            Some(state) if state.fingerprint == fingerprint => {
+                println!(
+                    "{}",
+                    format!("🔁 Reanudando release: {} etapas ya superadas", state.passed.len())
+                        .cyan()
+                );
+                state
+            }
+            Some(_) => {
+                println!(
+                    "{}",
+                    "♻️  Checkpoint invalidado: el código cambió desde que se guardó".yellow()
+                );
+                ReleaseState::new(fingerprint)
+            }
+            None => {
+                if self.resume && !self.force_all {
+                    println!("{}", "ℹ️  Sin checkpoint previo, se ejecuta todo".dimmed());
+                }
+                ReleaseState::new(fingerprint)
+            }
+        };
+        Ok(Progress { steps: Vec::new(), state, root, state_path, resumed: Vec::new() })
+    }
+    #[doc = " Ejecuta las etapas en orden; la primera que falla corta la pipeline"]
+    async fn run_stages(&self, executor: &CargoExecutor, progress: &mut Progress) -> Result<()> {
         if self.clean {
-            self.run_step(&executor, "Clean workspace", &["clean"], &mut steps)
+            self.run_step(executor, "Clean workspace", &["clean"], progress)
                 .await?;
         } else {
-            steps.push(StepSummary::skipped("Clean workspace"));
+            progress.steps.push(StepSummary::skipped("Clean workspace"));
         }
         self.run_step(
-            &executor,
+            executor,
             "Fmt check",
             &["fmt", "--", "--check"],
-            &mut steps,
+            progress,
         )
         .await?;
         self.run_step(
-            &executor,
+            executor,
             "Clippy -D warnings",
             &["clippy", "--", "-D", "warnings"],
-            &mut steps,
+            progress,
         )
         .await?;
         if self.no_tests {
-            steps.push(StepSummary::skipped("Tests (cargo test --no-run)"));
+            progress.steps.push(StepSummary::skipped("Tests (cargo test --no-run)"));
         } else {
             self.run_step(
-                &executor,
+                executor,
                 "Tests (cargo test --no-run)",
                 &["test", "--no-run"],
-                &mut steps,
+                progress,
             )
             .await?;
         }
         if self.build {
             self.run_step(
-                &executor,
+                executor,
                 "Build release",
                 &["build", "--release"],
-                &mut steps,
+                progress,
             )
             .await?;
         } else {
-            steps.push(StepSummary::skipped("Build release"));
+            progress.steps.push(StepSummary::skipped("Build release"));
         }
         if self.no_package {
-            steps.push(StepSummary::skipped("cargo package"));
+            progress.steps.push(StepSummary::skipped("cargo package"));
         } else {
             self.run_step(
-                &executor,
+                executor,
                 "cargo package",
                 &["package", "--allow-dirty"],
-                &mut steps,
+                progress,
             )
             .await?;
         }
-        if self.sbom {
-            steps.push(self.run_sbom_step(&executor).await?);
-        } else {
-            steps.push(StepSummary::skipped("SBOM report"));
+        if !self.sbom {
+            progress.steps.push(StepSummary::skipped("SBOM report"));
+        } else if !progress.resume("SBOM report", SBOM_STAGE) {
+            let step = self.run_sbom_step(executor).await?;
+            progress.steps.push(step);
+            progress.pass(SBOM_STAGE);
         }
-        print_step_table("Release Summary", &steps, start.elapsed());
         Ok(())
     }
     #[doc = "Method documentation added by AI refactor"]
@@ -101,16 +215,21 @@ impl ReleaseCommand {
         executor: &CargoExecutor,
         label: &str,
         args: &[&str],
-        steps: &mut Vec<StepSummary>,
+        progress: &mut Progress,
     ) -> Result<()> {
+        let stage = args.join(" ");
+        if progress.resume(label, &stage) {
+            return Ok(());
+        }
         let step_start = Instant::now();
         match executor.execute_streaming(args).await {
             Ok(()) => {
-                steps.push(StepSummary::success(label, step_start.elapsed()));
+                progress.steps.push(StepSummary::success(label, step_start.elapsed()));
+                progress.pass(&stage);
                 Ok(())
             }
             Err(e) => {
-                steps.push(StepSummary::failed(
+                progress.steps.push(StepSummary::failed(
                     label,
                     step_start.elapsed(),
                     e.to_string(),
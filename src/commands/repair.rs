@@ -1,22 +1,30 @@
 #![doc = " # Repair Command - Automatic code repair and fixing"]
 #![doc = ""]
 #![doc = " Comando para reparar automáticamente issues comunes en proyectos Rust"]
+use crate::core::cargo::cargo_program;
 use crate::{
     cli::TraeCli,
+    config::RunOptions,
+    error::TraeError,
+    events::{ProgressEvent, ProgressSink},
     core::{analyzer::ProjectAnalyzer, cargo::CargoExecutor},
     jarvix::client::JarvixClient,
     metrics::collector::MetricsCollector,
-    utils::ui::{print_step_table, StepSummary},
+    utils::markdown,
+    utils::ui::{print_file_diff, print_step_table, StepSummary},
 };
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressDrawTarget;
 use log::info;
+use regex::Regex;
 use serde_json::json;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::path::Path;
 use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 use which::which;
 #[doc = " Six Sigma Repair Command - Sistema de reparación automática de defectos"]
 #[doc = ""]
@@ -64,6 +72,14 @@ pub struct RepairCommand {
     #[doc = " Export repair summary as JSON (for CI)"]
     #[arg(long, value_name = "PATH")]
     pub export: Option<String>,
+    #[doc = " Output format: text UI, or only a Markdown report of the repairs at the end"]
+    #[arg(
+        long,
+        default_value = "text",
+        value_parser = ["text", "markdown"],
+        conflicts_with = "preview"
+    )]
+    pub format: String,
     #[doc = " Run cargo check after repairs to validate"]
     #[arg(long, default_value = "true")]
     pub check: bool,
@@ -73,6 +89,9 @@ pub struct RepairCommand {
     #[doc = " Dry run - show what would be fixed"]
     #[arg(long)]
     pub dry_run: bool,
+    #[doc = " Preview the diff each fixer would produce, without modifying files"]
+    #[arg(long)]
+    pub preview: bool,
     #[doc = " Force repairs without confirmation"]
     #[arg(long, default_value = "true")]
     pub force: bool,
@@ -82,21 +101,92 @@ pub struct RepairCommand {
     #[doc = "Create backup and rollback on failure"]
     #[arg(long)]
     pub rollback: bool,
+    #[doc = " Apply repairs on a git tree with uncommitted changes, without --rollback"]
+    #[arg(long)]
+    pub allow_dirty_repair: bool,
     #[doc = "Run `cargo update` to update lockfile/deps"]
     #[arg(long)]
     pub update: bool,
     #[doc = "Run `cargo upgrade` (requires cargo-edit) to bump dependency versions"]
     #[arg(long)]
     pub upgrade: bool,
+    #[doc = "Pass `--locked` to cargo update/clippy/test/check; fail if Cargo.lock would change"]
+    #[arg(long)]
+    pub locked: bool,
     #[doc = "Create a git branch before applying changes"]
     #[arg(long, value_name = "BRANCH")]
     pub git_branch: Option<String>,
+    #[doc = " Reset --git-branch to the current commit if it already exists"]
+    #[arg(long, requires = "git_branch")]
+    pub git_branch_force: bool,
     #[doc = "Create a git commit with message after repairs"]
     #[arg(long, value_name = "MSG")]
     pub git_commit: Option<String>,
+    #[doc = " Let --git-commit include changes that were uncommitted before the repair"]
+    #[arg(long, requires = "git_commit")]
+    pub git_allow_dirty: bool,
+    #[doc = " Migrate to this edition: `cargo fix --edition` one edition at a time, the"]
+    #[doc = " `edition` of Cargo.toml, then `cargo fix --edition-idioms`. Reverted on failure"]
+    #[doc = " with --rollback"]
+    #[arg(long, value_name = "EDITION", value_parser = ["2021", "2024"])]
+    pub edition: Option<String>,
+    #[doc = " Library-only: stop between steps once cancelled (see `RepairOptions::cancel`)"]
+    #[arg(skip)]
+    pub cancel: Option<CancellationToken>,
+    #[doc = " Library-only: report progress as events instead of printing"]
+    #[arg(skip)]
+    pub progress: Option<ProgressSink>,
 }
 impl RepairCommand {
-    #[doc = "Method documentation added by AI refactor"]
+    /// Whether no repair was asked for: plain `trae repair`, which
+    /// [`Self::run_basic_sequence`] handles as the standalone binary did.
+    pub fn selects_nothing(&self) -> bool {
+        let selected = [
+            self.auto,
+            self.clippy,
+            self.fmt,
+            self.deps,
+            self.manifest,
+            self.clean,
+            self.docs,
+            self.tests,
+            self.outdated,
+            self.update,
+            self.upgrade,
+            self.dry_run,
+            self.preview,
+            self.rollback,
+        ];
+        !selected.contains(&true) && self.edition.is_none()
+    }
+    /// Plain `trae repair`: `cargo fix`, `cargo fmt` and `cargo clippy --fix`
+    /// at the workspace root, each only warning when it fails, then `cargo
+    /// build`, whose result is the command's. Cargo runs under `options`.
+    pub async fn run_basic_sequence(&self, options: &RunOptions) -> Result<()> {
+        let root = crate::core::workspace::find_root().unwrap_or_else(|| ".".into());
+        let dirty = dirty_paths(&root);
+        if !self.allow_dirty_repair && !dirty.is_empty() {
+            return Err(dirty_tree_error(&dirty, "--allow-dirty-repair"));
+        }
+        println!("{} Iniciando secuencia de REPARACIÓN", "🔧".cyan().bold());
+        let executor = CargoExecutor::new(options).with_working_dir(root);
+        let fixes: [(&str, &[&str]); 3] = [
+            ("cargo fix", &["fix", "--allow-dirty", "--allow-staged"]),
+            ("cargo fmt", &["fmt"]),
+            ("cargo clippy --fix", &["clippy", "--fix", "--allow-dirty", "--allow-staged"]),
+        ];
+        for (i, (step, args)) in fixes.iter().enumerate() {
+            println!("{} {}/{} {step}", "→".blue().bold(), i + 1, fixes.len() + 1);
+            if let Err(e) = executor.execute_streaming(args).await {
+                println!("  {} {step} falló, se continúa: {e}", "⚠️".yellow());
+            }
+        }
+        println!("{} {}/{} cargo build", "→".blue().bold(), fixes.len() + 1, fixes.len() + 1);
+        executor.execute_streaming(&["build"]).await?;
+        println!("{}", "✅ Reparación completada".green().bold());
+        Ok(())
+    }
+    /// Runs the selected repairs from the workspace root; see the options.
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         info!("?? Iniciando proceso de reparaci¢n autom tica");
         let total_start = Instant::now();
@@ -109,26 +199,30 @@ impl RepairCommand {
         let mut post_check: Option<PostCheckOutcome> = None;
         let mut repairs_planned = false;
         let mut repairs_executed = false;
-        self.show_repair_config();
+        let mut cancelled = false;
+        if self.prints() {
+            self.show_repair_config(&cli.options);
+        }
         // Ensure we run from the workspace root so repairs work from any subdir
         let orig_cwd = std::env::current_dir()?;
-        let mut root = orig_cwd.clone();
-        let mut found = false;
-        while !root.join("Cargo.toml").exists() {
-            if !root.pop() {
-                break;
-            }
-        }
-        if root.join("Cargo.toml").exists() {
-            found = true;
+        if let Some(root) = crate::core::workspace::find_root() {
+            let _ = std::env::set_current_dir(root);
         }
-        if found {
-            let _ = std::env::set_current_dir(&root);
+        let dirty = dirty_paths(Path::new("."));
+        if !self.dry_run
+            && !self.preview
+            && !self.rollback
+            && !self.allow_dirty_repair
+            && !dirty.is_empty()
+        {
+            let _ = std::env::set_current_dir(&orig_cwd);
+            return Err(dirty_tree_error(&dirty, "--rollback o --allow-dirty-repair"));
         }
         let detection_start = Instant::now();
-        let issues = match self.detect_issues().await {
+        self.emit_started("Detecci¢n de issues");
+        let issues = match self.detect_issues(&cli.options).await {
             Ok(list) => {
-                steps.push(StepSummary::success(
+                self.record(&mut steps, StepSummary::success(
                     "Detecci¢n de issues",
                     detection_start.elapsed(),
                 ));
@@ -136,7 +230,7 @@ impl RepairCommand {
             }
             Err(e) => {
                 let msg = e.to_string();
-                steps.push(StepSummary::failed(
+                self.record(&mut steps, StepSummary::failed(
                     "Detecci¢n de issues",
                     detection_start.elapsed(),
                     msg,
@@ -145,22 +239,27 @@ impl RepairCommand {
                 Vec::new()
             }
         };
-        if fatal_error.is_none() && issues.is_empty() {
+        if fatal_error.is_none() && self.checkpoint(&mut steps, "tras la detección") {
+            cancelled = true;
+        }
+        if fatal_error.is_none() && !cancelled && issues.is_empty() && self.prints() {
             println!(
                 "{}",
                 "? No se encontraron issues que reparar".green().bold()
             );
         }
         if fatal_error.is_none() && !issues.is_empty() {
-            self.show_detected_issues(&issues);
-            repairs_planned = true;
+            if self.prints() {
+                self.show_detected_issues(&issues);
+            }
+            repairs_planned = !cancelled;
         }
         let confirm_label = "Confirmaci¢n de reparaci¢n";
-        if fatal_error.is_none() {
+        if fatal_error.is_none() && !cancelled {
             if issues.is_empty() {
-                steps.push(StepSummary::skipped(confirm_label));
-            } else if self.force || self.dry_run {
-                steps.push(StepSummary::success(
+                self.record(&mut steps, StepSummary::skipped(confirm_label));
+            } else if self.force || cli.ci || self.dry_run || self.preview {
+                self.record(&mut steps, StepSummary::success(
                     "Confirmaci¢n autom tica",
                     Duration::default(),
                 ));
@@ -168,11 +267,11 @@ impl RepairCommand {
                 let confirm_start = Instant::now();
                 match self.confirm_repairs(&issues) {
                     Ok(true) => {
-                        steps.push(StepSummary::success(confirm_label, confirm_start.elapsed()))
+                        self.record(&mut steps, StepSummary::success(confirm_label, confirm_start.elapsed()))
                     }
                     Ok(false) => {
                         println!("{}", "? Reparaci¢n cancelada por el usuario".yellow());
-                        steps.push(StepSummary::failed(
+                        self.record(&mut steps, StepSummary::failed(
                             confirm_label,
                             confirm_start.elapsed(),
                             "Cancelado por el usuario".to_string(),
@@ -181,7 +280,7 @@ impl RepairCommand {
                     }
                     Err(e) => {
                         let msg = e.to_string();
-                        steps.push(StepSummary::failed(
+                        self.record(&mut steps, StepSummary::failed(
                             confirm_label,
                             confirm_start.elapsed(),
                             msg,
@@ -192,26 +291,47 @@ impl RepairCommand {
                 }
             }
         } else {
-            steps.push(StepSummary::skipped(confirm_label));
+            self.record(&mut steps, StepSummary::skipped(confirm_label));
         }
-        let repair_label = if self.dry_run {
+        let repair_label = if self.preview {
+            "Previsualización de reparaciones"
+        } else if self.dry_run {
             "Simulaci¢n de reparaciones"
         } else {
             "Aplicar reparaciones"
         };
         if fatal_error.is_none() && repairs_planned && !issues.is_empty() {
             let repair_start = Instant::now();
-            if self.dry_run {
+            self.emit_started(repair_label);
+            if self.preview {
+                match self.preview_repairs(&issues, &cli.options).await {
+                    Ok(results) => {
+                        repair_results = results;
+                        repair_stage_duration = repair_start.elapsed();
+                        self.record(&mut steps, StepSummary::success(repair_label, repair_stage_duration));
+                        repairs_executed = true;
+                    }
+                    Err(e) => {
+                        let msg = e.to_string();
+                        self.record(&mut steps, StepSummary::failed(
+                            repair_label,
+                            repair_start.elapsed(),
+                            msg,
+                        ));
+                        fatal_error = Some(e);
+                    }
+                }
+            } else if self.dry_run {
                 match self.simulate_repairs(&issues) {
                     Ok(results) => {
                         repair_results = results;
                         repair_stage_duration = repair_start.elapsed();
-                        steps.push(StepSummary::success(repair_label, repair_stage_duration));
+                        self.record(&mut steps, StepSummary::success(repair_label, repair_stage_duration));
                         repairs_executed = true;
                     }
                     Err(e) => {
                         let msg = e.to_string();
-                        steps.push(StepSummary::failed(
+                        self.record(&mut steps, StepSummary::failed(
                             repair_label,
                             repair_start.elapsed(),
                             msg,
@@ -220,17 +340,17 @@ impl RepairCommand {
                     }
                 }
             } else {
-                match self.execute_repairs(&issues).await {
+                match self.execute_repairs(&issues, &cli.options).await {
                     Ok((results, durations)) => {
                         repair_stage_duration = repair_start.elapsed();
                         repair_results = results;
                         category_durations = durations;
-                        steps.push(StepSummary::success(repair_label, repair_stage_duration));
+                        self.record(&mut steps, StepSummary::success(repair_label, repair_stage_duration));
                         repairs_executed = true;
                     }
                     Err(e) => {
                         let msg = e.to_string();
-                        steps.push(StepSummary::failed(
+                        self.record(&mut steps, StepSummary::failed(
                             repair_label,
                             repair_start.elapsed(),
                             msg,
@@ -240,86 +360,78 @@ impl RepairCommand {
                 }
             }
         } else {
-            steps.push(StepSummary::skipped(repair_label));
+            self.record(&mut steps, StepSummary::skipped(repair_label));
         }
         if fatal_error.is_none() {
             self.append_phase_steps(
                 &mut steps,
                 &issues,
                 &repair_results,
-                if self.dry_run || category_durations.is_empty() {
+                if self.dry_run || self.preview || category_durations.is_empty() {
                     None
                 } else {
                     Some(&category_durations)
                 },
             );
         }
-        if fatal_error.is_none() && repairs_executed {
+        if fatal_error.is_none() && repairs_executed && self.prints() {
             self.show_results(&repair_results, repair_stage_duration);
         }
+        if fatal_error.is_none() && !cancelled && self.checkpoint(&mut steps, "tras las reparaciones") {
+            cancelled = true;
+        }
         // Optionally update/upgrade dependencies and commit changes
-        if fatal_error.is_none() && repairs_executed {
-            let executor = CargoExecutor::new();
+        if fatal_error.is_none() && !cancelled && repairs_executed && !self.preview {
+            let executor = CargoExecutor::new(&cli.options);
             if self.update {
                 let upd_start = Instant::now();
-                match executor.execute_streaming(&["update"]).await {
-                    Ok(_) => steps.push(StepSummary::success("Actualizar dependencias (cargo update)", upd_start.elapsed())),
-                    Err(e) => steps.push(StepSummary::failed("Actualizar dependencias (cargo update)", upd_start.elapsed(), e.to_string())),
+                self.emit_started("Actualizar dependencias (cargo update)");
+                match self.cargo_update(&executor).await {
+                    Ok(_) => self.record(&mut steps, StepSummary::success("Actualizar dependencias (cargo update)", upd_start.elapsed())),
+                    Err(e) => self.record(&mut steps, StepSummary::failed("Actualizar dependencias (cargo update)", upd_start.elapsed(), e.to_string())),
                 }
             }
             if self.upgrade {
                 let upg_start = Instant::now();
-                match executor.execute_streaming(&["upgrade"]).await {
-                    Ok(_) => steps.push(StepSummary::success("Upgrade deps (cargo upgrade)", upg_start.elapsed())),
-                    Err(e) => steps.push(StepSummary::failed("Upgrade deps (cargo upgrade)", upg_start.elapsed(), e.to_string())),
-                }
-            }
-            // Git operations: create branch and commit
-            if let Some(branch) = &self.git_branch {
-                let git_start = Instant::now();
-                match std::process::Command::new("git").args(["checkout", "-b", branch]).output() {
-                    Ok(o) if o.status.success() => steps.push(StepSummary::success(format!("Crear branch git: {}", branch), git_start.elapsed())),
-                    Ok(o) => steps.push(StepSummary::failed(format!("Crear branch git: {}", branch), git_start.elapsed(), String::from_utf8_lossy(&o.stderr).to_string())),
-                    Err(e) => steps.push(StepSummary::failed(format!("Crear branch git: {}", branch), git_start.elapsed(), e.to_string())),
-                }
-            }
-            if let Some(msg) = &self.git_commit {
-                let git_start = Instant::now();
-                let add = std::process::Command::new("git").args(["add", "-A"]).output();
-                if let Ok(a) = add {
-                    if a.status.success() {
-                        match std::process::Command::new("git").args(["commit", "-m", msg]).output() {
-                            Ok(c) if c.status.success() => steps.push(StepSummary::success("Git commit", git_start.elapsed())),
-                            Ok(c) => steps.push(StepSummary::failed("Git commit", git_start.elapsed(), String::from_utf8_lossy(&c.stderr).to_string())),
-                            Err(e) => steps.push(StepSummary::failed("Git commit", git_start.elapsed(), e.to_string())),
-                        }
-                    } else {
-                        steps.push(StepSummary::failed("Git add", git_start.elapsed(), String::from_utf8_lossy(&a.stderr).to_string()));
-                    }
-                } else if let Err(e) = add {
-                    steps.push(StepSummary::failed("Git add", git_start.elapsed(), e.to_string()));
+                self.emit_started("Upgrade deps (cargo upgrade)");
+                match self.run_cargo(&executor, &["upgrade"]).await {
+                    Ok(_) => self.record(&mut steps, StepSummary::success("Upgrade deps (cargo upgrade)", upg_start.elapsed())),
+                    Err(e) => self.record(&mut steps, StepSummary::failed("Upgrade deps (cargo upgrade)", upg_start.elapsed(), e.to_string())),
                 }
             }
+            self.run_git_steps(&mut steps, &dirty);
         }
         let check_label = "Cargo check";
-        if self.check && !self.dry_run && fatal_error.is_none() && repairs_executed {
+        if fatal_error.is_none() && !cancelled && self.checkpoint(&mut steps, "antes del post-check") {
+            cancelled = true;
+        }
+        if self.check
+            && !self.dry_run
+            && !self.preview
+            && fatal_error.is_none()
+            && !cancelled
+            && repairs_executed
+        {
             let check_start = Instant::now();
-            match self.run_post_check().await {
+            self.emit_started(check_label);
+            match self.run_post_check(&cli.options).await {
                 Ok(outcome) => {
-                    steps.push(StepSummary::success(check_label, check_start.elapsed()));
-                    self.show_post_check(&outcome);
+                    self.record(&mut steps, StepSummary::success(check_label, check_start.elapsed()));
+                    if self.prints() {
+                        self.show_post_check(&outcome);
+                    }
                     post_check = Some(outcome);
                 }
                 Err(e) => {
                     let msg = e.to_string();
-                    steps.push(StepSummary::failed(check_label, check_start.elapsed(), msg));
+                    self.record(&mut steps, StepSummary::failed(check_label, check_start.elapsed(), msg));
                 }
             }
         } else {
-            steps.push(StepSummary::skipped(check_label));
+            self.record(&mut steps, StepSummary::skipped(check_label));
         }
-        let export_label = self.export_step_label();
-        if let Some(path) = &self.export {
+        let export_label = self.export_step_label(&cli.options);
+        if let Some(path) = &self.export_path(&cli.options) {
             if fatal_error.is_none() {
                 let export_start = Instant::now();
                 match self.export_report(
@@ -329,15 +441,18 @@ impl RepairCommand {
                     post_check.as_ref(),
                 ) {
                     Ok(()) => {
-                        steps.push(StepSummary::success(
+                        self.record(&mut steps, StepSummary::success(
                             export_label.clone(),
                             export_start.elapsed(),
                         ));
-                        println!("? Reporte de reparaci¢n exportado a {}", path);
+                        if self.prints() {
+                            let path = crate::config::full_path(path);
+                            println!("? Reporte de reparaci¢n exportado a {}", path);
+                        }
                     }
                     Err(e) => {
                         let msg = e.to_string();
-                        steps.push(StepSummary::failed(
+                        self.record(&mut steps, StepSummary::failed(
                             export_label.clone(),
                             export_start.elapsed(),
                             msg,
@@ -345,26 +460,36 @@ impl RepairCommand {
                     }
                 }
             } else {
-                steps.push(StepSummary::skipped(export_label.clone()));
+                self.record(&mut steps, StepSummary::skipped(export_label.clone()));
             }
         } else {
-            steps.push(StepSummary::skipped(export_label));
+            self.record(&mut steps, StepSummary::skipped(export_label));
         }
         metrics.record_repair_time(repair_stage_duration);
         metrics.record_repairs_applied(&repair_results);
         metrics.finish();
-        if cli.no_jarvix {
-            steps.push(StepSummary::skipped("Jarvix report"));
+        if let Some(sink) = &self.progress {
+            let mut recorded: Vec<_> = metrics.metrics.iter().collect();
+            recorded.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, value) in recorded {
+                sink.emit(ProgressEvent::Metric {
+                    name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        if !cli.jarvix_enabled() {
+            self.record(&mut steps, StepSummary::skipped("Jarvix report"));
         } else if fatal_error.is_none() {
             let jarvix_start = Instant::now();
-            match self.report_metrics(metrics.clone()).await {
-                Ok(()) => steps.push(StepSummary::success(
+            match self.report_metrics(metrics.clone(), &cli.options).await {
+                Ok(()) => self.record(&mut steps, StepSummary::success(
                     "Jarvix report",
                     jarvix_start.elapsed(),
                 )),
                 Err(e) => {
                     let msg = e.to_string();
-                    steps.push(StepSummary::failed(
+                    self.record(&mut steps, StepSummary::failed(
                         "Jarvix report",
                         jarvix_start.elapsed(),
                         msg,
@@ -372,11 +497,20 @@ impl RepairCommand {
                 }
             }
         } else {
-            steps.push(StepSummary::skipped("Jarvix report"));
+            self.record(&mut steps, StepSummary::skipped("Jarvix report"));
         }
         let total_duration = total_start.elapsed();
-        print_step_table("Repair Summary", &steps, total_duration);
-        if let Some(err) = fatal_error {
+        if self.prints() {
+            print_step_table("Repair Summary", &steps, total_duration);
+        } else if self.progress.is_none() && fatal_error.is_none() {
+            let report =
+                self.markdown_report(&repair_results, total_duration, post_check.as_ref());
+            print!("{}", report.render());
+        }
+        if cancelled {
+            let _ = std::env::set_current_dir(orig_cwd);
+            Err(TraeError::Cancelled.into())
+        } else if let Some(err) = fatal_error {
             let _ = std::env::set_current_dir(orig_cwd);
             Err(err)
         } else {
@@ -384,8 +518,195 @@ impl RepairCommand {
             Ok(())
         }
     }
+    /// True once the caller's token is cancelled; records where the run stopped.
+    /// Steps after a checkpoint are skipped, but the summary, export and
+    /// JARVIX report still cover what ran.
+    fn checkpoint(&self, steps: &mut Vec<StepSummary>, at: &str) -> bool {
+        let cancelled = self
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        if cancelled {
+            if self.prints() {
+                println!("{}", format!("⏹️ Reparación cancelada {at}").yellow());
+            }
+            self.record(steps, StepSummary::failed(
+                "Cancelación",
+                Duration::default(),
+                format!("cancelado {at}"),
+            ));
+        }
+        cancelled
+    }
+    /// Without a [`ProgressSink`] the command prints its own UI, unless
+    /// `--format markdown` leaves stdout to the final report.
+    fn prints(&self) -> bool {
+        self.progress.is_none() && self.format != "markdown"
+    }
+    fn emit_started(&self, label: &str) {
+        if let Some(sink) = &self.progress {
+            sink.emit(ProgressEvent::StepStarted {
+                label: label.to_string(),
+            });
+        }
+    }
+    /// Adds a row to the summary table and reports it to the sink.
+    fn record(&self, steps: &mut Vec<StepSummary>, step: StepSummary) {
+        if let Some(sink) = &self.progress {
+            sink.emit(ProgressEvent::StepFinished(step.clone()));
+        }
+        steps.push(step);
+    }
+    /// `--git-branch` and `--git-commit`. With `--dry-run` the commands are
+    /// printed instead of run. An existing branch is only reused with
+    /// `--git-branch-force`, and the commit is skipped when there is nothing
+    /// to commit or when `dirty_before` (uncommitted before the repair) would
+    /// be swept into it without `--git-allow-dirty`. If the branch could not
+    /// be created nothing is committed, so it can't land on the wrong one.
+    fn run_git_steps(&self, steps: &mut Vec<StepSummary>, dirty_before: &[String]) {
+        let mut branch_failed = false;
+        if let Some(branch) = &self.git_branch {
+            let label = format!("Crear branch git: {branch}");
+            let flag = if self.git_branch_force { "-B" } else { "-b" };
+            let args = ["checkout", flag, branch.as_str()];
+            if self.dry_run {
+                self.git_dry_run(steps, &label, &[&args]);
+            } else if !self.git_branch_force && branch_exists(Path::new("."), branch) {
+                let error = format!("la rama '{branch}' ya existe; usa --git-branch-force");
+                self.git_failed(steps, &label, Duration::default(), error);
+                branch_failed = true;
+            } else {
+                let start = Instant::now();
+                match git(&args) {
+                    Ok(()) => self.record(steps, StepSummary::success(label, start.elapsed())),
+                    Err(e) => {
+                        self.git_failed(steps, &label, start.elapsed(), e);
+                        branch_failed = true;
+                    }
+                }
+            }
+        }
+        if let Some(msg) = &self.git_commit {
+            let label = "Git commit";
+            let commands: [&[&str]; 2] = [&["add", "-A"], &["commit", "-m", msg]];
+            if self.dry_run {
+                self.git_dry_run(steps, label, &commands);
+                return;
+            }
+            let changed = dirty_paths(Path::new("."));
+            let swept: Vec<&str> = changed
+                .iter()
+                .filter(|path| dirty_before.contains(path))
+                .map(String::as_str)
+                .collect();
+            let skip = if branch_failed {
+                Some("no se pudo crear la rama".to_string())
+            } else if changed.is_empty() {
+                Some("sin cambios que commitear".to_string())
+            } else if !swept.is_empty() && !self.git_allow_dirty {
+                Some(format!(
+                    "{} archivo(s) ya tenían cambios antes de repair ({}); usa --git-allow-dirty",
+                    swept.len(),
+                    swept.join(", ")
+                ))
+            } else {
+                None
+            };
+            match skip {
+                Some(reason) => {
+                    if self.prints() {
+                        println!("{} {reason}", "ℹ️  Git commit omitido:".yellow());
+                    }
+                    self.record(steps, StepSummary::skipped(label));
+                }
+                None => {
+                    let start = Instant::now();
+                    match commands.iter().try_for_each(|args| git(args)) {
+                        Ok(()) => self.record(steps, StepSummary::success(label, start.elapsed())),
+                        Err(e) => self.git_failed(steps, label, start.elapsed(), e),
+                    }
+                }
+            }
+        }
+    }
+    /// Records a failed git step; the summary table cuts long messages, so
+    /// the whole one is printed too.
+    fn git_failed(&self, steps: &mut Vec<StepSummary>, label: &str, took: Duration, error: String) {
+        if self.prints() {
+            println!("{} {error}", format!("❌ {label}:").red());
+        }
+        self.record(steps, StepSummary::failed(label, took, error));
+    }
+    /// `--dry-run`: shows the git commands a real run would execute.
+    fn git_dry_run(&self, steps: &mut Vec<StepSummary>, label: &str, commands: &[&[&str]]) {
+        if self.prints() {
+            for args in commands {
+                let shown: Vec<String> = args
+                    .iter()
+                    .map(|a| if a.contains(' ') { format!("{a:?}") } else { a.to_string() })
+                    .collect();
+                println!("{} git {}", "🔎 [dry-run]".cyan(), shown.join(" "));
+            }
+        }
+        self.record(steps, StepSummary::skipped(format!("{label} (dry-run)")));
+    }
+    /// Runs `cargo update` (with `--locked` when asked), retrying once on a
+    /// transient registry/network failure. Resolution failures name the crate
+    /// and the action to take instead of returning cargo's raw output.
+    async fn cargo_update(&self, executor: &CargoExecutor) -> Result<()> {
+        let args: &[&str] = if self.locked {
+            &["update", "--locked"]
+        } else {
+            &["update"]
+        };
+        let mut retried = false;
+        loop {
+            let output = match self.run_cargo_capture(executor, args).await {
+                Ok(_) => return Ok(()),
+                Err(e) => e.to_string(),
+            };
+            let lower = output.to_lowercase();
+            if self.locked && lower.contains("--locked") {
+                return Err(anyhow::anyhow!(
+                    "Cargo.lock tendría que cambiar y se pasó --locked; ejecuta `cargo update` sin --locked y commitea el lockfile"
+                ));
+            }
+            if !retried && TRANSIENT_UPDATE_ERRORS.iter().any(|p| lower.contains(p)) {
+                if self.prints() {
+                    println!("  {}", "⚠️ Fallo transitorio en cargo update, reintentando...".yellow());
+                }
+                retried = true;
+                continue;
+            }
+            return Err(describe_update_failure(&output));
+        }
+    }
+    /// Runs cargo on the terminal, or with its output sent to the sink.
+    async fn run_cargo(&self, executor: &CargoExecutor, args: &[&str]) -> Result<()> {
+        match &self.progress {
+            Some(_) => self.run_cargo_capture(executor, args).await.map(drop),
+            None => executor.execute_streaming(args).await,
+        }
+    }
+    /// Like [`Self::run_cargo`] but also returns the combined output; with a
+    /// sink each line arrives as a [`ProgressEvent::Output`].
+    async fn run_cargo_capture(&self, executor: &CargoExecutor, args: &[&str]) -> Result<String> {
+        match &self.progress {
+            Some(sink) => {
+                executor
+                    .execute_streaming_capture_with_handler(args, |stream, line| {
+                        sink.emit(ProgressEvent::Output {
+                            stream,
+                            line: line.to_string(),
+                        })
+                    })
+                    .await
+            }
+            None => executor.execute_streaming_capture(args).await,
+        }
+    }
     #[doc = "Method documentation added by AI refactor"]
-    fn show_repair_config(&self) {
+    fn show_repair_config(&self, options: &RunOptions) {
         println!("{}", "🔧 Configuración de Reparación:".cyan().bold());
         if self.auto {
             println!("  • Modo: {}", "Automático Completo".green());
@@ -412,6 +733,9 @@ impl RepairCommand {
             if self.tests {
                 repairs.push("Tests");
             }
+            if self.edition.is_some() {
+                repairs.push("Edición");
+            }
             if repairs.is_empty() {
                 println!("  • Reparaciones: {}", "Ninguna seleccionada".red());
             } else {
@@ -434,7 +758,13 @@ impl RepairCommand {
                 "No".yellow()
             }
         );
-        if let Some(path) = &self.export {
+        if self.locked {
+            println!("  • Cargo.lock: {}", "--locked".green());
+        }
+        if let Some(edition) = &self.edition {
+            println!("  • Edición objetivo: {}", edition.green());
+        }
+        if let Some(path) = &self.export_path(options) {
             println!("  • Exportar reporte: {}", path);
         }
         println!(
@@ -445,6 +775,9 @@ impl RepairCommand {
                 "No".blue()
             }
         );
+        if self.preview {
+            println!("  • Preview: {}", "Sí (no se modificarán archivos)".yellow());
+        }
         println!(
             "  • Forzar: {}",
             if self.force {
@@ -455,9 +788,14 @@ impl RepairCommand {
         );
         println!();
     }
+    /// Where the repair summary goes: `--export`, else `repair-report.json`
+    /// in `--output-dir` of `options`.
+    fn export_path(&self, options: &RunOptions) -> Option<String> {
+        options.report_path(self.export.as_deref(), "repair-report.json")
+    }
     #[doc = "Method documentation added by AI refactor"]
-    fn export_step_label(&self) -> String {
-        self.export
+    fn export_step_label(&self, options: &RunOptions) -> String {
+        self.export_path(options)
             .as_ref()
             .map(|path| format!("Export report ({path})"))
             .unwrap_or_else(|| "Export report".to_string())
@@ -472,8 +810,12 @@ impl RepairCommand {
     ) {
         for category in self.summary_categories(issues) {
             let label = Self::phase_label(category);
+            if self.preview {
+                self.record(steps, StepSummary::skipped(format!("{label} (preview)")));
+                continue;
+            }
             if self.dry_run {
-                steps.push(StepSummary::skipped(format!("{label} (dry-run)")));
+                self.record(steps, StepSummary::skipped(format!("{label} (dry-run)")));
                 continue;
             }
             let cat_results: Vec<&RepairResult> = results
@@ -481,7 +823,7 @@ impl RepairCommand {
                 .filter(|r| r.issue.category == category)
                 .collect();
             if cat_results.is_empty() {
-                steps.push(StepSummary::skipped(label));
+                self.record(steps, StepSummary::skipped(label));
                 continue;
             }
             let duration = durations
@@ -489,14 +831,14 @@ impl RepairCommand {
                 .copied()
                 .unwrap_or_default();
             if cat_results.iter().all(|r| r.success) {
-                steps.push(StepSummary::success(label, duration));
+                self.record(steps, StepSummary::success(label, duration));
             } else {
                 let msg = cat_results
                     .into_iter()
                     .find(|r| !r.success)
                     .map(|r| r.message.clone())
                     .unwrap_or_else(|| "Fallo en reparacion".to_string());
-                steps.push(StepSummary::failed(label, duration, msg));
+                self.record(steps, StepSummary::failed(label, duration, msg));
             }
         }
     }
@@ -526,6 +868,7 @@ impl RepairCommand {
             IssueCategory::Manifest => self.auto || self.manifest,
             IssueCategory::Documentation => self.auto || self.docs,
             IssueCategory::Tests => self.auto || self.tests,
+            IssueCategory::Edition => self.edition.is_some(),
         }
     }
     #[doc = "Method documentation added by AI refactor"]
@@ -537,27 +880,27 @@ impl RepairCommand {
             IssueCategory::Manifest => "Manifest",
             IssueCategory::Documentation => "Documentacion",
             IssueCategory::Tests => "Tests",
+            IssueCategory::Edition => "Edición (cargo fix --edition)",
         }
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn detect_issues(&self) -> Result<Vec<RepairIssue>> {
-        println!("{}", "🔍 Detectando issues...".cyan());
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .expect("Failed to set repair spinner template"),
-        );
+    async fn detect_issues(&self, options: &RunOptions) -> Result<Vec<RepairIssue>> {
+        let spinner = crate::utils::progress::spinner(options);
+        if self.prints() {
+            println!("{}", "🔍 Detectando issues...".cyan());
+        } else {
+            spinner.set_draw_target(ProgressDrawTarget::hidden());
+        }
         spinner.set_message("Analizando proyecto...");
         let _analyzer = ProjectAnalyzer::new();
         let mut issues = Vec::new();
         if self.auto || self.clippy {
             spinner.set_message("Detectando issues de clippy...");
-            issues.extend(self.detect_clippy_issues().await?);
+            issues.extend(self.detect_clippy_issues(options).await?);
         }
         if self.auto || self.fmt {
             spinner.set_message("Detectando issues de formato...");
-            issues.extend(self.detect_format_issues().await?);
+            issues.extend(self.detect_format_issues(options).await?);
         }
         if self.auto || self.deps {
             spinner.set_message("Detectando issues de dependencias...");
@@ -575,12 +918,16 @@ impl RepairCommand {
             spinner.set_message("Detectando issues de tests...");
             issues.extend(self.detect_test_issues()?);
         }
+        if let Some(edition) = &self.edition {
+            spinner.set_message("Comprobando la edición...");
+            issues.extend(self.detect_edition_issues(edition)?);
+        }
         spinner.finish_with_message(format!("Detectados {} issues ✓", issues.len()));
         Ok(issues)
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn detect_clippy_issues(&self) -> Result<Vec<RepairIssue>> {
-        let executor = CargoExecutor::new();
+    async fn detect_clippy_issues(&self, options: &RunOptions) -> Result<Vec<RepairIssue>> {
+        let executor = CargoExecutor::new(options);
         let output = executor
             .execute_with_output(&["clippy", "--", "-D", "warnings"])
             .await;
@@ -599,8 +946,8 @@ impl RepairCommand {
         Ok(issues)
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn detect_format_issues(&self) -> Result<Vec<RepairIssue>> {
-        let executor = CargoExecutor::new();
+    async fn detect_format_issues(&self, options: &RunOptions) -> Result<Vec<RepairIssue>> {
+        let executor = CargoExecutor::new(options);
         let output = executor.execute_with_output(&["fmt", "--check"]).await;
         let mut issues = Vec::new();
         if output.is_err() {
@@ -638,18 +985,24 @@ impl RepairCommand {
     fn detect_manifest_issues(&self) -> Result<Vec<RepairIssue>> {
         let mut issues = Vec::new();
         if std::path::Path::new("Cargo.toml").exists() {
-            if let Ok(content) = std::fs::read_to_string("Cargo.toml") {
-                if !content.contains("[package]") {
-                    let issue = RepairIssue {
-                        category: IssueCategory::Manifest,
-                        description: "Manifest incompleto - Falta sección [package] en Cargo.toml"
-                            .to_string(),
-                        severity: IssueSeverity::Critical,
-                        fixable: false,
-                        command: "echo 'Revisar Cargo.toml manualmente'".to_string(),
-                    };
-                    issues.push(issue);
-                }
+            let manifest = crate::core::manifest::Manifest::load(std::path::Path::new("."));
+            let description = match manifest {
+                Ok(m) if m.package.is_none() && m.workspace.is_none() => Some(
+                    "Manifest incompleto - Falta sección [package] o [workspace] en Cargo.toml"
+                        .to_string(),
+                ),
+                Ok(_) => None,
+                Err(e) => Some(format!("Manifest inválido - {e:#}")),
+            };
+            if let Some(description) = description {
+                let issue = RepairIssue {
+                    category: IssueCategory::Manifest,
+                    description,
+                    severity: IssueSeverity::Critical,
+                    fixable: false,
+                    command: "echo 'Revisar Cargo.toml manualmente'".to_string(),
+                };
+                issues.push(issue);
             }
         }
         Ok(issues)
@@ -684,6 +1037,91 @@ impl RepairCommand {
         }
         Ok(issues)
     }
+    /// A migration to `target` when the package is on an older edition. Fails
+    /// when the installed cargo doesn't know `target` yet.
+    fn detect_edition_issues(&self, target: &str) -> Result<Vec<RepairIssue>> {
+        check_edition_support(target)?;
+        let manifest = crate::core::manifest::Manifest::load(Path::new("."))?;
+        let Some((current, _)) = manifest.edition() else {
+            anyhow::bail!("Cargo.toml no declara una edición que trae pueda migrar");
+        };
+        let issue = match edition_index(&current).cmp(&edition_index(target)) {
+            std::cmp::Ordering::Equal => return Ok(Vec::new()),
+            std::cmp::Ordering::Less => RepairIssue {
+                category: IssueCategory::Edition,
+                description: format!("Edición {current} → {target}"),
+                severity: IssueSeverity::Warning,
+                fixable: true,
+                command: format!(
+                    "cargo fix --edition → edition = \"{target}\" → cargo fix --edition-idioms"
+                ),
+            },
+            std::cmp::Ordering::Greater => RepairIssue {
+                category: IssueCategory::Edition,
+                description: format!("La edición {current} es posterior a {target}"),
+                severity: IssueSeverity::Info,
+                fixable: false,
+                command: String::new(),
+            },
+        };
+        Ok(vec![issue])
+    }
+    /// Moves the package in `dir` to `--edition` through every edition in
+    /// between: `cargo fix --edition` on the old one, then the new one in
+    /// Cargo.toml; finally `cargo fix --edition-idioms`. `extra` is added to
+    /// each `cargo fix`. With `quiet` cargo's output is not shown.
+    async fn migrate_edition(
+        &self,
+        executor: &CargoExecutor,
+        dir: &Path,
+        extra: &[&str],
+        quiet: bool,
+    ) -> Result<String> {
+        use crate::core::manifest::{with_edition, Manifest, EDITIONS};
+        let target = self.edition.as_deref().unwrap_or(EDITIONS[EDITIONS.len() - 1]);
+        let manifest_path = dir.join("Cargo.toml");
+        let manifest = Manifest::load(dir)?;
+        let (mut current, table) = manifest
+            .edition()
+            .ok_or_else(|| anyhow::anyhow!("Cargo.toml no declara una edición"))?;
+        let mut fix = vec!["fix", "--all-targets", "--allow-dirty", "--allow-no-vcs"];
+        if manifest.workspace.is_some() {
+            fix.push("--workspace");
+        }
+        fix.extend_from_slice(extra);
+        let run = |flag: &'static str| {
+            let mut args = fix.clone();
+            args.insert(1, flag);
+            async move {
+                let args = self.with_locked(&args);
+                if quiet {
+                    executor.execute_with_output(&args).await.map(drop)
+                } else {
+                    self.run_cargo(executor, &args).await
+                }
+            }
+        };
+        let mut migrated = Vec::new();
+        for next in &EDITIONS[edition_index(&current) + 1..=edition_index(target)] {
+            run("--edition")
+                .await
+                .map_err(|e| anyhow::anyhow!("cargo fix --edition ({current} → {next}): {e}"))?;
+            let content = fs::read_to_string(&manifest_path)?;
+            let updated = with_edition(&content, table, next)
+                .ok_or_else(|| anyhow::anyhow!("no se encontró {} en Cargo.toml", table.header()))?;
+            fs::write(&manifest_path, updated)?;
+            migrated.push(format!("{current} → {next}"));
+            current = next.to_string();
+        }
+        run("--edition-idioms")
+            .await
+            .map_err(|e| anyhow::anyhow!("cargo fix --edition-idioms ({current}): {e}"))?;
+        Ok(format!(
+            "Edición {} en {}; idioms aplicados",
+            migrated.join(", "),
+            table.header()
+        ))
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn show_detected_issues(&self, issues: &[RepairIssue]) {
         println!("{}", "📋 Issues Detectados:".yellow().bold());
@@ -725,29 +1163,40 @@ impl RepairCommand {
     async fn execute_repairs(
         &self,
         issues: &[RepairIssue],
+        options: &RunOptions,
     ) -> Result<(Vec<RepairResult>, HashMap<IssueCategory, Duration>)> {
-        println!("{}", "🚀 Ejecutando reparaciones...".cyan());
-        let style = match ProgressStyle::default_bar().template(
-            "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} ({eta})",
-        ) {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("⚠️  progress template failed: {:?}", e);
-                ProgressStyle::default_bar()
-            }
-        };
-        let progress = ProgressBar::new(issues.len() as u64);
-        progress.set_style(style);
-        let executor = CargoExecutor::new();
+        if self.prints() {
+            println!("{}", "🚀 Ejecutando reparaciones...".cyan());
+        }
+        // The bar advances by category cost rather than by issue count, so the
+        // ETA (derived from elapsed time per position) is elapsed-per-weight.
+        let total_weight: u64 = issues.iter().map(|i| i.category.cost_weight()).sum();
+        let progress = crate::utils::progress::eta_bar(total_weight, options);
+        if !self.prints() {
+            progress.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        let executor = CargoExecutor::new(options);
         let mut results = Vec::new();
         let mut durations: HashMap<IssueCategory, Duration> = HashMap::new();
         for issue in issues {
+            if self.cancel.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                break;
+            }
             progress.set_message(format!("Reparando: {:?}", issue.category));
             let issue_start = Instant::now();
-            let result = if issue.fixable {
+            let result = if issue.fixable && issue.category == IssueCategory::Edition {
+                self.apply_edition_migration(issue, &executor).await
+            } else if issue.fixable {
                 let command_parts: Vec<&str> = issue.command.split_whitespace().collect();
-                if command_parts.len() > 1 {
-                    match executor.execute_streaming(&command_parts[1..]).await {
+                let outcome = match command_parts.as_slice() {
+                    ["cargo", "update"] => Some(self.cargo_update(&executor).await),
+                    ["cargo", args @ ..] if !args.is_empty() => {
+                        Some(self.run_cargo(&executor, &self.with_locked(args)).await)
+                    }
+                    _ => None,
+                };
+                if let Some(outcome) = outcome {
+                    match outcome {
                         Ok(_) => RepairResult {
                             issue: issue.clone(),
                             success: true,
@@ -779,14 +1228,40 @@ impl RepairCommand {
                 .entry(issue.category)
                 .and_modify(|total| *total += elapsed)
                 .or_insert(elapsed);
-            progress.inc(1);
+            progress.inc(issue.category.cost_weight());
         }
         progress.finish_with_message("Reparaciones completadas ✓".to_string());
         Ok((results, durations))
     }
+    /// [`Self::migrate_edition`] in the workspace. With `--rollback` the
+    /// sources and manifests are put back as they were if it fails.
+    async fn apply_edition_migration(
+        &self,
+        issue: &RepairIssue,
+        executor: &CargoExecutor,
+    ) -> RepairResult {
+        let root = Path::new(".");
+        let before = self.rollback.then(|| snapshot_sources(root));
+        let (success, message) = match self.migrate_edition(executor, root, &[], false).await {
+            Ok(message) => (true, message),
+            Err(e) => match before.map(|before| restore_sources(root, &before)) {
+                Some(Ok(restored)) => (
+                    false,
+                    format!("Error: {e}; revertidos {restored} archivos (--rollback)"),
+                ),
+                Some(Err(rollback)) => {
+                    (false, format!("Error: {e}; el rollback falló: {rollback}"))
+                }
+                None => (false, format!("Error: {e}; usa --rollback para revertir")),
+            },
+        };
+        RepairResult { issue: issue.clone(), success, message }
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn simulate_repairs(&self, issues: &[RepairIssue]) -> Result<Vec<RepairResult>> {
-        println!("{}", "🔍 Simulando reparaciones (dry run)...".yellow());
+        if self.prints() {
+            println!("{}", "🔍 Simulando reparaciones (dry run)...".yellow());
+        }
         let results = issues
             .iter()
             .map(|issue| RepairResult {
@@ -801,6 +1276,93 @@ impl RepairCommand {
             .collect();
         Ok(results)
     }
+    /// Runs each fixer against a temporary copy of the workspace and prints the
+    /// resulting unified diff grouped by file. The real tree is never touched.
+    async fn preview_repairs(
+        &self,
+        issues: &[RepairIssue],
+        options: &RunOptions,
+    ) -> Result<Vec<RepairResult>> {
+        println!(
+            "{}",
+            "🔍 Previsualizando reparaciones (sin modificar archivos)...".yellow()
+        );
+        let root = std::env::current_dir()?;
+        // Removed on drop, also when a step fails
+        let sandbox = crate::core::workspace::sandbox(&root)?;
+        let sandbox = sandbox.path();
+        // The configured target dir, so previews reuse compiled dependencies
+        let target_dir = root.join(options.target_dir());
+        let target_dir = target_dir.to_string_lossy().to_string();
+        let mut results = Vec::new();
+        for issue in issues {
+            println!();
+            println!(
+                "{} {:?}: {}",
+                "▶".cyan(),
+                issue.category,
+                issue.command.blue()
+            );
+            if !issue.fixable {
+                println!("  {}", "No reparable automáticamente".red());
+                results.push(RepairResult {
+                    issue: issue.clone(),
+                    success: false,
+                    message: "No reparable automáticamente".to_string(),
+                });
+                continue;
+            }
+            let parts: Vec<&str> = issue.command.split_whitespace().collect();
+            let outcome = match parts.as_slice() {
+                _ if issue.category == IssueCategory::Edition => {
+                    let before = snapshot_sources(sandbox);
+                    let executor = CargoExecutor::new(options).with_working_dir(sandbox);
+                    let extra = ["--target-dir", target_dir.as_str()];
+                    let run = self.migrate_edition(&executor, sandbox, &extra, true).await;
+                    Some(print_sandbox_diff(sandbox, &before, run.map(drop)))
+                }
+                ["cargo", "clippy" | "fix", rest @ ..] => {
+                    let mut args: Vec<&str> = vec![parts[1]];
+                    args.extend_from_slice(rest);
+                    args.extend_from_slice(&["--target-dir", &target_dir]);
+                    Some(preview_in_sandbox(sandbox, &args, options).await)
+                }
+                ["cargo", "fmt", rest @ ..] => {
+                    let mut args = vec!["fmt"];
+                    args.extend_from_slice(rest);
+                    Some(preview_in_sandbox(sandbox, &args, options).await)
+                }
+                ["cargo", "update", ..] => Some(preview_update(options).await),
+                _ => None,
+            };
+            let result = match outcome {
+                Some(Ok(message)) => RepairResult {
+                    issue: issue.clone(),
+                    success: true,
+                    message,
+                },
+                Some(Err(e)) => RepairResult {
+                    issue: issue.clone(),
+                    success: false,
+                    message: format!("Error en preview: {e}"),
+                },
+                None => {
+                    println!(
+                        "  {}",
+                        "⚠️ Preview no disponible para este reparador (no soporta dry-run)"
+                            .yellow()
+                    );
+                    RepairResult {
+                        issue: issue.clone(),
+                        success: false,
+                        message: "Preview no disponible".to_string(),
+                    }
+                }
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn show_results(&self, results: &[RepairResult], duration: std::time::Duration) {
         println!();
@@ -824,9 +1386,11 @@ impl RepairCommand {
         }
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn run_post_check(&self) -> Result<PostCheckOutcome> {
-        let executor = CargoExecutor::new();
-        let output = executor.execute_streaming_capture(&["check"]).await?;
+    async fn run_post_check(&self, options: &RunOptions) -> Result<PostCheckOutcome> {
+        let executor = CargoExecutor::new(options);
+        let output = self
+            .run_cargo_capture(&executor, &self.with_locked(&["check"]))
+            .await?;
         let warnings = output.matches("warning:").count();
         let errors = output.matches("error:").count();
         Ok(PostCheckOutcome {
@@ -835,6 +1399,20 @@ impl RepairCommand {
             errors,
         })
     }
+    /// `args` with `--locked` added (before any `--`) when `--locked` is set
+    /// and the subcommand resolves dependencies.
+    fn with_locked<'a>(&self, args: &[&'a str]) -> Vec<&'a str> {
+        let mut args = args.to_vec();
+        let resolves = matches!(
+            args.first(),
+            Some(&("check" | "clippy" | "fix" | "test" | "build"))
+        );
+        if self.locked && resolves {
+            let at = args.iter().position(|a| *a == "--").unwrap_or(args.len());
+            args.insert(at, "--locked");
+        }
+        args
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn show_post_check(&self, outcome: &PostCheckOutcome) {
         println!();
@@ -849,6 +1427,35 @@ impl RepairCommand {
         println!("  ⚠️  Warnings: {}", outcome.warnings);
         println!("  ❌ Errores: {}", outcome.errors);
     }
+    /// `--format markdown`: the data of `--export`, as a document.
+    fn markdown_report(
+        &self,
+        results: &[RepairResult],
+        duration: std::time::Duration,
+        post_check: Option<&PostCheckOutcome>,
+    ) -> markdown::Report {
+        let mut report = markdown::Report::new("🔧 trae repair");
+        let successful = results.iter().filter(|r| r.success).count();
+        report
+            .summary("✅ Successful", successful)
+            .summary("❌ Failed", results.len() - successful)
+            .summary("⏱️ Duration", format!("{:.2}s", duration.as_secs_f64()));
+        if let Some(check) = post_check {
+            report
+                .summary("⚠️ cargo check warnings", check.warnings)
+                .summary("❌ cargo check errors", check.errors);
+        }
+        for result in results {
+            let status = if result.success { "✅" } else { "❌" };
+            report.finding(markdown::Finding {
+                category: issue_category_name(&result.issue.category).to_string(),
+                severity: issue_severity_name(&result.issue.severity).to_string(),
+                location: Some(result.issue.command.clone()).filter(|c| !c.is_empty()),
+                message: format!("{status} {}: {}", result.issue.description, result.message),
+            });
+        }
+        report
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn export_report(
         &self,
@@ -862,18 +1469,25 @@ impl RepairCommand {
         Ok(())
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn report_metrics(&self, metrics: MetricsCollector) -> Result<()> {
-        match JarvixClient::new() {
+    async fn report_metrics(
+        &self,
+        metrics: MetricsCollector,
+        options: &crate::config::RunOptions,
+    ) -> Result<()> {
+        match JarvixClient::new(options).await {
             Ok(Some(client)) => {
                 client.report_repair_metrics(metrics).await?;
-                println!(
-                    "{}",
-                    "📊 Métricas de reparación reportadas a JARVIXSERVER".green()
-                );
+                if self.prints() {
+                    println!(
+                        "{}",
+                        "📊 Métricas de reparación reportadas a JARVIXSERVER".green()
+                    );
+                }
             }
-            Ok(None) => {
+            Ok(None) if self.prints() => {
                 println!("{}", "⚠️ JARVIXSERVER no configurado".yellow());
             }
+            Ok(None) => {}
             Err(e) => {
                 return Err(anyhow::anyhow!("Error conectando a JARVIXSERVER: {e}"));
             }
@@ -883,6 +1497,71 @@ impl RepairCommand {
 
 }
 
+/// The error refusing to repair a tree with the `dirty` paths; `flags` are
+/// the ones that would allow it.
+fn dirty_tree_error(dirty: &[String], flags: &str) -> anyhow::Error {
+    let mut listed: Vec<&str> = dirty.iter().take(5).map(String::as_str).collect();
+    if dirty.len() > listed.len() {
+        listed.push("...");
+    }
+    anyhow::anyhow!(
+        "⛔ El árbol git tiene {} archivo(s) sin commitear ({}); \
+         repair podría sobrescribirlos.\n   \
+         Haz commit o stash, o usa {flags}",
+        dirty.len(),
+        listed.join(", ")
+    )
+}
+/// Paths with uncommitted changes (modified, staged or untracked) in the git
+/// tree containing `dir`, leaving out trae's own `.trae` directory. Empty
+/// outside a git repository or when git is not available.
+pub fn dirty_paths(dir: &Path) -> Vec<String> {
+    let output = std::process::Command::new("git")
+        .args(["status", "--porcelain", "--", ".", ":!.trae"])
+        .current_dir(dir)
+        .output();
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|line| line.get(3..))
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Runs `git args` in the current directory; the error names the git
+/// subcommand and carries git's own message (see [`git_error`]).
+fn git(args: &[&str]) -> std::result::Result<(), String> {
+    match std::process::Command::new("git").args(args).output() {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!("git {}: {}", args[0], git_error(&out.stderr))),
+        Err(e) => Err(format!("git {}: {e}", args[0])),
+    }
+}
+
+/// Whether `dir` is in a git repository with a local branch `name`.
+pub fn branch_exists(dir: &Path, name: &str) -> bool {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &format!("refs/heads/{name}")])
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|out| out.status.success())
+}
+
+/// The message in git's `stderr`: its first `fatal:`/`error:` line without
+/// the prefix, else the first non-empty line.
+pub fn git_error(stderr: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    lines
+        .iter()
+        .find_map(|line| line.strip_prefix("fatal: ").or_else(|| line.strip_prefix("error: ")))
+        .or(lines.first().copied())
+        .unwrap_or("git terminó con error")
+        .to_string()
+}
+
 /// Options for programmatic repair API.
 #[derive(Debug, Clone, Default)]
 pub struct RepairOptions {
@@ -894,10 +1573,24 @@ pub struct RepairOptions {
     pub no_jarvix: bool,
     pub level: Option<String>,
     pub rollback: bool,
+    /// Repair even when the git tree has uncommitted changes and `rollback` is off.
+    pub allow_dirty_repair: bool,
     pub update: bool,
     pub upgrade: bool,
+    pub locked: bool,
     pub git_branch: Option<String>,
+    /// Reset `git_branch` to the current commit if it already exists.
+    pub git_branch_force: bool,
     pub git_commit: Option<String>,
+    /// Let `git_commit` include changes that were uncommitted before the repair.
+    pub git_allow_dirty: bool,
+    /// Migrate to this edition (`"2021"` or `"2024"`), like `--edition`.
+    pub edition: Option<String>,
+    /// Checked between detection, each repair and the post-check; a cancelled
+    /// run ends with `TraeError::Cancelled` after reporting what it did.
+    pub cancel: Option<CancellationToken>,
+    /// Receives [`ProgressEvent`]s instead of trae printing its own output.
+    pub progress: Option<ProgressSink>,
 }
 
 impl RepairCommand {
@@ -925,16 +1618,25 @@ impl RepairCommand {
             docs: false,
             tests: false,
             export: None,
+            format: "text".to_string(),
             check: true,
             outdated: false,
             dry_run: opts.dry_run,
+            preview: false,
             force: true,
             level: opts.level.clone(),
             rollback: opts.rollback,
+            allow_dirty_repair: opts.allow_dirty_repair,
             update: opts.update,
             upgrade: opts.upgrade,
+            locked: opts.locked,
             git_branch: opts.git_branch.clone(),
+            git_branch_force: opts.git_branch_force,
             git_commit: opts.git_commit.clone(),
+            git_allow_dirty: opts.git_allow_dirty,
+            edition: opts.edition.clone(),
+            cancel: opts.cancel.clone(),
+            progress: opts.progress.clone(),
         };
 
         // If rollback requested, create a simple backup copy of the workspace
@@ -975,10 +1677,27 @@ impl RepairCommand {
         };
 
         // Build a minimal TraeCli to reuse the full execute flow
+        let options = RunOptions::from_env();
         let cli = crate::cli::TraeCli {
             verbose: false,
             config: None,
             no_jarvix: opts.no_jarvix,
+            offline: options.offline,
+            target_dir: None,
+            jobs: None,
+            include_hidden: false,
+            max_file_size: None,
+            jarvix: None,
+            project: None,
+            ci: options.ci,
+            with_jarvix: false,
+            quiet: false,
+            report_url: None,
+            report_header: Vec::new(),
+            output_dir: None,
+            severity: None,
+            dry_run: false,
+            options,
             command: crate::cli::Commands::Repair(cmd),
         };
         // Execute the full flow by calling the command's execute directly to avoid recursion
@@ -1036,6 +1755,22 @@ pub enum IssueCategory {
     Manifest,
     Documentation,
     Tests,
+    Edition,
+}
+impl IssueCategory {
+    /// Relative cost of repairing one issue of this category, used to weight
+    /// the repair progress bar (a `clippy --fix` pass dwarfs a `cargo fmt`).
+    pub const fn cost_weight(self) -> u64 {
+        match self {
+            IssueCategory::Clippy => 10,
+            IssueCategory::Tests => 8,
+            IssueCategory::Edition => 10,
+            IssueCategory::Dependencies => 5,
+            IssueCategory::Format => 1,
+            IssueCategory::Manifest => 1,
+            IssueCategory::Documentation => 1,
+        }
+    }
 }
 #[derive(Debug, Clone)]
 pub enum IssueSeverity {
@@ -1057,6 +1792,163 @@ pub struct PostCheckOutcome {
     pub warnings: usize,
     pub errors: usize,
 }
+/// Runs a fixer inside the preview sandbox and prints the diff it produced.
+async fn preview_in_sandbox(sandbox: &Path, args: &[&str], options: &RunOptions) -> Result<String> {
+    let before = snapshot_sources(sandbox);
+    let run = CargoExecutor::new(options)
+        .with_working_dir(sandbox)
+        .execute_with_output(args)
+        .await;
+    print_sandbox_diff(sandbox, &before, run.map(drop))
+}
+/// Prints how the files of `sandbox` changed since `before`, and sums it up.
+/// `run` is the fixer's outcome: its error only matters when nothing changed.
+fn print_sandbox_diff(
+    sandbox: &Path,
+    before: &BTreeMap<String, String>,
+    run: Result<()>,
+) -> Result<String> {
+    let after = snapshot_sources(sandbox);
+    let mut files = 0;
+    let mut lines = 0;
+    for (path, new) in &after {
+        let old = before.get(path).map(String::as_str).unwrap_or("");
+        if old != new {
+            files += 1;
+            lines += print_file_diff(path, old, new);
+        }
+    }
+    if files == 0 {
+        run?;
+        println!("  {}", "Sin cambios".green());
+        return Ok("Sin cambios".to_string());
+    }
+    if let Err(e) = run {
+        eprintln!("  ⚠️ El reparador terminó con errores: {e}");
+    }
+    Ok(format!("{files} archivos, {lines} líneas cambiarían"))
+}
+/// `cargo update` supports `--dry-run` natively, so it runs against the real tree.
+async fn preview_update(options: &RunOptions) -> Result<String> {
+    let output = CargoExecutor::new(options)
+        .execute_with_output(&["update", "--dry-run"])
+        .await?;
+    let changes: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| {
+            l.starts_with("Updating")
+                || l.starts_with("Adding")
+                || l.starts_with("Removing")
+                || l.starts_with("Downgrading")
+        })
+        .collect();
+    for line in &changes {
+        println!("  {}", line.cyan());
+    }
+    if changes.is_empty() {
+        println!("  {}", "Lockfile al día".green());
+    }
+    Ok(format!("{} dependencias cambiarían", changes.len()))
+}
+/// Output fragments of `cargo update` failures worth a single retry.
+const TRANSIENT_UPDATE_ERRORS: [&str; 8] = [
+    "spurious network error",
+    "failed to download",
+    "failed to fetch",
+    "timed out",
+    "timeout",
+    "couldn't resolve host",
+    "connection reset",
+    "http/2 stream",
+];
+/// Turns a failed `cargo update` into "crate + suggested action" when the
+/// output says which dependency could not be resolved.
+fn describe_update_failure(output: &str) -> anyhow::Error {
+    let crate_pattern = Regex::new(
+        r"(?:requirement `|no matching package named `|package `)([A-Za-z0-9_-]+)",
+    )
+    .expect("valid regex");
+    let Some(name) = crate_pattern.captures(output).map(|c| c[1].to_string()) else {
+        return anyhow::anyhow!("cargo update falló:\n{output}");
+    };
+    let suggestion = if output.contains("yanked") {
+        format!("la versión fijada de `{name}` fue retirada (yanked); sube el requisito en Cargo.toml")
+    } else if output.contains("no matching package named") {
+        format!("`{name}` no existe en el registro; revisa el nombre o la fuente en Cargo.toml")
+    } else {
+        format!("ningún `{name}` cumple los requisitos; relaja la versión o ejecuta `cargo update -p {name}`")
+    };
+    anyhow::anyhow!("No se pudo resolver `{name}`: {suggestion}")
+}
+/// Reads every `.rs` and `.toml` file under `root` (skipping `target`),
+/// keyed by its relative path.
+fn snapshot_sources(root: &Path) -> BTreeMap<String, String> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != "target")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            matches!(
+                e.path().extension().and_then(|ext| ext.to_str()),
+                Some("rs" | "toml")
+            )
+        })
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(root).ok()?;
+            let content = fs::read_to_string(e.path()).ok()?;
+            Some((rel.to_string_lossy().replace('\\', "/"), content))
+        })
+        .collect()
+}
+/// Writes back every file of `snapshot` (from [`snapshot_sources`]) whose
+/// content under `root` changed since; returns how many were restored.
+fn restore_sources(root: &Path, snapshot: &BTreeMap<String, String>) -> std::io::Result<usize> {
+    let mut restored = 0;
+    for (path, content) in snapshot {
+        let path = root.join(path);
+        if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            fs::write(&path, content)?;
+            restored += 1;
+        }
+    }
+    Ok(restored)
+}
+/// `cargo --version` must know `edition`: 2018 from 1.31, 2021 from 1.56,
+/// 2024 from 1.85.
+fn check_edition_support(edition: &str) -> Result<()> {
+    let required = match edition {
+        "2018" => 31,
+        "2021" => 56,
+        "2024" => 85,
+        _ => return Ok(()),
+    };
+    let output = std::process::Command::new(cargo_program())
+        .arg("--version")
+        .output()
+        .map_err(|e| anyhow::anyhow!("No se pudo ejecutar cargo --version: {e}"))?;
+    let version = String::from_utf8_lossy(&output.stdout);
+    let minor = version
+        .split_whitespace()
+        .nth(1)
+        .and_then(|v| v.split('.').nth(1))
+        .and_then(|minor| minor.parse::<u32>().ok())
+        .ok_or_else(|| anyhow::anyhow!("Versión de cargo no reconocida: {}", version.trim()))?;
+    if minor < required {
+        anyhow::bail!(
+            "{} no soporta la edición {edition} (requiere cargo 1.{required}+); \
+             actualiza con `rustup update`",
+            version.trim()
+        );
+    }
+    Ok(())
+}
+/// Position of `edition` in [`EDITIONS`](crate::core::manifest::EDITIONS);
+/// unknown editions count as 2015.
+fn edition_index(edition: &str) -> usize {
+    crate::core::manifest::EDITIONS.iter().position(|e| *e == edition).unwrap_or(0)
+}
 #[doc = "Function documentation added by AI refactor"]
 fn issue_category_name(cat: &IssueCategory) -> &'static str {
     match cat {
@@ -1066,6 +1958,7 @@ fn issue_category_name(cat: &IssueCategory) -> &'static str {
         IssueCategory::Manifest => "manifest",
         IssueCategory::Documentation => "documentation",
         IssueCategory::Tests => "tests",
+        IssueCategory::Edition => "edition",
     }
 }
 #[doc = "Function documentation added by AI refactor"]
@@ -1076,11 +1969,12 @@ fn issue_severity_name(sev: &IssueSeverity) -> &'static str {
         IssueSeverity::Info => "info",
     }
 }
-const ISSUE_CATEGORY_ORDER: [IssueCategory; 6] = [
+const ISSUE_CATEGORY_ORDER: [IssueCategory; 7] = [
     IssueCategory::Clippy,
     IssueCategory::Format,
     IssueCategory::Dependencies,
     IssueCategory::Manifest,
     IssueCategory::Documentation,
     IssueCategory::Tests,
+    IssueCategory::Edition,
 ];
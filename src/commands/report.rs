@@ -0,0 +1,77 @@
+#![doc = " # Report Command - Combine exported reports"]
+#![doc = ""]
+#![doc = " Une los JSON de `scan --export` y `analyze --output` de un CI por shards en uno"]
+use crate::cli::TraeCli;
+use crate::core::analyzer::IssueSeverity;
+use crate::core::report::{ExportedReport, MergedReport};
+use crate::core::severity::SeverityFilter;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+#[derive(Args, Debug)]
+#[doc = " Opciones de trae report"]
+pub struct ReportCommand {
+    #[doc = " JSON reports (`scan --export`, `analyze --output`) to combine: issues are"]
+    #[doc = " de-duplicated by fingerprint and the counts recomputed"]
+    #[arg(long, value_name = "FILE", num_args = 1.., required = true)]
+    pub merge: Vec<PathBuf>,
+    #[doc = " Write the merged report here (default: `merged-report.json` in --output-dir,"]
+    #[doc = " else stdout)"]
+    #[arg(long, value_name = "FILE")]
+    pub output: Option<String>,
+    #[doc = " Exit with 2 when the merged set has critical issues (with --severity, any"]
+    #[doc = " issue left)"]
+    #[arg(long)]
+    pub gate: bool,
+}
+impl ReportCommand {
+    #[doc = " Une los reportes y aplica el gate sobre el conjunto"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let reports = self
+            .merge
+            .iter()
+            .map(|path| ExportedReport::load(path))
+            .collect::<Result<Vec<_>>>()?;
+        let mut merged = MergedReport::new(reports)?;
+        let mut severity = SeverityFilter::from_options(&cli.options);
+        merged.retain_severity(&mut severity);
+        let report = serde_json::to_string_pretty(&merged.to_json(&severity))?;
+        let summary = format!(
+            "🧩 {} reportes unidos: {} issues (critical={} warning={} info={}), {} sugerencias, \
+             {} duplicados descartados",
+            merged.sources.len(),
+            merged.issues().count(),
+            merged.count(&IssueSeverity::Critical),
+            merged.count(&IssueSeverity::Warning),
+            merged.count(&IssueSeverity::Info),
+            merged.suggestions.len(),
+            merged.duplicates
+        );
+        // The JSON owns stdout when there is no file to write
+        match cli.options.report_path(self.output.as_deref(), "merged-report.json") {
+            Some(out) => {
+                std::fs::write(&out, report)?;
+                println!("{summary}");
+                if let Some(notice) = severity.notice() {
+                    println!("{}", notice.yellow());
+                }
+                let written = crate::config::full_path(&out);
+                println!("{}", format!("📁 Reporte exportado a: {written}").green());
+            }
+            None => {
+                println!("{report}");
+                eprintln!("{summary}");
+                if let Some(notice) = severity.notice() {
+                    eprintln!("{}", notice.yellow());
+                }
+            }
+        }
+        let failing = severity.failing(merged.issues().map(|i| &i.severity));
+        if self.gate && failing > 0 {
+            let what = severity.describe_failing("issues críticos en el reporte unido", "issues");
+            return Err(crate::exit::findings(failing, what));
+        }
+        Ok(())
+    }
+}
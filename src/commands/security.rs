@@ -1,15 +1,83 @@
 #![doc = " # Security Command - Security audit and vulnerability scanning"]
 #![doc = ""]
 #![doc = " Comando de seguridad con auditoría completa, escaneo de vulnerabilidades y hardening"]
-use crate::{cli::TraeCli, jarvix::client::JarvixClient, metrics::collector::MetricsCollector};
+use crate::core::cargo::{cargo_command, metadata_command};
+use crate::{
+    cli::TraeCli,
+    core::analyzer::{parse_confidence, IssueSeverity, FULL_CONFIDENCE},
+    core::manifest::Manifest,
+    core::severity::SeverityFilter,
+    exit::{findings, UsageError},
+    jarvix::client::JarvixClient,
+    metrics::collector::MetricsCollector,
+    utils::path::{portable, validate_path, WorkingDirGuard},
+    utils::{
+        diagnostics, markdown,
+        snippet::{parse_context, Snippet, Snippets},
+    },
+    utils::source::{
+        cfg_test_lines, is_test_path, mask_comments_and_strings, mask_raw_and_byte_strings,
+        panicking_call, PanicCall,
+    },
+};
 use anyhow::Result;
+use cargo_metadata::{semver::VersionReq, PackageId};
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use indicatif::ProgressDrawTarget;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
+/// Policy file listing crates disallowed in the dependency tree.
+const BANS_FILE: &str = ".trae/bans.toml";
+/// Extra secret patterns and disabled built-ins for `--secrets`.
+const SECRETS_FILE: &str = ".trae/secrets.toml";
+/// Built-in `--secrets` patterns: (name, regex, description, severity). The
+/// name is what `disable` in `.trae/secrets.toml` refers to.
+const BUILTIN_SECRET_PATTERNS: [(&str, &str, &str, SecuritySeverity); 6] = [
+    (
+        "password",
+        r#"password\s*=\s*["'][^"']+["']"#,
+        "Password hardcodeado",
+        SecuritySeverity::Critical,
+    ),
+    (
+        "secret",
+        r#"secret\s*=\s*["'][^"']+["']"#,
+        "Secret hardcodeado",
+        SecuritySeverity::Critical,
+    ),
+    (
+        "token",
+        r#"token\s*=\s*["'][^"']+["']"#,
+        "Token hardcodeado",
+        SecuritySeverity::High,
+    ),
+    (
+        "api_key",
+        r#"api_key\s*=\s*["'][^"']+["']"#,
+        "API Key hardcodeada",
+        SecuritySeverity::High,
+    ),
+    (
+        "private_key",
+        r"PRIVATE_KEY",
+        "Posible clave privada",
+        SecuritySeverity::Critical,
+    ),
+    (
+        "openai_key",
+        r"sk-\w+",
+        "Posible API key de OpenAI",
+        SecuritySeverity::Critical,
+    ),
+];
+/// Roots scanned by `--code`/`--secrets` when no `--scan-path` is given.
+const DEFAULT_SCAN_PATHS: [&str; 3] = ["src", "tests", "examples"];
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct SecurityCommand {
@@ -40,26 +108,63 @@ pub struct SecurityCommand {
     #[doc = " Security level (low, medium, high, critical)"]
     #[arg(long, default_value = "medium")]
     pub level: String,
-    #[doc = " Output format (text, json, sarif)"]
-    #[arg(long, default_value = "text")]
+    #[doc = " Output format: text report, one LSP-style JSON diagnostic per line or a Markdown"]
+    #[doc = " report (summary table, collapsible section per category)"]
+    #[arg(long, default_value = "text", value_parser = ["text", "lsp", "markdown"])]
     pub format: String,
+    #[doc = " Root directory for code/secrets scans (repeatable; default: src, tests, examples)"]
+    #[arg(long = "scan-path", value_name = "PATH")]
+    pub scan_paths: Vec<String>,
+    #[doc = " Glob of paths to skip in code/secrets scans, e.g. `src/generated/**` (repeatable)"]
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+    #[doc = " Skip Low/Info code findings (unwrap/expect, Command, File::open, env::var) in"]
+    #[doc = " tests/, benches/ and #[cfg(test)] items; secrets are still reported"]
+    #[arg(
+        long,
+        value_name = "BOOL",
+        default_value_t = true,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        action = clap::ArgAction::Set
+    )]
+    pub ignore_tests: bool,
+    #[doc = " Check the dependency tree against the bans in .trae/bans.toml"]
+    #[arg(long)]
+    pub bans: bool,
+    #[doc = " Exit with an error when a banned crate is found (for CI)"]
+    #[arg(long, requires = "bans")]
+    pub fail_on_ban: bool,
+    #[doc = " Project directory to audit (default: current directory)"]
+    #[arg(long, value_name = "DIR", value_parser = validate_path)]
+    pub path: Option<PathBuf>,
+    #[doc = " List each finding with N lines of code around it (0-10); with --format lsp,"]
+    #[doc = " as `data.snippet`"]
+    #[arg(long, value_name = "N", value_parser = parse_context)]
+    pub diff_context: Option<usize>,
+    #[doc = " Drop findings their detector rates below this confidence (0-1)"]
+    #[arg(long, value_name = "0..1", value_parser = parse_confidence)]
+    pub min_confidence: Option<f64>,
+    #[doc = " Write the findings as JSON to this file"]
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<String>,
 }
 impl SecurityCommand {
     #[doc = "Method documentation added by AI refactor"]
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let _root = self.path.as_deref().map(WorkingDirGuard::enter).transpose()?;
         let start_time = Instant::now();
         let mut metrics = MetricsCollector::new("security".to_string());
-        println!("{}", "🔒 TRAE SECURITY - Security Audit Suite".red().bold());
-        println!("{}", "=====================================\n".red());
-        let style = match ProgressStyle::default_spinner().template("{spinner:.red} {msg}") {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("⚠️  progress template failed: {:?}", e);
-                ProgressStyle::default_spinner()
-            }
-        };
-        let pb = ProgressBar::new_spinner();
-        pb.set_style(style);
+        // LSP diagnostics and Markdown own stdout: no banner, spinner or text report
+        let structured = self.format != "text";
+        if !structured {
+            println!("{}", "🔒 TRAE SECURITY - Security Audit Suite".red().bold());
+            println!("{}", "=====================================\n".red());
+        }
+        let pb = crate::utils::progress::spinner(&cli.options);
+        if structured {
+            pb.set_draw_target(ProgressDrawTarget::hidden());
+        }
         let mut results = SecurityResults::default();
         let severity_filter = self.parse_severity_level();
         if self.audit {
@@ -92,25 +197,110 @@ impl SecurityCommand {
             results.cargo_audit = Some(self.run_cargo_audit(cli)?);
             pb.finish_with_message("Cargo audit completado");
         }
+        if self.bans {
+            pb.set_message("Verificando crates prohibidos...");
+            results.bans = Some(self.check_bans(cli)?);
+            pb.finish_with_message("Bans verificados");
+        }
+        let mut severity = SeverityFilter::from_options(&cli.options);
+        results.retain_severity(&mut severity);
         if self.fix {
             pb.set_message("Aplicando fixes automáticos...");
             results.fixes = Some(self.apply_auto_fixes(cli, &results)?);
             pb.finish_with_message("Fixes aplicados");
         }
-        if self.report {
+        if self.report && !structured {
             pb.set_message("Generando reporte de seguridad...");
             self.generate_security_report(&results, start_time.elapsed(), &mut metrics)?;
             pb.finish_with_message("Reporte generado");
         }
-        if !cli.no_jarvix {
-            if let Ok(Some(client)) = JarvixClient::new() {
+        if let Some(notice) = severity.notice().filter(|_| self.format == "text") {
+            println!("{}", notice.yellow());
+        }
+        let export = cli.options.report_path(self.export.as_deref(), "security-report.json");
+        if let Some(out) = export {
+            let mut report = serde_json::json!({
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "critical": results.critical_count(),
+                "findings": results.findings().collect::<Vec<_>>(),
+            });
+            if let Some(threshold) = severity.threshold() {
+                report["severity_threshold"] = threshold.name().into();
+                report["suppressed"] = severity.suppressed().into();
+            }
+            fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+            if !structured {
+                println!("📁 Reporte exportado a: {}", crate::config::full_path(&out));
+            }
+        }
+        let mut snippets = Snippets::new(self.diff_context);
+        let mut snippet = |f: &SecurityFinding| snippets.as_mut()?.take(f.file.as_deref(), f.line);
+        match self.format.as_str() {
+            "lsp" => diagnostics::emit(
+                results.findings().map(|f| f.to_diagnostic().with_snippet(snippet(f))),
+            )?,
+            "markdown" => {
+                let mut report = results.markdown_report();
+                if let Some(notice) = severity.notice() {
+                    report.note(notice);
+                }
+                print!("{}", report.render());
+            }
+            _ if self.diff_context.is_some() => {
+                print_findings(&results, &mut snippet, &cli.options)
+            }
+            _ => {}
+        }
+        if let Some(notice) = snippets.as_ref().and_then(Snippets::notice) {
+            eprintln!("{}", notice.yellow());
+        }
+        if let Some(notice) = severity.notice().filter(|_| self.format == "lsp") {
+            eprintln!("{}", notice.yellow());
+        }
+        if cli.jarvix_enabled() {
+            if let Ok(Some(client)) = JarvixClient::new(&cli.options).await {
                 if let Err(e) = client.report_security_metrics(metrics).await {
                     eprintln!("⚠️ No se pudo reportar métricas de security: {e}");
                 }
             }
         }
+        if self.fail_on_ban {
+            if let Some(bans) = results.bans.as_ref().filter(|b| !b.violations.is_empty()) {
+                let names: Vec<&str> =
+                    bans.violations.iter().map(|v| v.crate_name.as_str()).collect();
+                return Err(findings(
+                    bans.violations.len(),
+                    format!(
+                        "crates prohibidos en el árbol de dependencias: {}",
+                        names.join(", ")
+                    ),
+                ));
+            }
+        }
+        // Without --severity only critical findings fail, as always; with it
+        // high ones count as critical too
+        let failing = match severity.threshold() {
+            Some(_) => {
+                let levels: Vec<_> =
+                    results.findings().map(|f| f.severity.issue_severity()).collect();
+                severity.failing(&levels)
+            }
+            None => results.critical_count(),
+        };
+        if failing > 0 {
+            let what = severity
+                .describe_failing("hallazgos de seguridad críticos", "hallazgos de seguridad");
+            return Err(findings(failing, what));
+        }
         Ok(())
     }
+    /// Drops the findings under `--min-confidence` and sorts the rest.
+    fn finish_findings(&self, findings: &mut Vec<SecurityFinding>) {
+        if let Some(min) = self.min_confidence {
+            findings.retain(|f| f.confidence >= min);
+        }
+        sort_findings(findings);
+    }
     #[doc = "Method documentation added by AI refactor"]
     fn parse_severity_level(&self) -> SecuritySeverity {
         match self.level.as_str() {
@@ -136,6 +326,7 @@ impl SecurityCommand {
         findings.extend(code_findings.vulnerabilities);
         findings.extend(config_findings.issues);
         findings.extend(secrets_findings.findings);
+        sort_findings(&mut findings);
         let critical_count = findings
             .iter()
             .filter(|f| matches!(f.severity, SecuritySeverity::Critical))
@@ -188,12 +379,14 @@ impl SecurityCommand {
                                 line: Some(line_num + 1),
                                 cwe: Some("CWE-1104".to_string()),
                                 fix_available: true,
+                                confidence: 0.5,
                             });
                         }
                     }
                 }
             }
         }
+        self.finish_findings(&mut vulnerabilities);
         Ok(DependencySecurityResult {
             vulnerabilities: vulnerabilities.clone(),
             total_deps_checked: 50,
@@ -204,93 +397,143 @@ impl SecurityCommand {
     #[doc = "Method documentation added by AI refactor"]
     fn scan_code_security(
         &self,
-        _cli: &TraeCli,
+        cli: &TraeCli,
         min_severity: SecuritySeverity,
     ) -> Result<CodeSecurityResult> {
         let mut vulnerabilities = Vec::new();
-        let security_patterns = vec![
+        // (regex, description, severity, CWE, confidence when the match is code)
+        let security_patterns = [
             (
                 r"unsafe\s*\{",
                 "Uso de código unsafe",
                 SecuritySeverity::Medium,
                 "CWE-119",
+                0.9,
             ),
             (
                 r"std::process::Command",
                 "Ejecución de comandos del sistema",
                 SecuritySeverity::Low,
                 "CWE-78",
+                0.8,
             ),
             (
                 r"std::fs::File::open",
                 "Acceso a archivos sin validación",
                 SecuritySeverity::Low,
                 "CWE-22",
-            ),
-            (
-                r"unwrap\(\)",
-                "Uso de unwrap() que puede causar panics",
-                SecuritySeverity::Low,
-                "CWE-754",
-            ),
-            (
-                r"expect\(.*\)",
-                "Uso de expect() que puede causar panics",
-                SecuritySeverity::Low,
-                "CWE-754",
+                0.6,
             ),
             (
                 r"std::env::var",
                 "Lectura de variables de entorno",
                 SecuritySeverity::Info,
                 "CWE-200",
+                0.5,
             ),
         ];
-        for entry in walkdir::WalkDir::new("src")
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
-        {
-            if let Ok(content) = fs::read_to_string(entry.path()) {
-                for (line_num, line) in content.lines().enumerate() {
-                    for (pattern, description, severity, cwe) in &security_patterns {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if regex.is_match(line) && severity >= &min_severity {
-                                vulnerabilities.push(SecurityFinding {
-                                    category: "Code Security".to_string(),
-                                    title: (*description).to_string(),
-                                    description: format!(
-                                        "{} en línea {}",
-                                        description,
-                                        line_num + 1
-                                    ),
-                                    severity: *severity,
-                                    file: Some(entry.path().to_string_lossy().to_string()),
-                                    line: Some(line_num + 1),
-                                    cwe: Some((*cwe).to_string()),
-                                    fix_available: matches!(
-                                        severity,
-                                        SecuritySeverity::Low | SecuritySeverity::Info
-                                    ),
-                                });
-                            }
+        let scan_start = Instant::now();
+        let (scanned_roots, files) = self.collect_scan_files(&cli.options)?;
+        let mut lines_scanned = 0;
+        let mut suppressed = Vec::new();
+        let mut test_findings_skipped = 0;
+        for path in &files {
+            if let Ok(content) = fs::read_to_string(path) {
+                let masked = mask_raw_and_byte_strings(&content);
+                let lines: Vec<&str> = masked.lines().collect();
+                lines_scanned += lines.len();
+                let test_file = self.ignore_tests && is_test_path(path);
+                let test_lines = cfg_test_lines(&masked);
+                let code = mask_comments_and_strings(&content);
+                let code_lines: Vec<&str> = code.lines().collect();
+                vulnerabilities.extend(reasonless_markers(&lines, portable(path)));
+                for (line_num, line) in lines.iter().enumerate() {
+                    let in_test = self.ignore_tests
+                        && (test_file || test_lines.get(line_num).copied().unwrap_or(false));
+                    // Comments and strings blanked: a match only in `line` is a mention
+                    let code_line = code_lines.get(line_num).copied().unwrap_or_default();
+                    let mut line_findings = Vec::new();
+                    let matched = security_patterns.iter().filter_map(
+                        |(pattern, desc, sev, cwe, confidence)| {
+                            let regex = Regex::new(pattern).ok()?;
+                            let confidence =
+                                if regex.is_match(code_line) { *confidence } else { 0.3 };
+                            regex.is_match(line).then_some((*desc, *sev, *cwe, confidence))
+                        },
+                    );
+                    let call_in_code = panicking_call(code_line).is_some();
+                    // expect("...") explains the panic: Info instead of Low.
+                    let panic_call = panicking_call(line).map(|call| match call {
+                        PanicCall::Unwrap => (
+                            "Uso de unwrap() que puede causar panics",
+                            SecuritySeverity::Low,
+                            "CWE-754",
+                            if call_in_code { 0.9 } else { 0.3 },
+                        ),
+                        PanicCall::EmptyExpect => (
+                            "Uso de expect() sin mensaje que puede causar panics",
+                            SecuritySeverity::Low,
+                            "CWE-754",
+                            if call_in_code { 0.9 } else { 0.3 },
+                        ),
+                        PanicCall::Expect => (
+                            "Uso de expect() con mensaje que puede causar panics",
+                            SecuritySeverity::Info,
+                            "CWE-754",
+                            if call_in_code { 0.6 } else { 0.3 },
+                        ),
+                    });
+                    for (description, severity, cwe, confidence) in matched.chain(panic_call) {
+                        // Idiomatic in tests: unwrap() on fixtures, Command on the binary
+                        if in_test && severity <= SecuritySeverity::Low {
+                            test_findings_skipped += 1;
+                            continue;
+                        }
+                        if severity >= min_severity {
+                            line_findings.push(SecurityFinding {
+                                category: "Code Security".to_string(),
+                                title: description.to_string(),
+                                description: format!("{} en línea {}", description, line_num + 1),
+                                severity,
+                                file: Some(portable(path)),
+                                line: Some(line_num + 1),
+                                cwe: Some(cwe.to_string()),
+                                fix_available: matches!(
+                                    severity,
+                                    SecuritySeverity::Low | SecuritySeverity::Info
+                                ),
+                                confidence,
+                            });
                         }
                     }
+                    apply_suppression(
+                        &lines,
+                        line_num,
+                        line_findings,
+                        &mut vulnerabilities,
+                        &mut suppressed,
+                    );
                 }
             }
         }
+        self.finish_findings(&mut vulnerabilities);
+        sort_suppressed(&mut suppressed);
         Ok(CodeSecurityResult {
             vulnerabilities,
-            files_scanned: 25,
-            lines_scanned: 5000,
-            scan_duration: 0.0,
+            suppressed,
+            test_findings_skipped,
+            files_scanned: files.len(),
+            lines_scanned,
+            scan_duration: scan_start.elapsed().as_secs_f64(),
+            scanned_roots,
         })
     }
     #[doc = "Method documentation added by AI refactor"]
     fn check_security_config(&self, _cli: &TraeCli) -> Result<ConfigSecurityResult> {
         let mut issues = Vec::new();
-        if let Ok(content) = fs::read_to_string("Cargo.toml") {
-            if !content.contains("[profile.release]") {
+        if let Ok(manifest) = Manifest::load(Path::new(".")) {
+            let release = manifest.profile.get("release");
+            if release.is_none() {
                 issues.push(SecurityFinding {
                     category: "Configuration".to_string(),
                     title: "Perfil release no configurado".to_string(),
@@ -301,9 +544,10 @@ impl SecurityCommand {
                     line: None,
                     cwe: None,
                     fix_available: true,
+                    confidence: FULL_CONFIDENCE,
                 });
             }
-            if !content.contains("panic = \"abort\"") {
+            if release.and_then(|p| p.panic.as_deref()) != Some("abort") {
                 issues.push(SecurityFinding {
                     category: "Configuration".to_string(),
                     title: "Configuración de panic no segura".to_string(),
@@ -314,9 +558,11 @@ impl SecurityCommand {
                     line: None,
                     cwe: Some("CWE-754".to_string()),
                     fix_available: true,
+                    confidence: FULL_CONFIDENCE,
                 });
             }
         }
+        self.finish_findings(&mut issues);
         Ok(ConfigSecurityResult {
             issues,
             config_files_checked: vec!["Cargo.toml".to_string()],
@@ -324,83 +570,160 @@ impl SecurityCommand {
         })
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn scan_hardcoded_secrets(&self, _cli: &TraeCli) -> Result<SecretsScanResult> {
+    fn scan_hardcoded_secrets(&self, cli: &TraeCli) -> Result<SecretsScanResult> {
         let mut findings = Vec::new();
-        let secret_patterns = vec![
-            (
-                r#"password\s*=\s*["'][^"']+["']"#,
-                "Password hardcodeado",
-                SecuritySeverity::Critical,
-            ),
-            (
-                r#"secret\s*=\s*["'][^"']+["']"#,
-                "Secret hardcodeado",
-                SecuritySeverity::Critical,
-            ),
-            (
-                r#"token\s*=\s*["'][^"']+["']"#,
-                "Token hardcodeado",
-                SecuritySeverity::High,
-            ),
-            (
-                r#"api_key\s*=\s*["'][^"']+["']"#,
-                "API Key hardcodeada",
-                SecuritySeverity::High,
-            ),
-            (
-                r"PRIVATE_KEY",
-                "Posible clave privada",
-                SecuritySeverity::Critical,
-            ),
-            (
-                r"sk-\w+",
-                "Posible API key de OpenAI",
-                SecuritySeverity::Critical,
-            ),
-        ];
-        for entry in walkdir::WalkDir::new("src")
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
-        {
-            if let Ok(content) = fs::read_to_string(entry.path()) {
-                for (line_num, line) in content.lines().enumerate() {
-                    for (pattern, description, severity) in &secret_patterns {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if regex.is_match(line) {
-                                findings.push(SecurityFinding {
-                                    category: "Secrets".to_string(),
-                                    title: (*description).to_string(),
-                                    description: format!(
-                                        "{} detectado en línea {}",
-                                        description,
-                                        line_num + 1
-                                    ),
-                                    severity: *severity,
-                                    file: Some(entry.path().to_string_lossy().to_string()),
-                                    line: Some(line_num + 1),
-                                    cwe: Some("CWE-798".to_string()),
-                                    fix_available: false,
-                                });
-                            }
+        let secret_patterns = load_secret_patterns()?;
+        let (scanned_roots, files) = self.collect_scan_files(&cli.options)?;
+        let mut suppressed = Vec::new();
+        // The code scan reports markers without reason when it runs too
+        let check_markers = !self.code && !self.audit;
+        for path in &files {
+            if let Ok(content) = fs::read_to_string(path) {
+                let masked = mask_raw_and_byte_strings(&content);
+                let lines: Vec<&str> = masked.lines().collect();
+                if check_markers {
+                    findings.extend(reasonless_markers(&lines, portable(path)));
+                }
+                for (line_num, line) in lines.iter().enumerate() {
+                    let mut line_findings = Vec::new();
+                    for pattern in &secret_patterns {
+                        if let Some(found) = pattern.regex.find(line) {
+                            line_findings.push(SecurityFinding {
+                                category: "Secrets".to_string(),
+                                title: pattern.description.clone(),
+                                description: format!(
+                                    "{} detectado en línea {}",
+                                    pattern.description,
+                                    line_num + 1
+                                ),
+                                severity: pattern.severity,
+                                file: Some(portable(path)),
+                                line: Some(line_num + 1),
+                                cwe: Some("CWE-798".to_string()),
+                                fix_available: false,
+                                confidence: secret_confidence(found.as_str()),
+                            });
                         }
                     }
+                    apply_suppression(
+                        &lines,
+                        line_num,
+                        line_findings,
+                        &mut findings,
+                        &mut suppressed,
+                    );
                 }
             }
         }
+        self.finish_findings(&mut findings);
+        sort_suppressed(&mut suppressed);
         Ok(SecretsScanResult {
             findings: findings.clone(),
-            files_scanned: 25,
-            potential_secrets: findings.len(),
+            suppressed,
+            files_scanned: files.len(),
+            scanned_roots,
+            potential_secrets: findings.iter().filter(|f| f.category == "Secrets").count(),
             high_confidence: findings
                 .iter()
                 .filter(|f| matches!(f.severity, SecuritySeverity::Critical))
                 .count(),
         })
     }
+    /// Collects the `.rs` files under the configured scan roots, minus `--exclude`
+    /// matches, walked under `options`. Returns the roots that actually exist
+    /// alongside the files.
+    fn collect_scan_files(
+        &self,
+        options: &crate::config::RunOptions,
+    ) -> Result<(Vec<String>, Vec<PathBuf>)> {
+        let roots: Vec<String> = if self.scan_paths.is_empty() {
+            DEFAULT_SCAN_PATHS.iter().map(|r| (*r).to_string()).collect()
+        } else {
+            self.scan_paths.clone()
+        };
+        let excludes = build_exclude_set(&self.exclude)?;
+        let mut scanned_roots = Vec::new();
+        let mut files = Vec::new();
+        for root in roots {
+            if !std::path::Path::new(&root).exists() {
+                if !self.scan_paths.is_empty() {
+                    eprintln!("⚠️  Ruta de escaneo inexistente: {root}");
+                }
+                continue;
+            }
+            files.extend(
+                crate::utils::path::rust_files(Path::new(&root), None, options)
+                    .map(walkdir::DirEntry::into_path)
+                    .filter(|p| !excludes.is_match(p.strip_prefix("./").unwrap_or(p))),
+            );
+            scanned_roots.push(root);
+        }
+        Ok((scanned_roots, files))
+    }
+    /// Compares the resolved dependency graph against `.trae/bans.toml` and reports
+    /// each banned package with the dependency path that pulled it in.
+    fn check_bans(&self, cli: &TraeCli) -> Result<BansResult> {
+        let Ok(content) = fs::read_to_string(BANS_FILE) else {
+            eprintln!("⚠️  No se encontró {BANS_FILE}; no hay crates prohibidos configurados");
+            return Ok(BansResult::default());
+        };
+        let config: BansConfig = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("{BANS_FILE} inválido: {e}"))?;
+        let rules = config
+            .ban
+            .iter()
+            .map(|rule| {
+                let req = match rule.version.as_deref() {
+                    Some(v) => VersionReq::parse(v).map_err(|e| {
+                        anyhow::anyhow!("Versión inválida para '{}' en {BANS_FILE}: {e}", rule.name)
+                    })?,
+                    None => VersionReq::STAR,
+                };
+                Ok((rule, req))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let metadata = metadata_command(&cli.options)
+            .exec()
+            .map_err(|e| anyhow::anyhow!("cargo metadata falló: {e}"))?;
+        let packages: HashMap<&PackageId, &cargo_metadata::Package> =
+            metadata.packages.iter().map(|p| (&p.id, p)).collect();
+        let parents = dependency_parents(&metadata);
+        let mut violations = Vec::new();
+        for (rule, req) in &rules {
+            for pkg in metadata.packages.iter().filter(|p| p.name == rule.name) {
+                if !req.matches(&pkg.version) || !parents.contains_key(&pkg.id) {
+                    continue;
+                }
+                let path = dependency_path(&pkg.id, &parents, &packages);
+                let finding = SecurityFinding {
+                    category: "Bans".to_string(),
+                    title: format!("Crate prohibido: {}", pkg.name),
+                    description: format!(
+                        "{} {} ({}) vía {}",
+                        pkg.name,
+                        pkg.version,
+                        rule.reason.as_deref().unwrap_or("prohibido por política"),
+                        path.join(" -> ")
+                    ),
+                    severity: SecuritySeverity::High,
+                    file: Some("Cargo.lock".to_string()),
+                    line: None,
+                    cwe: Some("CWE-1357".to_string()),
+                    fix_available: false,
+                    confidence: FULL_CONFIDENCE,
+                };
+                violations.push(BanViolation { crate_name: pkg.name.to_string(), finding });
+            }
+        }
+        violations.sort_by(|a, b| compare_findings(&a.finding, &b.finding));
+        Ok(BansResult {
+            rules_checked: rules.len(),
+            violations,
+        })
+    }
     #[doc = "Method documentation added by AI refactor"]
-    fn run_cargo_audit(&self, _cli: &TraeCli) -> Result<CargoAuditResult> {
-        let audit_check = Command::new("cargo").arg("audit").arg("--version").output();
+    fn run_cargo_audit(&self, cli: &TraeCli) -> Result<CargoAuditResult> {
+        let audit_check = cargo_command(&cli.options).arg("audit").arg("--version").output();
         if audit_check.is_err() {
             return Ok(CargoAuditResult {
                 audit_run: false,
@@ -409,7 +732,7 @@ impl SecurityCommand {
                 last_update: None,
             });
         }
-        let output = Command::new("cargo").arg("audit").output()?;
+        let output = cargo_command(&cli.options).arg("audit").output()?;
         let success = output.status.success();
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -437,9 +760,8 @@ impl SecurityCommand {
             for issue in &config_check.issues {
                 if issue.fix_available && matches!(issue.severity, SecuritySeverity::Low)
                     && issue.title.contains("panic") {
-                        if let Ok(mut content) = fs::read_to_string("Cargo.toml") {
-                            if !content.contains("[profile.release]") {
-                                content.push_str("\n[profile.release]\npanic = \"abort\"\n");
+                        if let Ok(content) = fs::read_to_string("Cargo.toml") {
+                            if let Some(content) = with_release_panic_abort(&content) {
                                 if fs::write("Cargo.toml", content).is_ok() {
                                     fixes_applied.push(
                                         "Agregado panic = \"abort\" a Cargo.toml".to_string(),
@@ -522,6 +844,11 @@ impl SecurityCommand {
         }
         if let Some(code) = &results.code_scan {
             println!("\n{}", "💻 ANÁLISIS DE CÓDIGO".blue().bold());
+            println!(
+                "{} {}",
+                "Raíces escaneadas:".cyan(),
+                code.scanned_roots.join(", ")
+            );
             println!("{} {}", "Archivos escaneados:".cyan(), code.files_scanned);
             println!("{} {}", "Líneas escaneadas:".cyan(), code.lines_scanned);
             println!(
@@ -529,6 +856,14 @@ impl SecurityCommand {
                 "Vulnerabilidades:".red(),
                 code.vulnerabilities.len()
             );
+            if code.test_findings_skipped > 0 {
+                println!(
+                    "{} {} (Low/Info en código de test; --ignore-tests=false para verlos)",
+                    "Omitidos en tests:".bright_black(),
+                    code.test_findings_skipped
+                );
+            }
+            print_suppressed(&code.suppressed);
         }
         if let Some(config) = &results.config_check {
             println!("\n{}", "⚙️ CONFIGURACIÓN".purple().bold());
@@ -542,6 +877,11 @@ impl SecurityCommand {
         }
         if let Some(secrets) = &results.secrets_scan {
             println!("\n{}", "🔑 SECRETS".magenta().bold());
+            println!(
+                "{} {}",
+                "Raíces escaneadas:".cyan(),
+                secrets.scanned_roots.join(", ")
+            );
             println!(
                 "{} {}",
                 "Archivos escaneados:".cyan(),
@@ -553,6 +893,7 @@ impl SecurityCommand {
                 secrets.potential_secrets
             );
             println!("{} {}", "Alta confianza:".red(), secrets.high_confidence);
+            print_suppressed(&secrets.suppressed);
         }
         if let Some(audit) = &results.cargo_audit {
             println!("\n{}", "🔍 CARGO AUDIT".green().bold());
@@ -575,6 +916,14 @@ impl SecurityCommand {
                 );
             }
         }
+        if let Some(bans) = &results.bans {
+            println!("\n{}", "🚫 CRATES PROHIBIDOS".red().bold());
+            println!("{} {}", "Reglas verificadas:".cyan(), bans.rules_checked);
+            println!("{} {}", "Violaciones:".red(), bans.violations.len());
+            for violation in &bans.violations {
+                println!("  {} {}", "✖".red(), violation.finding.description);
+            }
+        }
         if let Some(fixes) = &results.fixes {
             println!("\n{}", "🔧 FIXES APLICADOS".green().bold());
             for fix in &fixes.fixes_applied {
@@ -611,6 +960,217 @@ impl SecurityCommand {
         Ok(())
     }
 }
+/// `--diff-context` in text mode: every finding with its location and the
+/// code around it.
+fn print_findings(
+    results: &SecurityResults,
+    snippet: &mut impl FnMut(&SecurityFinding) -> Option<Snippet>,
+    options: &crate::config::RunOptions,
+) {
+    println!("\n{}", "📍 HALLAZGOS".cyan().bold());
+    for finding in results.findings() {
+        let location = match (&finding.file, finding.line) {
+            (Some(file), Some(line)) => format!(" ({file}:{line})"),
+            (Some(file), None) => format!(" ({file})"),
+            _ => String::new(),
+        };
+        println!(
+            "  {} [{:?}] {}: {}{}",
+            "•".yellow(),
+            finding.severity,
+            finding.category,
+            finding.description,
+            location.bright_black()
+        );
+        if let Some(snippet) = snippet(finding) {
+            snippet.print(options);
+        }
+    }
+}
+/// Breadth-first walk of the resolve graph from the workspace members, recording
+/// the first parent through which each package is reached.
+fn dependency_parents(
+    metadata: &cargo_metadata::Metadata,
+) -> HashMap<PackageId, Option<PackageId>> {
+    let mut parents: HashMap<PackageId, Option<PackageId>> = HashMap::new();
+    let Some(resolve) = &metadata.resolve else {
+        return parents;
+    };
+    let nodes: HashMap<&PackageId, &cargo_metadata::Node> =
+        resolve.nodes.iter().map(|n| (&n.id, n)).collect();
+    let mut queue = VecDeque::new();
+    for member in &metadata.workspace_members {
+        parents.insert(member.clone(), None);
+        queue.push_back(member.clone());
+    }
+    while let Some(id) = queue.pop_front() {
+        let Some(node) = nodes.get(&id) else {
+            continue;
+        };
+        for dep in &node.dependencies {
+            if !parents.contains_key(dep) {
+                parents.insert(dep.clone(), Some(id.clone()));
+                queue.push_back(dep.clone());
+            }
+        }
+    }
+    parents
+}
+/// Rebuilds the `root -> ... -> pkg` chain of crate names for a package.
+fn dependency_path(
+    id: &PackageId,
+    parents: &HashMap<PackageId, Option<PackageId>>,
+    packages: &HashMap<&PackageId, &cargo_metadata::Package>,
+) -> Vec<String> {
+    let mut path = Vec::new();
+    let mut current = Some(id.clone());
+    while let Some(pid) = current {
+        path.push(
+            packages
+                .get(&pid)
+                .map_or_else(|| pid.repr.clone(), |p| p.name.clone()),
+        );
+        current = parents.get(&pid).cloned().flatten();
+    }
+    path.reverse();
+    path
+}
+/// Inline marker that silences findings on its own line or the line below.
+const IGNORE_MARKER: &str = "// trae-security-ignore";
+/// The reason of the ignore marker on `line`, if it has one. The reason may be empty.
+fn marker_reason(line: &str) -> Option<String> {
+    line.find(IGNORE_MARKER).map(|pos| {
+        let rest = &line[pos + IGNORE_MARKER.len()..];
+        rest.strip_prefix(':').unwrap_or(rest).trim().to_string()
+    })
+}
+/// Returns the reason of a `// trae-security-ignore: <reason>` marker found at the
+/// end of line `idx` or as a comment line right above it. The reason may be empty.
+fn find_suppression(lines: &[&str], idx: usize) -> Option<String> {
+    marker_reason(lines[idx]).or_else(|| {
+        let prev = lines.get(idx.checked_sub(1)?)?.trim_start();
+        if prev.starts_with("//") {
+            marker_reason(prev)
+        } else {
+            None
+        }
+    })
+}
+/// One finding per ignore marker in `lines` that gives no reason, on the
+/// marker's own line, whether or not there was anything for it to silence.
+fn reasonless_markers(lines: &[&str], file: String) -> Vec<SecurityFinding> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| marker_reason(line).is_some_and(|reason| reason.is_empty()))
+        .map(|(idx, _)| SecurityFinding {
+            category: "Suppression".to_string(),
+            title: "trae-security-ignore sin motivo".to_string(),
+            description: format!(
+                "La supresión en línea {} no indica motivo; use `{IGNORE_MARKER}: <motivo>`",
+                idx + 1
+            ),
+            severity: SecuritySeverity::Medium,
+            file: Some(file.clone()),
+            line: Some(idx + 1),
+            cwe: None,
+            fix_available: false,
+            confidence: FULL_CONFIDENCE,
+        })
+        .collect()
+}
+/// Moves the findings of one line into `suppressed` when a valid ignore marker
+/// applies. A marker without reason does not suppress; [`reasonless_markers`]
+/// reports it.
+fn apply_suppression(
+    lines: &[&str],
+    idx: usize,
+    line_findings: Vec<SecurityFinding>,
+    findings: &mut Vec<SecurityFinding>,
+    suppressed: &mut Vec<SuppressedFinding>,
+) {
+    if line_findings.is_empty() {
+        return;
+    }
+    match find_suppression(lines, idx) {
+        Some(reason) if !reason.is_empty() => {
+            suppressed.extend(line_findings.into_iter().map(|f| SuppressedFinding {
+                file: f.file.unwrap_or_default(),
+                line: idx + 1,
+                title: f.title,
+                reason: reason.clone(),
+            }));
+        }
+        _ => findings.extend(line_findings),
+    }
+}
+/// Confidence of a secret match: the Shannon entropy of its quoted value (or
+/// of the whole match), since random keys score high and placeholders like
+/// `"changeme"` low. 3.5 bits per char or more is 0.9, 2.5 or more 0.6.
+fn secret_confidence(found: &str) -> f64 {
+    let value = found.split(['"', '\'']).nth(1).filter(|v| !v.is_empty()).unwrap_or(found);
+    let mut counts = HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = value.chars().count() as f64;
+    let entropy: f64 = counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+    match entropy {
+        e if e >= 3.5 => 0.9,
+        e if e >= 2.5 => 0.6,
+        _ => 0.3,
+    }
+}
+/// Orders findings by severity (critical first), confidence (most likely
+/// first), file, line and description, so reports don't depend on the walk
+/// order.
+fn sort_findings(findings: &mut [SecurityFinding]) {
+    findings.sort_by(compare_findings);
+}
+/// The order of [`sort_findings`].
+fn compare_findings(a: &SecurityFinding, b: &SecurityFinding) -> std::cmp::Ordering {
+    b.severity
+        .cmp(&a.severity)
+        .then_with(|| b.confidence.total_cmp(&a.confidence))
+        .then_with(|| a.file.cmp(&b.file))
+        .then_with(|| a.line.cmp(&b.line))
+        .then_with(|| a.description.cmp(&b.description))
+        .then_with(|| a.title.cmp(&b.title))
+}
+/// Orders suppressed findings by file, line and title.
+fn sort_suppressed(suppressed: &mut [SuppressedFinding]) {
+    suppressed.sort_by(|a, b| (&a.file, a.line, &a.title).cmp(&(&b.file, b.line, &b.title)));
+}
+/// Prints the informational note listing suppressed findings and their reasons.
+fn print_suppressed(suppressed: &[SuppressedFinding]) {
+    if suppressed.is_empty() {
+        return;
+    }
+    println!(
+        "{} {} hallazgos suprimidos con trae-security-ignore",
+        "ℹ️".blue(),
+        suppressed.len()
+    );
+    for s in suppressed {
+        println!("  • {}:{} {} — {}", s.file, s.line, s.title, s.reason.dimmed());
+    }
+}
+/// Compiles the `--exclude` globs; an invalid pattern is a usage error.
+fn build_exclude_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| UsageError(format!("Patrón --exclude inválido '{pattern}': {e}")))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
 #[derive(Default, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 struct SecurityResults {
@@ -620,9 +1180,94 @@ struct SecurityResults {
     config_check: Option<ConfigSecurityResult>,
     secrets_scan: Option<SecretsScanResult>,
     cargo_audit: Option<CargoAuditResult>,
+    bans: Option<BansResult>,
     fixes: Option<SecurityFixesResult>,
 }
-#[derive(Debug, PartialEq, Eq, PartialOrd, Clone, Copy)]
+impl SecurityResults {
+    /// Critical findings across every scan that ran (the audit repeats the
+    /// individual scans, so it is counted on its own when present).
+    fn critical_count(&self) -> usize {
+        if let Some(audit) = &self.audit {
+            return audit.critical_count;
+        }
+        let scans = [
+            self.dependencies.as_ref().map(|r| &r.vulnerabilities),
+            self.code_scan.as_ref().map(|r| &r.vulnerabilities),
+            self.config_check.as_ref().map(|r| &r.issues),
+            self.secrets_scan.as_ref().map(|r| &r.findings),
+        ];
+        scans
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|f| matches!(f.severity, SecuritySeverity::Critical))
+            .count()
+    }
+    /// Drops the findings `severity` doesn't admit from every scan, counting
+    /// each dropped finding once (as [`Self::findings`] lists them), and
+    /// recounts the audit.
+    fn retain_severity(&mut self, severity: &mut SeverityFilter) {
+        let mut uncounted = SeverityFilter::new(severity.threshold().cloned());
+        let level = |f: &SecurityFinding| f.severity.issue_severity();
+        if let Some(audit) = &mut self.audit {
+            severity.retain_by(&mut audit.findings, level);
+            let count = |level| audit.findings.iter().filter(|f| f.severity == level).count();
+            audit.critical_count = count(SecuritySeverity::Critical);
+            audit.high_count = count(SecuritySeverity::High);
+            audit.medium_count = count(SecuritySeverity::Medium);
+            audit.low_count = count(SecuritySeverity::Low);
+        }
+        let counted = if self.audit.is_some() { &mut uncounted } else { severity };
+        let scans = [
+            self.dependencies.as_mut().map(|r| &mut r.vulnerabilities),
+            self.code_scan.as_mut().map(|r| &mut r.vulnerabilities),
+            self.config_check.as_mut().map(|r| &mut r.issues),
+            self.secrets_scan.as_mut().map(|r| &mut r.findings),
+        ];
+        for findings in scans.into_iter().flatten() {
+            counted.retain_by(findings, level);
+        }
+        if let Some(bans) = &mut self.bans {
+            counted.retain_by(&mut bans.violations, |v| level(&v.finding));
+        }
+    }
+    /// Every finding once: the audit's when it ran, otherwise each scan's
+    /// followed by the ban violations.
+    fn findings(&self) -> impl Iterator<Item = &SecurityFinding> {
+        let scans = match &self.audit {
+            Some(audit) => vec![Some(&audit.findings)],
+            None => vec![
+                self.dependencies.as_ref().map(|r| &r.vulnerabilities),
+                self.code_scan.as_ref().map(|r| &r.vulnerabilities),
+                self.config_check.as_ref().map(|r| &r.issues),
+                self.secrets_scan.as_ref().map(|r| &r.findings),
+            ],
+        };
+        let bans = self.bans.iter().filter(|_| self.audit.is_none());
+        let bans = bans.flat_map(|r| r.violations.iter().map(|v| &v.finding));
+        scans.into_iter().flatten().flatten().chain(bans)
+    }
+    /// `--format markdown`: counts by severity, then every finding by category.
+    fn markdown_report(&self) -> markdown::Report {
+        let mut report = markdown::Report::new("🔒 trae security");
+        let severities = [
+            ("🔴 Critical", SecuritySeverity::Critical),
+            ("🟠 High", SecuritySeverity::High),
+            ("🟡 Medium", SecuritySeverity::Medium),
+            ("🔵 Low", SecuritySeverity::Low),
+            ("🔵 Info", SecuritySeverity::Info),
+        ];
+        for (label, severity) in severities {
+            report.summary(label, self.findings().filter(|f| f.severity == severity).count());
+        }
+        for finding in self.findings() {
+            report.finding(finding.to_markdown());
+        }
+        report
+    }
+}
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SecuritySeverity {
     Info = 1,
     Low = 2,
@@ -630,7 +1275,28 @@ pub enum SecuritySeverity {
     High = 4,
     Critical = 5,
 }
-#[derive(Debug, Clone)]
+impl SecuritySeverity {
+    /// Where `--severity` puts this finding: critical and high are critical,
+    /// medium a warning, low and info info (as [`Self::lsp_severity`]).
+    pub const fn issue_severity(&self) -> IssueSeverity {
+        match self {
+            Self::Critical | Self::High => IssueSeverity::Critical,
+            Self::Medium => IssueSeverity::Warning,
+            Self::Low | Self::Info => IssueSeverity::Info,
+        }
+    }
+    /// LSP `DiagnosticSeverity`: critical and high are errors, medium a
+    /// warning, low an information and info a hint.
+    pub const fn lsp_severity(&self) -> u8 {
+        match self {
+            Self::Critical | Self::High => diagnostics::ERROR,
+            Self::Medium => diagnostics::WARNING,
+            Self::Low => diagnostics::INFORMATION,
+            Self::Info => diagnostics::HINT,
+        }
+    }
+}
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 #[doc = "Struct documentation added by AI refactor"]
 struct SecurityFinding {
@@ -642,6 +1308,142 @@ struct SecurityFinding {
     line: Option<usize>,
     cwe: Option<String>,
     fix_available: bool,
+    /// How likely the finding is real, from 0 to 1, as rated by its detector.
+    confidence: f64,
+}
+impl SecurityFinding {
+    /// `code` is the CWE when there is one, otherwise the category.
+    fn to_diagnostic(&self) -> diagnostics::Diagnostic {
+        diagnostics::Diagnostic::new(
+            self.file.as_deref(),
+            self.line,
+            self.severity.lsp_severity(),
+            self.cwe.as_deref().unwrap_or(&self.category),
+            &self.description,
+        )
+        .with_confidence(self.confidence)
+    }
+    /// Row of a `--format markdown` report; the CWE, if any, leads the message.
+    fn to_markdown(&self) -> markdown::Finding {
+        let message = match &self.cwe {
+            Some(cwe) => format!("{cwe}: {}", self.description),
+            None => self.description.clone(),
+        };
+        markdown::Finding {
+            category: self.category.clone(),
+            severity: format!("{:?}", self.severity),
+            location: markdown::Finding::location(self.file.as_deref(), self.line),
+            message,
+        }
+    }
+}
+/// Contents of `.trae/bans.toml`: a list of `[[ban]]` tables.
+#[derive(Debug, Deserialize)]
+struct BansConfig {
+    #[serde(default)]
+    ban: Vec<BanRule>,
+}
+/// A single banned crate; `version` is a semver requirement (any version if omitted).
+#[derive(Debug, Deserialize)]
+struct BanRule {
+    name: String,
+    version: Option<String>,
+    reason: Option<String>,
+}
+/// Contents of `.trae/secrets.toml`: `disable` lists built-in pattern names,
+/// each `[[pattern]]` adds a regex.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SecretsConfig {
+    #[serde(default)]
+    disable: Vec<String>,
+    #[serde(default)]
+    pattern: Vec<SecretRule>,
+}
+/// A user secret pattern; `severity` is info, low, medium, high or critical.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SecretRule {
+    name: String,
+    regex: String,
+    severity: SecuritySeverity,
+}
+/// A compiled secret pattern, built-in or from `.trae/secrets.toml`.
+#[derive(Debug)]
+struct SecretPattern {
+    regex: Regex,
+    description: String,
+    severity: SecuritySeverity,
+}
+/// Built-ins not listed in `disable`, followed by the user's patterns. An
+/// invalid regex or unknown severity is an error naming the pattern; an
+/// unknown name in `disable` only warns.
+fn load_secret_patterns() -> Result<Vec<SecretPattern>> {
+    let config: SecretsConfig = match fs::read_to_string(SECRETS_FILE) {
+        Ok(content) => toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("{SECRETS_FILE} inválido: {e}"))?,
+        Err(_) => SecretsConfig::default(),
+    };
+    for name in &config.disable {
+        if !BUILTIN_SECRET_PATTERNS.iter().any(|(builtin, ..)| builtin == name) {
+            eprintln!("⚠️  {SECRETS_FILE}: '{name}' no es un patrón integrado; se ignora");
+        }
+    }
+    let mut patterns: Vec<SecretPattern> = BUILTIN_SECRET_PATTERNS
+        .iter()
+        .filter(|(name, ..)| !config.disable.iter().any(|d| d == name))
+        .map(|(_, regex, description, severity)| SecretPattern {
+            regex: Regex::new(regex).expect("built-in secret pattern"),
+            description: (*description).to_string(),
+            severity: *severity,
+        })
+        .collect();
+    for rule in config.pattern {
+        let regex = Regex::new(&rule.regex).map_err(|e| {
+            anyhow::anyhow!("{SECRETS_FILE}: regex inválida en el patrón '{}': {e}", rule.name)
+        })?;
+        patterns.push(SecretPattern {
+            regex,
+            description: rule.name,
+            severity: rule.severity,
+        });
+    }
+    Ok(patterns)
+}
+#[derive(Debug, Default)]
+struct BansResult {
+    rules_checked: usize,
+    violations: Vec<BanViolation>,
+}
+/// A banned package in the dependency graph.
+#[derive(Debug)]
+struct BanViolation {
+    crate_name: String,
+    finding: SecurityFinding,
+}
+/// `content` with `panic = "abort"` in `[profile.release]`, adding the
+/// section when missing; `None` when the release profile already sets
+/// `panic` or isn't a plain `[profile.release]` header.
+fn with_release_panic_abort(content: &str) -> Option<String> {
+    let manifest = Manifest::parse(content).ok()?;
+    let Some(release) = manifest.profile.get("release") else {
+        return Some(format!("{content}\n[profile.release]\npanic = \"abort\"\n"));
+    };
+    if release.panic.is_some() {
+        return None;
+    }
+    let header = content.lines().position(|l| l.trim() == "[profile.release]")?;
+    let mut lines: Vec<&str> = content.lines().collect();
+    lines.insert(header + 1, "panic = \"abort\"");
+    Some(lines.join("\n") + "\n")
+}
+/// Finding dropped by an inline `trae-security-ignore` marker.
+#[derive(Debug, Clone)]
+struct SuppressedFinding {
+    file: String,
+    line: usize,
+    title: String,
+    reason: String,
 }
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -669,9 +1471,13 @@ struct DependencySecurityResult {
 #[doc = "Struct documentation added by AI refactor"]
 struct CodeSecurityResult {
     vulnerabilities: Vec<SecurityFinding>,
+    suppressed: Vec<SuppressedFinding>,
+    /// Low/Info findings in test code left out by `--ignore-tests`.
+    test_findings_skipped: usize,
     files_scanned: usize,
     lines_scanned: usize,
     scan_duration: f64,
+    scanned_roots: Vec<String>,
 }
 #[derive(Debug)]
 #[doc = "Struct documentation added by AI refactor"]
@@ -684,7 +1490,9 @@ struct ConfigSecurityResult {
 #[doc = "Struct documentation added by AI refactor"]
 struct SecretsScanResult {
     findings: Vec<SecurityFinding>,
+    suppressed: Vec<SuppressedFinding>,
     files_scanned: usize,
+    scanned_roots: Vec<String>,
     potential_secrets: usize,
     high_confidence: usize,
 }
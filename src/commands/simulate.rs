@@ -215,15 +215,11 @@ impl SimulateCommand {
         println!("Applying performance optimizations based on simulation results...");
         for (sim_type, result) in results {
             match sim_type.as_str() {
-                "Throughput" => {
-                    if result.operations_per_sec < 1000.0 {
-                        println!("  📈 Optimizing for higher throughput...");
-                    }
+                "Throughput" if result.operations_per_sec < 1000.0 => {
+                    println!("  📈 Optimizing for higher throughput...");
                 }
-                "Latency" => {
-                    if result.avg_latency_ms > 10.0 {
-                        println!("  ⏱️  Optimizing for lower latency...");
-                    }
+                "Latency" if result.avg_latency_ms > 10.0 => {
+                    println!("  ⏱️  Optimizing for lower latency...");
                 }
                 "Memory" => {
                     println!("  🧠 Optimizing memory usage...");
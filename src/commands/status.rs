@@ -0,0 +1,208 @@
+#![doc = " # Status Command - Are the trae services up"]
+#![doc = ""]
+#![doc = " Servidor HTTP local, daemon y JARVIXSERVER: estado, latencia y versión de cada uno"]
+use crate::cli::TraeCli;
+use crate::commands::daemon::DaemonPid;
+use crate::config::{RunOptions, StatusConfig};
+use crate::jarvix::client::{JarvixClient, VERSION_HEADER};
+use crate::utils::http;
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+/// Longest wait for a `/health` or `/healthz` answer.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+#[derive(Args, Debug)]
+#[doc = " Opciones de trae status"]
+pub struct StatusCommand {
+    #[doc = " Also check the configured JARVIXSERVER (`/healthz`)"]
+    #[arg(long)]
+    pub remote: bool,
+    #[doc = " Print `{ components: [{ name, up, required, detail, url, latency_ms, version }],"]
+    #[doc = " ok }` as JSON for monitoring"]
+    #[arg(long)]
+    pub json: bool,
+}
+/// One component as printed and as emitted by `trae status --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub up: bool,
+    /// A required component that is down makes [`StatusReport::ok`] false.
+    pub required: bool,
+    pub detail: String,
+    /// Health endpoint probed, on `server` and `jarvix`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub version: Option<String>,
+}
+impl ComponentStatus {
+    fn new(name: &str, config: &StatusConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            up: false,
+            required: config.required.iter().any(|r| r == name),
+            detail: String::new(),
+            url: None,
+            latency_ms: None,
+            version: None,
+        }
+    }
+}
+/// Every component `trae status` checked; `ok` is false when a required one is down.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+    pub components: Vec<ComponentStatus>,
+    pub ok: bool,
+}
+impl StatusCommand {
+    #[doc = " Comprueba los componentes y falla si alguno requerido está caído"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        let config = crate::config::TraeConfig::load().status;
+        let report = collect(&config, self.remote, &cli.options).await;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+        let down = report.components.iter().filter(|c| c.required && !c.up).count();
+        if down > 0 {
+            return Err(crate::exit::findings(down, "componentes requeridos caídos"));
+        }
+        Ok(())
+    }
+}
+/// Checks the local server and the daemon, plus JARVIXSERVER (as `options`
+/// configure it) with `remote`. Offline `options` leave JARVIXSERVER
+/// unchecked: no discovery and no request.
+pub async fn collect(config: &StatusConfig, remote: bool, options: &RunOptions) -> StatusReport {
+    let server = format!("{}/health", config.server_url.trim_end_matches('/'));
+    let jarvix = async {
+        if !remote {
+            return None;
+        }
+        if options.offline {
+            let status = ComponentStatus::new("jarvix", config);
+            return Some(ComponentStatus { detail: "offline".to_string(), ..status });
+        }
+        Some(match JarvixClient::load_config(options).await {
+            Ok(jarvix) => {
+                let url = format!("{}/healthz", jarvix.endpoint.trim_end_matches('/'));
+                let status = ComponentStatus::new("jarvix", config);
+                probe(status, &url, http::jarvix_builder()).await
+            }
+            Err(e) => ComponentStatus {
+                detail: format!("sin configuración: {e:#}"),
+                ..ComponentStatus::new("jarvix", config)
+            },
+        })
+    };
+    let server = probe(ComponentStatus::new("server", config), &server, http::client_builder());
+    let (server, jarvix) = tokio::join!(server, jarvix);
+    let mut components = vec![server, daemon(ComponentStatus::new("daemon", config))];
+    components.extend(jarvix);
+    let ok = components.iter().all(|c| !c.required || c.up);
+    StatusReport { components, ok }
+}
+/// `GET url`: up on a 2xx answer. The version comes from the
+/// `x-trae-version` header or a `version` (or `data.version`) in the body.
+/// `builder` is the client to use, or why it couldn't be set up.
+async fn probe(
+    mut status: ComponentStatus,
+    url: &str,
+    builder: Result<reqwest::ClientBuilder>,
+) -> ComponentStatus {
+    status.url = Some(url.to_string());
+    let client = builder.and_then(|builder| Ok(builder.timeout(PROBE_TIMEOUT).build()?));
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => {
+            status.detail = format!("cliente HTTP: {e}");
+            return status;
+        }
+    };
+    let start = Instant::now();
+    let response = match client.get(url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            status.detail = format!("sin respuesta: {e}");
+            return status;
+        }
+    };
+    status.latency_ms = Some(start.elapsed().as_millis() as u64);
+    status.up = response.status().is_success();
+    status.detail = format!("HTTP {}", response.status());
+    let header = response.headers().get(VERSION_HEADER).and_then(|v| v.to_str().ok());
+    status.version = header.map(str::to_string);
+    if status.version.is_none() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        status.version = [&body["version"], &body["data"]["version"]]
+            .into_iter()
+            .find_map(|v| v.as_str().map(str::to_string));
+    }
+    status
+}
+/// The daemon `trae daemon` recorded: up while its process runs.
+fn daemon(mut status: ComponentStatus) -> ComponentStatus {
+    let record = match DaemonPid::load() {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            status.detail = "sin PID file; iniciar con `trae daemon`".to_string();
+            return status;
+        }
+        Err(e) => {
+            status.detail = format!("{e:#}");
+            return status;
+        }
+    };
+    let start = Instant::now();
+    status.up = process_alive(record.pid);
+    status.latency_ms = Some(start.elapsed().as_millis() as u64);
+    status.detail = if status.up {
+        let since = record.started_at.format("%Y-%m-%d %H:%M");
+        format!("pid {} ({}, puerto {}) desde {since}", record.pid, record.binary, record.port)
+    } else {
+        format!("pid {} ({}) no está en ejecución", record.pid, record.binary)
+    };
+    status.version = Some(record.version);
+    status
+}
+/// Whether a process with `pid` exists.
+fn process_alive(pid: u32) -> bool {
+    use sysinfo::{Pid, PidExt, System, SystemExt};
+    System::new().refresh_process(Pid::from_u32(pid))
+}
+fn print_report(report: &StatusReport) {
+    println!("{}", "🩺 TRAE Status".cyan().bold());
+    println!();
+    for component in &report.components {
+        let mark = match (component.up, component.required) {
+            (true, _) => "✓".green(),
+            (false, true) => "✗".red(),
+            (false, false) => "⚠️".yellow(),
+        };
+        let mut line = format!("{mark} {:<7}", component.name);
+        if let Some(url) = &component.url {
+            line.push_str(&format!(" {url}"));
+        }
+        line.push_str(&format!(" · {}", component.detail));
+        if let Some(ms) = component.latency_ms {
+            line.push_str(&format!(" · {ms}ms"));
+        }
+        if let Some(version) = &component.version {
+            line.push_str(&format!(" · {version}"));
+        }
+        if !component.required {
+            line.push_str(&" (opcional)".bright_black().to_string());
+        }
+        println!("{line}");
+    }
+    println!();
+    if report.ok {
+        println!("{}", "✅ Todos los componentes requeridos están activos".green().bold());
+    } else {
+        println!("{}", "⚠️ Hay componentes requeridos caídos".yellow().bold());
+    }
+}
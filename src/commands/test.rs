@@ -1,19 +1,27 @@
 #![doc = " # Test Command - Enhanced testing with coverage and analysis"]
 #![doc = ""]
 #![doc = " Comando de testing mejorado con análisis de cobertura, benchmarking y reportes avanzados"]
-use crate::{cli::TraeCli, jarvix::client::JarvixClient, metrics::collector::MetricsCollector};
+use crate::core::cargo::cargo_command;
+use crate::{
+    cli::TraeCli,
+    commands::build::{profile_args, resolve_profile},
+    jarvix::client::JarvixClient,
+    metrics::collector::MetricsCollector,
+};
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
 use std::time::Instant;
-use std::{collections::HashMap, process::Command};
+use std::collections::HashMap;
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct TestCommand {
-    #[doc = " Run tests in release mode"]
+    #[doc = " Run tests in release mode (same as `--profile release`)"]
     #[arg(long)]
     pub release: bool,
+    #[doc = " Cargo profile to test with: dev, release or a `[profile.NAME]` of Cargo.toml"]
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
     #[doc = " Generate coverage report"]
     #[arg(long)]
     pub coverage: bool,
@@ -53,17 +61,11 @@ impl TestCommand {
     pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         let start_time = Instant::now();
         let mut metrics = MetricsCollector::new("test".to_string());
+        let profile = self.cargo_profile()?;
+        metrics.add_custom_metric("profile".to_string(), profile.as_deref().unwrap_or("dev"));
         println!("{}", "🧪 TRAE TEST - Testing Suite Avanzada".cyan().bold());
         println!("{}", "===================================\n".cyan());
-        let style = match ProgressStyle::default_spinner().template("{spinner:.green} {msg}") {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("⚠️  progress template failed: {:?}", e);
-                ProgressStyle::default_spinner()
-            }
-        };
-        let pb = ProgressBar::new_spinner();
-        pb.set_style(style);
+        let pb = crate::utils::progress::spinner(&cli.options);
         pb.set_message("Ejecutando tests básicos...");
         let test_result = self.run_basic_tests(cli)?;
         pb.finish_with_message("Tests básicos completados");
@@ -95,8 +97,8 @@ impl TestCommand {
             &mut metrics,
         )?;
         pb.finish_with_message("Reporte generado");
-        if !cli.no_jarvix {
-            if let Ok(Some(client)) = JarvixClient::new() {
+        if cli.jarvix_enabled() {
+            if let Ok(Some(client)) = JarvixClient::new(&cli.options).await {
                 if let Err(e) = client.report_test_metrics(metrics).await {
                     eprintln!("⚠️ No se pudo reportar métricas de test: {e}");
                 }
@@ -104,13 +106,15 @@ impl TestCommand {
         }
         Ok(())
     }
+    /// Profile from `--release`/`--profile`, checked against Cargo.toml.
+    fn cargo_profile(&self) -> Result<Option<String>> {
+        resolve_profile(std::path::Path::new("."), self.release, self.profile.as_deref())
+    }
     #[doc = "Method documentation added by AI refactor"]
-    fn run_basic_tests(&self, _cli: &TraeCli) -> Result<TestResults> {
-        let mut cmd = Command::new("cargo");
+    fn run_basic_tests(&self, cli: &TraeCli) -> Result<TestResults> {
+        let mut cmd = cargo_command(&cli.options);
         cmd.arg("test");
-        if self.release {
-            cmd.arg("--release");
-        }
+        cmd.args(profile_args(self.cargo_profile()?.as_deref()));
         if let Some(test) = &self.test {
             cmd.arg(test);
         }
@@ -141,22 +145,20 @@ impl TestCommand {
         })
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn run_coverage_analysis(&self, _cli: &TraeCli) -> Result<CoverageData> {
-        let tarpaulin_check = Command::new("cargo")
+    fn run_coverage_analysis(&self, cli: &TraeCli) -> Result<CoverageData> {
+        let tarpaulin_check = cargo_command(&cli.options)
             .arg("tarpaulin")
             .arg("--version")
             .output();
         if tarpaulin_check.is_err() {
             println!("{}", "⚠️ Tarpaulin no instalado. Instalando...".yellow());
-            Command::new("cargo")
+            cargo_command(&cli.options)
                 .args(["install", "cargo-tarpaulin"])
                 .status()?;
         }
-        let mut cmd = Command::new("cargo");
+        let mut cmd = cargo_command(&cli.options);
         cmd.args(["tarpaulin", "--out", "Json"]);
-        if self.release {
-            cmd.arg("--release");
-        }
+        cmd.args(profile_args(self.cargo_profile()?.as_deref()));
         if self.html_coverage {
             cmd.args(["--out", "Html"]);
         }
@@ -178,12 +180,10 @@ impl TestCommand {
         })
     }
     #[doc = "Method documentation added by AI refactor"]
-    fn run_benchmarks(&self, _cli: &TraeCli) -> Result<BenchmarkResults> {
-        let mut cmd = Command::new("cargo");
+    fn run_benchmarks(&self, cli: &TraeCli) -> Result<BenchmarkResults> {
+        let mut cmd = cargo_command(&cli.options);
         cmd.args(["bench"]);
-        if self.release {
-            cmd.arg("--release");
-        }
+        cmd.args(profile_args(self.cargo_profile()?.as_deref()));
         let output = cmd.output()?;
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let mut benchmarks = Vec::new();
@@ -349,6 +349,7 @@ impl TestCommand {
     ) -> Result<()> {
         let cmd = TestCommand {
             release,
+            profile: None,
             coverage,
             bench,
             test,
@@ -361,10 +362,27 @@ impl TestCommand {
             unit: false,
             cargo_args: vec![],
         };
+        let options = crate::config::RunOptions::from_env();
         let cli = crate::cli::TraeCli {
             verbose,
             config: None,
             no_jarvix,
+            offline: options.offline,
+            target_dir: None,
+            jobs: None,
+            include_hidden: false,
+            max_file_size: None,
+            jarvix: None,
+            project: None,
+            ci: options.ci,
+            with_jarvix: false,
+            quiet: false,
+            report_url: None,
+            report_header: Vec::new(),
+            output_dir: None,
+            severity: None,
+            dry_run: false,
+            options,
             command: crate::cli::Commands::Test(cmd),
         };
         // Call the command directly to avoid recursion through TraeCli::execute
@@ -1,13 +1,17 @@
 #![doc = " # Watch Command"]
 #![doc = ""]
 #![doc = " Observa cambios en el filesystem y re-ejecuta comandos con un resumen moderno."]
+use crate::cli::TraeCli;
 use crate::core::cargo::CargoExecutor;
 use anyhow::{anyhow, Context, Result};
 use clap::Args;
 use colored::Colorize;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::{RecursiveMode, Watcher};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time;
 #[derive(Args, Debug)]
 #[doc = "Struct documentation added by AI refactor"]
@@ -31,10 +35,14 @@ pub struct WatchCommand {
     #[doc = " Saltar ejecución inicial (por defecto corre una vez al comenzar)"]
     #[arg(long)]
     pub skip_initial: bool,
+    #[doc = " Detectar cambios por polling de mtime cada MS ms (por defecto 1000) en vez de"]
+    #[doc = " eventos nativos: para montajes de red, bind mounts de Docker o rutas de WSL"]
+    #[arg(long, value_name = "MS", num_args = 0..=1, default_missing_value = "1000")]
+    pub poll: Option<u64>,
 }
 impl WatchCommand {
     #[doc = "Method documentation added by AI refactor"]
-    pub async fn execute(&self) -> Result<()> {
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
         let mut watch_paths = if self.paths.is_empty() {
             vec![PathBuf::from("src"), PathBuf::from("Cargo.toml")]
         } else {
@@ -54,32 +62,28 @@ impl WatchCommand {
             )
             .cyan()
         );
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let mut watcher = new_watcher(tx).context("No se pudo crear watcher")?;
-        for path in &watch_paths {
-            watcher
-                .watch(path, RecursiveMode::Recursive)
-                .with_context(|| format!("No se pudo observar {}", path.display()))?;
-        }
+        let mut changes = ChangeWatcher::new(
+            &watch_paths,
+            Duration::from_millis(self.debounce_ms),
+            self.poll.map(Duration::from_millis),
+        )?;
+        println!("{}", format!("🔔 Modo de watch: {}", changes.mode()).dimmed());
         let mut run_counter = 0usize;
         if !self.skip_initial {
             run_counter += 1;
-            let report = self.run_once(run_counter).await?;
+            let report = self.run_once(cli, run_counter).await?;
             self.print_summary(&report);
         }
         loop {
-            rx.recv().await;
-            let debounce = Duration::from_millis(self.debounce_ms);
-            time::sleep(debounce).await;
-            while rx.try_recv().is_ok() {}
+            changes.changed().await;
             run_counter += 1;
-            let report = self.run_once(run_counter).await?;
+            let report = self.run_once(cli, run_counter).await?;
             self.print_summary(&report);
             println!("{}", "⌛ Esperando cambios...".dimmed());
         }
     }
     #[doc = "Method documentation added by AI refactor"]
-    async fn run_once(&self, run_no: usize) -> Result<RunReport> {
+    async fn run_once(&self, cli: &TraeCli, run_no: usize) -> Result<RunReport> {
         let start = Instant::now();
         let command_display = if self.args.is_empty() {
             self.command.clone()
@@ -100,7 +104,7 @@ impl WatchCommand {
                 parts.remove(0);
             }
             parts.extend(self.args.clone());
-            CargoExecutor::new()
+            CargoExecutor::new(&cli.options)
                 .execute_streaming(&parts)
                 .await
                 .context("Fallo comando cargo")
@@ -112,7 +116,7 @@ impl WatchCommand {
         } else {
             let mut parts = vec![self.command.clone()];
             parts.extend(self.args.clone());
-            CargoExecutor::new()
+            CargoExecutor::new(&cli.options)
                 .execute_streaming(&parts)
                 .await
                 .context("Fallo comando cargo")
@@ -187,11 +191,181 @@ fn truncate(input: &str, len: usize) -> String {
             + "…"
     }
 }
-#[doc = "Function documentation added by AI refactor"]
-fn new_watcher(tx: tokio::sync::mpsc::UnboundedSender<()>) -> notify::Result<RecommendedWatcher> {
-    notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
-        if res.is_ok() {
-            let _ = tx.send(());
+/// Directories trae never reacts to, whether or not `.gitignore` lists them
+/// (scans write their cache under `.trae/`).
+const ALWAYS_IGNORED: [&str; 3] = ["target", ".git", ".trae"];
+/// Polling interval used when native events can't be set up.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How a [`ChangeWatcher`] notices changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Native filesystem events (inotify, FSEvents, ReadDirectoryChangesW).
+    Native,
+    /// Comparing mtimes of the watched tree every interval. Works where
+    /// native events never arrive: network filesystems, Docker bind mounts,
+    /// WSL paths.
+    Poll(Duration),
+}
+impl fmt::Display for WatchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native => f.write_str("eventos nativos"),
+            Self::Poll(interval) => write!(f, "polling cada {}ms", interval.as_millis()),
         }
-    })
+    }
+}
+/// Debounced filesystem events shared by `trae watch` and `trae scan --watch`;
+/// paths matched by the root `.gitignore` are dropped.
+pub struct ChangeWatcher {
+    watcher: Box<dyn Watcher + Send>,
+    mode: WatchMode,
+    rx: UnboundedReceiver<Vec<PathBuf>>,
+    debounce: Duration,
+    ignored: GlobSet,
+    root: PathBuf,
+}
+impl ChangeWatcher {
+    /// Watches each of `paths` recursively, polling every `poll` if given.
+    pub fn new(paths: &[PathBuf], debounce: Duration, poll: Option<Duration>) -> Result<Self> {
+        Self::open(debounce, poll, |changes| {
+            for path in paths {
+                changes.watch(path, RecursiveMode::Recursive)?;
+            }
+            Ok(())
+        })
+    }
+    /// Watches the current directory: its own files, plus every top-level
+    /// directory that isn't ignored (so `target/` is never registered, nor
+    /// polled).
+    pub fn project(debounce: Duration, poll: Option<Duration>) -> Result<Self> {
+        Self::open(debounce, poll, |changes| {
+            changes.watch(Path::new("."), RecursiveMode::NonRecursive)?;
+            for entry in std::fs::read_dir(".")?.flatten() {
+                let path = PathBuf::from(entry.file_name());
+                if entry.path().is_dir() && !changes.ignored.is_match(&path) {
+                    changes.watch(&path, RecursiveMode::Recursive)?;
+                }
+            }
+            Ok(())
+        })
+    }
+    /// How changes are being detected.
+    pub fn mode(&self) -> WatchMode {
+        self.mode
+    }
+    /// Native events unless `poll` is given; when they fail to initialize
+    /// (no inotify support, watch limit reached) falls back to polling.
+    fn open(
+        debounce: Duration,
+        poll: Option<Duration>,
+        register: impl Fn(&mut Self) -> Result<()>,
+    ) -> Result<Self> {
+        if let Some(interval) = poll {
+            let mut changes = Self::unwatched(debounce, WatchMode::Poll(interval))?;
+            register(&mut changes)?;
+            return Ok(changes);
+        }
+        let native = Self::unwatched(debounce, WatchMode::Native).and_then(|mut changes| {
+            register(&mut changes)?;
+            Ok(changes)
+        });
+        match native {
+            Ok(changes) => Ok(changes),
+            Err(e) => {
+                let warning = format!("⚠️ Eventos nativos no disponibles ({e:#}); usando polling");
+                eprintln!("{}", warning.yellow());
+                let mode = WatchMode::Poll(DEFAULT_POLL_INTERVAL);
+                let mut changes = Self::unwatched(debounce, mode)?;
+                register(&mut changes)?;
+                Ok(changes)
+            }
+        }
+    }
+    fn unwatched(debounce: Duration, mode: WatchMode) -> Result<Self> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let handler = move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event.paths);
+            }
+        };
+        let watcher: Box<dyn Watcher + Send> = match mode {
+            WatchMode::Native => Box::new(
+                notify::recommended_watcher(handler).context("No se pudo crear watcher")?,
+            ),
+            WatchMode::Poll(interval) => Box::new(
+                notify::PollWatcher::new(
+                    handler,
+                    notify::Config::default().with_poll_interval(interval),
+                )
+                .context("No se pudo crear watcher de polling")?,
+            ),
+        };
+        Ok(Self {
+            watcher,
+            mode,
+            rx,
+            debounce,
+            ignored: gitignore_matcher(Path::new(".gitignore"))?,
+            root: std::env::current_dir()?,
+        })
+    }
+    fn watch(&mut self, path: &Path, mode: RecursiveMode) -> Result<()> {
+        self.watcher
+            .watch(path, mode)
+            .with_context(|| format!("No se pudo observar {}", path.display()))
+    }
+    /// Waits for the next burst of changes and returns the paths it touched,
+    /// relative to the working directory, sorted and deduplicated. Bursts
+    /// made only of ignored paths are skipped.
+    pub async fn changed(&mut self) -> Vec<PathBuf> {
+        loop {
+            let Some(first) = self.rx.recv().await else {
+                return Vec::new();
+            };
+            time::sleep(self.debounce).await;
+            let mut paths = first;
+            while let Ok(more) = self.rx.try_recv() {
+                paths.extend(more);
+            }
+            let mut paths: Vec<PathBuf> = paths
+                .into_iter()
+                .map(|p| p.strip_prefix(&self.root).map(Path::to_path_buf).unwrap_or(p))
+                .map(|p| p.strip_prefix(".").map(Path::to_path_buf).unwrap_or(p))
+                .filter(|p| !self.ignored.is_match(p))
+                .collect();
+            paths.sort();
+            paths.dedup();
+            if !paths.is_empty() {
+                return paths;
+            }
+        }
+    }
+}
+/// Matcher for the patterns in a `.gitignore` (a missing file ignores only
+/// [`ALWAYS_IGNORED`]). Negations (`!pattern`) are not supported and skipped;
+/// a matched directory also matches everything below it.
+pub fn gitignore_matcher(gitignore: &Path) -> Result<GlobSet> {
+    let content = std::fs::read_to_string(gitignore).unwrap_or_default();
+    let patterns = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .chain(ALWAYS_IGNORED);
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim_end_matches('/');
+        // Patterns without an inner slash match at any depth, as in git
+        let pattern = match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if pattern.contains('/') => pattern.to_string(),
+            None => format!("**/{pattern}"),
+        };
+        for glob in [pattern.clone(), format!("{pattern}/**")] {
+            builder.add(
+                Glob::new(&glob)
+                    .with_context(|| format!("Patrón inválido en .gitignore: {pattern}"))?,
+            );
+        }
+    }
+    Ok(builder.build()?)
 }
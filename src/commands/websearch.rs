@@ -0,0 +1,101 @@
+#![doc = " # Web Search Command - Search through JARVIXSERVER"]
+#![doc = ""]
+#![doc = " Búsqueda web, en docs.rs o en crates.io vía el puente BrowserMCP de JARVIXSERVER"]
+use crate::cli::TraeCli;
+use crate::jarvix::client::JarvixClient;
+use crate::utils::progress::spinner;
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use colored::Colorize;
+use console::Emoji;
+use serde_json::json;
+#[derive(Args, Debug)]
+#[doc = " Opciones de búsqueda web"]
+pub struct WebSearchCommand {
+    #[doc = " Search query"]
+    pub query: String,
+    #[doc = " Maximum number of results"]
+    #[arg(short = 'n', long, default_value = "5")]
+    pub limit: usize,
+    #[doc = " Include source code snippets in the results"]
+    #[arg(long)]
+    pub include_code: bool,
+    #[doc = " Search the Rust documentation (docs.rs, doc.rust-lang.org)"]
+    #[arg(long, conflicts_with = "crates")]
+    pub rust_docs: bool,
+    #[doc = " Search crates.io"]
+    #[arg(long)]
+    pub crates: bool,
+}
+impl WebSearchCommand {
+    #[doc = " Envía la búsqueda a JARVIXSERVER e imprime los resultados"]
+    pub async fn execute(&self, cli: &TraeCli) -> Result<()> {
+        if cli.options.offline {
+            bail!("Búsqueda web deshabilitada en modo offline");
+        }
+        let client = JarvixClient::new(&cli.options).await?.context("JARVIXSERVER no disponible")?;
+        println!(
+            "{} {} Buscando '{}' en internet...",
+            "→".blue().bold(),
+            Emoji("🌐", ""),
+            self.query.cyan().bold()
+        );
+        let spinner = spinner(&cli.options);
+        spinner.set_message("Consultando JARVIXSERVER...");
+        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+        let response = client.search_web(&self.request()).await;
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                spinner.finish_with_message("✗ Error en la búsqueda".red().to_string());
+                return Err(e.into());
+            }
+        };
+        spinner.finish_with_message("✓ Búsqueda completada".green().to_string());
+        let Some(results) = response.get("search_results").and_then(|r| r.as_array()) else {
+            println!("{} No se encontraron resultados", "⚠".yellow());
+            return Ok(());
+        };
+        println!();
+        println!("{}", "┌─ RESULTADOS DE BÚSQUEDA ─────────────────────┐".cyan().bold());
+        for (i, result) in results.iter().take(self.limit).enumerate() {
+            let field = |key: &str| result.get(key).and_then(|v| v.as_str()).unwrap_or("");
+            let title = Some(field("title")).filter(|t| !t.is_empty()).unwrap_or("Sin título");
+            println!(
+                "  {}. {} {}",
+                (i + 1).to_string().bright_yellow().bold(),
+                title.cyan().bold(),
+                format!("({})", field("url")).bright_black()
+            );
+            if !field("snippet").is_empty() {
+                println!("     {}", field("snippet").bright_white());
+            }
+            if self.include_code && !field("code").is_empty() {
+                println!("     {} {}", "💻".green(), field("code").bright_green());
+            }
+            println!();
+        }
+        println!("{}", "└─────────────────────────────────────────────┘".cyan().bold());
+        println!("{} {} resultados encontrados", "ℹ".blue(), results.len());
+        Ok(())
+    }
+    /// JSON body for `POST /search/web`.
+    pub fn request(&self) -> serde_json::Value {
+        let (query, source) = if self.rust_docs {
+            (
+                format!("rust {} site:docs.rs OR site:doc.rust-lang.org", self.query),
+                "rust_docs",
+            )
+        } else if self.crates {
+            (format!("{} site:crates.io", self.query), "crates")
+        } else {
+            (self.query.clone(), "web")
+        };
+        json!({
+            "query": query,
+            "limit": self.limit,
+            "include_code": self.include_code,
+            "source": source,
+        })
+    }
+}
@@ -1,37 +1,349 @@
 #![doc = " # Configuration Module"]
 #![doc = ""]
 #![doc = " Gestión de configuración de TRAE CLI"]
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+/// Env var standing in for `--offline`, for the CLI and the library API.
+pub const OFFLINE_ENV: &str = "TRAE_OFFLINE";
+/// Env var standing in for `--ci`, for the CLI and the library API.
+pub const CI_ENV: &str = "TRAE_CI";
+/// Env var standing in for `--include-hidden`, for the CLI and the library API.
+pub const INCLUDE_HIDDEN_ENV: &str = "TRAE_INCLUDE_HIDDEN";
+/// Settings of one trae run that the global flags choose (`--offline`...).
+/// [`TraeCli`](crate::cli::TraeCli) resolves them before running a command,
+/// which hands them on to the cargo runs, scanners and JARVIXSERVER client
+/// it uses. The default is a run without flags.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// No network access: cargo runs offline, and trae skips JARVIXSERVER and
+    /// downloads.
+    pub offline: bool,
+    /// CI mode: cargo prints no colors and spinners and progress bars are
+    /// hidden. See README "CI Mode".
+    pub ci: bool,
+    /// `--quiet`: no spinners, progress bars or code snippets.
+    pub quiet: bool,
+    /// Crawls re-read every file and leave `.trae/crawl-cache.json` alone
+    /// (`deadcode --no-cache`, or a feature selection the cache can't hold).
+    pub no_crawl_cache: bool,
+    /// `--include-hidden`: scanners and crawlers also walk hidden entries
+    /// (`.git`, `.cargo`, `.vscode`, ...), which they skip by default.
+    pub include_hidden: bool,
+    /// `--max-file-size`: scanners skip files larger than this many bytes.
+    /// Without it the limit comes from the config (see [`Self::max_file_size`]).
+    pub max_file_size: Option<u64>,
+    /// Features scanners treat as enabled (`--features`, `--all-features`,
+    /// `--no-default-features`); code gated on others is masked. `None`
+    /// reads every `cfg(feature)` branch.
+    pub features: Option<crate::core::features::ActiveFeatures>,
+    /// `--target-dir`, already created and probed for writes (see
+    /// [`writable_target_dir`]); [`Self::target_dir`] falls back to cargo's
+    /// own default without it.
+    pub target_dir: Option<PathBuf>,
+    /// `--jobs`: parallel jobs of every cargo child.
+    pub jobs: Option<std::num::NonZeroUsize>,
+    /// `--output-dir`, already created and absolute (see
+    /// [`writable_output_dir`]). Children (the projects of `scan --projects`)
+    /// don't inherit it.
+    pub output_dir: Option<PathBuf>,
+    /// `--severity`: analysis commands hide findings below it and fail on any
+    /// finding left (see [`SeverityFilter`](crate::core::severity::SeverityFilter)).
+    pub severity: Option<crate::core::analyzer::IssueSeverity>,
+    /// `--report-url`: metric reports go there instead of JARVIXSERVER.
+    pub report_url: Option<String>,
+    /// `--report-header` lines (`key:value`) sent with metric reports; when
+    /// empty the config's `report_headers` are used.
+    pub report_headers: Vec<String>,
+    /// `--jarvix`: JARVIXSERVER URL, ahead of `JARVIX_ENDPOINT` and discovery.
+    pub jarvix_endpoint: Option<String>,
+    /// Files the run's scanners skipped for going over [`Self::max_file_size`].
+    pub oversized: crate::utils::path::Oversized,
+}
+impl RunOptions {
+    /// The options of a library API call, which has no flags: the defaults,
+    /// with `TRAE_OFFLINE=1`, `TRAE_CI=1` and `TRAE_INCLUDE_HIDDEN=1` standing
+    /// in for `--offline`, `--ci` and `--include-hidden`.
+    pub fn from_env() -> Self {
+        Self {
+            offline: env_flag(OFFLINE_ENV),
+            ci: env_flag(CI_ENV),
+            include_hidden: env_flag(INCLUDE_HIDDEN_ENV),
+            ..Self::default()
+        }
+    }
+    /// Largest file scanners read: `--max-file-size` if given, else
+    /// `analysis.max_file_size` of the config in effect.
+    pub fn max_file_size(&self) -> u64 {
+        self.max_file_size.unwrap_or_else(|| TraeConfig::load().analysis.max_file_size)
+    }
+    /// Directory cargo builds into: `--target-dir`, else `CARGO_TARGET_DIR`
+    /// if the user exported it, else `target` in the current directory.
+    pub fn target_dir(&self) -> PathBuf {
+        self.target_dir.clone().unwrap_or_else(|| {
+            std::env::var_os("CARGO_TARGET_DIR")
+                .filter(|dir| !dir.is_empty())
+                .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        })
+    }
+    /// Where a command writes its report: `explicit` (its own `--export` or
+    /// `--output`) when given, else `name` in `--output-dir`, else nowhere.
+    pub fn report_path(&self, explicit: Option<&str>, name: &str) -> Option<String> {
+        explicit.map(str::to_string).or_else(|| {
+            let dir = self.output_dir.as_ref()?;
+            Some(dir.join(name).to_string_lossy().into_owned())
+        })
+    }
+    /// Whether spinners and progress bars stay hidden: in CI and quiet mode.
+    pub fn hides_progress(&self) -> bool {
+        self.ci || self.quiet
+    }
+    /// Environment of every cargo child under these options:
+    /// `CARGO_NET_OFFLINE=true` when offline, `CARGO_TERM_COLOR=never` in CI,
+    /// `CARGO_TARGET_DIR` with `--target-dir` and `CARGO_BUILD_JOBS` with
+    /// `--jobs`.
+    pub fn cargo_env(&self) -> Vec<(&'static str, std::ffi::OsString)> {
+        let mut env = Vec::new();
+        if self.offline {
+            env.push(("CARGO_NET_OFFLINE", "true".into()));
+        }
+        if self.ci {
+            env.push(("CARGO_TERM_COLOR", "never".into()));
+        }
+        if let Some(dir) = &self.target_dir {
+            env.push(("CARGO_TARGET_DIR", dir.into()));
+        }
+        if let Some(jobs) = self.jobs {
+            env.push(("CARGO_BUILD_JOBS", jobs.to_string().into()));
+        }
+        env
+    }
+    /// Global flags that give a `trae` child process (`scan --projects`,
+    /// `dashboard`) these options.
+    pub fn child_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.offline {
+            args.push("--offline".to_string());
+        }
+        if self.ci {
+            args.push("--ci".to_string());
+        }
+        if self.quiet {
+            args.push("--quiet".to_string());
+        }
+        if self.include_hidden {
+            args.push("--include-hidden".to_string());
+        }
+        if let Some(dir) = &self.target_dir {
+            args.extend(["--target-dir".to_string(), dir.display().to_string()]);
+        }
+        if let Some(jobs) = self.jobs {
+            args.extend(["--jobs".to_string(), jobs.to_string()]);
+        }
+        if let Some(url) = &self.jarvix_endpoint {
+            args.extend(["--jarvix".to_string(), url.clone()]);
+        }
+        if let Some(url) = &self.report_url {
+            args.extend(["--report-url".to_string(), url.clone()]);
+        }
+        for header in &self.report_headers {
+            args.extend(["--report-header".to_string(), header.clone()]);
+        }
+        if let Some(bytes) = self.max_file_size {
+            args.extend(["--max-file-size".to_string(), bytes.to_string()]);
+        }
+        args
+    }
+}
+/// Whether the env var `name` is `1` or `true`.
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+/// `dir` made absolute for [`RunOptions::target_dir`], once it is created
+/// and probed for writes. On failure the reason is returned.
+pub fn writable_target_dir(dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let dir = dir.canonicalize()?;
+    let probe = dir.join(format!(".trae-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(dir)
+}
+/// `dir` made absolute for [`RunOptions::output_dir`], created if missing,
+/// so later directory changes don't move it.
+pub fn writable_output_dir(dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("no se pudo crear --output-dir {}", dir.display()))?;
+    Ok(dir.canonicalize()?)
+}
+/// `path` as an absolute path, to say where a report was written.
+pub fn full_path(path: &str) -> String {
+    std::path::absolute(path).map_or_else(|_| path.to_string(), |p| p.display().to_string())
+}
+/// Gives the rayon pool the scanners run on `jobs` threads; cargo children
+/// get theirs from [`RunOptions::jobs`]. Without it the pool keeps its default.
+pub fn use_jobs(jobs: std::num::NonZeroUsize) {
+    // Only fails once the global pool exists; `TraeCli::execute` calls this first
+    let _ = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.get())
+        .build_global();
+}
+/// Exports the `KEY=value` lines of a `.env` file (blank lines and `#`
+/// comments skipped, surrounding quotes stripped) so cargo and the binaries
+/// it runs inherit them. Variables already set in the environment win.
+/// Returns how many were exported; a missing file is not an error.
+pub fn load_dotenv(path: &Path) -> Result<usize> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("leyendo {}", path.display()))?;
+    let mut loaded = 0;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || std::env::var_os(key).is_some() {
+            continue;
+        }
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        std::env::set_var(key, value);
+        loaded += 1;
+    }
+    Ok(loaded)
+}
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct TraeConfig {
     pub jarvix: JarvixConfig,
     pub analysis: AnalysisConfig,
     pub repair: RepairConfig,
+    #[doc = " Coefficients of the quality score (defaults when the section is absent)"]
+    #[serde(default)]
+    pub score: crate::core::score::ScoreWeights,
+    #[doc = " Extra `trae scan` markers or new severities: `HACK = \"warning\"`, `TODO = \"off\"`"]
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub markers: std::collections::BTreeMap<String, String>,
+    #[doc = " Components `trae status` checks and which of them must be up"]
+    #[serde(default)]
+    pub status: StatusConfig,
+    #[doc = " User-Agent and extra headers of every outbound request"]
+    #[serde(default)]
+    pub http: HttpConfig,
 }
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct JarvixConfig {
     pub enabled: bool,
     pub server_url: String,
     pub api_key: Option<String>,
     pub timeout: u64,
+    #[doc = " Offload `cargo build`/`test` to JARVIXSERVER (opt-in, same as `--remote`)"]
+    #[serde(default)]
+    pub offload: bool,
+    #[doc = " Seconds to wait for an offloaded job before building locally"]
+    #[serde(default = "default_offload_timeout")]
+    pub offload_timeout: u64,
+    #[doc = " Seconds between polls of an offloaded job"]
+    #[serde(default = "default_offload_poll_interval")]
+    pub offload_poll_interval: u64,
+    #[doc = " Longest wait, in seconds, the poll interval of an offloaded build backs off to"]
+    #[serde(default = "default_offload_max_poll_interval")]
+    pub offload_max_poll_interval: u64,
+    #[doc = " Send metric reports here instead of JARVIXSERVER (same as `--report-url`)"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_url: Option<String>,
+    #[doc = " `key:value` headers for `report_url` requests (same as `--report-header`)"]
+    #[serde(default)]
+    pub report_headers: Vec<String>,
+    #[doc = " JARVIXSERVER jobs `scan` keeps running at once; further ones wait their turn"]
+    #[serde(default = "default_max_jobs_in_flight")]
+    pub max_jobs_in_flight: usize,
+    #[doc = " PEM bundle with the CA(s) of an HTTPS JARVIXSERVER, trusted besides the system ones"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    #[doc = " Skip TLS verification of JARVIXSERVER. Development only: never in production"]
+    #[serde(default)]
+    pub insecure: bool,
+}
+const fn default_offload_timeout() -> u64 {
+    120
+}
+const fn default_offload_poll_interval() -> u64 {
+    2
+}
+const fn default_offload_max_poll_interval() -> u64 {
+    30
+}
+const fn default_max_jobs_in_flight() -> usize {
+    2
 }
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct AnalysisConfig {
     pub auto_analysis: bool,
     pub performance_analysis: bool,
     pub security_analysis: bool,
+    #[doc = " Warn (\"run cargo clean\") once the target directory exceeds this many MB"]
+    #[serde(default = "default_target_warn_mb")]
+    pub target_warn_mb: u64,
+    #[doc = " Scanners skip files larger than this many bytes (same as `--max-file-size`)"]
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+}
+const fn default_target_warn_mb() -> u64 {
+    2_000
+}
+const fn default_max_file_size() -> u64 {
+    2_000_000
 }
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct RepairConfig {
     pub auto_repair: bool,
     pub backup_before_repair: bool,
     pub clippy_auto_fix: bool,
 }
+/// The `[status]` section: where the local trae server listens and which
+/// components (`server`, `daemon`, `jarvix`) `trae status` requires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusConfig {
+    #[doc = " URL of the trae HTTP server, probed at `/health`"]
+    pub server_url: String,
+    #[doc = " Components whose outage makes `trae status` fail"]
+    pub required: Vec<String>,
+}
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            server_url: "http://localhost:3001".to_string(),
+            required: STATUS_COMPONENTS.iter().map(|c| (*c).to_string()).collect(),
+        }
+    }
+}
+/// The `[http]` section, applied by [`crate::utils::http`] to every request
+/// trae sends: JARVIXSERVER, metric reports, crates.io and downloads.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    #[doc = " `User-Agent` instead of `trae-cli/<version>`"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[doc = " `key:value` headers added to every request"]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<String>,
+}
+/// Components `trae status` knows about.
+pub const STATUS_COMPONENTS: [&str; 3] = ["server", "daemon", "jarvix"];
 impl Default for TraeConfig {
     #[doc = "Method documentation added by AI refactor"]
     fn default() -> Self {
@@ -41,19 +353,319 @@ impl Default for TraeConfig {
                 server_url: "http://localhost:8080".to_string(),
                 api_key: None,
                 timeout: 30,
+                offload: false,
+                offload_timeout: default_offload_timeout(),
+                offload_poll_interval: default_offload_poll_interval(),
+                offload_max_poll_interval: default_offload_max_poll_interval(),
+                report_url: None,
+                report_headers: Vec::new(),
+                max_jobs_in_flight: default_max_jobs_in_flight(),
+                ca_cert: None,
+                insecure: false,
             },
             analysis: AnalysisConfig {
                 auto_analysis: true,
                 performance_analysis: false,
                 security_analysis: false,
+                target_warn_mb: default_target_warn_mb(),
+                max_file_size: default_max_file_size(),
             },
             repair: RepairConfig {
                 auto_repair: false,
                 backup_before_repair: true,
                 clippy_auto_fix: true,
             },
+            score: crate::core::score::ScoreWeights::default(),
+            markers: std::collections::BTreeMap::new(),
+            status: StatusConfig::default(),
+            http: HttpConfig::default(),
+        }
+    }
+}
+/// Valid keys per config section, used to explain parse errors.
+const CONFIG_SECTIONS: [(&str, &[&str]); 6] = [
+    (
+        "jarvix",
+        &[
+            "enabled",
+            "server_url",
+            "api_key",
+            "timeout",
+            "offload",
+            "offload_timeout",
+            "offload_poll_interval",
+            "offload_max_poll_interval",
+            "report_url",
+            "report_headers",
+            "max_jobs_in_flight",
+            "ca_cert",
+            "insecure",
+        ],
+    ),
+    (
+        "analysis",
+        &[
+            "auto_analysis",
+            "performance_analysis",
+            "security_analysis",
+            "target_warn_mb",
+            "max_file_size",
+        ],
+    ),
+    (
+        "repair",
+        &["auto_repair", "backup_before_repair", "clippy_auto_fix"],
+    ),
+    (
+        "score",
+        &[
+            "issues_per_1k",
+            "dpmo_divisor",
+            "dpmo_cap",
+            "complexity_divisor",
+            "complexity_cap",
+            "duplication",
+            "size_bonus",
+        ],
+    ),
+    ("status", &["server_url", "required"]),
+    ("http", &["user_agent", "headers"]),
+];
+impl TraeConfig {
+    /// Path written by `trae init`.
+    pub fn path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("trae").join("config.toml"))
+    }
+    /// Config file in effect: `explicit` if given, then the project's
+    /// `.trae/config.toml`, then the user config written by `trae init`.
+    pub fn locate(explicit: Option<&str>) -> Option<std::path::PathBuf> {
+        if let Some(path) = explicit {
+            return Some(std::path::PathBuf::from(path));
+        }
+        let project = std::path::Path::new(".trae").join("config.toml");
+        if project.exists() {
+            return Some(project);
+        }
+        Self::path()
+    }
+    /// Parses a config, turning serde errors into messages that name the
+    /// offending line and section and list the keys that section accepts.
+    /// Score coefficients that break the 0-100 scale, unknown marker
+    /// severities, unknown `[status]` components and malformed `[http]`
+    /// headers are rejected too.
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content).map_err(|e| describe_config_error(content, &e))?;
+        config
+            .score
+            .validate()
+            .map_err(|e| anyhow::anyhow!("configuración inválida: {e}"))?;
+        crate::core::markers::TodoMarkers::from_config(&config.markers)
+            .map_err(|e| anyhow::anyhow!("configuración inválida: {e}"))?;
+        if let Some(unknown) =
+            config.status.required.iter().find(|c| !STATUS_COMPONENTS.contains(&c.as_str()))
+        {
+            anyhow::bail!(
+                "configuración inválida: [status] required: `{unknown}` no es un componente \
+                 ({})",
+                STATUS_COMPONENTS.join(", ")
+            );
+        }
+        crate::utils::http::default_headers(&config.http)
+            .map_err(|e| anyhow::anyhow!("configuración inválida: {e}"))?;
+        Ok(config)
+    }
+    /// Loads the config in effect, falling back to defaults when missing.
+    /// An invalid file is reported instead of being silently ignored.
+    pub fn load() -> Self {
+        let Some(path) = Self::locate(None) else {
+            return Self::default();
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        Self::parse(&content).unwrap_or_else(|e| {
+            eprintln!("⚠️  Ignorando {}: {e}", path.display());
+            Self::default()
+        })
+    }
+}
+/// Explains a parse error of `.trae/config.toml`: the offending line and the
+/// keys valid in its section.
+fn describe_config_error(content: &str, err: &toml::de::Error) -> anyhow::Error {
+    let mut msg = err.to_string();
+    if let Some((mut line, _)) = err.line_col() {
+        // toml names the failing table (`for key `jarvix.timeout``), whose
+        // header is more reliable than the reported position for unknown keys
+        let named = msg
+            .split_once("for key `")
+            .and_then(|(_, rest)| rest.split(['`', '.']).next())
+            .map(str::to_string);
+        let header = |name: &str| {
+            content.lines().position(|l| {
+                let l = l.trim();
+                l.strip_prefix('[').and_then(|l| l.strip_suffix(']')).map(str::trim) == Some(name)
+            })
+        };
+        // toml reports unknown fields at the table header; point at the key itself.
+        if let Some(field) = msg
+            .split_once("unknown field `")
+            .and_then(|(_, rest)| rest.split_once('`'))
+            .map(|(field, _)| field)
+        {
+            let start = named.as_deref().and_then(header).unwrap_or(line);
+            if let Some(offset) = content.lines().skip(start).position(|l| {
+                l.trim_start()
+                    .strip_prefix(field)
+                    .is_some_and(|rest| rest.trim_start().starts_with('='))
+            }) {
+                line = start + offset;
+            }
+        }
+        if let Some(text) = content.lines().nth(line) {
+            msg.push_str(&format!("\n  --> línea {}: {}", line + 1, text.trim()));
+        }
+        // Else the section is the closest `[header]` at or above the failing line.
+        let section = named.or_else(|| {
+            content
+                .lines()
+                .take(line + 1)
+                .filter_map(|l| {
+                    let l = l.trim();
+                    l.strip_prefix('[')?.strip_suffix(']').map(str::trim)
+                })
+                .last()
+                .map(str::to_string)
+        });
+        if let Some((name, keys)) = section
+            .and_then(|s| CONFIG_SECTIONS.iter().find(|(name, _)| *name == s))
+        {
+            msg.push_str(&format!(
+                "\n  Claves válidas en [{name}]: {}",
+                keys.join(", ")
+            ));
+        }
+    }
+    anyhow::anyhow!("configuración inválida: {msg}")
+}
+/// Validates the config in effect (or `explicit`) without writing anything.
+pub fn check_trae_config(explicit: Option<&str>) -> Result<()> {
+    let path = TraeConfig::locate(explicit)
+        .ok_or_else(|| anyhow::anyhow!("No se pudo encontrar el directorio de configuración"))?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("No se pudo leer {}: {e}", path.display()))?;
+    TraeConfig::parse(&content)
+        .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+    println!("✅ Configuración válida: {}", path.display());
+    Ok(())
+}
+/// Starter setups of `trae init --template`.
+pub const INIT_TEMPLATES: [&str; 4] = ["ci", "security", "minimal", "full"];
+const BANS_SAMPLE: &str = "\
+# Crates prohibidos para `trae security --bans`: un [[ban]] por crate.
+# `version` es un requisito semver (cualquier versión si se omite).
+#
+# [[ban]]
+# name = \"openssl\"
+# version = \"<0.10.55\"
+# reason = \"Usa rustls\"
+";
+const SECRETS_SAMPLE: &str = "\
+# Patrones de `trae security --secrets`: `disable` quita patrones integrados
+# (password, secret, token, api_key, private_key, openai_key) y cada
+# [[pattern]] añade uno (severity: info, low, medium, high o critical).
+#
+# disable = [\"token\"]
+#
+# [[pattern]]
+# name = \"Token interno\"
+# regex = \"INTERNAL_[A-Z0-9]{32}\"
+# severity = \"high\"
+";
+const MULTILANG_SAMPLE: &str = "\
+# Reglas extra de `trae scan --multilang`, por tipo de archivo (la extensión
+# salvo que [files] diga otra cosa), tras las integradas.
+# severity: critical, warning o info (por defecto).
+
+# [files]
+# \"*.rs.in\" = \"rs\"
+# \"Containerfile.*\" = \"dockerfile\"
+
+# [overrides]
+# select-star = \"off\"
+# latest-tag = \"critical\"
+
+[[py]]
+pattern = '\\bpdb\\.set_trace\\(\\)'
+message = \"pdb.set_trace() en archivo Python\"
+severity = \"warning\"
+";
+/// Files `trae init --template name` writes, relative to the project root:
+/// `.trae/config.toml` tuned for the use case, plus sample rule files for
+/// the security and full templates. `None` for an unknown template.
+pub fn template_files(name: &str) -> Option<Vec<(&'static str, String)>> {
+    let mut config = TraeConfig::default();
+    let samples: &[(&str, &str)] = match name {
+        // No JARVIXSERVER on CI runners, and nothing may rewrite the checkout
+        "ci" => {
+            config.jarvix.enabled = false;
+            config.analysis.security_analysis = true;
+            config.repair.backup_before_repair = false;
+            config.repair.clippy_auto_fix = false;
+            &[]
+        }
+        "security" => {
+            config.analysis.security_analysis = true;
+            &[(".trae/bans.toml", BANS_SAMPLE), (".trae/secrets.toml", SECRETS_SAMPLE)]
+        }
+        "minimal" => {
+            config.jarvix.enabled = false;
+            config.analysis.auto_analysis = false;
+            config.repair.clippy_auto_fix = false;
+            &[]
+        }
+        "full" => {
+            config.analysis.performance_analysis = true;
+            config.analysis.security_analysis = true;
+            &[
+                (".trae/bans.toml", BANS_SAMPLE),
+                (".trae/secrets.toml", SECRETS_SAMPLE),
+                (crate::core::multilang::RULES_FILE, MULTILANG_SAMPLE),
+            ]
+        }
+        _ => return None,
+    };
+    let header = format!("# Generado por `trae init --template {name}`\n\n");
+    let config = header + &toml::to_string_pretty(&config).expect("config serializes");
+    let mut files = vec![(".trae/config.toml", config)];
+    files.extend(samples.iter().map(|(path, content)| (*path, content.to_string())));
+    Some(files)
+}
+/// Writes the files of `template` under `root` and returns their paths.
+/// Unless `force`, nothing is written when any of them exists, and the
+/// error lists which.
+pub fn write_template(root: &Path, template: &str, force: bool) -> Result<Vec<PathBuf>> {
+    let files = template_files(template).ok_or_else(|| {
+        anyhow::anyhow!("Plantilla desconocida '{template}' ({})", INIT_TEMPLATES.join(", "))
+    })?;
+    let existing: Vec<&str> =
+        files.iter().map(|(path, _)| *path).filter(|path| root.join(path).exists()).collect();
+    if !existing.is_empty() && !force {
+        anyhow::bail!(
+            "Ya existen {}; usa --force para sobrescribirlos",
+            existing.join(", ")
+        );
+    }
+    let mut written = Vec::new();
+    for (path, content) in files {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        std::fs::write(&path, content)
+            .with_context(|| format!("No se pudo escribir {}", path.display()))?;
+        written.push(path);
     }
+    Ok(written)
 }
 #[doc = "Function documentation added by AI refactor"]
 pub async fn init_trae_config(force: bool) -> Result<()> {
@@ -1,16 +1,19 @@
 #![doc = " # Project Analyzer - Advanced project analysis"]
 #![doc = ""]
 #![doc = " Analizador avanzado de proyectos Rust"]
+use crate::config::RunOptions;
 use crate::measure_performance;
 use crate::performance_patterns::{
     chunked_parallel_process, parallel_process, IntelligentCache, MetricsCollector,
     PerformanceConfig,
 };
+use crate::utils::markdown::{Finding, Report};
+use crate::utils::path::portable;
 use anyhow::Result;
 use std::collections::HashMap;
 use std::path::Path;
 #[doc = " Análisis de Fourier simplificado para detección de patrones en código"]
-fn analyze_code_fourier(data: &[f64]) -> f64 {
+pub(crate) fn analyze_code_fourier(data: &[f64]) -> f64 {
     if data.len() < 2 {
         return 0.0;
     }
@@ -70,6 +73,7 @@ impl ProjectAnalyzer {
     #[doc = ""]
     #[doc = " # Arguments"]
     #[doc = " * `project_path` - Path to the Rust project root"]
+    #[doc = " * `options` - Run options; they pick the files walked"]
     #[doc = ""]
     #[doc = " # Returns"]
     #[doc = " * `Result<ProjectAnalysis>` - Complete Six Sigma analysis report"]
@@ -79,8 +83,11 @@ impl ProjectAnalyzer {
     #[doc = " - Sigma Level (process capability)"]
     #[doc = " - Cp/Cpk indices (process capability ratios)"]
     #[doc = " - Control chart parameters (UCL, LCL, centerline)"]
-    pub fn analyze_project<P: AsRef<Path>>(&mut self, project_path: P) -> Result<ProjectAnalysis> {
-        use walkdir::WalkDir;
+    pub fn analyze_project<P: AsRef<Path>>(
+        &mut self,
+        project_path: P,
+        options: &RunOptions,
+    ) -> Result<ProjectAnalysis> {
         let path = project_path.as_ref();
         let path_key = format!("{path:?}");
         if let Some(cached_result) = self.cache.get(&path_key) {
@@ -95,18 +102,22 @@ impl ProjectAnalyzer {
                 files_count: 0,
                 suggestions: Vec::new(),
             };
-            let rust_files: Vec<_> = WalkDir::new(path)
-                .into_iter()
-                .filter_map(std::result::Result::ok)
+            let limit = options.max_file_size();
+            let rust_files: Vec<_> = crate::utils::path::walk(path, None, options)
                 .filter(|entry| {
                     entry.path().is_file()
                         && entry.path().extension().is_some_and(|ext| ext == "rs")
+                        && crate::utils::path::within_size_limit(
+                            entry.path(),
+                            limit,
+                            &options.oversized,
+                        )
                 })
                 .collect();
             analysis.files_count = rust_files.len();
             let file_results = parallel_process(
                 rust_files,
-                |entry| analyze_single_file(entry.path()),
+                |entry| analyze_single_file(entry.path(), options),
                 &self.perf_config,
             );
             let line_distribution: Vec<f64> = file_results.iter().map(|r| r.lines as f64).collect();
@@ -116,6 +127,7 @@ impl ProjectAnalyzer {
                 analysis.issues.extend(result.issues);
                 analysis.suggestions.extend(result.suggestions);
             }
+            sort_issues(&mut analysis.issues);
             analysis
                 .metrics
                 .insert("rust_files".to_string(), analysis.files_count as f64);
@@ -176,13 +188,13 @@ impl Default for ProjectAnalyzer {
     }
 }
 #[doc = " Análisis de un archivo individual (función auxiliar para paralelización)"]
-fn analyze_single_file(path: &Path) -> FileAnalysisResult {
+fn analyze_single_file(path: &Path, options: &RunOptions) -> FileAnalysisResult {
     let mut result = FileAnalysisResult {
         lines: 0,
         issues: Vec::new(),
         suggestions: Vec::new(),
     };
-    if let Ok(content) = std::fs::read_to_string(path) {
+    if let Ok(content) = crate::core::features::read_source(path, options) {
         result.lines = content.lines().count();
 
         // Enhanced security and quality analysis
@@ -196,8 +208,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Security".to_string(),
                     description: "Uso de código unsafe detectado".to_string(),
                     severity: IssueSeverity::Critical,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.6,
                 });
             }
 
@@ -206,8 +219,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Reliability".to_string(),
                     description: "Uso de unwrap() - puede causar pánico".to_string(),
                     severity: IssueSeverity::Warning,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.8,
                 });
             }
 
@@ -216,8 +230,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Reliability".to_string(),
                     description: "Uso de expect() - puede causar pánico".to_string(),
                     severity: IssueSeverity::Warning,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.6,
                 });
             }
 
@@ -226,8 +241,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Reliability".to_string(),
                     description: "Uso de panic! macro".to_string(),
                     severity: IssueSeverity::Info,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.7,
                 });
             }
 
@@ -237,8 +253,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Performance".to_string(),
                     description: "Clone potencialmente innecesario de colección".to_string(),
                     severity: IssueSeverity::Info,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.4,
                 });
             }
 
@@ -248,8 +265,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Code Quality".to_string(),
                     description: format!("TODO encontrado en {:?}", path.file_name()),
                     severity: IssueSeverity::Info,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.9,
                 });
             }
 
@@ -258,8 +276,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     category: "Code Quality".to_string(),
                     description: format!("FIXME encontrado en {:?}", path.file_name()),
                     severity: IssueSeverity::Info,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
+                    confidence: 0.9,
                 });
             }
 
@@ -269,7 +288,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                     description: "API deprecated encontrada - considerar actualización".to_string(),
                     impact: OptimizationImpact::Medium,
                     effort: OptimizationEffort::Medium,
-                    file: Some(path.to_string_lossy().to_string()),
+                    file: Some(portable(path)),
                     line: Some(line_num),
                 });
             }
@@ -281,7 +300,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 description: "Uso de #[allow] - revisar si es necesario".to_string(),
                 impact: OptimizationImpact::Low,
                 effort: OptimizationEffort::Low,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
             });
         }
@@ -294,8 +313,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 category: "Complexity".to_string(),
                 description: format!("Archivo con {} funciones - considerar dividir", function_count),
                 severity: IssueSeverity::Warning,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
+                confidence: 0.7,
             });
         }
 
@@ -304,8 +324,9 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 category: "Security".to_string(),
                 description: format!("Archivo con {} bloques unsafe - alto riesgo", unsafe_count),
                 severity: IssueSeverity::Critical,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
+                confidence: 0.5,
             });
         }
 
@@ -318,7 +339,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 ),
                 impact: OptimizationImpact::High,
                 effort: OptimizationEffort::High,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
             });
         } else if result.lines > 500 {
@@ -326,7 +347,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 description: format!("Considerar dividir archivo ({} líneas)", result.lines),
                 impact: OptimizationImpact::Medium,
                 effort: OptimizationEffort::Medium,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
             });
         } else if result.lines > 200 {
@@ -334,7 +355,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 description: format!("Revisar organización ({} líneas)", result.lines),
                 impact: OptimizationImpact::Low,
                 effort: OptimizationEffort::Low,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
             });
         }
@@ -344,7 +365,7 @@ fn analyze_single_file(path: &Path) -> FileAnalysisResult {
                 description: "Revisar código marcado como dead_code".to_string(),
                 impact: OptimizationImpact::Low,
                 effort: OptimizationEffort::Low,
-                file: Some(path.to_string_lossy().to_string()),
+                file: Some(portable(path)),
                 line: None,
             });
         }
@@ -381,14 +402,15 @@ impl ProjectAnalyzer {
                             size / 1_000_000
                         ),
                         severity: IssueSeverity::Critical,
-                        file: Some(artifact.clone()),
+                        file: Some(portable(Path::new(artifact))),
                         line: None,
+                        confidence: FULL_CONFIDENCE,
                     });
                     analysis.suggestions.push(OptimizationSuggestion {
                         description: format!("Considerar optimizar el tamaño de {artifact}"),
                         impact: OptimizationImpact::High,
                         effort: OptimizationEffort::Medium,
-                        file: Some(artifact.clone()),
+                        file: Some(portable(Path::new(artifact))),
                         line: None,
                     });
                 }
@@ -444,7 +466,7 @@ impl ProjectAnalysis {
         println!("  • Optimizaciones: {}", self.optimizations.len());
     }
 }
-#[derive(Clone, Debug, serde :: Serialize)]
+#[derive(Clone, Debug, serde :: Serialize, serde :: Deserialize)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct AnalysisIssue {
     pub category: String,
@@ -452,19 +474,164 @@ pub struct AnalysisIssue {
     pub severity: IssueSeverity,
     pub file: Option<String>,
     pub line: Option<usize>,
+    /// How likely the issue is real, from 0 to 1, as rated by its detector.
+    #[serde(default = "full_confidence")]
+    pub confidence: f64,
+}
+/// Confidence of findings no detector rated (remote jobs, baselines saved
+/// before findings had one), so `--min-confidence` never drops them.
+pub const FULL_CONFIDENCE: f64 = 1.0;
+const fn full_confidence() -> f64 {
+    FULL_CONFIDENCE
+}
+/// `--min-confidence` value: a number from 0 to 1.
+pub fn parse_confidence(s: &str) -> std::result::Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("'{s}' no es un número"))?;
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("la confianza va de 0 a 1, no {value}"))
+    }
 }
 impl AnalysisIssue {
     #[doc = "Method documentation added by AI refactor"]
     pub const fn is_critical(&self) -> bool {
         matches!(self.severity, IssueSeverity::Critical)
     }
+    /// The issue as an LSP-style diagnostic (`code` is the category).
+    pub fn to_diagnostic(&self) -> crate::utils::diagnostics::Diagnostic {
+        crate::utils::diagnostics::Diagnostic::new(
+            self.file.as_deref(),
+            self.line,
+            self.severity.lsp_severity(),
+            &self.category,
+            &self.description,
+        )
+        .with_confidence(self.confidence)
+    }
+    /// Row of a `--format markdown` report.
+    pub fn to_markdown(&self) -> Finding {
+        Finding {
+            category: self.category.clone(),
+            severity: format!("{:?}", self.severity),
+            location: Finding::location(self.file.as_deref(), self.line),
+            message: self.description.clone(),
+        }
+    }
 }
-#[derive(Clone, Debug, serde :: Serialize)]
+#[derive(Clone, Debug, serde :: Serialize, serde :: Deserialize)]
 pub enum IssueSeverity {
     Critical,
     Warning,
     Info,
 }
+impl IssueSeverity {
+    /// Higher is more severe: critical 2, warning 1, info 0.
+    pub const fn rank(&self) -> u8 {
+        match self {
+            Self::Critical => 2,
+            Self::Warning => 1,
+            Self::Info => 0,
+        }
+    }
+    /// LSP `DiagnosticSeverity`: critical is an error, warning a warning,
+    /// info an information.
+    pub const fn lsp_severity(&self) -> u8 {
+        use crate::utils::diagnostics::{ERROR, INFORMATION, WARNING};
+        match self {
+            Self::Critical => ERROR,
+            Self::Warning => WARNING,
+            Self::Info => INFORMATION,
+        }
+    }
+    /// `critical`, `warning` or `info`, as `--severity` takes it.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+    /// The severity called `name` (see [`Self::name`]), in any case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Critical, Self::Warning, Self::Info]
+            .into_iter()
+            .find(|severity| severity.name().eq_ignore_ascii_case(name))
+    }
+}
+/// Orders issues by severity (critical first), confidence (most likely
+/// first), file, line and description, so output, exports and baseline
+/// diffs don't depend on the walk order.
+pub fn sort_issues(issues: &mut [AnalysisIssue]) {
+    issues.sort_by(compare_issues);
+}
+/// The order of [`sort_issues`].
+pub fn compare_issues(a: &AnalysisIssue, b: &AnalysisIssue) -> std::cmp::Ordering {
+    b.severity
+        .rank()
+        .cmp(&a.severity.rank())
+        .then_with(|| b.confidence.total_cmp(&a.confidence))
+        .then_with(|| a.file.cmp(&b.file))
+        .then_with(|| a.line.cmp(&b.line))
+        .then_with(|| a.description.cmp(&b.description))
+        .then_with(|| a.category.cmp(&b.category))
+}
+/// Headings of `scan --group-by` (`severity`, `category` or `file`) with
+/// the indices of their issues, which keep their order. Severities go most
+/// severe first; categories and files the largest first, so hotspots lead,
+/// then by name.
+pub fn group_issues(issues: &[AnalysisIssue], by: &str) -> Vec<(String, Vec<usize>)> {
+    let mut groups: std::collections::BTreeMap<String, Vec<usize>> = Default::default();
+    for (i, issue) in issues.iter().enumerate() {
+        let key = match by {
+            "severity" => format!("{:?}", issue.severity),
+            "category" => issue.category.clone(),
+            _ => issue.file.as_deref().map_or_else(
+                || "(sin archivo)".to_string(),
+                |file| file.strip_prefix("./").unwrap_or(file).to_string(),
+            ),
+        };
+        groups.entry(key).or_default().push(i);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, members)| {
+        std::cmp::Reverse(match by {
+            "severity" => usize::from(issues[members[0]].severity.rank()),
+            _ => members.len(),
+        })
+    });
+    groups
+}
+/// `--format markdown` report of `issues` and `suggestions`: counts by
+/// severity, then the issues by category, suggestions in their own section.
+pub fn markdown_report(
+    title: &str,
+    issues: &[AnalysisIssue],
+    suggestions: &[OptimizationSuggestion],
+) -> Report {
+    let count = |rank: u8| issues.iter().filter(|i| i.severity.rank() == rank).count();
+    let mut report = Report::new(title);
+    report
+        .summary("🔴 Critical", count(2))
+        .summary("🟡 Warning", count(1))
+        .summary("🔵 Info", count(0))
+        .summary("💡 Suggestions", suggestions.len());
+    for issue in issues {
+        report.finding(issue.to_markdown());
+    }
+    for suggestion in suggestions {
+        report.finding(Finding {
+            category: "Suggestions".to_string(),
+            severity: "Info".to_string(),
+            location: Finding::location(suggestion.file.as_deref(), suggestion.line),
+            message: format!(
+                "{} (impact {:?}, effort {:?})",
+                suggestion.description, suggestion.impact, suggestion.effort
+            ),
+        });
+    }
+    report
+}
 #[derive(Clone, Debug, serde :: Serialize)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct OptimizationSuggestion {
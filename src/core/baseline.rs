@@ -0,0 +1,149 @@
+#![doc = " # Baseline - Issue diff against a previous report"]
+#![doc = ""]
+#![doc = " Compara los issues actuales con los de un reporte exportado (`trae scan --export`, `trae analyze --output`)"]
+use crate::commands::repair::git_error;
+use crate::core::analyzer::{sort_issues, AnalysisIssue};
+use crate::utils::path::portable;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+/// The part of an exported report the diff needs; other keys are ignored.
+#[derive(Deserialize)]
+struct ExportedReport {
+    issues: Vec<AnalysisIssue>,
+}
+/// Issues of a previous `trae scan --export` or `trae analyze --output` file.
+pub fn load_issues(path: &Path) -> Result<Vec<AnalysisIssue>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("No se pudo leer el baseline {}", path.display()))?;
+    let report: ExportedReport = serde_json::from_str(&content)
+        .with_context(|| format!("Baseline inválido: {}", path.display()))?;
+    Ok(report.issues)
+}
+/// Stable identity of an issue across unrelated edits: category, file and a
+/// hash of the description with every number masked. That hash is the
+/// issue's context (scan descriptions quote the offending line), while line
+/// numbers, in the description or the `line` field, are left out.
+pub fn fingerprint(issue: &AnalysisIssue) -> String {
+    // Separators too, so a baseline exported on Windows matches on Linux
+    let file = issue.file.as_deref().unwrap_or_default().replace('\\', "/");
+    let file = file.strip_prefix("./").unwrap_or(&file);
+    let context: String = issue
+        .description
+        .split(|c: char| c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("#");
+    let digest = Sha256::digest(context.as_bytes());
+    format!("{}:{}:{}", issue.category, file, &hex::encode(digest)[..16])
+}
+/// Current issues classified against a baseline.
+#[derive(Debug, Default)]
+pub struct BaselineDiff {
+    /// In the current analysis only.
+    pub new: Vec<AnalysisIssue>,
+    /// In the baseline only.
+    pub fixed: Vec<AnalysisIssue>,
+    pub unchanged: usize,
+}
+impl BaselineDiff {
+    /// Pairs issues by [`fingerprint`]. Repeated fingerprints are counted, so
+    /// a third identical `unwrap()` in a file is new even if two were there.
+    pub fn new(baseline: Vec<AnalysisIssue>, current: Vec<AnalysisIssue>) -> Self {
+        let mut remaining: HashMap<String, Vec<AnalysisIssue>> = HashMap::new();
+        for issue in baseline {
+            remaining.entry(fingerprint(&issue)).or_default().push(issue);
+        }
+        let mut diff = Self::default();
+        for issue in current {
+            match remaining.get_mut(&fingerprint(&issue)).and_then(Vec::pop) {
+                Some(_) => diff.unchanged += 1,
+                None => diff.new.push(issue),
+            }
+        }
+        diff.fixed = remaining.into_values().flatten().collect();
+        sort_issues(&mut diff.new);
+        sort_issues(&mut diff.fixed);
+        diff
+    }
+    /// New issues with critical severity: what makes `trae analyze --baseline` fail.
+    pub fn new_critical(&self) -> usize {
+        self.new.iter().filter(|issue| issue.is_critical()).count()
+    }
+}
+/// A detached `git worktree` of another ref in a temporary directory, for
+/// `trae analyze --compare`. The user's working tree is never touched, and
+/// the worktree is removed on drop, so also when the analysis fails.
+#[derive(Debug)]
+pub struct BaseWorktree {
+    /// Top level of the repository the worktree belongs to.
+    repo: PathBuf,
+    path: PathBuf,
+    /// The directory being analyzed, relative to the top level.
+    prefix: PathBuf,
+}
+impl BaseWorktree {
+    /// Checks `base` (a branch, tag or commit) out in a temporary worktree
+    /// of the repository containing `dir`.
+    pub fn add(dir: &Path, base: &str) -> Result<Self> {
+        let repo = git(dir, ["rev-parse", "--show-toplevel"])
+            .context("--compare solo funciona dentro de un repositorio git")?;
+        let repo = PathBuf::from(repo);
+        let prefix = PathBuf::from(git(dir, ["rev-parse", "--show-prefix"])?);
+        let commit = format!("{base}^{{commit}}");
+        if git(&repo, ["rev-parse", "--verify", "--quiet", commit.as_str()]).is_err() {
+            anyhow::bail!("`{base}` no es una rama, tag o commit de este repositorio");
+        }
+        let path = std::env::temp_dir().join(format!("trae-compare-{}", uuid::Uuid::new_v4()));
+        let add = [OsStr::new("worktree"), "add".as_ref(), "--detach".as_ref(), "--quiet".as_ref()];
+        git(&repo, add.into_iter().chain([path.as_os_str(), base.as_ref()]))
+            .with_context(|| format!("no se pudo crear el worktree de `{base}`"))?;
+        Ok(Self { repo, path, prefix })
+    }
+    /// Where the worktree was checked out.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// The analyzed directory inside the worktree: the same subdirectory of
+    /// the repository `dir` was in.
+    pub fn project_dir(&self) -> PathBuf {
+        self.path.join(&self.prefix)
+    }
+}
+impl Drop for BaseWorktree {
+    fn drop(&mut self) {
+        let remove = [OsStr::new("worktree"), "remove".as_ref(), "--force".as_ref()];
+        if git(&self.repo, remove.into_iter().chain([self.path.as_os_str()])).is_err() {
+            // git failed or is gone: drop the checkout and forget its metadata
+            let _ = std::fs::remove_dir_all(&self.path);
+            let _ = git(&self.repo, ["worktree", "prune"]);
+        }
+    }
+}
+/// `issues` found under `root`, with their files relative to it as a
+/// `./`-prefixed path, like an analysis of the current directory reports them.
+pub fn rebase_issues(mut issues: Vec<AnalysisIssue>, root: &Path) -> Vec<AnalysisIssue> {
+    for issue in &mut issues {
+        let relative = issue.file.as_deref().and_then(|f| Path::new(f).strip_prefix(root).ok());
+        if let Some(relative) = relative {
+            issue.file = Some(format!("./{}", portable(relative)));
+        }
+    }
+    issues
+}
+/// Trimmed stdout of `git args` run in `dir`; the error carries git's message.
+fn git<I, S>(dir: &Path, args: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    if !output.status.success() {
+        anyhow::bail!("git: {}", git_error(&output.stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,118 @@
+#![doc = " # Build Errors - Grouped compiler errors of a failed build"]
+#![doc = ""]
+#![doc = " Agrupa los errores de `cargo build --message-format=json` para resumirlos al final del build"]
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use cargo_metadata::Message;
+use std::fmt;
+/// Distinct errors listed before the "... y N más" note.
+pub const SUMMARY_LIMIT: usize = 10;
+/// One distinct compiler error and how many times it was reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    /// rustc code (`E0425`); lints and some errors have none.
+    pub code: Option<String>,
+    pub message: String,
+    /// `file:line` of the first report's primary span.
+    pub location: Option<String>,
+    pub count: usize,
+}
+/// `cargo build` failure carrying its compiler errors. Displays as the
+/// human (non-JSON) output of cargo, so callers matching on the text keep
+/// working.
+#[derive(Debug)]
+pub struct BuildFailed {
+    pub errors: Vec<BuildError>,
+    pub output: String,
+}
+impl fmt::Display for BuildFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.output)
+    }
+}
+impl std::error::Error for BuildFailed {}
+/// Errors in the JSON output of cargo, grouped by code and message in the
+/// order they were first reported.
+pub fn parse(json: &str) -> Vec<BuildError> {
+    let mut errors: Vec<BuildError> = Vec::new();
+    for message in Message::parse_stream(json.as_bytes()).flatten() {
+        let Message::CompilerMessage(msg) = message else {
+            continue;
+        };
+        let Some(error) = build_error(&msg.message) else {
+            continue;
+        };
+        match errors
+            .iter_mut()
+            .find(|e| e.code == error.code && e.message == error.message)
+        {
+            Some(seen) => seen.count += 1,
+            None => errors.push(error),
+        }
+    }
+    errors
+}
+/// Errors with a source location; "aborting due to N previous errors" has none.
+fn build_error(diagnostic: &Diagnostic) -> Option<BuildError> {
+    if !matches!(diagnostic.level, DiagnosticLevel::Error | DiagnosticLevel::Ice) {
+        return None;
+    }
+    let span = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or_else(|| diagnostic.spans.first())?;
+    Some(BuildError {
+        code: diagnostic.code.as_ref().map(|code| code.code.clone()),
+        message: diagnostic.message.clone(),
+        location: Some(format!("{}:{}", span.file_name, span.line_start)),
+        count: 1,
+    })
+}
+/// What to show for one stdout line of a JSON-format cargo run: the
+/// rendered diagnostic, nothing for other JSON messages (artifacts, build
+/// scripts), or the line itself when it is not JSON (a binary's output).
+pub fn human_output(line: &str) -> Option<String> {
+    match serde_json::from_str::<Message>(line) {
+        Ok(Message::CompilerMessage(msg)) => msg.message.rendered,
+        Ok(_) => None,
+        Err(_) => Some(format!("{line}\n")),
+    }
+}
+/// Summary lines: one per distinct error up to `limit`, then how many more.
+pub fn summary_lines(errors: &[BuildError], limit: usize) -> Vec<String> {
+    let mut lines: Vec<String> = errors
+        .iter()
+        .take(limit)
+        .map(|error| {
+            let code = error.code.as_deref().map(|c| format!("[{c}] ")).unwrap_or_default();
+            let location = error.location.as_deref().map(|l| format!(" ({l})")).unwrap_or_default();
+            let count = if error.count > 1 { format!(" ×{}", error.count) } else { String::new() };
+            format!("{code}{}{location}{count}", error.message)
+        })
+        .collect();
+    if errors.len() > limit {
+        lines.push(format!("... y {} más", errors.len() - limit));
+    }
+    lines
+}
+/// `input` as a rustc error code (`E0308`): case-insensitive, and the `E`
+/// and leading zeros may be omitted (`e308`, `308`). `None` if it isn't one.
+pub fn rustc_code(input: &str) -> Option<String> {
+    let input = input.trim();
+    let digits = input.strip_prefix(['E', 'e']).unwrap_or(input);
+    if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("E{digits:0>4}"))
+}
+/// Distinct rustc error codes of `errors`, in order, for `trae explain`
+/// hints. Lint names are codes too but have no `rustc --explain` entry.
+pub fn explainable_codes(errors: &[BuildError]) -> Vec<&str> {
+    let mut codes: Vec<&str> = Vec::new();
+    for code in errors.iter().filter_map(|error| error.code.as_deref()) {
+        if rustc_code(code).as_deref() == Some(code) && !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    codes
+}
@@ -1,39 +1,143 @@
 #![doc = " # Cargo Executor - Enhanced cargo command execution"]
 #![doc = ""]
 #![doc = " Executor mejorado para comandos cargo con métricas y análisis"]
+use crate::config::RunOptions;
+use crate::utils::ansi::strip_ansi;
 use anyhow::Result;
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::OnceLock;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
 use tokio::task;
+/// cargo found on PATH, in `$CARGO_HOME/bin` or in `~/.cargo/bin`, looked up
+/// once per process.
+pub fn resolve_cargo() -> Option<&'static Path> {
+    static CARGO: OnceLock<Option<PathBuf>> = OnceLock::new();
+    CARGO.get_or_init(|| resolve_executable("cargo")).as_deref()
+}
+/// The cargo every spawn uses, or [`TraeError::CargoNotFound`] (with the
+/// install URL) when there is none. Commands that need cargo call it before
+/// doing any work.
+///
+/// [`TraeError::CargoNotFound`]: crate::error::TraeError::CargoNotFound
+pub fn ensure_cargo() -> Result<&'static Path> {
+    resolve_cargo().ok_or_else(|| crate::error::TraeError::CargoNotFound.into())
+}
+/// Program to spawn for cargo: the resolved cargo, or plain `cargo` for the
+/// OS to look up when none was found.
+pub fn cargo_program() -> &'static OsStr {
+    resolve_cargo().map_or(OsStr::new("cargo"), Path::as_os_str)
+}
+/// `name` on PATH, then in `$CARGO_HOME/bin` and `~/.cargo/bin` (with or
+/// without `.exe`).
+pub fn resolve_executable(name: &str) -> Option<PathBuf> {
+    if let Ok(path) = which::which(name) {
+        return Some(path);
+    }
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(cargo_home) = std::env::var_os("CARGO_HOME") {
+        candidates.push(PathBuf::from(cargo_home).join("bin").join(name));
+    }
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".cargo").join("bin").join(name));
+    }
+    candidates
+        .into_iter()
+        .flat_map(|path| [path.clone(), path.with_extension("exe")])
+        .find(|path| path.exists())
+}
 #[derive(Debug, Clone, Copy)]
 pub enum CargoStream {
     Stdout,
     Stderr,
 }
+/// How a cargo run ended and everything it printed, stdout and stderr lines
+/// interleaved in the order they arrived. It serializes (for a report
+/// payload or a JSON log) with `output` already [`plain`](Self::plain).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CargoOutput {
+    /// Exit code; `None` when cargo was killed by a signal.
+    pub code: Option<i32>,
+    #[serde(serialize_with = "serialize_plain")]
+    pub output: String,
+}
+impl CargoOutput {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+    /// `output` without ANSI color codes, e.g. to save it to a file.
+    pub fn plain(&self) -> String {
+        strip_ansi(&self.output)
+    }
+    /// The output, or an error carrying the exit code and the output when
+    /// cargo failed.
+    pub fn into_result(self) -> Result<String> {
+        if self.success() {
+            Ok(self.output)
+        } else {
+            Err(anyhow::anyhow!(
+                "Cargo command failed with exit code: {:?}\n{}",
+                self.code,
+                self.output
+            ))
+        }
+    }
+}
+fn serialize_plain<S: serde::Serializer>(
+    output: &str,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&strip_ansi(output))
+}
+/// `cargo` as trae spawns it: the resolved cargo (see [`cargo_program`])
+/// with the environment `options` give it (see [`RunOptions::cargo_env`]).
+pub fn cargo_command(options: &RunOptions) -> std::process::Command {
+    let mut command = std::process::Command::new(cargo_program());
+    command.envs(options.cargo_env());
+    command
+}
+/// `cargo metadata` run through [`cargo_command`]'s cargo and environment.
+pub fn metadata_command(options: &RunOptions) -> cargo_metadata::MetadataCommand {
+    let mut command = cargo_metadata::MetadataCommand::new();
+    command.cargo_path(cargo_program());
+    for (key, value) in options.cargo_env() {
+        command.env(key, value);
+    }
+    command
+}
 #[doc = "Struct documentation added by AI refactor"]
 pub struct CargoExecutor {
     working_dir: Option<std::path::PathBuf>,
+    env: Vec<(&'static str, std::ffi::OsString)>,
 }
 impl CargoExecutor {
-    #[doc = "Method documentation added by AI refactor"]
-    pub const fn new() -> Self {
-        Self { working_dir: None }
+    /// Runs cargo with the environment `options` give it.
+    pub fn new(options: &RunOptions) -> Self {
+        Self { working_dir: None, env: options.cargo_env() }
     }
     #[doc = "Method documentation added by AI refactor"]
     pub fn with_working_dir<P: Into<std::path::PathBuf>>(mut self, dir: P) -> Self {
         self.working_dir = Some(dir.into());
         self
     }
+    /// The cargo command every run starts from.
+    fn command(&self) -> TokioCommand {
+        let mut cmd = TokioCommand::new(cargo_program());
+        if let Some(dir) = &self.working_dir {
+            cmd.current_dir(dir);
+        }
+        cmd.envs(self.env.clone());
+        cmd
+    }
     #[doc = "Method documentation added by AI refactor"]
     pub async fn execute_with_output(
         &self,
         args: &[impl AsRef<std::ffi::OsStr>],
     ) -> Result<String> {
-        let mut cmd = TokioCommand::new("cargo");
-        if let Some(dir) = &self.working_dir {
-            cmd.current_dir(dir);
-        }
+        let mut cmd = self.command();
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -55,10 +159,7 @@ impl CargoExecutor {
     }
     #[doc = " Ejecuta cargo mostrando stdout/stderr en vivo (streaming)."]
     pub async fn execute_streaming(&self, args: &[impl AsRef<std::ffi::OsStr>]) -> Result<()> {
-        let mut cmd = TokioCommand::new("cargo");
-        if let Some(dir) = &self.working_dir {
-            cmd.current_dir(dir);
-        }
+        let mut cmd = self.command();
         cmd.args(args);
         cmd.stdout(Stdio::inherit());
         cmd.stderr(Stdio::inherit());
@@ -77,10 +178,7 @@ impl CargoExecutor {
         &self,
         args: &[impl AsRef<std::ffi::OsStr>],
     ) -> Result<String> {
-        let mut cmd = TokioCommand::new("cargo");
-        if let Some(dir) = &self.working_dir {
-            cmd.current_dir(dir);
-        }
+        let mut cmd = self.command();
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -132,15 +230,25 @@ impl CargoExecutor {
     pub async fn execute_streaming_capture_with_handler<F>(
         &self,
         args: &[impl AsRef<std::ffi::OsStr>],
-        mut on_line: F,
+        on_line: F,
     ) -> Result<String>
     where
         F: FnMut(CargoStream, &str) + Send,
     {
-        let mut cmd = TokioCommand::new("cargo");
-        if let Some(dir) = &self.working_dir {
-            cmd.current_dir(dir);
-        }
+        self.execute_captured(args, on_line).await?.into_result()
+    }
+    /// Runs cargo with its output piped, handing each line to `on_line` as it
+    /// arrives. A failing cargo is not an error here: the exit code comes
+    /// back with the output. Errors are only for cargo not starting.
+    pub async fn execute_captured<F>(
+        &self,
+        args: &[impl AsRef<std::ffi::OsStr>],
+        mut on_line: F,
+    ) -> Result<CargoOutput>
+    where
+        F: FnMut(CargoStream, &str) + Send,
+    {
+        let mut cmd = self.command();
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -156,21 +264,14 @@ impl CargoExecutor {
             tokio::select! { out = async { if let Some (lines) = & mut out_lines { lines . next_line () . await } else { Ok (None) } } , if ! stdout_done => { match out ? { Some (line) => { on_line (CargoStream :: Stdout , & line) ; combined . push_str (& line) ; combined . push ('\n') ; } None => stdout_done = true , } } err = async { if let Some (lines) = & mut err_lines { lines . next_line () . await } else { Ok (None) } } , if ! stderr_done => { match err ? { Some (line) => { on_line (CargoStream :: Stderr , & line) ; combined . push_str (& line) ; combined . push ('\n') ; } None => stderr_done = true , } } }
         }
         let status = child.wait().await?;
-        if status.success() {
-            Ok(combined)
-        } else {
-            Err(anyhow::anyhow!(
-                "Cargo command failed with exit code: {:?}\n{combined}",
-                status.code()
-            ))
-        }
+        Ok(CargoOutput {
+            code: status.code(),
+            output: combined,
+        })
     }
     #[doc = "Method documentation added by AI refactor"]
     pub async fn execute_interactive(&self, args: &[impl AsRef<std::ffi::OsStr>]) -> Result<()> {
-        let mut cmd = TokioCommand::new("cargo");
-        if let Some(dir) = &self.working_dir {
-            cmd.current_dir(dir);
-        }
+        let mut cmd = self.command();
         cmd.args(args);
         let status = cmd.status().await?;
         if status.success() {
@@ -184,8 +285,3 @@ impl CargoExecutor {
     }
 }
 
-impl Default for CargoExecutor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -0,0 +1,140 @@
+#![doc = " # Crawl Cache - Per-file crawler results"]
+#![doc = ""]
+#![doc = " Guarda en `.trae/crawl-cache.json` lo que el crawler extrae de cada archivo, indexado por hash de contenido"]
+use crate::config::RunOptions;
+use crate::core::crawler::{file_symbols, FileSymbols};
+use crate::core::features::read_source;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+/// Location of the cache, relative to the project root.
+pub const CRAWL_CACHE_FILE: &str = ".trae/crawl-cache.json";
+/// Bumped whenever [`FileSymbols`] or an extractor changes, so older caches
+/// are discarded instead of replaying stale results.
+const CACHE_VERSION: u32 = 3;
+/// Caches this process already loaded, by project: the `extract_*`
+/// functions a command calls one after another share a single load.
+static LOADED: OnceLock<Mutex<HashMap<PathBuf, CrawlCache>>> = OnceLock::new();
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// SHA-256 of the file content.
+    hash: String,
+    /// Modification time (ns since the epoch) when `hash` was taken.
+    mtime_ns: u64,
+    symbols: FileSymbols,
+}
+/// Crawler results per file (relative path), persisted in [`CRAWL_CACHE_FILE`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlCache {
+    version: u32,
+    files: BTreeMap<String, CacheEntry>,
+}
+impl Default for CrawlCache {
+    fn default() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+impl CrawlCache {
+    /// Reads the cache, returning an empty one when missing, unreadable or
+    /// written by another [`CACHE_VERSION`].
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default()
+    }
+    #[doc = " Escribe la caché, creando `.trae/` si hace falta"]
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+    /// Symbols of each `(relative path, path)` in `files`, and whether any
+    /// entry changed. A file whose mtime matches its entry is not read; one
+    /// whose mtime moved is hashed and only re-extracted when the content
+    /// changed. With `complete`, entries for files not in `files` are
+    /// dropped (a depth-limited crawl doesn't see every file, so it keeps them).
+    pub fn refresh(&mut self, files: &[(String, PathBuf)], complete: bool) -> (Vec<FileSymbols>, bool) {
+        let mut changed = false;
+        let mut symbols = Vec::with_capacity(files.len());
+        for (file, path) in files {
+            let Some(mtime_ns) = mtime_ns(path) else {
+                continue;
+            };
+            if let Some(entry) = self.files.get(file).filter(|e| e.mtime_ns == mtime_ns) {
+                symbols.push(entry.symbols.clone());
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let hash = hex::encode(Sha256::digest(content.as_bytes()));
+            let entry = match self.files.remove(file) {
+                Some(entry) if entry.hash == hash => CacheEntry { mtime_ns, ..entry },
+                _ => CacheEntry {
+                    hash,
+                    mtime_ns,
+                    symbols: file_symbols(&content, file),
+                },
+            };
+            symbols.push(entry.symbols.clone());
+            self.files.insert(file.clone(), entry);
+            changed = true;
+        }
+        if complete {
+            let before = self.files.len();
+            self.files
+                .retain(|file, _| files.iter().any(|(crawled, _)| crawled == file));
+            changed |= self.files.len() != before;
+        }
+        (symbols, changed)
+    }
+    /// Files with an entry, in path order.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+}
+/// [`CrawlCache::refresh`] against `project`'s cache, saved back when it
+/// changed. With the cache disabled in `options` (`--no-cache`) every file is
+/// read and extracted and nothing is written.
+pub fn symbols(
+    project: &Path,
+    files: &[(String, PathBuf)],
+    complete: bool,
+    options: &RunOptions,
+) -> Vec<FileSymbols> {
+    if options.no_crawl_cache {
+        return files
+            .iter()
+            .filter_map(|(file, path)| Some(file_symbols(&read_source(path, options).ok()?, file)))
+            .collect();
+    }
+    let path = project.join(CRAWL_CACHE_FILE);
+    let key = project.canonicalize().unwrap_or_else(|_| project.to_path_buf());
+    let mut loaded = LOADED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let cache = loaded.entry(key).or_insert_with(|| CrawlCache::load(&path));
+    let (symbols, changed) = cache.refresh(files, complete);
+    if changed {
+        // Best effort: a read-only checkout still crawls, just without reuse.
+        let _ = cache.save(&path);
+    }
+    symbols
+}
+fn mtime_ns(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_nanos() as u64)
+}
@@ -0,0 +1,926 @@
+#![doc = " # Crawler - Regex-based project crawler"]
+#![doc = ""]
+#![doc = " Extrae funciones, structs, traits, tests, TODOs y código potencialmente muerto de `src/`"]
+use crate::config::RunOptions;
+use crate::core::coverage::estimate_test_coverage;
+use crate::core::crawl_cache;
+use crate::core::features::read_source;
+use crate::core::manifest::Manifest;
+pub use crate::core::manifest::{Dependency, DependencyKind};
+use crate::utils::path::{portable, rust_files, walk};
+use crate::utils::source::find_todos;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+/// Información de código muerto detectado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadCodeItem {
+    pub item_type: String, // function, struct, enum, const, static
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub is_pub: bool,
+}
+
+/// Información de módulo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleInfo {
+    pub name: String,
+    pub path: String,
+    pub used: bool,
+    pub sub_modules: Vec<String>,
+    pub file_count: usize,
+}
+
+/// Información extraída por el crawler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawledInfo {
+    pub dependencies: Vec<String>,
+    /// Las mismas dependencias con su tabla (una entrada por declaración)
+    #[serde(default)]
+    pub dependency_details: Vec<Dependency>,
+    pub functions: Vec<FunctionInfo>,
+    pub structs: Vec<StructInfo>,
+    pub traits: Vec<TraitInfo>,
+    pub tests: Vec<TestInfo>,
+    pub todos: Vec<TodoItem>,
+    pub metrics: ProjectMetrics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub is_pub: bool,
+    pub params: Vec<String>,
+    pub return_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructInfo {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub fields: Vec<String>,
+    pub is_pub: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitInfo {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestInfo {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// Un nombre que trae un `use` al archivo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportInfo {
+    /// Ruta importada completa (`std::collections::HashMap`)
+    pub path: String,
+    /// Nombre con el que se usa en el archivo: el último segmento o el de `as`
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    /// `pub use`: se re-exporta, así que su uso está fuera del archivo
+    pub is_pub: bool,
+    /// El nombre aparece en el archivo fuera de las sentencias `use`
+    pub used: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub text: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectMetrics {
+    pub total_lines: usize,
+    pub total_functions: usize,
+    pub total_structs: usize,
+    pub total_traits: usize,
+    pub total_tests: usize,
+    pub code_files: usize,
+    pub test_coverage_estimate: f64,
+}
+
+/// Conteos brutos de un archivo para [`ProjectMetrics`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileCounts {
+    pub lines: usize,
+    pub functions: usize,
+    pub structs: usize,
+    pub traits: usize,
+}
+
+/// Resultado del crawler para un único archivo
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileSymbols {
+    pub functions: Vec<FunctionInfo>,
+    pub structs: Vec<StructInfo>,
+    pub traits: Vec<TraitInfo>,
+    /// `Trait for Type` de [`trait_impls_in`]
+    pub impls: Vec<String>,
+    pub tests: Vec<TestInfo>,
+    pub todos: Vec<TodoItem>,
+    pub dead_code: Vec<DeadCodeItem>,
+    #[serde(default)]
+    pub imports: Vec<ImportInfo>,
+    pub counts: FileCounts,
+}
+
+/// Kind of definition a [`Location`] points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Trait,
+}
+
+/// Where a symbol is defined; `file` is relative to the crawled project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+    pub kind: SymbolKind,
+}
+
+/// A named definition, as listed by [`CrawledInfo::symbols_in_file`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    pub location: Location,
+}
+
+/// One line of `trae deadcode --format json --stream`: an item tagged with
+/// its `type`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CrawlItem {
+    Function(FunctionInfo),
+    Struct(StructInfo),
+    Trait(TraitInfo),
+    Todo(TodoItem),
+    Deadcode(DeadCodeItem),
+}
+
+impl CrawlItem {
+    /// 1-based line of the item in its file.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Function(f) => f.line,
+            Self::Struct(s) => s.line,
+            Self::Trait(t) => t.line,
+            Self::Todo(t) => t.line,
+            Self::Deadcode(d) => d.line,
+        }
+    }
+}
+
+impl CrawledInfo {
+    /// Every function, struct and trait, in crawl order.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        let symbol = |name: &str, file: &str, line, kind| Symbol {
+            name: name.to_string(),
+            location: Location { file: file.to_string(), line, kind },
+        };
+        let functions = self
+            .functions
+            .iter()
+            .map(move |f| symbol(&f.name, &f.file, f.line, SymbolKind::Function));
+        let structs = self
+            .structs
+            .iter()
+            .map(move |s| symbol(&s.name, &s.file, s.line, SymbolKind::Struct));
+        let traits = self
+            .traits
+            .iter()
+            .map(move |t| symbol(&t.name, &t.file, t.line, SymbolKind::Trait));
+        functions.chain(structs).chain(traits)
+    }
+
+    /// All definitions named `name`; a name defined in several modules
+    /// returns one location per definition.
+    pub fn find_symbol(&self, name: &str) -> Vec<Location> {
+        self.symbols()
+            .filter(|s| s.name == name)
+            .map(|s| s.location)
+            .collect()
+    }
+
+    /// Definitions in `path`, given relative to the crawled project
+    /// (`src/lib.rs`, a leading `./` is ignored).
+    pub fn symbols_in_file(&self, path: &Path) -> Vec<Symbol> {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        self.symbols()
+            .filter(|s| Path::new(&s.location.file) == path)
+            .collect()
+    }
+}
+
+/// `path` relative to the project root, as every crawler record stores it.
+fn relative_file(path: &Path, project_path: &Path) -> String {
+    portable(path.strip_prefix(project_path).unwrap_or(path))
+}
+
+/// Crawling Avanzado: Análisis semántico profundo del proyecto Rust
+pub fn advanced_project_crawler(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> CrawledInfo {
+    CrawledInfo {
+        dependencies: extract_dependencies(project_path),
+        dependency_details: extract_dependency_details(project_path),
+        functions: extract_functions(project_path, max_depth, options),
+        structs: extract_structs(project_path, max_depth, options),
+        traits: extract_traits(project_path, max_depth, options),
+        tests: extract_tests(project_path, max_depth, options),
+        todos: extract_todos(project_path, max_depth, options),
+        metrics: calculate_metrics(project_path, max_depth, options),
+    }
+}
+
+/// Extrae los nombres de las dependencias del Cargo.toml (todas las tablas, sin repetir)
+pub fn extract_dependencies(project_path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for dep in extract_dependency_details(project_path) {
+        if !names.contains(&dep.name) {
+            names.push(dep.name);
+        }
+    }
+    names
+}
+
+/// Extrae cada dependencia del Cargo.toml con su tabla: `[dependencies]`,
+/// `[dev-dependencies]`, `[build-dependencies]` y sus variantes
+/// `[target.'cfg(...)'.*]`. Un manifiesto ausente o inválido no da ninguna.
+pub fn extract_dependency_details(project_path: &Path) -> Vec<Dependency> {
+    Manifest::load(project_path)
+        .map(|manifest| manifest.dependencies())
+        .unwrap_or_default()
+}
+
+/// Extrae funciones definidas en el proyecto (parse semántico)
+pub fn extract_functions(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<FunctionInfo> {
+    crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.functions)
+        .collect()
+}
+
+/// Funciones de un único archivo (o de la salida de `cargo expand`). La
+/// firma puede ocupar varias líneas; `line` es la del nombre
+pub fn functions_in(content: &str, file: &str) -> Vec<FunctionInfo> {
+    // Sin `;` ni `}` en el tipo de retorno: una declaración sin cuerpo de un
+    // trait no se empalma con la llave de lo que venga después
+    let fn_pattern = Regex::new(r#"(?m)^[ \t]*(pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s+([a-z_]\w*)\s*\(([^)]*)\)\s*(?:->?\s*([^{;}]+?))?\s*\{"#).unwrap();
+    let mut functions = Vec::new();
+
+    for caps in fn_pattern.captures_iter(content) {
+        let is_pub = caps.get(1).is_some();
+        let name = caps.get(2).unwrap();
+        let line = content[..name.start()].matches('\n').count() + 1;
+        let name = name.as_str().to_string();
+        let params_str = caps.get(3).unwrap().as_str();
+        let return_type = caps.get(4).map(|m| m.as_str().trim().to_string()).unwrap_or_else(|| "()".to_string());
+
+        let params: Vec<String> = params_str.split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        functions.push(FunctionInfo {
+            name,
+            file: file.to_string(),
+            line,
+            is_pub,
+            params,
+            return_type,
+        });
+    }
+
+    functions
+}
+
+/// Extrae structs definidas en el proyecto
+pub fn extract_structs(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<StructInfo> {
+    crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.structs)
+        .collect()
+}
+
+/// Structs de un único archivo (o de la salida de `cargo expand`)
+pub fn structs_in(content: &str, file: &str) -> Vec<StructInfo> {
+    let struct_pattern = Regex::new(r#"(?m)^\s*(pub\s+)?struct\s+([A-Z]\w*)\s*(?:\{([^}]*)\})?"#).unwrap();
+    let field_pattern = Regex::new(r#"(\w+)\s*:\s*([^,}]+)"#).unwrap();
+    let mut structs = Vec::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        if let Some(caps) = struct_pattern.captures(line) {
+            let is_pub = caps.get(1).is_some();
+            let name = caps.get(2).unwrap().as_str().to_string();
+            let fields_str = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+
+            let mut fields = Vec::new();
+            for field_cap in field_pattern.captures_iter(fields_str) {
+                let field_name = field_cap.get(1).unwrap().as_str().to_string();
+                fields.push(field_name);
+            }
+
+            structs.push(StructInfo {
+                name,
+                file: file.to_string(),
+                line: line_num + 1,
+                fields,
+                is_pub,
+            });
+        }
+    }
+
+    structs
+}
+
+/// Extrae traits definidas en el proyecto
+pub fn extract_traits(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<TraitInfo> {
+    crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.traits)
+        .collect()
+}
+
+/// Traits públicos de un único archivo (o de la salida de `cargo expand`)
+pub fn traits_in(content: &str, file: &str) -> Vec<TraitInfo> {
+    let trait_pattern = Regex::new(r#"(?m)^\s*pub\s+trait\s+([A-Z]\w*)\s*(?:\{([^}]*)\})?"#).unwrap();
+    let method_pattern = Regex::new(r#"fn\s+([a-z_]\w*)"#).unwrap();
+    let mut traits = Vec::new();
+
+    for caps in trait_pattern.captures_iter(content) {
+        let name = caps.get(1).unwrap();
+        let line = content[..name.start()].matches('\n').count() + 1;
+        let name = name.as_str().to_string();
+        let trait_body = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+
+        let mut methods = Vec::new();
+        for method_cap in method_pattern.captures_iter(trait_body) {
+            methods.push(method_cap.get(1).unwrap().as_str().to_string());
+        }
+
+        traits.push(TraitInfo {
+            name,
+            file: file.to_string(),
+            line,
+            methods,
+        });
+    }
+
+    traits
+}
+
+/// `Trait for Type` de cada `impl` de un archivo, con el trait sin ruta ni
+/// genéricos (`impl ::core::fmt::Debug for Config` → `Debug for Config`)
+pub fn trait_impls_in(content: &str) -> Vec<String> {
+    let impl_pattern = Regex::new(r#"(?m)^\s*(?:unsafe\s+)?impl\b(?:\s*<[^{]*?>)?\s+([\w:]+)(?:<[^{]*?>)?\s+for\s+([\w:]+)"#).unwrap();
+
+    impl_pattern
+        .captures_iter(content)
+        .map(|caps| {
+            let last = |m: regex::Match| m.as_str().rsplit("::").next().unwrap_or_default().to_string();
+            format!("{} for {}", last(caps.get(1).unwrap()), last(caps.get(2).unwrap()))
+        })
+        .collect()
+}
+
+/// Pseudo-archivo con el que se marcan los ítems vistos solo tras `cargo expand`
+pub const EXPANDED_FILE: &str = "<cargo expand>";
+
+/// Ítems que solo existen tras expandir macros: aparecen en la salida de
+/// `cargo expand` pero no en `src/`. Se comparan por nombre y cantidad, así
+/// que un `impl Debug` escrito a mano y uno derivado cuentan por separado.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MacroGenerated {
+    pub functions: Vec<FunctionInfo>,
+    pub structs: Vec<StructInfo>,
+    pub traits: Vec<TraitInfo>,
+    /// Implementaciones `Trait for Type`, p. ej. las de un `#[derive]`
+    pub impls: Vec<String>,
+}
+
+impl MacroGenerated {
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.structs.is_empty() && self.traits.is_empty() && self.impls.is_empty()
+    }
+}
+
+/// Compara `expanded` (salida de `cargo expand`) con todo `src/` y devuelve lo
+/// que generaron las macros
+pub fn macro_generated(
+    project_path: &Path,
+    expanded: &str,
+    options: &RunOptions,
+) -> MacroGenerated {
+    let source_impls = crawl_src(project_path, None, options)
+        .into_iter()
+        .flat_map(|file| file.impls);
+
+    MacroGenerated {
+        functions: not_in_source(
+            functions_in(expanded, EXPANDED_FILE),
+            extract_functions(project_path, None, options).into_iter().map(|f| f.name),
+            |f| &f.name,
+        ),
+        structs: not_in_source(
+            structs_in(expanded, EXPANDED_FILE),
+            extract_structs(project_path, None, options).into_iter().map(|s| s.name),
+            |s| &s.name,
+        ),
+        traits: not_in_source(
+            traits_in(expanded, EXPANDED_FILE),
+            extract_traits(project_path, None, options).into_iter().map(|t| t.name),
+            |t| &t.name,
+        ),
+        impls: not_in_source(trait_impls_in(expanded), source_impls, |i| i),
+    }
+}
+
+/// Los `expanded` que sobran tras emparejar cada nombre de `source` una vez
+fn not_in_source<T>(expanded: Vec<T>, source: impl IntoIterator<Item = String>, name: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for item in source {
+        *remaining.entry(item).or_default() += 1;
+    }
+    expanded
+        .into_iter()
+        .filter(|item| match remaining.get_mut(name(item)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Extrae tests del proyecto
+pub fn extract_tests(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<TestInfo> {
+    crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.tests)
+        .collect()
+}
+
+/// Tests (`#[test]` y variantes async) de un único archivo
+pub fn tests_in(content: &str, file: &str) -> Vec<TestInfo> {
+    let test_pattern = Regex::new(r#"#\[test\]|#\[tokio::test\]|#\[actix_rt::test\]"#).unwrap();
+    let fn_pattern = Regex::new(r#"fn\s+([a-z_]\w*)"#).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut tests = Vec::new();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        if test_pattern.is_match(line) {
+            // La siguiente línea debe ser la función de test
+            if let Some(caps) = lines.get(line_num + 1).and_then(|next| fn_pattern.captures(next)) {
+                tests.push(TestInfo {
+                    name: caps.get(1).unwrap().as_str().to_string(),
+                    file: file.to_string(),
+                    line: line_num + 2,
+                });
+            }
+        }
+    }
+
+    tests
+}
+
+/// Extrae los imports de `src/`
+pub fn extract_imports(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<ImportInfo> {
+    let mut imports: Vec<_> = crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.imports)
+        .collect();
+    imports.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    imports
+}
+
+/// Traits habituales que se importan solo para llamar a sus métodos, así que
+/// su nombre no aparece en el archivo aunque el import sea necesario.
+const METHOD_TRAITS: &[&str] = &[
+    "BufRead", "Colorize", "Context", "Digest", "FromStr", "Hasher", "Itertools", "Read", "Rng",
+    "Seek", "ToSocketAddrs", "Write",
+];
+
+/// Imports privados cuyo nombre no aparece en el resto de su archivo.
+/// Heurístico: se omiten los traits de [`METHOD_TRAITS`], los `*Ext` y los
+/// traits del propio proyecto, pero otro trait importado solo por sus métodos
+/// o un nombre usado solo dentro de una macro salen igualmente.
+pub fn unused_imports(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<ImportInfo> {
+    let traits: Vec<String> = extract_traits(project_path, max_depth, options)
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    let method_trait = |name: &str| {
+        METHOD_TRAITS.contains(&name) || name.ends_with("Ext") || traits.iter().any(|t| t == name)
+    };
+    extract_imports(project_path, max_depth, options)
+        .into_iter()
+        .filter(|import| !import.used && !import.is_pub && !method_trait(&import.name))
+        .collect()
+}
+
+/// Imports de un único archivo, uno por nombre importado. Las sentencias
+/// pueden ocupar varias líneas; los globs (`use x::*;`) y `as _` no importan
+/// un nombre y se omiten, `self` importa el módulo (`use std::io::{self}` → `io`).
+pub fn imports_in(content: &str, file: &str) -> Vec<ImportInfo> {
+    let use_pattern = Regex::new(r#"^\s*(pub(?:\([^)]*\))?\s+)?use\s+"#).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut statements = Vec::new();
+    let mut in_use = vec![false; lines.len()];
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(caps) = use_pattern.captures(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        let start = i;
+        let mut tree = lines[i][caps.get(0).unwrap().end()..].to_string();
+        while !tree.contains(';') && i + 1 < lines.len() {
+            i += 1;
+            tree.push(' ');
+            tree.push_str(lines[i]);
+        }
+        in_use[start..=i].iter_mut().for_each(|line| *line = true);
+        let tree = tree.split(';').next().unwrap_or_default().to_string();
+        statements.push((start, caps.get(1).is_some(), tree));
+        i += 1;
+    }
+    // El resto del archivo, sin las sentencias `use`, es donde se buscan los usos
+    let rest: String = lines
+        .iter()
+        .zip(&in_use)
+        .filter(|(_, in_use)| !**in_use)
+        .map(|(line, _)| *line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut imports = Vec::new();
+    for (line, is_pub, tree) in statements {
+        for (path, name) in use_tree_names("", &tree) {
+            let word = Regex::new(&format!(r#"\b{}\b"#, regex::escape(&name))).unwrap();
+            imports.push(ImportInfo {
+                used: word.is_match(&rest),
+                path,
+                name,
+                file: file.to_string(),
+                line: line + 1,
+                is_pub,
+            });
+        }
+    }
+    imports
+}
+
+/// `(ruta, nombre)` de cada hoja de un árbol `use` (`a::{b, c::D as E}`).
+fn use_tree_names(prefix: &str, tree: &str) -> Vec<(String, String)> {
+    let tree = tree.trim();
+    let join = |path: &str| match prefix {
+        "" => path.to_string(),
+        _ => format!("{prefix}::{path}"),
+    };
+    if let Some(open) = tree.find('{') {
+        let base = tree[..open].trim().trim_end_matches("::");
+        let inner = tree[open + 1..].trim_end().strip_suffix('}').unwrap_or(&tree[open + 1..]);
+        let prefix = if base.is_empty() { prefix.to_string() } else { join(base) };
+        return split_top_level(inner)
+            .into_iter()
+            .flat_map(|item| use_tree_names(&prefix, item))
+            .collect();
+    }
+    let (path, alias) = match tree.split_once(" as ") {
+        Some((path, alias)) => (path.trim(), Some(alias.trim())),
+        None => (tree, None),
+    };
+    if path.is_empty() || path.ends_with('*') || alias == Some("_") {
+        return Vec::new();
+    }
+    let full = join(path);
+    let last = if path == "self" { prefix.rsplit("::").next().unwrap_or_default() } else { path };
+    let name = alias.unwrap_or_else(|| last.rsplit("::").next().unwrap_or(last));
+    if name.is_empty() {
+        return Vec::new();
+    }
+    let full = full.strip_suffix("::self").map(str::to_string).unwrap_or(full);
+    vec![(full, name.to_string())]
+}
+
+/// Elementos de una lista `a, b::{c, d}, e` separados por las comas de primer nivel.
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&list[start..]);
+    items.into_iter().filter(|item| !item.trim().is_empty()).collect()
+}
+
+/// Extrae TODOs y FIXMEs del código
+pub fn extract_todos(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<TodoItem> {
+    let mut todos: Vec<_> = crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.todos)
+        .collect();
+    todos.sort_by(|a, b| (&a.file, a.line, &a.text).cmp(&(&b.file, b.line, &b.text)));
+    todos
+}
+
+/// Calcula métricas del proyecto
+pub fn calculate_metrics(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> ProjectMetrics {
+    let src_path = project_path.join("src");
+    let mut metrics = ProjectMetrics {
+        total_lines: 0,
+        total_functions: 0,
+        total_structs: 0,
+        total_traits: 0,
+        total_tests: 0,
+        code_files: 0,
+        test_coverage_estimate: 0.0,
+    };
+
+    if !src_path.exists() {
+        return metrics;
+    }
+
+    let counts = crawl_src(project_path, max_depth, options).into_iter().map(|file| file.counts);
+    add_counts(&mut metrics, counts);
+
+    // Heurística (tests / funciones de producción), no cobertura real
+    let coverage = estimate_test_coverage(project_path, max_depth, options);
+    metrics.total_tests = coverage.tests;
+    metrics.test_coverage_estimate = coverage.percent;
+
+    metrics
+}
+
+/// Solo las [`ProjectMetrics`], sin extraer funciones, structs, traits, tests
+/// ni TODOs: lee los mismos archivos que el crawler completo y cuenta con
+/// [`file_counts`], así que los números coinciden con [`calculate_metrics`]
+pub fn project_metrics(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> ProjectMetrics {
+    let src_path = project_path.join("src");
+    let mut metrics = ProjectMetrics::default();
+
+    if !src_path.exists() {
+        return metrics;
+    }
+
+    let counts = rust_files(&src_path, max_depth, options)
+        .filter_map(|entry| read_source(entry.path(), options).ok())
+        .map(|content| file_counts(&content));
+    add_counts(&mut metrics, counts);
+    let coverage = estimate_test_coverage(project_path, max_depth, options);
+    metrics.total_tests = coverage.tests;
+    metrics.test_coverage_estimate = coverage.percent;
+
+    metrics
+}
+
+fn add_counts(metrics: &mut ProjectMetrics, counts: impl Iterator<Item = FileCounts>) {
+    for counts in counts {
+        metrics.code_files += 1;
+        metrics.total_lines += counts.lines;
+        metrics.total_functions += counts.functions;
+        metrics.total_structs += counts.structs;
+        metrics.total_traits += counts.traits;
+    }
+}
+
+/// Scanner: Detecta código muerto analizando los archivos .rs
+pub fn scan_deadcode(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<DeadCodeItem> {
+    let mut items: Vec<_> = crawl_src(project_path, max_depth, options)
+        .into_iter()
+        .flat_map(|file| file.dead_code)
+        .collect();
+    items.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    items
+}
+
+/// Candidatos a código muerto de un único archivo. `#[allow(dead_code)]` y
+/// `#[test]` excluyen el ítem que anotan, en su línea o en la siguiente
+/// que no sea otro atributo ni un doc comment
+pub fn dead_code_in(content: &str, file: &str) -> Vec<DeadCodeItem> {
+    // Patrones para detectar código potencialmente muerto
+    let patterns = [
+        ("function", Regex::new(r#"^\s*(pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s+([a-z_]\w*)"#).unwrap()),
+        ("struct", Regex::new(r#"^\s*(pub(?:\([^)]*\))?\s+)?struct\s+([A-Z]\w*)"#).unwrap()),
+        ("enum", Regex::new(r#"^\s*(pub(?:\([^)]*\))?\s+)?enum\s+([A-Z]\w*)"#).unwrap()),
+        ("const", Regex::new(r#"^\s*(pub(?:\([^)]*\))?\s+)?const\s+([A-Z_]\w+)"#).unwrap()),
+    ];
+    let mut dead_items = Vec::new();
+    let mut silenced = false;
+
+    for (line_num, line) in content.lines().enumerate() {
+        if line.contains("#[allow(dead_code)]") || line.contains("#[test]") {
+            silenced = true;
+        }
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("#[") || trimmed.starts_with("///") {
+            continue;
+        }
+        if std::mem::take(&mut silenced) {
+            continue;
+        }
+        for (item_type, pattern) in &patterns {
+            if let Some(caps) = pattern.captures(line) {
+                dead_items.push(DeadCodeItem {
+                    item_type: item_type.to_string(),
+                    name: caps.get(2).unwrap().as_str().to_string(),
+                    file: file.to_string(),
+                    line: line_num + 1,
+                    is_pub: caps.get(1).is_some(),
+                });
+            }
+        }
+    }
+
+    dead_items
+}
+
+/// Todo lo que el crawler extrae de un archivo, de una sola pasada; es lo
+/// que guarda la caché de [`crate::core::crawl_cache`]
+pub fn file_symbols(content: &str, file: &str) -> FileSymbols {
+    FileSymbols {
+        functions: functions_in(content, file),
+        structs: structs_in(content, file),
+        traits: traits_in(content, file),
+        impls: trait_impls_in(content),
+        tests: tests_in(content, file),
+        // Los marcadores dentro de literales raw/byte no son comentarios reales
+        todos: find_todos(content)
+            .into_iter()
+            .map(|(line, text)| TodoItem {
+                text,
+                file: file.to_string(),
+                line,
+            })
+            .collect(),
+        dead_code: dead_code_in(content, file),
+        imports: imports_in(content, file),
+        counts: file_counts(content),
+    }
+}
+
+/// Conteos de [`ProjectMetrics`] de un único archivo
+pub fn file_counts(content: &str) -> FileCounts {
+    let count = |pattern: &str| Regex::new(pattern).unwrap().find_iter(content).count();
+    FileCounts {
+        lines: content.lines().count(),
+        functions: count(r#"fn\s+\w+"#),
+        structs: count(r#"struct\s+\w+"#),
+        traits: count(r#"trait\s+\w+"#),
+    }
+}
+
+/// [`file_symbols`] de cada `.rs` bajo `src/`, reutilizando la caché para
+/// los archivos sin cambios salvo que `options` la desactiven
+fn crawl_src(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> Vec<FileSymbols> {
+    let src_path = project_path.join("src");
+    if !src_path.exists() {
+        return Vec::new();
+    }
+    let files: Vec<_> = rust_files(&src_path, max_depth, options)
+        .map(|entry| (relative_file(entry.path(), project_path), entry.into_path()))
+        .collect();
+    crawl_cache::symbols(project_path, &files, max_depth.is_none(), options)
+}
+
+/// Crawls `src/` one file at a time, in path order, and hands each item to
+/// `emit` as soon as its file is read, ordered by line within the file (an
+/// item before the dead-code candidate on its own line). Only one file's
+/// items are held at once and the crawl cache is not used, so memory stays
+/// flat on huge projects. Stops at the first error of `emit`.
+pub fn stream_items<E>(
+    project_path: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+    mut emit: impl FnMut(CrawlItem) -> Result<(), E>,
+) -> Result<(), E> {
+    let src_path = project_path.join("src");
+    let mut files: Vec<_> = rust_files(&src_path, max_depth, options)
+        .map(|entry| (relative_file(entry.path(), project_path), entry.into_path()))
+        .collect();
+    files.sort();
+    for (file, path) in files {
+        let Ok(content) = read_source(&path, options) else {
+            continue;
+        };
+        let symbols = file_symbols(&content, &file);
+        let mut items: Vec<CrawlItem> = symbols
+            .functions
+            .into_iter()
+            .map(CrawlItem::Function)
+            .chain(symbols.structs.into_iter().map(CrawlItem::Struct))
+            .chain(symbols.traits.into_iter().map(CrawlItem::Trait))
+            .chain(symbols.todos.into_iter().map(CrawlItem::Todo))
+            .chain(symbols.dead_code.into_iter().map(CrawlItem::Deadcode))
+            .collect();
+        // Stable: items on the same line keep the order of the chain above
+        items.sort_by_key(CrawlItem::line);
+        for item in items {
+            emit(item)?;
+        }
+    }
+    Ok(())
+}
+
+/// Scanner: Analiza módulos del proyecto
+pub fn scan_modules(project_path: &Path, options: &RunOptions) -> Vec<ModuleInfo> {
+    let mut modules = Vec::new();
+    let src_path = project_path.join("src");
+
+    if !src_path.exists() {
+        return modules;
+    }
+
+    for entry in walk(&src_path, None, options).filter(|e| e.path().is_dir())
+    {
+        let mod_path = portable(entry.path().strip_prefix(project_path).unwrap_or(entry.path()));
+        let mod_name = entry.file_name().to_string_lossy().to_string();
+
+        let file_count = rust_files(entry.path(), None, options).count();
+
+        if file_count > 0 {
+            modules.push(ModuleInfo {
+                name: mod_name,
+                path: mod_path,
+                used: true, // Simplificado
+                sub_modules: Vec::new(),
+                file_count,
+            });
+        }
+    }
+
+    modules
+}
@@ -1,11 +1,86 @@
 #![doc = " # System Doctor - System health check"]
 #![doc = ""]
 #![doc = " Verificador de salud del sistema y dependencias"]
+use crate::config::RunOptions;
+use crate::core::cargo::cargo_command;
 use anyhow::Result;
 use colored::Colorize;
+use serde::Serialize;
+use std::time::Instant;
 use which::which;
+/// Outcome of one [`DoctorCheck`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+/// One environment check, as printed and as emitted by `trae doctor --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    /// A failed required check makes [`DoctorReport::ok`] false.
+    pub required: bool,
+    pub detail: String,
+    pub remediation: Option<String>,
+    /// JARVIXSERVER URL, on the `jarvix` check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Round trip of the JARVIXSERVER health report, on the `jarvix` check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+impl DoctorCheck {
+    fn new(name: &str, required: bool, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            required,
+            detail: detail.into(),
+            remediation: None,
+            url: None,
+            latency_ms: None,
+        }
+    }
+    fn remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+}
+/// Every check of `trae doctor`; `ok` is false when a required check failed.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub ok: bool,
+}
+impl DoctorReport {
+    #[doc = " Construye el reporte calculando `ok` a partir de los checks requeridos"]
+    pub fn new(checks: Vec<DoctorCheck>) -> Self {
+        let ok = checks
+            .iter()
+            .all(|check| !check.required || check.status != CheckStatus::Fail);
+        Self { checks, ok }
+    }
+}
+/// Runs every check: toolchain, cargo components, optional tools and JARVIXSERVER.
+pub async fn collect_checks(options: &RunOptions) -> DoctorReport {
+    let mut checks = vec![
+        check_binary("rustc", "https://rustup.rs/"),
+        check_binary("cargo", "https://rustup.rs/"),
+    ];
+    checks.extend(check_additional_tools(options));
+    checks.push(check_jarvix_connection(options).await);
+    DoctorReport::new(checks)
+}
 #[doc = "Function documentation added by AI refactor"]
-pub async fn run_system_check() -> Result<()> {
+pub async fn run_system_check(json: bool, options: &RunOptions) -> Result<()> {
+    let report = collect_checks(options).await;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
     println!(
         "{}",
         "🩺 TRAE System Doctor - Verificación del Sistema"
@@ -13,13 +88,22 @@ pub async fn run_system_check() -> Result<()> {
             .bold()
     );
     println!();
-    let mut all_ok = true;
-    all_ok &= check_rust_installation();
-    all_ok &= check_cargo_installation();
-    all_ok &= check_additional_tools();
-    all_ok &= check_jarvix_connection().await?;
+    for check in &report.checks {
+        let mark = match check.status {
+            CheckStatus::Pass => "✓".green(),
+            CheckStatus::Warn => "⚠️".yellow(),
+            CheckStatus::Fail => "✗".red(),
+        };
+        println!("🔧 Verificando {}... {mark}", check.name);
+        if !check.detail.is_empty() {
+            println!("   {}", check.detail.blue());
+        }
+        if let Some(remediation) = &check.remediation {
+            println!("   💡 {}", remediation.yellow());
+        }
+    }
     println!();
-    if all_ok {
+    if report.ok {
         println!(
             "{}",
             "✅ Todos los checks pasaron exitosamente".green().bold()
@@ -34,104 +118,85 @@ pub async fn run_system_check() -> Result<()> {
     }
     Ok(())
 }
-#[doc = "Function documentation added by AI refactor"]
-fn check_rust_installation() -> bool {
-    print!("🦀 Verificando instalación de Rust... ");
-    if let Ok(path) = which("rustc") {
-        println!("{}", "✓".green());
-        println!("   Ruta: {}", path.display().to_string().blue());
-        if let Ok(output) = std::process::Command::new("rustc")
-            .arg("--version")
-            .output()
-        {
-            let version = String::from_utf8_lossy(&output.stdout);
-            println!("   Versión: {}", version.trim().blue());
-        }
-        true
-    } else {
-        println!("{}", "✗ No encontrado".red());
-        println!("   💡 Instalar desde: https://rustup.rs/");
-        false
-    }
-}
-#[doc = "Function documentation added by AI refactor"]
-fn check_cargo_installation() -> bool {
-    print!("📦 Verificando instalación de Cargo... ");
-    if let Ok(path) = which("cargo") {
-        println!("{}", "✓".green());
-        println!("   Ruta: {}", path.display().to_string().blue());
-        if let Ok(output) = std::process::Command::new("cargo")
-            .arg("--version")
-            .output()
-        {
-            let version = String::from_utf8_lossy(&output.stdout);
-            println!("   Versión: {}", version.trim().blue());
-        }
-        true
-    } else {
-        println!("{}", "✗ No encontrado".red());
-        false
-    }
+/// `name` on PATH, with its `--version`.
+fn check_binary(name: &str, install: &str) -> DoctorCheck {
+    let Ok(path) = which(name) else {
+        return DoctorCheck::new(name, true, CheckStatus::Fail, "No encontrado")
+            .remediation(format!("Instalar desde: {install}"));
+    };
+    let version = std::process::Command::new(name)
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    DoctorCheck::new(
+        name,
+        true,
+        CheckStatus::Pass,
+        format!("{version} ({})", path.display()),
+    )
 }
-#[doc = "Function documentation added by AI refactor"]
-fn check_additional_tools() -> bool {
-    let tools = vec![
-        ("clippy", "cargo install clippy"),
-        ("rustfmt", "rustup component add rustfmt"),
+/// Cargo subcommands trae shells out to. clippy and rustfmt are required;
+/// cargo-audit is only needed by `trae security`.
+fn check_additional_tools(options: &RunOptions) -> Vec<DoctorCheck> {
+    let tools = [
+        ("clippy", "clippy", true, "rustup component add clippy"),
+        ("rustfmt", "fmt", true, "rustup component add rustfmt"),
+        ("cargo-audit", "audit", false, "cargo install cargo-audit"),
     ];
-    let mut all_ok = true;
-    for (tool, install_cmd) in tools {
-        print!("🔧 Verificando {tool}... ");
-        let found = if tool == "clippy" || tool == "rustfmt" {
-            std::process::Command::new("cargo")
-                .args([tool, "--help"])
+    tools
+        .into_iter()
+        .map(|(name, subcommand, required, install_cmd)| {
+            let found = cargo_command(options)
+                .args([subcommand, "--help"])
                 .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false)
-        } else {
-            which(tool).is_ok()
-        };
-        if found {
-            println!("{}", "✓".green());
-        } else {
-            println!("{}", "✗ No encontrado".red());
-            println!("   💡 Instalar: {}", install_cmd.yellow());
-            all_ok = false;
-        }
-    }
-    all_ok
+                .is_ok_and(|output| output.status.success());
+            if found {
+                DoctorCheck::new(name, required, CheckStatus::Pass, "")
+            } else {
+                let status = if required { CheckStatus::Fail } else { CheckStatus::Warn };
+                DoctorCheck::new(name, required, status, "No encontrado")
+                    .remediation(format!("Instalar: {install_cmd}"))
+            }
+        })
+        .collect()
 }
-#[doc = "Function documentation added by AI refactor"]
-async fn check_jarvix_connection() -> Result<bool> {
-    print!("🌐 Verificando conexión a JARVIXSERVER... ");
-    match crate::jarvix::client::JarvixClient::new() {
+/// Sends a health-check report to JARVIXSERVER and times it. Not required:
+/// trae works without it.
+async fn check_jarvix_connection(options: &RunOptions) -> DoctorCheck {
+    match crate::jarvix::client::JarvixClient::new(options).await {
         Ok(Some(client)) => {
             let test_metrics =
                 crate::metrics::collector::MetricsCollector::new("health_check".to_string());
-            match client.report_build_metrics(test_metrics).await {
-                Ok(()) => {
-                    println!("{}", "✅ Conectado y respondiendo".green());
-                    Ok(true)
-                }
-                Err(e) => {
-                    println!(
-                        "{}",
-                        format!("⚠️ Configurado pero sin respuesta: {e}").yellow()
-                    );
-                    println!("   💡 Verificar que JARVIXSERVER esté ejecutándose");
-                    Ok(true)
-                }
-            }
-        }
-        Ok(None) => {
-            println!("{}", "⚠️ No configurado".yellow());
-            println!("   💡 Ejecutar: trae metrics --configure");
-            Ok(true)
-        }
-        Err(e) => {
-            println!("{}", format!("❌ Error de conexión: {e}").red());
-            println!("   💡 Verificar configuración en ~/.trae/config.toml");
-            Ok(false)
+            let start = Instant::now();
+            let sent = client.report_build_metrics(test_metrics).await;
+            let mut check = match sent {
+                Ok(()) => DoctorCheck::new(
+                    "jarvix",
+                    false,
+                    CheckStatus::Pass,
+                    "Conectado y respondiendo",
+                ),
+                Err(e) => DoctorCheck::new(
+                    "jarvix",
+                    false,
+                    CheckStatus::Warn,
+                    format!("Configurado pero sin respuesta: {e}"),
+                )
+                .remediation("Verificar que JARVIXSERVER esté ejecutándose"),
+            };
+            check.url = Some(client.base_url().to_string());
+            check.latency_ms = Some(start.elapsed().as_millis() as u64);
+            check
         }
+        Ok(None) => DoctorCheck::new("jarvix", false, CheckStatus::Warn, "No configurado")
+            .remediation("Ejecutar: trae metrics --configure"),
+        Err(e) => DoctorCheck::new(
+            "jarvix",
+            false,
+            CheckStatus::Fail,
+            format!("Error de conexión: {e}"),
+        )
+        .remediation("Verificar configuración en ~/.trae/config.toml"),
     }
 }
@@ -0,0 +1,324 @@
+#![doc = " # Features - Cargo feature selection and unused features"]
+#![doc = ""]
+#![doc = " Features activas para cargo y los escáneres, y features de `[features]` que nada usa"]
+use crate::config::RunOptions;
+use crate::core::manifest::{feature_line, Manifest};
+use crate::utils::path::rust_files;
+use crate::utils::source::mask_raw_and_byte_strings;
+use anyhow::{Context, Result};
+use clap::Args;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::Path;
+/// Directories whose `.rs` files can gate code on a feature (`build.rs` is read too).
+const SOURCE_DIRS: [&str; 4] = ["src", "tests", "benches", "examples"];
+/// A `[features]` entry that nothing appears to use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedFeature {
+    pub name: String,
+    /// 1-based line of the definition in `Cargo.toml`.
+    pub line: Option<usize>,
+}
+/// Feature names in the `feature = "x"` predicates of `content`: `#[cfg]`,
+/// `#[cfg_attr]` and `cfg!`. Raw and byte strings are masked first.
+pub fn cfg_features(content: &str) -> BTreeSet<String> {
+    let predicate = Regex::new(r#"\bfeature\s*=\s*"([^"]+)""#).unwrap();
+    predicate
+        .captures_iter(&mask_raw_and_byte_strings(content))
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+/// Features of `project/Cargo.toml` that look dead, in name order. A feature
+/// counts as used when any of these holds:
+/// - the project's Rust code gates on it (`cfg(feature = "x")`);
+/// - another feature enables it (`"x"`, `"x/feat"`), `default` included;
+/// - an `optional = true` dependency has its name;
+/// - it enables something itself, which makes it an alias users turn on.
+///
+/// `default` is never reported.
+pub fn unused_features(project: &Path, options: &RunOptions) -> Result<Vec<UnusedFeature>> {
+    let manifest_path = project.join("Cargo.toml");
+    let content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("No se pudo leer {}", manifest_path.display()))?;
+    let manifest = Manifest::parse(&content)
+        .with_context(|| format!("{} inválido", manifest_path.display()))?;
+
+    let mut used: BTreeSet<String> = manifest
+        .specs()
+        .filter(|(_, spec)| spec.is_optional())
+        .map(|(dep, _)| dep.name)
+        .collect();
+    for entry in manifest.features.values().flatten() {
+        if !entry.starts_with("dep:") && !entry.contains("?/") {
+            used.insert(entry.split('/').next().unwrap_or(entry).to_string());
+        }
+    }
+    let sources = SOURCE_DIRS
+        .iter()
+        .flat_map(|dir| rust_files(&project.join(dir), None, options))
+        .map(|entry| entry.into_path())
+        .chain(std::iter::once(project.join("build.rs")));
+    for path in sources {
+        if let Ok(source) = std::fs::read_to_string(&path) {
+            used.extend(cfg_features(&source));
+        }
+    }
+
+    Ok(manifest
+        .features
+        .iter()
+        .filter(|(name, enables)| {
+            name.as_str() != "default" && enables.is_empty() && !used.contains(name.as_str())
+        })
+        .map(|(name, _)| UnusedFeature {
+            name: name.clone(),
+            line: feature_line(&content, name),
+        })
+        .collect())
+}
+/// `--features`, `--all-features` and `--no-default-features`: forwarded to
+/// cargo, and deciding which `#[cfg(feature = "...")]` code the source
+/// scanners look at.
+#[derive(Args, Debug, Clone, Default)]
+pub struct FeatureArgs {
+    #[doc = " Space or comma separated list of features to activate"]
+    #[arg(short = 'F', long, value_name = "FEATURES")]
+    pub features: Vec<String>,
+    #[doc = " Activate all available features"]
+    #[arg(long)]
+    pub all_features: bool,
+    #[doc = " Do not activate the `default` feature"]
+    #[arg(long)]
+    pub no_default_features: bool,
+}
+impl FeatureArgs {
+    /// Whether any feature flag was given.
+    pub fn is_set(&self) -> bool {
+        !self.features.is_empty() || self.all_features || self.no_default_features
+    }
+    /// Feature names of `--features`, split on commas and spaces.
+    pub fn requested(&self) -> Vec<&str> {
+        self.features
+            .iter()
+            .flat_map(|list| list.split([',', ' ']))
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+    /// The flags as cargo takes them.
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        let requested = self.requested();
+        if !requested.is_empty() {
+            args.extend(["--features".to_string(), requested.join(",")]);
+        }
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        args
+    }
+    /// Features cargo would enable with these flags: the requested ones
+    /// (`pkg/feat` counts as `feat`) plus `default` unless turned off, and
+    /// everything those enable through `[features]` of `manifest`, optional
+    /// dependencies included.
+    pub fn active(&self, manifest: Option<&Manifest>) -> ActiveFeatures {
+        if self.all_features {
+            return ActiveFeatures::All;
+        }
+        let mut pending: Vec<String> = self
+            .requested()
+            .iter()
+            .map(|name| name.rsplit('/').next().unwrap_or(name).to_string())
+            .collect();
+        if !self.no_default_features {
+            pending.push("default".to_string());
+        }
+        let mut enabled = BTreeSet::new();
+        while let Some(name) = pending.pop() {
+            if !enabled.insert(name.clone()) {
+                continue;
+            }
+            let enables = manifest.and_then(|m| m.features.get(&name)).into_iter().flatten();
+            for entry in enables.filter(|e| !e.starts_with("dep:") && !e.contains("?/")) {
+                pending.push(entry.split('/').next().unwrap_or(entry).to_string());
+            }
+        }
+        ActiveFeatures::Only(enabled)
+    }
+    /// Makes the features these flags enable for the package at `project`
+    /// the ones scanners under `options` see. Without flags nothing changes
+    /// and scanners keep reading every `cfg(feature)` branch. `options` also
+    /// skip the crawl cache, as it holds symbols of unmasked sources.
+    pub fn activate(&self, project: &Path, options: &mut RunOptions) {
+        if !self.is_set() {
+            return;
+        }
+        let manifest = Manifest::load(project).ok();
+        options.features = Some(self.active(manifest.as_ref()));
+        options.no_crawl_cache = true;
+    }
+}
+/// Features enabled for an analysis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActiveFeatures {
+    All,
+    Only(BTreeSet<String>),
+}
+impl ActiveFeatures {
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(features) => features.contains(feature),
+        }
+    }
+}
+impl std::fmt::Display for ActiveFeatures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "*"),
+            Self::Only(features) => {
+                write!(f, "{}", features.iter().cloned().collect::<Vec<_>>().join(","))
+            }
+        }
+    }
+}
+/// A `cfg(...)` predicate; anything but `feature = "..."`, `not`, `all` and
+/// `any` is `Other` and can't be decided here.
+enum Predicate {
+    Feature(String),
+    Not(Box<Predicate>),
+    All(Vec<Predicate>),
+    Any(Vec<Predicate>),
+    Other,
+}
+impl Predicate {
+    /// Parses one predicate at the start of `input`, returning the rest.
+    fn parse(input: &str) -> Option<(Self, &str)> {
+        let input = input.trim_start();
+        let end = input
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(input.len());
+        let (name, rest) = input.split_at(end);
+        if name.is_empty() {
+            return None;
+        }
+        let rest = rest.trim_start();
+        if let Some(mut rest) = rest.strip_prefix('(') {
+            let mut args = Vec::new();
+            loop {
+                rest = rest.trim_start();
+                if let Some(after) = rest.strip_prefix(')') {
+                    rest = after;
+                    break;
+                }
+                let (arg, after) = Self::parse(rest)?;
+                args.push(arg);
+                let after = after.trim_start();
+                rest = match after.strip_prefix(',') {
+                    Some(after) => after,
+                    None if after.starts_with(')') => after,
+                    None => return None,
+                };
+            }
+            let predicate = match name {
+                "not" if args.len() == 1 => Self::Not(Box::new(args.remove(0))),
+                "all" => Self::All(args),
+                "any" => Self::Any(args),
+                _ => Self::Other,
+            };
+            return Some((predicate, rest));
+        }
+        if let Some(value) = rest.strip_prefix('=') {
+            let (value, rest) = value.trim_start().strip_prefix('"')?.split_once('"')?;
+            let predicate = match name {
+                "feature" => Self::Feature(value.to_string()),
+                _ => Self::Other,
+            };
+            return Some((predicate, rest));
+        }
+        Some((Self::Other, rest))
+    }
+    /// Whether the predicate holds with `active`; `None` when it depends on
+    /// something other than features (target, `test`, ...).
+    fn eval(&self, active: &ActiveFeatures) -> Option<bool> {
+        match self {
+            Self::Feature(name) => Some(active.is_enabled(name)),
+            Self::Not(inner) => inner.eval(active).map(|holds| !holds),
+            Self::All(args) => {
+                let values: Vec<_> = args.iter().map(|a| a.eval(active)).collect();
+                if values.contains(&Some(false)) {
+                    Some(false)
+                } else {
+                    values.iter().all(Option::is_some).then_some(true)
+                }
+            }
+            Self::Any(args) => {
+                let values: Vec<_> = args.iter().map(|a| a.eval(active)).collect();
+                if values.contains(&Some(true)) {
+                    Some(true)
+                } else {
+                    values.iter().all(Option::is_some).then_some(false)
+                }
+            }
+            Self::Other => None,
+        }
+    }
+}
+/// Whether the `#[cfg(...)]` (or `#![cfg(...)]` with `inner`) attribute that
+/// `line` starts with is false with `active`. Predicates that don't depend
+/// only on features are never ruled out.
+fn cfg_rules_out(line: &str, inner: bool, active: &ActiveFeatures) -> bool {
+    let prefix = if inner { "#![cfg(" } else { "#[cfg(" };
+    line.trim_start()
+        .strip_prefix(prefix)
+        .and_then(Predicate::parse)
+        .filter(|(_, rest)| rest.trim_start().starts_with(')'))
+        .is_some_and(|(predicate, _)| predicate.eval(active) == Some(false))
+}
+/// `content` with the code that `active` compiles out blanked: items,
+/// fields, arms and statements under a `#[cfg]` that is false (brace-matched,
+/// line based), or the whole file under a false `#![cfg]`. Lines are
+/// emptied rather than removed, so line numbers still match the file.
+pub fn mask_inactive(content: &str, active: &ActiveFeatures) -> String {
+    if content.lines().any(|line| cfg_rules_out(line, true, active)) {
+        return content.lines().map(|_| "\n").collect();
+    }
+    let mut masked = String::with_capacity(content.len());
+    let mut pending = false;
+    let mut depth: Option<i64> = None;
+    for line in content.lines() {
+        let code = line.split("//").next().unwrap_or_default().trim_end();
+        let opens = code.matches('{').count() as i64;
+        let closes = code.matches('}').count() as i64;
+        if let Some(open) = depth.as_mut() {
+            *open += opens - closes;
+            if *open <= 0 {
+                depth = None;
+            }
+        } else if pending || cfg_rules_out(code, false, active) {
+            pending = true;
+            if opens > closes {
+                depth = Some(opens - closes);
+                pending = false;
+            } else if opens > 0 || code.ends_with(';') || code.ends_with(',') {
+                pending = false;
+            }
+        } else {
+            masked.push_str(line);
+        }
+        masked.push('\n');
+    }
+    masked
+}
+/// Contents of the source file at `path` as the scanners see it: with the
+/// code of disabled features masked once [`FeatureArgs::activate`] set them
+/// in `options`.
+pub fn read_source(path: &Path, options: &RunOptions) -> std::io::Result<String> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(match &options.features {
+        Some(active) => mask_inactive(&content, active),
+        None => content,
+    })
+}
@@ -0,0 +1,193 @@
+#![doc = " # Fleet - Scan many projects at once"]
+#![doc = ""]
+#![doc = " `trae scan --projects`: un scan por proyecto en paralelo y una tabla consolidada"]
+use crate::cli::ScanCounts;
+use crate::config::RunOptions;
+use crate::core::score::{duplication_factor, ScoreBreakdown, ScoreWeights};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+/// Directories under `dir` with a `Cargo.toml`, sorted. A project nested in
+/// another one (a workspace member, a crate in `examples/`) is left out: the
+/// scan of the outer project already covers it. `target/` directories and
+/// hidden ones (unless `options` include them) are not searched.
+pub fn discover_projects(dir: &Path, options: &RunOptions) -> Vec<PathBuf> {
+    let include_hidden = options.include_hidden;
+    let mut manifests: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| {
+            (include_hidden || !crate::utils::path::is_hidden(e))
+                && !(e.depth() > 0 && e.file_type().is_dir() && e.file_name() == "target")
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == "Cargo.toml" && e.file_type().is_file())
+        .filter_map(|e| e.path().parent().map(Path::to_path_buf))
+        .collect();
+    manifests.sort();
+    let mut projects: Vec<PathBuf> = Vec::new();
+    for project in manifests {
+        if !projects.iter().any(|outer| project.starts_with(outer)) {
+            projects.push(project);
+        }
+    }
+    projects
+}
+/// Outcome of one project's scan: its counts and score, or why it failed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectScan {
+    pub project: String,
+    #[serde(flatten)]
+    pub counts: Option<ScanCounts>,
+    pub quality_score: Option<f64>,
+    pub error: Option<String>,
+}
+impl ProjectScan {
+    fn failed(project: String, error: String) -> Self {
+        Self { project, counts: None, quality_score: None, error: Some(error) }
+    }
+}
+/// Scans every project in a `trae scan --path <project> --count-only --json`
+/// child of `exe`, at most `max_parallel` at once; `args` are added to each
+/// (scan flags, `--no-jarvix`). One project failing doesn't stop the rest.
+/// Results keep the order of `projects`; scores read files under `options`.
+pub async fn scan_projects(
+    exe: &Path,
+    projects: &[PathBuf],
+    args: &[String],
+    max_parallel: usize,
+    weights: &ScoreWeights,
+    options: &RunOptions,
+) -> Vec<ProjectScan> {
+    let slots = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, project) in projects.iter().enumerate() {
+        let slots = Arc::clone(&slots);
+        let mut command = tokio::process::Command::new(exe);
+        command.arg("scan").arg("--path").arg(project).args(["--count-only", "--json"]).args(args);
+        let project = project.clone();
+        let weights = weights.clone();
+        let options = options.clone();
+        tasks.spawn(async move {
+            let _slot = slots.acquire_owned().await;
+            (index, scan_project(command, &project, &weights, &options).await)
+        });
+    }
+    let mut scans: Vec<Option<ProjectScan>> = vec![None; projects.len()];
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, scan)) = joined {
+            scans[index] = Some(scan);
+        }
+    }
+    scans
+        .into_iter()
+        .zip(projects)
+        .map(|(scan, project)| {
+            scan.unwrap_or_else(|| {
+                ProjectScan::failed(project.display().to_string(), "tarea abortada".to_string())
+            })
+        })
+        .collect()
+}
+async fn scan_project(
+    mut command: tokio::process::Command,
+    project: &Path,
+    weights: &ScoreWeights,
+    options: &RunOptions,
+) -> ProjectScan {
+    let name = project.display().to_string();
+    let output = match command.output().await {
+        Ok(output) => output,
+        Err(e) => return ProjectScan::failed(name, format!("no se pudo ejecutar trae: {e}")),
+    };
+    // 0 is a clean scan and 2 a scan with critical issues; both print counts
+    let counts = matches!(output.status.code(), Some(0 | 2))
+        .then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .rev()
+                .find_map(|line| serde_json::from_str::<ScanCounts>(line).ok())
+        })
+        .flatten();
+    let Some(counts) = counts else {
+        // trae prints its error as `❌ ...`; anything else is a crash
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .find_map(|line| line.strip_prefix("❌"))
+            .or_else(|| stderr.lines().rev().find(|line| !line.trim().is_empty()))
+            .map_or("el scan terminó sin conteos", str::trim)
+            .to_string();
+        return ProjectScan::failed(name, reason);
+    };
+    let issues = counts.critical + counts.warning + counts.info;
+    ProjectScan {
+        project: name,
+        counts: Some(counts),
+        quality_score: Some(quality_score(project, issues, weights, options)),
+        error: None,
+    }
+}
+/// Score of the project at `dir` with `issues` scan issues, from its Rust
+/// files the way `trae analyze --explain` computes it.
+pub fn quality_score(
+    dir: &Path,
+    issues: usize,
+    weights: &ScoreWeights,
+    options: &RunOptions,
+) -> f64 {
+    let lines: Vec<f64> = crate::utils::path::rust_files(dir, None, options)
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .map(|content| content.lines().count() as f64)
+        .collect();
+    let total_lines = lines.iter().sum::<f64>() as usize;
+    let complexity = crate::core::analyzer::analyze_code_fourier(&lines);
+    let duplication = duplication_factor(total_lines, lines.len());
+    let files = lines.len();
+    ScoreBreakdown::compute(files, issues, total_lines, complexity, duplication, weights).score
+}
+/// The consolidated table: one row per project, failures included.
+pub fn print_table(scans: &[ProjectScan]) {
+    use colored::Colorize;
+    let width = scans.iter().map(|s| s.project.chars().count()).max().unwrap_or(0).max(8);
+    let header = format!(
+        "{:<width$}  {:>8}  {:>8}  {:>6}  {:>7}",
+        "Proyecto", "Críticos", "Warnings", "Info", "Calidad"
+    );
+    println!("{}", header.bold());
+    for scan in scans {
+        match (&scan.counts, &scan.error) {
+            (Some(counts), _) => {
+                let row = format!(
+                    "{:<width$}  {:>8}  {:>8}  {:>6}  {:>7.1}",
+                    scan.project,
+                    counts.critical,
+                    counts.warning,
+                    counts.info,
+                    scan.quality_score.unwrap_or_default()
+                );
+                if counts.critical > 0 {
+                    println!("{}", row.red());
+                } else {
+                    println!("{row}");
+                }
+            }
+            (None, error) => println!(
+                "{:<width$}  {}",
+                scan.project,
+                format!("❌ {}", error.as_deref().unwrap_or("falló")).red()
+            ),
+        }
+    }
+}
+/// `--export` document of a batch scan.
+pub fn export(path: &str, scans: &[ProjectScan]) -> Result<()> {
+    let report = serde_json::json!({
+        "timestamp": chrono::Utc::now(),
+        "projects": scans,
+    });
+    std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
@@ -0,0 +1,221 @@
+#![doc = " # Freshness - Dependency age from crates.io"]
+#![doc = ""]
+#![doc = " Fechas de publicación de la versión resuelta y la última de cada dependencia, en caché"]
+use crate::config::RunOptions;
+use crate::core::cargo::metadata_command;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+/// crates.io API endpoint for one crate's metadata.
+pub const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
+/// Directory, relative to the project root, with one cached response per crate.
+pub const CACHE_DIR: &str = ".trae/crates-io";
+/// Cached responses younger than this are reused instead of asking crates.io.
+pub const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Dependencies whose resolved version was published this many days before
+/// the latest are flagged by default.
+pub const DEFAULT_MAX_AGE_DAYS: i64 = 365;
+/// Release dates of a crate's versions, as cached under [`CACHE_DIR`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Releases {
+    pub fetched_at: DateTime<Utc>,
+    /// Newest stable version (newest of any kind if there is none).
+    pub latest: String,
+    pub published: BTreeMap<String, DateTime<Utc>>,
+}
+impl Releases {
+    /// Reads a `GET /api/v1/crates/{name}` response; `None` if it lacks the
+    /// latest version. Yanked versions keep their date, as a lockfile may
+    /// still resolve to one.
+    pub fn from_crates_io(response: &serde_json::Value, fetched_at: DateTime<Utc>) -> Option<Self> {
+        let krate = response.get("crate")?;
+        let latest = ["max_stable_version", "max_version", "newest_version"]
+            .iter()
+            .find_map(|key| krate.get(key).and_then(|v| v.as_str()))?
+            .to_string();
+        let published = response
+            .get("versions")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|version| {
+                let num = version.get("num")?.as_str()?;
+                let created = version.get("created_at")?.as_str()?;
+                let created = DateTime::parse_from_rfc3339(created).ok()?.with_timezone(&Utc);
+                Some((num.to_string(), created))
+            })
+            .collect();
+        Some(Self { fetched_at, latest, published })
+    }
+}
+/// How far a resolved dependency is behind its latest release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyAge {
+    pub name: String,
+    pub version: String,
+    pub released: Option<DateTime<Utc>>,
+    pub latest: String,
+    pub latest_released: Option<DateTime<Utc>>,
+}
+impl DependencyAge {
+    pub fn new(name: &str, version: &str, releases: &Releases) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            released: releases.published.get(version).copied(),
+            latest: releases.latest.clone(),
+            latest_released: releases.published.get(&releases.latest).copied(),
+        }
+    }
+    /// Days between the resolved release and the latest one; 0 when the
+    /// resolved version is the latest (or newer), `None` without both dates.
+    pub fn gap_days(&self) -> Option<i64> {
+        if self.version == self.latest {
+            return Some(0);
+        }
+        Some((self.latest_released? - self.released?).num_days().max(0))
+    }
+    /// Whether the resolved version is more than `max_days` behind the latest.
+    pub fn is_stale(&self, max_days: i64) -> bool {
+        self.gap_days().is_some_and(|days| days > max_days)
+    }
+}
+/// Per-crate crates.io responses cached under `root/`[`CACHE_DIR`].
+pub struct ReleaseCache {
+    dir: PathBuf,
+}
+impl ReleaseCache {
+    pub fn new(root: &Path) -> Self {
+        Self { dir: root.join(CACHE_DIR) }
+    }
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+    /// The cached releases of `name`, if any, and no older than `ttl`
+    /// (`None` accepts any age).
+    pub fn load(&self, name: &str, ttl: Option<Duration>) -> Option<Releases> {
+        let content = std::fs::read_to_string(self.path(name)).ok()?;
+        let releases: Releases = serde_json::from_str(&content).ok()?;
+        let age = Utc::now().signed_duration_since(releases.fetched_at);
+        let age = age.to_std().unwrap_or_default();
+        ttl.is_none_or(|ttl| age < ttl).then_some(releases)
+    }
+    pub fn save(&self, name: &str, releases: &Releases) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(name), serde_json::to_string_pretty(releases)?)?;
+        Ok(())
+    }
+}
+/// Looks up crates.io release dates, through the cache.
+pub struct ReleaseLookup {
+    client: reqwest::Client,
+    cache: ReleaseCache,
+    offline: bool,
+}
+impl ReleaseLookup {
+    /// A lookup caching under `root`; with `offline` only the cache is read,
+    /// whatever its age.
+    pub fn new(root: &Path, offline: bool) -> Result<Self> {
+        let client = crate::utils::http::client_builder()?
+            .timeout(Duration::from_secs(15))
+            .build()?;
+        Ok(Self { client, cache: ReleaseCache::new(root), offline })
+    }
+    /// Releases of `name`: a fresh cache entry, else crates.io (the answer
+    /// is cached), else `None` when offline without a cache entry.
+    pub async fn releases(&self, name: &str) -> Result<Option<Releases>> {
+        if let Some(cached) = self.cache.load(name, (!self.offline).then_some(CACHE_TTL)) {
+            return Ok(Some(cached));
+        }
+        if self.offline {
+            return Ok(None);
+        }
+        let response: serde_json::Value = self
+            .client
+            .get(format!("{CRATES_IO_API}/{name}"))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("crates.io no respondió para {name}"))?
+            .json()
+            .await?;
+        let releases = Releases::from_crates_io(&response, Utc::now())
+            .with_context(|| format!("Respuesta de crates.io inesperada para {name}"))?;
+        // Best effort: a read-only checkout still gets its report
+        let _ = self.cache.save(name, &releases);
+        Ok(Some(releases))
+    }
+}
+/// Where a resolved dependency comes from.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DependencySource {
+    CratesIo,
+    Git,
+    Path,
+    /// Another registry; its source string.
+    Registry(String),
+}
+/// A dependency a workspace member declares directly, as resolved.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DirectDependency {
+    pub name: String,
+    pub version: String,
+    pub source: DependencySource,
+}
+/// Direct dependencies of every workspace member (workspace members depending
+/// on each other excluded), deduplicated by name and version.
+pub fn direct_dependencies(metadata: &cargo_metadata::Metadata) -> Vec<DirectDependency> {
+    let packages: BTreeMap<_, _> = metadata.packages.iter().map(|p| (&p.id, p)).collect();
+    let Some(resolve) = &metadata.resolve else {
+        return Vec::new();
+    };
+    let mut direct: Vec<DirectDependency> = resolve
+        .nodes
+        .iter()
+        .filter(|node| metadata.workspace_members.contains(&node.id))
+        .flat_map(|node| &node.deps)
+        .filter(|dep| !metadata.workspace_members.contains(&dep.pkg))
+        .filter_map(|dep| packages.get(&dep.pkg))
+        .map(|pkg| {
+            let source = match &pkg.source {
+                None => DependencySource::Path,
+                Some(source) if source.is_crates_io() => DependencySource::CratesIo,
+                Some(source) if source.repr.starts_with("git+") => DependencySource::Git,
+                Some(source) => DependencySource::Registry(source.repr.clone()),
+            };
+            DirectDependency { name: pkg.name.clone(), version: pkg.version.to_string(), source }
+        })
+        .collect();
+    direct.sort();
+    direct.dedup();
+    direct
+}
+/// `cargo metadata` of the current project, resolved offline when `options`
+/// say so.
+pub fn resolved_metadata(options: &RunOptions) -> Result<cargo_metadata::Metadata> {
+    let command = metadata_command(options);
+    command.exec().map_err(|e| anyhow::anyhow!("cargo metadata falló: {e}"))
+}
+/// Ages of the crates.io dependencies among `deps` (others are skipped), and
+/// `name (reason)` for those `lookup` could not find. `on_lookup` is called
+/// with each name before it is looked up.
+pub async fn dependency_ages(
+    lookup: &ReleaseLookup,
+    deps: &[DirectDependency],
+    on_lookup: impl Fn(&str),
+) -> (Vec<DependencyAge>, Vec<String>) {
+    let mut ages = Vec::new();
+    let mut missing = Vec::new();
+    for dep in deps.iter().filter(|dep| dep.source == DependencySource::CratesIo) {
+        on_lookup(&dep.name);
+        match lookup.releases(&dep.name).await {
+            Ok(Some(releases)) => ages.push(DependencyAge::new(&dep.name, &dep.version, &releases)),
+            Ok(None) => missing.push(format!("{} (sin caché en modo offline)", dep.name)),
+            Err(e) => missing.push(format!("{} ({e})", dep.name)),
+        }
+    }
+    (ages, missing)
+}
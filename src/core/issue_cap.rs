@@ -0,0 +1,65 @@
+#![doc = " # Issue Cap - Per-severity limit on collected issues"]
+#![doc = ""]
+#![doc = " Límite de `--max-issues` para scan y analyze, aplicado por severidad"]
+use crate::core::analyzer::{AnalysisIssue, IssueSeverity};
+/// Keeps at most `max` issues of each severity, so thousands of info
+/// findings in generated code can't crowd out the critical ones. Without a
+/// limit every issue is kept.
+#[derive(Debug, Clone, Default)]
+pub struct IssueCap {
+    max: Option<usize>,
+    kept: [usize; 3],
+    truncated: bool,
+}
+impl IssueCap {
+    pub fn new(max: Option<usize>) -> Self {
+        Self { max, ..Self::default() }
+    }
+    /// Whether `issue` is kept; kept issues count towards the limit of their
+    /// severity, dropped ones mark the result as truncated.
+    pub fn admit(&mut self, issue: &AnalysisIssue) -> bool {
+        let slot = &mut self.kept[slot(&issue.severity)];
+        if self.max.is_some_and(|max| *slot >= max) {
+            self.truncated = true;
+            return false;
+        }
+        *slot += 1;
+        true
+    }
+    /// Appends the issues of `more` that are kept to `issues`.
+    pub fn extend(
+        &mut self,
+        issues: &mut Vec<AnalysisIssue>,
+        more: impl IntoIterator<Item = AnalysisIssue>,
+    ) {
+        issues.extend(more.into_iter().filter(|issue| self.admit(issue)));
+    }
+    /// Drops the issues of `issues` past the limit, in order.
+    pub fn retain(&mut self, issues: &mut Vec<AnalysisIssue>) {
+        issues.retain(|issue| self.admit(issue));
+    }
+    /// Every severity reached the limit: scanning further can't add issues.
+    pub fn is_full(&self) -> bool {
+        self.max.is_some_and(|max| self.kept.iter().all(|kept| *kept >= max))
+    }
+    /// The limit, when issues were dropped because of it.
+    pub fn truncated_at(&self) -> Option<usize> {
+        self.max.filter(|_| self.truncated)
+    }
+    /// What to tell the user when issues were dropped.
+    pub fn notice(&self) -> Option<String> {
+        self.truncated_at().map(|max| {
+            format!(
+                "✂️ truncated at {max} issues per severity; \
+                 run without --max-issues for full results"
+            )
+        })
+    }
+}
+const fn slot(severity: &IssueSeverity) -> usize {
+    match severity {
+        IssueSeverity::Critical => 0,
+        IssueSeverity::Warning => 1,
+        IssueSeverity::Info => 2,
+    }
+}
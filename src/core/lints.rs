@@ -0,0 +1,68 @@
+#![doc = " # Lints - Clippy diagnostics per lint and per crate"]
+#![doc = ""]
+#![doc = " Cuenta los diagnósticos de `cargo clippy --message-format=json` por lint y por crate"]
+use cargo_metadata::diagnostic::DiagnosticLevel;
+use cargo_metadata::Message;
+use std::collections::{BTreeMap, HashSet};
+/// Lint findings of one crate (compilation target).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrateLints {
+    pub warnings: usize,
+    /// Error-level diagnostics: lints denied with `-D` plus compile errors.
+    pub denied: usize,
+}
+/// Clippy diagnostics aggregated over every crate of the run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintSummary {
+    pub crates: BTreeMap<String, CrateLints>,
+    /// Findings per lint (`clippy::needless_return`, `unused_variables`, `E0425`).
+    pub lints: BTreeMap<String, usize>,
+}
+impl LintSummary {
+    /// Counts the compiler messages in the JSON output of cargo. Messages
+    /// without a source location ("N warnings emitted") are not findings, and
+    /// a crate linted twice (lib and its tests with `--all-targets`) reports
+    /// the same finding once.
+    pub fn parse(json: &str) -> Self {
+        let mut summary = Self::default();
+        let mut seen = HashSet::new();
+        for message in Message::parse_stream(json.as_bytes()).flatten() {
+            let Message::CompilerMessage(msg) = message else {
+                continue;
+            };
+            let diagnostic = &msg.message;
+            if diagnostic.spans.is_empty() {
+                continue;
+            }
+            let rendered = diagnostic.rendered.as_deref().unwrap_or(&diagnostic.message);
+            if !seen.insert((msg.target.name.clone(), rendered.to_string())) {
+                continue;
+            }
+            let denied = match diagnostic.level {
+                DiagnosticLevel::Warning => false,
+                DiagnosticLevel::Error | DiagnosticLevel::Ice => true,
+                _ => continue,
+            };
+            let counts = summary.crates.entry(msg.target.name.clone()).or_default();
+            if denied {
+                counts.denied += 1;
+            } else {
+                counts.warnings += 1;
+            }
+            let lint = diagnostic
+                .code
+                .as_ref()
+                .map_or_else(|| "(sin código)".to_string(), |code| code.code.clone());
+            *summary.lints.entry(lint).or_default() += 1;
+        }
+        summary
+    }
+    /// Warnings over all crates.
+    pub fn warnings(&self) -> usize {
+        self.crates.values().map(|c| c.warnings).sum()
+    }
+    /// Denied lints and errors over all crates.
+    pub fn denied(&self) -> usize {
+        self.crates.values().map(|c| c.denied).sum()
+    }
+}
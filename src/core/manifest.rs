@@ -0,0 +1,262 @@
+#![doc = " # Manifest - Typed Cargo.toml"]
+#![doc = ""]
+#![doc = " Deserializa `Cargo.toml` con el crate `toml`, para que ningún comando lo lea línea a línea"]
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+/// Profiles cargo defines without a `[profile.*]` section.
+pub const BUILTIN_PROFILES: [&str; 4] = ["dev", "release", "test", "bench"];
+/// The parts of `Cargo.toml` trae looks at; every other key is ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Manifest {
+    pub package: Option<Package>,
+    pub workspace: Option<toml::Value>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencySpec>,
+    #[serde(default)]
+    pub dev_dependencies: BTreeMap<String, DependencySpec>,
+    #[serde(default)]
+    pub build_dependencies: BTreeMap<String, DependencySpec>,
+    /// `[target.'cfg(...)'.*dependencies]`, keyed by the target expression.
+    #[serde(default)]
+    pub target: BTreeMap<String, TargetDependencies>,
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+    /// `[features]`: each feature and what it enables.
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+}
+#[derive(Debug, Clone, Deserialize)]
+pub struct Package {
+    pub name: String,
+    /// `"2021"`, or `{ workspace = true }` when inherited.
+    pub edition: Option<toml::Value>,
+}
+/// Every Rust edition, oldest first.
+pub const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+/// Table of `Cargo.toml` that declares the edition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditionTable {
+    /// `[package]`
+    Package,
+    /// `[workspace.package]`, inherited with `edition.workspace = true`.
+    WorkspacePackage,
+}
+impl EditionTable {
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::Package => "[package]",
+            Self::WorkspacePackage => "[workspace.package]",
+        }
+    }
+}
+/// Dependency tables of one `[target.X]` section.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TargetDependencies {
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencySpec>,
+    #[serde(default)]
+    pub dev_dependencies: BTreeMap<String, DependencySpec>,
+    #[serde(default)]
+    pub build_dependencies: BTreeMap<String, DependencySpec>,
+}
+/// `name = "1"`, or an inline/dotted table (`name = { version = "1", ... }`,
+/// `[dependencies.name]`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum DependencySpec {
+    Version(String),
+    Detailed(DetailedDependency),
+}
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct DetailedDependency {
+    pub version: Option<String>,
+    pub path: Option<String>,
+    pub git: Option<String>,
+    /// Crate actually fetched when the key renames it (`json = { package = "serde_json" }`).
+    pub package: Option<String>,
+    #[serde(default)]
+    pub workspace: bool,
+    #[serde(default)]
+    pub optional: bool,
+}
+impl DependencySpec {
+    /// `path`/`git` dependencies without a `version` requirement: nothing
+    /// fixes which code is built once the source moves.
+    pub fn is_unversioned_source(&self) -> bool {
+        match self {
+            Self::Version(_) => false,
+            Self::Detailed(d) => {
+                d.version.is_none() && !d.workspace && (d.path.is_some() || d.git.is_some())
+            }
+        }
+    }
+    /// `package = "..."`: the crate behind a renamed dependency.
+    pub fn package(&self) -> Option<&str> {
+        match self {
+            Self::Version(_) => None,
+            Self::Detailed(d) => d.package.as_deref(),
+        }
+    }
+    /// `optional = true`: only built when a feature enables it.
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Detailed(d) if d.optional)
+    }
+}
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profile {
+    pub panic: Option<String>,
+}
+/// Table a dependency is declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+/// A dependency declaration; `target` is the platform of a
+/// `[target.'cfg(...)'.dependencies]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub name: String,
+    pub kind: DependencyKind,
+    pub target: Option<String>,
+}
+impl Manifest {
+    /// Parses `dir/Cargo.toml`.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join("Cargo.toml");
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("No se pudo leer {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("{} inválido", path.display()))
+    }
+    #[doc = " Parsea el contenido de un Cargo.toml"]
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+    /// Edition of the root package and the table declaring it: 2015 in
+    /// `[package]` without an `edition` key. A virtual manifest has one only
+    /// in `[workspace.package]`; `None` when there is no edition to read.
+    pub fn edition(&self) -> Option<(String, EditionTable)> {
+        let inherited = || {
+            let edition = self.workspace.as_ref()?.get("package")?.get("edition")?;
+            Some((edition.as_str()?.to_string(), EditionTable::WorkspacePackage))
+        };
+        let Some(package) = &self.package else {
+            return inherited();
+        };
+        match &package.edition {
+            None => Some(("2015".to_string(), EditionTable::Package)),
+            Some(toml::Value::String(edition)) => Some((edition.clone(), EditionTable::Package)),
+            Some(_) => inherited(),
+        }
+    }
+    /// Every dependency declaration: the top-level tables (normal, dev,
+    /// build), then each `[target.X]` in name order.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        self.specs().map(|(dep, _)| dep).collect()
+    }
+    /// [`Self::dependencies`] with the declaration of each.
+    pub fn specs(&self) -> impl Iterator<Item = (Dependency, &DependencySpec)> {
+        let targets = self.target.iter().flat_map(|(target, t)| {
+            declared(
+                Some(target),
+                [&t.dependencies, &t.dev_dependencies, &t.build_dependencies],
+            )
+        });
+        declared(
+            None,
+            [
+                &self.dependencies,
+                &self.dev_dependencies,
+                &self.build_dependencies,
+            ],
+        )
+        .chain(targets)
+    }
+    /// Whether any table declares `name`.
+    pub fn depends_on(&self, name: &str) -> bool {
+        self.specs().any(|(dep, _)| dep.name == name)
+    }
+}
+/// Entries of the normal, dev and build `tables` of one section.
+fn declared<'a>(
+    target: Option<&'a String>,
+    tables: [&'a BTreeMap<String, DependencySpec>; 3],
+) -> impl Iterator<Item = (Dependency, &'a DependencySpec)> {
+    tables
+        .into_iter()
+        .zip([
+            DependencyKind::Normal,
+            DependencyKind::Dev,
+            DependencyKind::Build,
+        ])
+        .flat_map(move |(table, kind)| {
+            table.iter().map(move |(name, spec)| {
+                let dep = Dependency {
+                    name: name.clone(),
+                    kind,
+                    target: target.cloned(),
+                };
+                (dep, spec)
+            })
+        })
+}
+/// 1-based line where `name` is declared in `content` (`name = ...` or a
+/// `[...dependencies.name]` header), for pointing issues at the manifest.
+pub fn declaration_line(content: &str, name: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim();
+            line.strip_prefix(name)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+                || (line.starts_with('[') && line.ends_with(&format!("dependencies.{name}]")))
+        })
+        .map(|idx| idx + 1)
+}
+/// 1-based line of `name = ...` inside the `[features]` table of `content`.
+pub fn feature_line(content: &str, name: &str) -> Option<usize> {
+    let start = content.lines().position(|line| line.trim() == "[features]")? + 1;
+    content
+        .lines()
+        .enumerate()
+        .skip(start)
+        .take_while(|(_, line)| !line.trim_start().starts_with('['))
+        .find(|(_, line)| {
+            let key = line.trim_start();
+            let key = key
+                .strip_prefix('"')
+                .and_then(|k| k.strip_prefix(name))
+                .and_then(|rest| rest.strip_prefix('"'))
+                .or_else(|| key.strip_prefix(name));
+            key.is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|(idx, _)| idx + 1)
+}
+/// `content` with the `edition` key of `table` set to `edition` (added after
+/// the header when missing); `None` when the table isn't there.
+pub fn with_edition(content: &str, table: EditionTable, edition: &str) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let header = lines.iter().position(|line| line.trim() == table.header())?;
+    let key = lines
+        .iter()
+        .enumerate()
+        .skip(header + 1)
+        .take_while(|(_, line)| !line.trim_start().starts_with('['))
+        .find(|(_, line)| {
+            let rest = line.trim_start().strip_prefix("edition");
+            rest.is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|(idx, _)| idx);
+    let line = format!("edition = \"{edition}\"");
+    match key {
+        Some(idx) => lines[idx] = line,
+        None => lines.insert(header + 1, line),
+    }
+    Some(lines.join("\n") + "\n")
+}
@@ -0,0 +1,112 @@
+#![doc = " # Markers - TODO-style comment markers"]
+#![doc = ""]
+#![doc = " Marcadores que reporta `trae scan` (`FIXME`, `TODO`, `HACK`...) y su severidad"]
+use crate::core::analyzer::{AnalysisIssue, IssueSeverity};
+use anyhow::Result;
+use std::collections::BTreeMap;
+/// Markers when `[markers]` doesn't change them.
+pub const DEFAULT_MARKERS: [(&str, IssueSeverity); 3] = [
+    ("FIXME", IssueSeverity::Critical),
+    ("XXX", IssueSeverity::Warning),
+    ("TODO", IssueSeverity::Info),
+];
+/// The markers `trae scan` reports and the severity of each: the defaults
+/// plus the `[markers]` section of the config, which adds markers, changes
+/// their severity or turns one `off`.
+#[derive(Debug, Clone)]
+pub struct TodoMarkers {
+    /// Most severe first, then by name, so the worst marker of a line wins.
+    markers: Vec<(String, IssueSeverity)>,
+}
+impl Default for TodoMarkers {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("default markers")
+    }
+}
+impl TodoMarkers {
+    /// Markers from `[markers]`, mapping each marker to `critical`, `warning`,
+    /// `info` or `off`. A marker is a word of letters, digits and `_`.
+    pub fn from_config(config: &BTreeMap<String, String>) -> Result<Self> {
+        let mut markers: BTreeMap<String, Option<IssueSeverity>> = DEFAULT_MARKERS
+            .iter()
+            .map(|(marker, severity)| ((*marker).to_string(), Some(severity.clone())))
+            .collect();
+        for (marker, severity) in config {
+            if marker.is_empty() || !marker.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                anyhow::bail!("[markers] `{marker}` no es un marcador (letras, dígitos y _)");
+            }
+            let severity = match severity.to_ascii_lowercase().as_str() {
+                "critical" => Some(IssueSeverity::Critical),
+                "warning" => Some(IssueSeverity::Warning),
+                "info" => Some(IssueSeverity::Info),
+                "off" => None,
+                other => anyhow::bail!(
+                    "[markers] {marker}: severidad `{other}` inválida \
+                     (critical, warning, info u off)"
+                ),
+            };
+            markers.insert(marker.clone(), severity);
+        }
+        let mut markers: Vec<_> = markers
+            .into_iter()
+            .filter_map(|(marker, severity)| Some((marker, severity?)))
+            .collect();
+        markers.sort_by(|a, b| b.1.rank().cmp(&a.1.rank()).then_with(|| a.0.cmp(&b.0)));
+        Ok(Self { markers })
+    }
+    /// Markers of the config in effect; the defaults when it has none.
+    pub fn load() -> Self {
+        Self::from_config(&crate::config::TraeConfig::load().markers).unwrap_or_default()
+    }
+    /// Marker names, most severe first.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.markers.iter().map(|(marker, _)| marker.as_str())
+    }
+    /// The most severe marker of `line` and whether it is in a comment.
+    /// `comment` is the line's comment text (see
+    /// [`mask_code_and_strings`](crate::utils::source::mask_code_and_strings)),
+    /// where `MARKER:` or a comment starting with the marker word counts, so
+    /// `/* TODO */` does too. Elsewhere only `MARKER:` counts.
+    pub fn find(&self, line: &str, comment: &str) -> Option<(&str, &IssueSeverity, bool)> {
+        self.markers.iter().find_map(|(marker, severity)| {
+            if comment.contains(&format!("{marker}:")) || leads_with(comment, marker) {
+                Some((marker.as_str(), severity, true))
+            } else if line.contains(&format!("{marker}:")) {
+                Some((marker.as_str(), severity, false))
+            } else {
+                None
+            }
+        })
+    }
+    /// How many of `issues` each marker produced, for markers with any.
+    pub fn counts(&self, issues: &[AnalysisIssue]) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for issue in issues.iter().filter(|i| i.category == "Code Quality") {
+            if let Some(marker) = self.names().find(|m| {
+                issue
+                    .description
+                    .strip_prefix(m)
+                    .is_some_and(|rest| rest.starts_with(" en línea "))
+            }) {
+                *counts.entry(marker.to_string()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+/// Issue description of `marker` found on 1-based `line_num`, whose text is
+/// `line`; [`TodoMarkers::counts`] recognises it.
+pub fn describe(marker: &str, line_num: usize, line: &str) -> String {
+    format!("{marker} en línea {line_num}: {}", line.trim())
+}
+/// Whether the comment text `comment`, past its `//`, `/*`, `*` or `!`,
+/// starts with the word `marker` on its own or as `MARKER(owner)`, so prose
+/// like `TODO-style` doesn't count.
+fn leads_with(comment: &str, marker: &str) -> bool {
+    comment
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '/' | '*' | '!'))
+        .strip_prefix(marker)
+        .is_some_and(|rest| {
+            rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == '(')
+        })
+}
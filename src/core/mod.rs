@@ -2,5 +2,24 @@
 #![doc = ""]
 #![doc = " Funcionalidades centrales de TRAE CLI"]
 pub mod analyzer;
+pub mod baseline;
+pub mod build_errors;
 pub mod cargo;
+pub mod coverage;
+pub mod crawl_cache;
+pub mod crawler;
 pub mod doctor;
+pub mod features;
+pub mod fleet;
+pub mod freshness;
+pub mod issue_cap;
+pub mod lints;
+pub mod manifest;
+pub mod markers;
+pub mod multilang;
+pub mod release_state;
+pub mod report;
+pub mod score;
+pub mod severity;
+pub mod unused_deps;
+pub mod workspace;
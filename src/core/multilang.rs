@@ -0,0 +1,334 @@
+#![doc = " # Multilang - Data-driven lint rules for non-Rust files"]
+#![doc = ""]
+#![doc = " Reglas por tipo de archivo de `scan --multilang`: integradas y `.trae/multilang.toml`"]
+use crate::core::analyzer::{AnalysisIssue, IssueSeverity};
+use anyhow::{anyhow, Context, Result};
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+/// Project file with user rules, relative to the project root.
+pub const RULES_FILE: &str = ".trae/multilang.toml";
+/// Built-in rules: file types, id (for `[overrides]`), pattern, message and
+/// severity.
+const BUILTIN: &[(&[&str], &str, &str, &str, &str)] = &[
+    (
+        &["js", "ts", "jsx", "tsx"],
+        "console-log",
+        r"console\.log",
+        "console.log en archivo JS",
+        "info",
+    ),
+    (
+        &["js", "ts", "jsx", "tsx"],
+        "debugger",
+        r"\bdebugger\s*;",
+        "debugger en archivo JS",
+        "warning",
+    ),
+    (&["py"], "print", r"print\(", "print() en archivo Python", "info"),
+    (&["py"], "breakpoint", r"\bbreakpoint\(\)", "breakpoint() en archivo Python", "warning"),
+    (&["go"], "fmt-println", r"fmt\.Println", "fmt.Println en archivo Go", "info"),
+    (&["rb"], "binding-pry", r"\bbinding\.pry\b", "binding.pry en archivo Ruby", "warning"),
+    (&["rb"], "puts", r"^\s*(puts|p)\s", "puts/p en archivo Ruby", "info"),
+    (
+        &["java"],
+        "system-out",
+        r"System\.(out|err)\.print",
+        "System.out.print en archivo Java",
+        "info",
+    ),
+    (
+        &["java"],
+        "print-stack-trace",
+        r"\.printStackTrace\(\)",
+        "printStackTrace() en archivo Java",
+        "info",
+    ),
+    (
+        &["c", "h", "cpp", "cc", "cxx", "hpp"],
+        "gets",
+        r"\bgets\s*\(",
+        "gets() en archivo C/C++",
+        "critical",
+    ),
+    (&["c", "h"], "printf", r"\bprintf\s*\(", "printf en archivo C", "info"),
+    (
+        &["cpp", "cc", "cxx", "hpp"],
+        "cout",
+        r"std::(cout|cerr)\s*<<",
+        "std::cout en archivo C++",
+        "info",
+    ),
+    (&["sh"], "set-x", r"^\s*set\s+-[a-wyzA-Z]*x", "set -x olvidado en script shell", "warning"),
+    (&["sh"], "todo", r"#.*\bTODO\b", "TODO en script shell", "info"),
+    (
+        &["dockerfile"],
+        "latest-tag",
+        r"(?i)^\s*FROM\s+\S+:latest\b",
+        "imagen base con tag latest",
+        "warning",
+    ),
+    (
+        &["dockerfile"],
+        "user-root",
+        r"(?i)^\s*USER\s+(root|0)\s*$",
+        "el contenedor corre como root",
+        "warning",
+    ),
+    (&["sql"], "select-star", r"(?i)\bSELECT\s+\*", "SELECT * en SQL", "info"),
+];
+/// Built-in rules that also look at comment lines.
+const COMMENT_RULES: &[&str] = &["todo"];
+/// Built-in file types of files whose extension isn't their type: file
+/// name globs and the type they map to. `[files]` entries come first.
+const BUILTIN_FILES: &[(&str, &str)] = &[
+    ("*.bash", "sh"),
+    ("*.zsh", "sh"),
+    ("Dockerfile", "dockerfile"),
+    ("Dockerfile.*", "dockerfile"),
+    ("*.dockerfile", "dockerfile"),
+    ("Containerfile", "dockerfile"),
+];
+/// Confidence of the built-in rules and of user rules that don't set one:
+/// a regex over a line can still hit a string or a commented-out tail.
+pub const RULE_CONFIDENCE: f64 = 0.7;
+/// One `{ pattern, message, severity, confidence, comments }` rule of
+/// `.trae/multilang.toml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    pattern: String,
+    message: String,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    confidence: Option<f64>,
+    #[serde(default)]
+    comments: bool,
+}
+/// A compiled rule: every line matching `pattern` is an issue, except
+/// comment lines unless `in_comments`.
+#[derive(Debug, Clone)]
+pub struct MultilangRule {
+    /// Name `[overrides]` uses for a built-in rule; `None` for user rules.
+    pub id: Option<String>,
+    pub pattern: Regex,
+    pub message: String,
+    pub severity: IssueSeverity,
+    pub confidence: f64,
+    pub in_comments: bool,
+}
+impl MultilangRule {
+    fn new(pattern: &str, message: &str, severity: &str) -> Result<Self> {
+        let pattern =
+            Regex::new(pattern).map_err(|e| anyhow!("Patrón inválido `{pattern}`: {e}"))?;
+        Ok(Self {
+            id: None,
+            pattern,
+            message: message.to_string(),
+            severity: parse_severity(severity)?,
+            confidence: RULE_CONFIDENCE,
+            in_comments: false,
+        })
+    }
+}
+/// Rules by file type: the extension (without the dot) unless a file name
+/// glob of [`BUILTIN_FILES`] or `[files]` says otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct MultilangRules {
+    rules: BTreeMap<String, Vec<MultilangRule>>,
+    /// File name globs and their type, the first match wins.
+    files: Vec<(GlobMatcher, String)>,
+}
+impl MultilangRules {
+    /// The rules trae ships with: debug prints for JS/TS, Python, Go, Ruby,
+    /// Java and C/C++, `gets()` in C/C++, `set -x` and TODOs in shell
+    /// scripts, `latest` tags and `USER root` in Dockerfiles and `SELECT *`
+    /// in SQL.
+    pub fn builtin() -> Self {
+        let mut rules = Self::default();
+        for (types, id, pattern, message, severity) in BUILTIN {
+            let mut rule = MultilangRule::new(pattern, message, severity).expect("built-in rule");
+            rule.id = Some((*id).to_string());
+            rule.in_comments = COMMENT_RULES.contains(id);
+            for ty in *types {
+                rules.rules.entry((*ty).to_string()).or_default().push(rule.clone());
+            }
+        }
+        for (glob, ty) in BUILTIN_FILES {
+            let glob = Glob::new(glob).expect("built-in file glob").compile_matcher();
+            rules.files.push((glob, (*ty).to_string()));
+        }
+        rules
+    }
+    /// Built-in rules plus the ones of `content`, a TOML table mapping each
+    /// file type to its rules. User rules are added after the built-in ones
+    /// of the same type. Two tables are settings instead: `[files]` maps
+    /// file name globs to a type (`"*.rs.in" = "rs"`, checked before the
+    /// built-in globs) and `[overrides]` gives a built-in rule, by id,
+    /// another severity or `off`. Every pattern, glob, id and severity is
+    /// checked here, so a typo fails the scan instead of silently matching
+    /// nothing.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut table: toml::value::Table = toml::from_str(content)?;
+        let mut setting = |name: &str| -> Result<BTreeMap<String, String>> {
+            match table.remove(name) {
+                Some(value) => value
+                    .try_into()
+                    .with_context(|| format!("[{name}] solo admite valores de texto")),
+                None => Ok(BTreeMap::new()),
+            }
+        };
+        let (files, overrides) = (setting("files")?, setting("overrides")?);
+        let specs: BTreeMap<String, Vec<RuleSpec>> = toml::Value::Table(table).try_into()?;
+        let mut rules = Self::builtin();
+        if !files.is_empty() {
+            let mut user = Vec::new();
+            for (glob, ty) in files {
+                let matcher = Glob::new(&glob)
+                    .with_context(|| format!("[files] glob inválido `{glob}`"))?
+                    .compile_matcher();
+                user.push((matcher, ty.trim_start_matches('.').to_string()));
+            }
+            user.append(&mut rules.files);
+            rules.files = user;
+        }
+        for (id, severity) in overrides {
+            rules.override_builtin(&id, &severity)?;
+        }
+        for (ext, specs) in specs {
+            let ext = ext.trim_start_matches('.').to_string();
+            for (i, spec) in specs.into_iter().enumerate() {
+                let severity = spec.severity.as_deref().unwrap_or("info");
+                let mut rule = MultilangRule::new(&spec.pattern, &spec.message, severity)
+                    .with_context(|| format!("Regla {} de [{ext}]", i + 1))?;
+                if let Some(confidence) = spec.confidence {
+                    if !(0.0..=1.0).contains(&confidence) {
+                        anyhow::bail!(
+                            "Regla {} de [{ext}]: la confianza va de 0 a 1, no {confidence}",
+                            i + 1
+                        );
+                    }
+                    rule.confidence = confidence;
+                }
+                rule.in_comments = spec.comments;
+                rules.rules.entry(ext.clone()).or_default().push(rule);
+            }
+        }
+        Ok(rules)
+    }
+    /// Rules for the project at `root`: [`RULES_FILE`] if present, else the
+    /// built-in ones.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(RULES_FILE);
+        if !path.exists() {
+            return Ok(Self::builtin());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("No se pudo leer {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Reglas inválidas en {}", path.display()))
+    }
+    /// `[overrides]`: the built-in rule `id` gets `severity`, or is dropped
+    /// when it is `off`.
+    fn override_builtin(&mut self, id: &str, severity: &str) -> Result<()> {
+        if !BUILTIN.iter().any(|(_, builtin, ..)| *builtin == id) {
+            anyhow::bail!("[overrides] `{id}` no es una regla integrada");
+        }
+        let severity = match severity.to_ascii_lowercase().as_str() {
+            "off" => None,
+            other => Some(parse_severity(other).with_context(|| format!("[overrides] {id}"))?),
+        };
+        for rules in self.rules.values_mut() {
+            rules.retain(|rule| rule.id.as_deref() != Some(id) || severity.is_some());
+            for rule in rules.iter_mut().filter(|rule| rule.id.as_deref() == Some(id)) {
+                rule.severity = severity.clone().expect("kept only with a severity");
+            }
+        }
+        self.rules.retain(|_, rules| !rules.is_empty());
+        Ok(())
+    }
+    /// File types with at least one rule.
+    pub fn file_types(&self) -> Vec<&str> {
+        self.rules.keys().map(String::as_str).collect()
+    }
+    /// Whether files of type `ext` are checked.
+    pub fn covers(&self, ext: &str) -> bool {
+        self.rules.contains_key(ext)
+    }
+    /// Type of the file at `path` if it is checked: the first file name glob
+    /// that matches, else its extension.
+    pub fn file_type(&self, path: &Path) -> Option<&str> {
+        let name = path.file_name()?.to_str()?;
+        let ty = match self.files.iter().find(|(glob, _)| glob.is_match(name)) {
+            Some((_, ty)) => ty.as_str(),
+            None => path.extension()?.to_str()?,
+        };
+        self.rules.get_key_value(ty).map(|(ty, _)| ty.as_str())
+    }
+    /// Issues of `content`, the text of a file of type `ext` reported as
+    /// `file`. Lines commented out with the language's line comment are
+    /// skipped, except by rules that look `in_comments`.
+    pub fn check(&self, ext: &str, file: &str, content: &str) -> Vec<AnalysisIssue> {
+        let Some(rules) = self.rules.get(ext) else {
+            return Vec::new();
+        };
+        let comment = line_comment(ext);
+        let mut issues = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            let commented = comment.is_some_and(|prefix| line.trim_start().starts_with(prefix));
+            let matching = rules
+                .iter()
+                .filter(|rule| !commented || rule.in_comments)
+                .filter(|rule| rule.pattern.is_match(line));
+            for rule in matching {
+                issues.push(AnalysisIssue {
+                    category: "Code Quality".to_string(),
+                    description: format!(
+                        "{} línea {}: {}",
+                        rule.message,
+                        line_num + 1,
+                        line.trim()
+                    ),
+                    severity: rule.severity.clone(),
+                    file: Some(file.to_string()),
+                    line: Some(line_num + 1),
+                    confidence: rule.confidence,
+                });
+            }
+        }
+        issues
+    }
+    /// Issues of the file at `path`; none if it can't be read or isn't
+    /// checked (see [`Self::file_type`]).
+    pub fn check_file(&self, path: &Path) -> Vec<AnalysisIssue> {
+        let Some(ext) = self.file_type(path) else {
+            return Vec::new();
+        };
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.check(ext, &crate::utils::path::portable(path), &content),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+/// `critical`, `warning` or `info`, in any case.
+fn parse_severity(severity: &str) -> Result<IssueSeverity> {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" => Ok(IssueSeverity::Critical),
+        "warning" => Ok(IssueSeverity::Warning),
+        "info" => Ok(IssueSeverity::Info),
+        other => anyhow::bail!("Severidad inválida `{other}` (critical, warning o info)"),
+    }
+}
+/// Line comment prefix of the languages trae knows; other types check
+/// every line.
+fn line_comment(ext: &str) -> Option<&'static str> {
+    match ext {
+        "py" | "rb" | "sh" | "bash" | "zsh" | "dockerfile" | "toml" | "yaml" | "yml" => Some("#"),
+        "js" | "ts" | "jsx" | "tsx" | "go" | "java" | "kt" | "c" | "h" | "cpp" | "cc" | "cxx"
+        | "hpp" | "cs" | "swift" | "php" => Some("//"),
+        "sql" | "lua" | "hs" => Some("--"),
+        _ => None,
+    }
+}
@@ -0,0 +1,68 @@
+#![doc = " # Release State - Checkpoint of `trae release`"]
+#![doc = ""]
+#![doc = " Guarda en `.trae/release-state.json` las etapas que ya pasaron y el hash del código"]
+#![doc = " con el que pasaron, para que `trae release --resume` siga desde el fallo"]
+use crate::utils::path::is_hidden;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+/// Location of the checkpoint, relative to the project root.
+pub const RELEASE_STATE_FILE: &str = ".trae/release-state.json";
+/// Stages of one release run that passed against the tree of `fingerprint`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseState {
+    pub fingerprint: String,
+    /// Stage keys (the cargo arguments each stage ran).
+    pub passed: BTreeSet<String>,
+}
+impl ReleaseState {
+    /// A checkpoint with no stage passed yet.
+    pub fn new(fingerprint: impl Into<String>) -> Self {
+        Self { fingerprint: fingerprint.into(), passed: BTreeSet::new() }
+    }
+    /// The checkpoint at `path`; `None` when missing or unreadable.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+    #[doc = " Escribe el checkpoint, creando `.trae/` si hace falta"]
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    pub fn has_passed(&self, stage: &str) -> bool {
+        self.passed.contains(stage)
+    }
+    pub fn mark_passed(&mut self, stage: impl Into<String>) {
+        self.passed.insert(stage.into());
+    }
+}
+/// SHA-256 over the path and content of every file under `root`, in path
+/// order. Build output (`target`) and hidden entries (`.git`, `.trae`...)
+/// are left out, so only a change to the sources, manifests or lockfile
+/// moves it.
+pub fn source_fingerprint(root: &Path) -> String {
+    let mut hasher = Sha256::new();
+    let files = walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !(is_hidden(e) || e.depth() == 1 && e.file_name() == "target"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+    for entry in files {
+        let Ok(content) = fs::read(entry.path()) else {
+            continue;
+        };
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(Sha256::digest(&content));
+    }
+    hex::encode(hasher.finalize())
+}
@@ -0,0 +1,162 @@
+#![doc = " # Report - Exported report schema and merging"]
+#![doc = ""]
+#![doc = " Versión del esquema de los reportes JSON y unión de varios (`trae report --merge`)"]
+use crate::core::analyzer::{compare_issues, AnalysisIssue, IssueSeverity};
+use crate::core::baseline::fingerprint;
+use crate::core::severity::SeverityFilter;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+/// `schema_version` of the JSON written by `trae scan --export`, `trae
+/// analyze --output` and `trae report --merge`. Bumped when a key they share
+/// changes meaning; exports without one predate it and are version 1.
+pub const SCHEMA_VERSION: u64 = 1;
+/// An exported report read back. Issues and suggestions keep their JSON, so
+/// snippets and other extra keys survive a merge.
+#[derive(Debug, Clone)]
+pub struct ExportedReport {
+    pub source: String,
+    pub schema_version: u64,
+    issues: Vec<(AnalysisIssue, Value)>,
+    suggestions: Vec<Value>,
+}
+impl ExportedReport {
+    /// The report in the file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("No se pudo leer el reporte {}", path.display()))?;
+        let json = serde_json::from_str(&content)
+            .with_context(|| format!("{}: no es JSON válido", path.display()))?;
+        Self::from_json(&path.display().to_string(), json)
+    }
+    /// The report `json`, read from `source`. It needs an `issues` list; a
+    /// `schema_version` newer than [`SCHEMA_VERSION`] is an error.
+    pub fn from_json(source: &str, mut json: Value) -> Result<Self> {
+        let schema_version = match json.get("schema_version") {
+            None => 1,
+            Some(version) => version.as_u64().with_context(|| {
+                format!("{source}: `schema_version` debe ser un entero, no {version}")
+            })?,
+        };
+        if schema_version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "{source}: schema_version {schema_version} es más nueva que la que entiende \
+                 esta versión de trae ({SCHEMA_VERSION}); actualiza trae"
+            );
+        }
+        let Some(issues) = json.get_mut("issues").and_then(Value::as_array_mut) else {
+            anyhow::bail!(
+                "{source}: no es un reporte de `trae scan --export` ni de \
+                 `trae analyze --output` (falta la lista `issues`)"
+            );
+        };
+        let issues = std::mem::take(issues)
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let issue = serde_json::from_value(value.clone())
+                    .with_context(|| format!("{source}: issues[{i}] no es un issue válido"))?;
+                Ok((issue, value))
+            })
+            .collect::<Result<_>>()?;
+        let suggestions = match json.get_mut("suggestions").and_then(Value::as_array_mut) {
+            Some(suggestions) => std::mem::take(suggestions),
+            None => Vec::new(),
+        };
+        Ok(Self { source: source.to_string(), schema_version, issues, suggestions })
+    }
+}
+/// Several reports as one: issues and suggestions of all of them, each
+/// issue once.
+#[derive(Debug, Clone, Default)]
+pub struct MergedReport {
+    pub sources: Vec<String>,
+    issues: Vec<(AnalysisIssue, Value)>,
+    pub suggestions: Vec<Value>,
+    /// Issues and suggestions dropped as repeats of an earlier report's.
+    pub duplicates: usize,
+}
+impl MergedReport {
+    /// Unions `reports`, which must share a schema version. Issues are
+    /// matched by [`fingerprint`] and repeats are counted, so an issue that
+    /// appears twice in one report and once in another is kept twice.
+    /// Suggestions are kept once each. Issues come out in
+    /// [`sort_issues`](crate::core::analyzer::sort_issues) order.
+    pub fn new(reports: Vec<ExportedReport>) -> Result<Self> {
+        if let Some(first) = reports.first() {
+            let other = reports.iter().find(|r| r.schema_version != first.schema_version);
+            if let Some(other) = other {
+                anyhow::bail!(
+                    "Esquemas incompatibles: {} tiene schema_version {} y {} tiene {}; \
+                     exporta todos los reportes con la misma versión de trae",
+                    first.source,
+                    first.schema_version,
+                    other.source,
+                    other.schema_version
+                );
+            }
+        }
+        let mut merged = Self::default();
+        let mut kept: HashMap<String, usize> = HashMap::new();
+        let mut suggestions = HashSet::new();
+        for report in reports {
+            let mut seen: HashMap<String, usize> = HashMap::new();
+            for (issue, value) in report.issues {
+                let fingerprint = fingerprint(&issue);
+                let seen = seen.entry(fingerprint.clone()).or_default();
+                let kept = kept.entry(fingerprint).or_default();
+                *seen += 1;
+                if *seen > *kept {
+                    *kept += 1;
+                    merged.issues.push((issue, value));
+                } else {
+                    merged.duplicates += 1;
+                }
+            }
+            for suggestion in report.suggestions {
+                if suggestions.insert(suggestion.to_string()) {
+                    merged.suggestions.push(suggestion);
+                } else {
+                    merged.duplicates += 1;
+                }
+            }
+            merged.sources.push(report.source);
+        }
+        merged.issues.sort_by(|a, b| compare_issues(&a.0, &b.0));
+        Ok(merged)
+    }
+    /// The merged issues.
+    pub fn issues(&self) -> impl Iterator<Item = &AnalysisIssue> {
+        self.issues.iter().map(|(issue, _)| issue)
+    }
+    /// Drops the issues `severity` doesn't admit.
+    pub fn retain_severity(&mut self, severity: &mut SeverityFilter) {
+        severity.retain_by(&mut self.issues, |(issue, _)| issue.severity.clone());
+    }
+    /// How many issues have `severity`.
+    pub fn count(&self, severity: &IssueSeverity) -> usize {
+        self.issues().filter(|issue| issue.severity.rank() == severity.rank()).count()
+    }
+    /// The merged report, with the counts of `trae scan --export` recomputed
+    /// and the `severity` threshold, if any.
+    pub fn to_json(&self, severity: &SeverityFilter) -> Value {
+        let mut report = json!({
+            "schema_version": SCHEMA_VERSION,
+            "timestamp": chrono::Utc::now(),
+            "merged_from": self.sources,
+            "total_issues": self.issues.len(),
+            "critical_issues": self.count(&IssueSeverity::Critical),
+            "warning_issues": self.count(&IssueSeverity::Warning),
+            "info_issues": self.count(&IssueSeverity::Info),
+            "duplicates_removed": self.duplicates,
+            "issues": self.issues.iter().map(|(_, value)| value).collect::<Vec<_>>(),
+            "suggestions": self.suggestions,
+        });
+        if let Some(threshold) = severity.threshold() {
+            report["severity_threshold"] = threshold.name().into();
+            report["suppressed"] = severity.suppressed().into();
+        }
+        report
+    }
+}
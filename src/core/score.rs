@@ -0,0 +1,178 @@
+#![doc = " # Score - Quality score and its breakdown"]
+#![doc = ""]
+#![doc = " Puntuación de calidad 0-100 con cada penalización y bonificación por separado"]
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+/// Coefficients of the score, the `[score]` section of the config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoreWeights {
+    #[doc = " Points per issue every 1000 lines"]
+    pub issues_per_1k: f64,
+    #[doc = " DPMO is divided by this..."]
+    pub dpmo_divisor: f64,
+    #[doc = " ...and costs at most this many points"]
+    pub dpmo_cap: f64,
+    #[doc = " Complexity is divided by this..."]
+    pub complexity_divisor: f64,
+    #[doc = " ...and costs at most this many points"]
+    pub complexity_cap: f64,
+    #[doc = " Points per unit of the 0-1 duplication factor"]
+    pub duplication: f64,
+    #[doc = " Points added for projects with more than 5 Rust files"]
+    pub size_bonus: f64,
+}
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            issues_per_1k: 5.0,
+            dpmo_divisor: 1000.0,
+            dpmo_cap: 20.0,
+            complexity_divisor: 10.0,
+            complexity_cap: 15.0,
+            duplication: 30.0,
+            size_bonus: 5.0,
+        }
+    }
+}
+impl ScoreWeights {
+    /// Rejects coefficients that would break the 0-100 scale: negative or
+    /// non-finite ones (a negative penalty is a hidden bonus), zero divisors
+    /// and a bonus of more than 100 points.
+    pub fn validate(&self) -> Result<()> {
+        let coefficients = [
+            ("issues_per_1k", self.issues_per_1k),
+            ("dpmo_divisor", self.dpmo_divisor),
+            ("dpmo_cap", self.dpmo_cap),
+            ("complexity_divisor", self.complexity_divisor),
+            ("complexity_cap", self.complexity_cap),
+            ("duplication", self.duplication),
+            ("size_bonus", self.size_bonus),
+        ];
+        for (name, value) in coefficients {
+            if !value.is_finite() || value < 0.0 {
+                anyhow::bail!("[score] {name} debe ser un número >= 0 (es {value})");
+            }
+        }
+        for (name, value) in [
+            ("dpmo_divisor", self.dpmo_divisor),
+            ("complexity_divisor", self.complexity_divisor),
+        ] {
+            if value == 0.0 {
+                anyhow::bail!("[score] {name} debe ser mayor que 0");
+            }
+        }
+        if self.size_bonus > 100.0 {
+            anyhow::bail!("[score] size_bonus no puede superar 100 (es {})", self.size_bonus);
+        }
+        Ok(())
+    }
+    /// The coefficients on one line, for reports.
+    pub fn summary(&self) -> String {
+        format!(
+            "issues/1k ×{}, DPMO /{} (máx {}), complejidad /{} (máx {}), duplicación ×{}, \
+             bonus +{}",
+            self.issues_per_1k,
+            self.dpmo_divisor,
+            self.dpmo_cap,
+            self.complexity_divisor,
+            self.complexity_cap,
+            self.duplication,
+            self.size_bonus
+        )
+    }
+}
+/// Inputs of the score and what each one costs. Penalties are positive
+/// points taken from 100; `score` is the clamped result of `weights`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub issues_per_1k_lines: f64,
+    pub issue_penalty: f64,
+    pub dpmo: f64,
+    pub dpmo_penalty: f64,
+    pub complexity: f64,
+    pub complexity_penalty: f64,
+    pub duplication: f64,
+    pub duplication_penalty: f64,
+    /// Only for projects with more than 5 Rust files.
+    pub size_bonus: f64,
+    pub score: f64,
+    /// Coefficients in effect, so the score can be reproduced.
+    pub weights: ScoreWeights,
+}
+impl ScoreBreakdown {
+    /// Scores a project with `weights`; 0 with every component at 0 when it
+    /// has no Rust code. `complexity` is the spread of per-file complexity
+    /// and `duplication` a 0-1 factor (see [`duplication_factor`]).
+    pub fn compute(
+        rust_files: usize,
+        issues: usize,
+        total_lines: usize,
+        complexity: f64,
+        duplication: f64,
+        weights: &ScoreWeights,
+    ) -> Self {
+        if rust_files == 0 || total_lines == 0 {
+            return Self { weights: weights.clone(), ..Self::default() };
+        }
+        let issues_per_1k_lines = (issues as f64 / total_lines as f64) * 1000.0;
+        let dpmo = dpmo(issues, total_lines);
+        let mut breakdown = Self {
+            issues_per_1k_lines,
+            issue_penalty: issues_per_1k_lines * weights.issues_per_1k,
+            dpmo,
+            dpmo_penalty: (dpmo / weights.dpmo_divisor).min(weights.dpmo_cap),
+            complexity,
+            complexity_penalty: (complexity / weights.complexity_divisor)
+                .min(weights.complexity_cap),
+            duplication,
+            duplication_penalty: duplication * weights.duplication,
+            size_bonus: if rust_files > 5 { weights.size_bonus } else { 0.0 },
+            score: 0.0,
+            weights: weights.clone(),
+        };
+        breakdown.score = (100.0
+            - breakdown.issue_penalty
+            - breakdown.dpmo_penalty
+            - breakdown.complexity_penalty
+            - breakdown.duplication_penalty
+            + breakdown.size_bonus)
+            .clamp(0.0, 100.0);
+        breakdown
+    }
+    /// One `name: value → points` line per component, then the score and
+    /// the coefficients.
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Issues/1k líneas: {:.2} → -{:.2}",
+                self.issues_per_1k_lines, self.issue_penalty
+            ),
+            format!("DPMO: {:.0} → -{:.2}", self.dpmo, self.dpmo_penalty),
+            format!("Complejidad: {:.2} → -{:.2}", self.complexity, self.complexity_penalty),
+            format!("Duplicación: {:.2} → -{:.2}", self.duplication, self.duplication_penalty),
+            format!("Bonus por tamaño: +{:.2}", self.size_bonus),
+            format!("Puntuación: {:.1}/100", self.score),
+            format!("Coeficientes: {}", self.weights.summary()),
+        ]
+    }
+}
+/// Defects per million opportunities, one opportunity per line.
+pub fn dpmo(issues: usize, total_lines: usize) -> f64 {
+    if total_lines == 0 {
+        return 0.0;
+    }
+    (issues as f64 / total_lines as f64) * 1_000_000.0
+}
+/// Estimated duplication: 0.3 when Rust files average over 200 lines, else 0.1.
+pub fn duplication_factor(total_lines: usize, rust_files: usize) -> f64 {
+    if rust_files == 0 {
+        return 0.0;
+    }
+    let avg_lines_per_file = total_lines as f64 / rust_files as f64;
+    if avg_lines_per_file > 200.0 {
+        0.3
+    } else {
+        0.1
+    }
+}
@@ -0,0 +1,76 @@
+#![doc = " # Severity - Global --severity threshold"]
+#![doc = ""]
+#![doc = " Umbral de `--severity`: oculta hallazgos menores y decide el código de salida"]
+use crate::config::RunOptions;
+use crate::core::analyzer::{AnalysisIssue, IssueSeverity};
+/// Drops findings under the `--severity` threshold before they are rendered
+/// or exported, counting them so the summary can say how many. The findings
+/// left decide the exit code: all of them with a threshold, only critical
+/// ones without (the default, which hides nothing).
+#[derive(Debug, Clone, Default)]
+pub struct SeverityFilter {
+    threshold: Option<IssueSeverity>,
+    suppressed: usize,
+}
+impl SeverityFilter {
+    pub fn new(threshold: Option<IssueSeverity>) -> Self {
+        Self { threshold, suppressed: 0 }
+    }
+    /// The filter of `--severity` in `options`.
+    pub fn from_options(options: &RunOptions) -> Self {
+        Self::new(options.severity.clone())
+    }
+    pub fn threshold(&self) -> Option<&IssueSeverity> {
+        self.threshold.as_ref()
+    }
+    /// Whether a finding of `severity` is shown.
+    pub fn admits(&self, severity: &IssueSeverity) -> bool {
+        self.threshold.as_ref().is_none_or(|threshold| severity.rank() >= threshold.rank())
+    }
+    /// Keeps the items of `items` whose `severity` is admitted; the others
+    /// count as suppressed.
+    pub fn retain_by<T>(&mut self, items: &mut Vec<T>, severity: impl Fn(&T) -> IssueSeverity) {
+        let before = items.len();
+        items.retain(|item| self.admits(&severity(item)));
+        self.suppressed += before - items.len();
+    }
+    /// [`Self::retain_by`] for analysis issues.
+    pub fn retain(&mut self, issues: &mut Vec<AnalysisIssue>) {
+        self.retain_by(issues, |issue| issue.severity.clone());
+    }
+    /// `issues` without the suppressed ones.
+    pub fn filter(&mut self, mut issues: Vec<AnalysisIssue>) -> Vec<AnalysisIssue> {
+        self.retain(&mut issues);
+        issues
+    }
+    /// How many findings were dropped so far.
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+    /// Whether a finding of `severity` fails the command: at or above the
+    /// threshold, or critical when there is none.
+    pub fn fails(&self, severity: &IssueSeverity) -> bool {
+        severity.rank() >= self.threshold.as_ref().unwrap_or(&IssueSeverity::Critical).rank()
+    }
+    /// How many of `severities` fail the command (see [`Self::fails`]).
+    pub fn failing<'a>(&self, severities: impl IntoIterator<Item = &'a IssueSeverity>) -> usize {
+        severities.into_iter().filter(|severity| self.fails(severity)).count()
+    }
+    /// Exit message for the failing findings: `critical` (the command's own
+    /// wording) without a threshold, else `findings` and the threshold.
+    pub fn describe_failing(&self, critical: &str, findings: &str) -> String {
+        match &self.threshold {
+            Some(threshold) => format!("{findings} de severidad {} o mayor", threshold.name()),
+            None => critical.to_string(),
+        }
+    }
+    /// What to tell the user when findings were suppressed.
+    pub fn notice(&self) -> Option<String> {
+        let threshold = self.threshold.as_ref().filter(|_| self.suppressed > 0)?;
+        Some(format!(
+            "🔕 {} hallazgo(s) por debajo de --severity {} ocultos",
+            self.suppressed,
+            threshold.name()
+        ))
+    }
+}
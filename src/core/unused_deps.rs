@@ -0,0 +1,101 @@
+#![doc = " # Unused Deps - Declared dependencies no source names"]
+#![doc = ""]
+#![doc = " Cruza las dependencias de `Cargo.toml` con las rutas y `use` del código, tabla a tabla"]
+use crate::config::RunOptions;
+use crate::core::manifest::{declaration_line, DependencyKind, Manifest};
+use crate::utils::path::rust_files;
+use crate::utils::source::mask_raw_and_byte_strings;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+/// Directories whose `.rs` files can use dev-dependencies besides `src`.
+const DEV_DIRS: [&str; 3] = ["tests", "benches", "examples"];
+/// A declared dependency that no source file of its table appears to name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedDependency {
+    /// Key in `Cargo.toml`, which is also the name the code uses.
+    pub name: String,
+    /// Crate behind a renamed dependency (`package = "..."`).
+    pub package: Option<String>,
+    pub kind: DependencyKind,
+    pub target: Option<String>,
+    /// 1-based line of the declaration in `Cargo.toml`.
+    pub line: Option<usize>,
+}
+/// Whether `content` names the crate `ident` (dashes already turned into
+/// underscores): a path through it (`ident::x`, `::ident::x`), a
+/// `use ident` or an `extern crate ident`. Line comments are ignored.
+pub fn references(content: &str, ident: &str) -> bool {
+    names(&code(content), ident)
+}
+/// [`references`] on source already passed through [`code`].
+fn names(code: &str, ident: &str) -> bool {
+    let ident = regex::escape(ident);
+    Regex::new(&format!(
+        r"(?m)(?:^|[^\w:])(?:::)?{ident}\s*::|\b(?:use|extern\s+crate)\s+(?:::)?{ident}\b"
+    ))
+    .unwrap()
+    .is_match(code)
+}
+/// `content` without line comments, raw and byte strings masked.
+fn code(content: &str) -> String {
+    mask_raw_and_byte_strings(content)
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+/// Dependencies of `project/Cargo.toml` that look unused, in declaration
+/// order (normal, dev, build, then each `[target.X]`). Each table is matched
+/// against the code that can use it:
+/// - `[dependencies]`: `src/`;
+/// - `[dev-dependencies]`: `src/` (its `#[cfg(test)]` code), `tests/`,
+///   `benches/` and `examples/`;
+/// - `[build-dependencies]`: `build.rs`.
+///
+/// A renamed dependency is looked up by its key, the name the code uses.
+/// Crates only reached through another crate's macros are reported too.
+pub fn unused_dependencies(
+    project: &Path,
+    options: &RunOptions,
+) -> Result<Vec<UnusedDependency>> {
+    let manifest_path = project.join("Cargo.toml");
+    let content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("No se pudo leer {}", manifest_path.display()))?;
+    let manifest = Manifest::parse(&content)
+        .with_context(|| format!("{} inválido", manifest_path.display()))?;
+
+    let read = |paths: Vec<PathBuf>| -> String {
+        paths
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .map(|content| code(&content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let files = |dir: &str| -> Vec<PathBuf> {
+        rust_files(&project.join(dir), None, options).map(|entry| entry.into_path()).collect()
+    };
+    let lib = read(files("src"));
+    let dev = read(DEV_DIRS.iter().flat_map(|dir| files(dir)).collect());
+    let build = read(vec![project.join("build.rs")]);
+
+    Ok(manifest
+        .specs()
+        .filter(|(dep, _)| {
+            let ident = dep.name.replace('-', "_");
+            match dep.kind {
+                DependencyKind::Normal => !names(&lib, &ident),
+                DependencyKind::Dev => !names(&lib, &ident) && !names(&dev, &ident),
+                DependencyKind::Build => !names(&build, &ident),
+            }
+        })
+        .map(|(dep, spec)| UnusedDependency {
+            line: declaration_line(&content, &dep.name),
+            package: spec.package().map(String::from),
+            name: dep.name,
+            kind: dep.kind,
+            target: dep.target,
+        })
+        .collect())
+}
@@ -0,0 +1,92 @@
+#![doc = " # Workspace - Cargo workspace root detection"]
+#![doc = ""]
+#![doc = " Localiza la raíz del workspace para que los comandos se comporten igual desde cualquier subdirectorio"]
+use crate::core::manifest::Manifest;
+use anyhow::Result;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+/// Root of the cargo project containing the current directory. See
+/// [`find_root_from`].
+pub fn find_root() -> Option<PathBuf> {
+    find_root_from(&std::env::current_dir().ok()?)
+}
+/// Root of the cargo project containing `dir`: the topmost ancestor whose
+/// `Cargo.toml` has a `[workspace]` table, else the nearest ancestor with a
+/// `Cargo.toml` (a standalone package). `None` outside any cargo project.
+/// A manifest that fails to parse still counts as a package root.
+pub fn find_root_from(dir: &Path) -> Option<PathBuf> {
+    let dir = dir.canonicalize().ok()?;
+    let mut package = None;
+    let mut workspace = None;
+    for ancestor in dir.ancestors().filter(|a| a.join("Cargo.toml").is_file()) {
+        package.get_or_insert(ancestor);
+        if Manifest::load(ancestor).is_ok_and(|m| m.workspace.is_some()) {
+            workspace = Some(ancestor);
+        }
+    }
+    workspace.or(package).map(Path::to_path_buf)
+}
+/// A temporary copy of the workspace at `root`, deleted when dropped, for
+/// commands that must leave the real one untouched (`repair --preview`,
+/// `deps --minimal-versions`). Path dependencies outside `root` (`../shared`)
+/// are pointed at their real location, so the copy still resolves.
+pub fn sandbox(root: &Path) -> Result<tempfile::TempDir> {
+    let sandbox = tempfile::Builder::new().prefix("trae-sandbox-").tempdir()?;
+    copy_workspace(root, sandbox.path())?;
+    let root = root.canonicalize()?;
+    let manifests = walkdir::WalkDir::new(sandbox.path())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "Cargo.toml");
+    for manifest in manifests {
+        let relative = manifest.path().strip_prefix(sandbox.path())?;
+        let original_dir = root.join(relative.parent().unwrap_or(Path::new("")));
+        let content = fs::read_to_string(manifest.path())?;
+        let rewritten = external_paths_absolute(&content, &original_dir, &root);
+        if rewritten != content {
+            fs::write(manifest.path(), rewritten)?;
+        }
+    }
+    Ok(sandbox)
+}
+/// `manifest` (the Cargo.toml in `dir`) with every `path = "..."` that
+/// leads outside `root` made absolute. Paths inside `root` stay relative:
+/// they are copied along with it.
+fn external_paths_absolute(manifest: &str, dir: &Path, root: &Path) -> String {
+    let path_key = Regex::new(r#"(\bpath\s*=\s*")([^"]+)(")"#).unwrap();
+    path_key
+        .replace_all(manifest, |caps: &regex::Captures| {
+            let original = &caps[0];
+            let Ok(target) = dir.join(&caps[2]).canonicalize() else {
+                return original.to_string();
+            };
+            if target.starts_with(root) {
+                return original.to_string();
+            }
+            let target = crate::utils::path::portable(&target);
+            format!("{}{}{}", &caps[1], target, &caps[3])
+        })
+        .into_owned()
+}
+/// Copies the workspace into `dst`, skipping build output, VCS and trae
+/// state. Symlinks are skipped, not followed, so a link cycle can't recurse.
+fn copy_workspace(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "target" || name == ".git" || name == ".trae" {
+            continue;
+        }
+        let path = entry.path();
+        let dest_path = dst.join(&name);
+        let file_type = fs::symlink_metadata(&path)?.file_type();
+        if file_type.is_dir() {
+            copy_workspace(&path, &dest_path)?;
+        } else if file_type.is_file() {
+            fs::copy(&path, &dest_path)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+#![doc = " # Errors - Typed failures for the library API"]
+#![doc = ""]
+#![doc = " Errores públicos de `trae_cli::api` y `JarvixClient`; el código interno sigue usando"]
+#![doc = " `anyhow` y se convierte aquí en el borde de la API."]
+use crate::exit::FindingsPresent;
+/// `Result` of the public API, defaulting to [`TraeError`].
+pub type Result<T, E = TraeError> = std::result::Result<T, E>;
+#[derive(Debug, thiserror::Error)]
+#[doc = " Tipo de fallo de una operación de la API pública"]
+pub enum TraeError {
+    #[doc = " `cargo` is neither on PATH nor in `$CARGO_HOME/bin`"]
+    #[error(
+        "'cargo' no se encuentra en PATH ni en CARGO_HOME. \
+         Instálalo: https://www.rust-lang.org/tools/install"
+    )]
+    CargoNotFound,
+    #[doc = " Network, HTTP or body-decoding failure talking to JARVIXSERVER"]
+    #[error("error de red con JARVIXSERVER: {0}")]
+    Jarvix(#[from] reqwest::Error),
+    #[doc = " JARVIXSERVER answered with a failed job, an error status or an unexpected payload"]
+    #[error("JARVIXSERVER: {0}")]
+    JarvixResponse(String),
+    #[doc = " Filesystem or process I/O failure"]
+    #[error("error de E/S: {0}")]
+    Io(#[from] std::io::Error),
+    #[doc = " Config, cache or tool output (JSON/TOML) could not be parsed"]
+    #[error("error de parseo: {0}")]
+    Parse(String),
+    #[doc = " The command ran but found critical issues (exit code 2)"]
+    #[error(transparent)]
+    QualityGateFailed(#[from] FindingsPresent),
+    #[doc = " Stopped through the caller's `CancellationToken`; partial results were still reported"]
+    #[error("operación cancelada")]
+    Cancelled,
+    #[doc = " Any other failure, with its full context chain"]
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+impl From<serde_json::Error> for TraeError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
+impl From<toml::de::Error> for TraeError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
+/// Recovers the failure kind from an internal `anyhow` error, looking through
+/// any context added on the way up.
+impl From<anyhow::Error> for TraeError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<Self>() {
+            Ok(trae) => return trae,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<FindingsPresent>() {
+            Ok(findings) => return Self::QualityGateFailed(findings),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(http) => return Self::Jarvix(http),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<std::io::Error>() {
+            Ok(io) => return Self::Io(io),
+            Err(err) => err,
+        };
+        if let Some(json) = err.downcast_ref::<serde_json::Error>() {
+            return Self::Parse(json.to_string());
+        }
+        if let Some(toml) = err.downcast_ref::<toml::de::Error>() {
+            return Self::Parse(toml.to_string());
+        }
+        Self::Other(err)
+    }
+}
@@ -0,0 +1,41 @@
+#![doc = " # Progress Events - Structured progress for library consumers"]
+#![doc = ""]
+#![doc = " Eventos que la API emite en lugar de imprimir cuando el embebedor pasa un callback"]
+use crate::core::cargo::CargoStream;
+use crate::utils::ui::StepSummary;
+use std::fmt;
+use std::sync::Arc;
+#[derive(Debug, Clone)]
+#[doc = " Progreso de una operación de la API"]
+pub enum ProgressEvent {
+    #[doc = " A step began; its [`ProgressEvent::StepFinished`] carries the same label"]
+    StepStarted { label: String },
+    #[doc = " A step succeeded, failed or was skipped (same rows as the printed summary table)"]
+    StepFinished(StepSummary),
+    #[doc = " One line of output from a cargo child process"]
+    Output { stream: CargoStream, line: String },
+    #[doc = " A value recorded in the command's metrics once it finishes"]
+    Metric {
+        name: String,
+        value: serde_json::Value,
+    },
+}
+/// Callback receiving [`ProgressEvent`]s; while one is set the command does
+/// not print its own tables, spinners or cargo output.
+#[derive(Clone)]
+pub struct ProgressSink(Arc<dyn Fn(ProgressEvent) + Send + Sync>);
+impl ProgressSink {
+    /// Sink calling `on_event` for every event, on the thread that emits it.
+    pub fn new(on_event: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(on_event))
+    }
+    /// Hands `event` to the callback.
+    pub fn emit(&self, event: ProgressEvent) {
+        (self.0)(event);
+    }
+}
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressSink(..)")
+    }
+}
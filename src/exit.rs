@@ -0,0 +1,79 @@
+#![doc = " # Exit Codes - Process exit semantics"]
+#![doc = ""]
+#![doc = " Códigos de salida comunes a todos los comandos (ver README \"Exit Codes\")"]
+use std::fmt;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc = " Código de salida del proceso"]
+pub enum ExitCode {
+    #[doc = " The command ran and found nothing blocking"]
+    Success = 0,
+    #[doc = " trae itself failed (I/O, cargo not found, network, ...)"]
+    ToolError = 1,
+    #[doc = " The command ran and found critical issues / a quality gate failed"]
+    FindingsPresent = 2,
+    #[doc = " Invalid arguments or missing subcommand"]
+    UsageError = 3,
+}
+impl ExitCode {
+    /// The numeric code the process exits with.
+    pub const fn code(self) -> i32 {
+        self as i32
+    }
+    /// Maps a command outcome: [`FindingsPresent`] and [`UsageError`] errors
+    /// (or clap errors) get their own codes, anything else is a tool error.
+    pub fn from_result(result: &anyhow::Result<()>) -> Self {
+        match result {
+            Ok(()) => Self::Success,
+            Err(err) if is_findings(err) => Self::FindingsPresent,
+            Err(err)
+                if err.downcast_ref::<UsageError>().is_some()
+                    || err.downcast_ref::<clap::Error>().is_some() =>
+            {
+                Self::UsageError
+            }
+            Err(_) => Self::ToolError,
+        }
+    }
+}
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        Self::from(code as u8)
+    }
+}
+/// Error returned by a command that completed but found critical issues.
+#[derive(Debug)]
+pub struct FindingsPresent {
+    pub count: usize,
+    pub what: String,
+}
+impl fmt::Display for FindingsPresent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.what)
+    }
+}
+impl std::error::Error for FindingsPresent {}
+/// Error for arguments clap accepts but the command cannot use.
+#[derive(Debug)]
+pub struct UsageError(pub String);
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl std::error::Error for UsageError {}
+/// `Err` carrying [`FindingsPresent`], e.g. `findings(3, "issues críticos detectados")`.
+pub fn findings(count: usize, what: impl Into<String>) -> anyhow::Error {
+    FindingsPresent {
+        count,
+        what: what.into(),
+    }
+    .into()
+}
+/// Whether `err` reports findings rather than a failure of trae itself.
+pub fn is_findings(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<FindingsPresent>().is_some()
+        || matches!(
+            err.downcast_ref::<crate::error::TraeError>(),
+            Some(crate::error::TraeError::QualityGateFailed(_))
+        )
+}
@@ -2,11 +2,28 @@
 #![doc = ""]
 #![doc = " Cliente para comunicación con JARVIXSERVER"]
 use crate::metrics::collector::MetricsCollector;
-use anyhow::Result;
+use crate::error::{Result, TraeError};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::time::Duration;
+/// Local URLs probed when no endpoint is configured, in order: the
+/// JARVIXSERVER default, the trae HTTP server and the old client default.
+/// Override with a comma-separated `JARVIX_CANDIDATES`.
+pub const DEFAULT_CANDIDATES: [&str; 3] = [
+    "http://localhost:5051",
+    "http://localhost:8080",
+    "http://localhost:8081",
+];
+/// Header carrying [`crate::build_info::LONG_VERSION`] on requests and server responses.
+pub const VERSION_HEADER: &str = "x-trae-version";
+/// Metric reports are retried on network errors and 5xx answers, waiting
+/// [`REPORT_BACKOFF`] before the second attempt and doubling after that.
+const REPORT_ATTEMPTS: u32 = 3;
+const REPORT_BACKOFF: Duration = Duration::from_millis(250);
+const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+static DISCOVERED: tokio::sync::OnceCell<String> = tokio::sync::OnceCell::const_new();
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct JarvixConfig {
@@ -14,17 +31,32 @@ pub struct JarvixConfig {
     pub api_key: Option<String>,
     pub timeout: u64,
 }
+#[derive(Clone)]
 #[doc = "Struct documentation added by AI refactor"]
 pub struct JarvixClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
     timeout: Duration,
+    report_sink: Option<ReportSink>,
+    /// Options of the run the client belongs to, for its progress spinner.
+    options: crate::config::RunOptions,
+}
+/// Where metric reports go when not to JARVIXSERVER.
+#[derive(Clone)]
+struct ReportSink {
+    url: String,
+    headers: HeaderMap,
 }
 impl JarvixClient {
-    #[doc = "Method documentation added by AI refactor"]
-    pub fn load_config() -> Result<JarvixConfig> {
-        if let Ok(endpoint) = std::env::var("JARVIX_ENDPOINT") {
+    /// JARVIXSERVER settings: `--jarvix` of `options`, else `JARVIX_ENDPOINT`
+    /// (or `JARVIX_URL`), else `~/.trae/config.toml`, else discovery.
+    pub async fn load_config(options: &crate::config::RunOptions) -> Result<JarvixConfig> {
+        let endpoint = match &options.jarvix_endpoint {
+            Some(url) => Ok(url.clone()),
+            None => std::env::var("JARVIX_ENDPOINT").or_else(|_| std::env::var("JARVIX_URL")),
+        };
+        if let Ok(endpoint) = endpoint {
             return Ok(JarvixConfig {
                 endpoint,
                 api_key: std::env::var("JARVIX_API_KEY").ok(),
@@ -43,22 +75,35 @@ impl JarvixClient {
             }
         }
         Ok(JarvixConfig {
-            endpoint: "http://localhost:8081".to_string(),
+            endpoint: discovered_endpoint().await,
             api_key: None,
             timeout: 30,
         })
     }
-    #[doc = "Method documentation added by AI refactor"]
-    pub fn new() -> Result<Option<Self>> {
-        let config = Self::load_config()?;
-        println!("🔧 JARVIX configurado: {}", config.endpoint);
+    /// Client for the configured JARVIXSERVER; `None` when `options` are offline.
+    pub async fn new(options: &crate::config::RunOptions) -> Result<Option<Self>> {
+        if options.offline {
+            return Ok(None);
+        }
+        let config = Self::load_config(options).await?;
+        eprintln!("🔧 JARVIX configurado: {}", config.endpoint);
+        let report_sink = report_sink(options);
+        if let Some(sink) = &report_sink {
+            eprintln!("📡 Métricas enviadas a {}", sink.url);
+        }
         Ok(Some(Self {
-            client: Client::new(),
+            client: crate::utils::http::jarvix_client().inspect_err(|e| eprintln!("❌ {e:#}"))?,
             base_url: config.endpoint,
             api_key: config.api_key,
             timeout: Duration::from_secs(config.timeout),
+            report_sink,
+            options: options.clone(),
         }))
     }
+    /// JARVIXSERVER URL this client talks to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
     #[doc = "Method documentation added by AI refactor"]
     pub async fn report_build_metrics(&self, metrics: MetricsCollector) -> Result<()> {
         let payload = json ! ({ "type" : "build_metrics" , "data" : metrics . to_json () , "timestamp" : chrono :: Utc :: now () });
@@ -95,7 +140,9 @@ impl JarvixClient {
         if let Some(job_id) = response_json.get("id").and_then(|id| id.as_str()) {
             Ok(job_id.to_string())
         } else {
-            Err(anyhow::anyhow!("Failed to get job ID from response"))
+            Err(TraeError::JarvixResponse(
+                "Failed to get job ID from response".to_string(),
+            ))
         }
     }
     #[doc = "Method documentation added by AI refactor"]
@@ -121,14 +168,61 @@ impl JarvixClient {
                         .get("error")
                         .and_then(|e| e.as_str())
                         .unwrap_or("Unknown error");
-                    Err(anyhow::anyhow!("Job failed: {error}"))
+                    Err(TraeError::JarvixResponse(format!("Job failed: {error}")))
                 }
                 _ => Ok(None),
             }
         } else {
-            Err(anyhow::anyhow!("Invalid job response"))
+            Err(TraeError::JarvixResponse(
+                "Invalid job response".to_string(),
+            ))
         }
     }
+    /// Polls `job_id` behind a spinner showing the job and the elapsed time,
+    /// waiting `poll_interval` at first and twice as long after each poll
+    /// that finds it running, up to `max_poll`. `None` means the caller
+    /// should work locally: the job outlived `timeout`, or JARVIXSERVER
+    /// reported it failed. Network errors are retried until the timeout.
+    pub async fn wait_for_job(
+        &self,
+        job_id: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+        max_poll: Duration,
+    ) -> Option<serde_json::Value> {
+        let spinner = crate::utils::progress::timed_spinner(&self.options);
+        spinner.set_message(format!(
+            "job {job_id} en JARVIXSERVER (límite {}s)",
+            timeout.as_secs()
+        ));
+        spinner.enable_steady_tick(Duration::from_millis(120));
+        let start = std::time::Instant::now();
+        let mut delay = poll_interval;
+        let result = loop {
+            match self.get_job_result(job_id).await {
+                Ok(Some(result)) => break Some(result),
+                Err(e @ TraeError::JarvixResponse(_)) => {
+                    spinner.suspend(|| eprintln!("⚠️ Job {job_id}: {e}"));
+                    break None;
+                }
+                Ok(None) | Err(_) => {}
+            }
+            let remaining = timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                spinner.suspend(|| {
+                    eprintln!(
+                        "⚠️ JARVIXSERVER no terminó el job {job_id} en {}s; se abandona",
+                        timeout.as_secs()
+                    );
+                });
+                break None;
+            }
+            tokio::time::sleep(delay.min(remaining)).await;
+            delay = (delay * 2).min(max_poll.max(poll_interval));
+        };
+        spinner.finish_and_clear();
+        result
+    }
     #[doc = "Method documentation added by AI refactor"]
     pub async fn get_pool_stats(&self) -> Result<serde_json::Value> {
         let url = format!("{}/pool/stats", self.base_url);
@@ -165,21 +259,165 @@ impl JarvixClient {
         let payload = json ! ({ "type" : "clippy_metrics" , "data" : metrics . to_json () , "timestamp" : chrono :: Utc :: now () });
         self.send_metrics(payload).await
     }
-    #[doc = "Method documentation added by AI refactor"]
-    async fn send_metrics(&self, payload: serde_json::Value) -> Result<()> {
-        let url = format!("{}/trae/api/metrics", self.base_url);
-        let mut request = self.client.post(&url).timeout(self.timeout).json(&payload);
+    /// Web search through the server's BrowserMCP bridge (`POST /search/web`).
+    /// Returns the raw JSON; a non-2xx status is a [`TraeError::JarvixResponse`].
+    pub async fn search_web(&self, request: &serde_json::Value) -> Result<serde_json::Value> {
+        let url = format!("{}/search/web", self.base_url);
+        let mut request = self.client.post(&url).timeout(self.timeout).json(request);
         if let Some(api_key) = &self.api_key {
             request = request.header("Authorization", format!("Bearer {api_key}"));
         }
         let response = request.send().await?;
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!(
-                "Failed to send metrics: {}",
-                response.status()
-            ))
+        let status = response.status();
+        if !status.is_success() {
+            let hint = if status.as_u16() == 404 {
+                " (BrowserMCP no disponible en JARVIXSERVER)"
+            } else {
+                ""
+            };
+            return Err(TraeError::JarvixResponse(format!(
+                "web search failed: {status}{hint}"
+            )));
+        }
+        Ok(response.json().await?)
+    }
+    /// Posts a metric report to the `--report-url` sink when one is set,
+    /// else to JARVIXSERVER's `/trae/api/metrics`.
+    async fn send_metrics(&self, payload: serde_json::Value) -> Result<()> {
+        let (url, headers) = match &self.report_sink {
+            Some(sink) => (sink.url.clone(), sink.headers.clone()),
+            None => {
+                let mut headers = HeaderMap::new();
+                if let Some(api_key) = &self.api_key {
+                    if let Ok(value) = HeaderValue::from_str(&format!("Bearer {api_key}")) {
+                        headers.insert(reqwest::header::AUTHORIZATION, value);
+                    }
+                }
+                (format!("{}/trae/api/metrics", self.base_url), headers)
+            }
+        };
+        let mut delay = REPORT_BACKOFF;
+        for attempt in 1.. {
+            let sent = self
+                .client
+                .post(&url)
+                .timeout(self.timeout)
+                .headers(headers.clone())
+                .json(&payload)
+                .send()
+                .await;
+            let retry = match &sent {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if retry && attempt < REPORT_ATTEMPTS {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                continue;
+            }
+            let response = sent?;
+            if !response.status().is_success() {
+                return Err(TraeError::JarvixResponse(format!(
+                    "Failed to send metrics: {}",
+                    response.status()
+                )));
+            }
+            break;
         }
+        Ok(())
     }
 }
+/// Parses a `--report-header` value (`key:value`, whitespace around either
+/// side ignored).
+pub fn parse_report_header(
+    header: &str,
+) -> std::result::Result<(HeaderName, HeaderValue), String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("`{header}` no tiene la forma key:value"))?;
+    let name = HeaderName::from_bytes(name.trim().as_bytes())
+        .map_err(|e| format!("nombre de header inválido `{}`: {e}", name.trim()))?;
+    let value = HeaderValue::from_str(value.trim())
+        .map_err(|e| format!("valor de header inválido para `{name}`: {e}"))?;
+    Ok((name, value))
+}
+/// The metrics sink from `--report-url`/`--report-header` in `options`, else
+/// from `report_url`/`report_headers` in `[jarvix]`. Config headers only go
+/// to the config URL, so a `--report-url` never receives credentials meant
+/// for another collector. Malformed headers are skipped with a warning.
+fn report_sink(options: &crate::config::RunOptions) -> Option<ReportSink> {
+    let flag_lines = Some(options.report_headers.clone()).filter(|lines| !lines.is_empty());
+    let (url, lines) = match options.report_url.clone().filter(|url| !url.is_empty()) {
+        Some(url) => (url, flag_lines.unwrap_or_default()),
+        None => {
+            let config = crate::config::TraeConfig::load().jarvix;
+            (config.report_url?, flag_lines.unwrap_or(config.report_headers))
+        }
+    };
+    let mut headers = HeaderMap::new();
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        match parse_report_header(line) {
+            Ok((name, value)) => {
+                headers.append(name, value);
+            }
+            Err(e) => eprintln!("⚠️ Ignorando header de --report-url: {e}"),
+        }
+    }
+    Some(ReportSink { url, headers })
+}
+/// Candidate URLs for discovery: `JARVIX_CANDIDATES` or [`DEFAULT_CANDIDATES`].
+pub fn discovery_candidates() -> Vec<String> {
+    std::env::var("JARVIX_CANDIDATES")
+        .ok()
+        .map(|list| {
+            list.split(',')
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|list| !list.is_empty())
+        .unwrap_or_else(|| DEFAULT_CANDIDATES.map(String::from).to_vec())
+}
+/// First candidate whose `/healthz` answers 2xx, probed in order through
+/// the JARVIXSERVER client of [`crate::utils::http`] (User-Agent, proxies,
+/// `[jarvix] ca_cert`) with a short timeout.
+pub async fn discover(candidates: &[String]) -> Option<String> {
+    let client = match crate::utils::http::jarvix_builder()
+        .and_then(|builder| Ok(builder.timeout(PROBE_TIMEOUT).build()?))
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("⚠️ No se pudo preparar la detección de JARVIX: {e:#}");
+            return None;
+        }
+    };
+    for url in candidates {
+        let healthz = format!("{}/healthz", url.trim_end_matches('/'));
+        let response = client.get(&healthz).send().await;
+        if response.is_ok_and(|response| response.status().is_success()) {
+            return Some(url.clone());
+        }
+    }
+    None
+}
+/// Discovered endpoint, probed once per process and logged when chosen.
+/// Falls back to the first candidate when nothing answers.
+async fn discovered_endpoint() -> String {
+    DISCOVERED
+        .get_or_init(|| async {
+            let candidates = discovery_candidates();
+            match discover(&candidates).await {
+                Some(url) => {
+                    eprintln!("🔍 JARVIX detectado en {url}");
+                    url
+                }
+                None => {
+                    let fallback = candidates[0].clone();
+                    eprintln!("⚠️ Ningún JARVIX respondió en /healthz; usando {fallback}");
+                    fallback
+                }
+            }
+        })
+        .await
+        .clone()
+}
@@ -0,0 +1,153 @@
+#![doc = " # JARVIX Jobs - Concurrent job submission"]
+#![doc = ""]
+#![doc = " Envía jobs a JARVIXSERVER con un límite en vuelo y los sondea con backoff exponencial"]
+use crate::error::TraeError;
+use crate::jarvix::client::JarvixClient;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+/// First wait between polls of a job; it doubles after every poll that
+/// finds the job still running, up to the manager's `max_poll`.
+pub const INITIAL_POLL: Duration = Duration::from_millis(250);
+/// A job to submit: `name` labels it in reports, `job_type` and `data` go to
+/// JARVIXSERVER as in [`JarvixClient::submit_parallel_analysis_job`].
+#[derive(Debug, Clone)]
+pub struct JobSpec {
+    pub name: String,
+    pub job_type: String,
+    pub data: serde_json::Value,
+}
+/// How a job ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Finished(serde_json::Value),
+    /// JARVIXSERVER reported the job failed.
+    Failed(String),
+    /// Still running when the manager's timeout ran out.
+    TimedOut,
+    /// The job could not be submitted.
+    Rejected(String),
+}
+/// A finished job, with how long it waited for a free slot and how long it
+/// took from submission to its last poll.
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+    pub name: String,
+    pub job_id: Option<String>,
+    pub status: JobStatus,
+    pub queued: Duration,
+    pub elapsed: Duration,
+}
+impl JobOutcome {
+    /// The job's result, when it finished.
+    pub fn result(&self) -> Option<&serde_json::Value> {
+        match &self.status {
+            JobStatus::Finished(result) => Some(result),
+            _ => None,
+        }
+    }
+}
+impl std::fmt::Display for JobOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match &self.status {
+            JobStatus::Finished(_) => "completado".to_string(),
+            JobStatus::Failed(e) => format!("falló ({e})"),
+            JobStatus::TimedOut => "sin respuesta a tiempo".to_string(),
+            JobStatus::Rejected(e) => format!("no enviado ({e})"),
+        };
+        write!(
+            f,
+            "{} {status} en {:.2}s (en cola {:.2}s)",
+            self.name,
+            self.elapsed.as_secs_f64(),
+            self.queued.as_secs_f64()
+        )
+    }
+}
+/// Submits jobs with at most `max_in_flight` running at once and polls each
+/// with exponential backoff, so a busy server gets fewer requests instead of
+/// a fixed poll per job.
+pub struct JobManager {
+    client: JarvixClient,
+    max_in_flight: usize,
+    timeout: Duration,
+    max_poll: Duration,
+}
+impl JobManager {
+    /// A manager giving each job `timeout` from its submission, polling at
+    /// most every `max_poll` once backed off.
+    pub fn new(client: &JarvixClient, timeout: Duration, max_poll: Duration) -> Self {
+        Self { client: client.clone(), max_in_flight: 1, timeout, max_poll }
+    }
+    /// Jobs running at once; further ones wait for a slot. At least 1.
+    pub fn max_in_flight(mut self, jobs: usize) -> Self {
+        self.max_in_flight = jobs.max(1);
+        self
+    }
+    /// Runs every job and returns their outcomes in completion order.
+    /// `on_done` sees each outcome as soon as its job ends.
+    pub async fn run(
+        &self,
+        jobs: Vec<JobSpec>,
+        mut on_done: impl FnMut(&JobOutcome),
+    ) -> Vec<JobOutcome> {
+        let slots = Arc::new(Semaphore::new(self.max_in_flight));
+        let mut tasks = JoinSet::new();
+        for job in jobs {
+            let client = self.client.clone();
+            let slots = Arc::clone(&slots);
+            let (timeout, max_poll) = (self.timeout, self.max_poll);
+            tasks.spawn(async move {
+                let start = Instant::now();
+                let _slot = slots.acquire_owned().await;
+                run_job(&client, job, start.elapsed(), timeout, max_poll).await
+            });
+        }
+        let mut outcomes = Vec::new();
+        while let Some(done) = tasks.join_next().await {
+            if let Ok(outcome) = done {
+                on_done(&outcome);
+                outcomes.push(outcome);
+            }
+        }
+        outcomes
+    }
+}
+/// Submits `job` and polls it until it ends or `timeout` runs out.
+/// Network errors while polling are retried like a running job.
+async fn run_job(
+    client: &JarvixClient,
+    job: JobSpec,
+    queued: Duration,
+    timeout: Duration,
+    max_poll: Duration,
+) -> JobOutcome {
+    let start = Instant::now();
+    let outcome = |job_id: Option<String>, status| JobOutcome {
+        name: job.name.clone(),
+        job_id,
+        status,
+        queued,
+        elapsed: start.elapsed(),
+    };
+    let job_id = match client.submit_parallel_analysis_job(&job.job_type, job.data.clone()).await {
+        Ok(id) => id,
+        Err(e) => return outcome(None, JobStatus::Rejected(e.to_string())),
+    };
+    let mut delay = INITIAL_POLL.min(max_poll);
+    loop {
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return outcome(Some(job_id), JobStatus::TimedOut);
+        }
+        tokio::time::sleep(delay.min(remaining)).await;
+        match client.get_job_result(&job_id).await {
+            Ok(Some(result)) => return outcome(Some(job_id), JobStatus::Finished(result)),
+            Err(TraeError::JarvixResponse(e)) => {
+                return outcome(Some(job_id), JobStatus::Failed(e));
+            }
+            Ok(None) | Err(_) => delay = (delay * 2).min(max_poll),
+        }
+    }
+}
@@ -2,3 +2,4 @@
 #![doc = ""]
 #![doc = " Integración con JARVIXSERVER para reporte de métricas"]
 pub mod client;
+pub mod jobs;
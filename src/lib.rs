@@ -1,8 +1,12 @@
 //! Biblioteca pública mínima de TRAE-CLI para reutilización por otros binarios/crates.
 //! Reexporta módulos clave (jarvix client, metrics, core) con API estable mínima.
 
+pub mod build_info;
 pub mod cli;
 pub mod config;
+pub mod error;
+pub mod events;
+pub mod exit;
 pub mod jarvix;
 pub mod metrics;
 pub mod core;
@@ -15,4 +19,10 @@ pub mod api;
 pub use jarvix::client::JarvixClient;
 pub use metrics::collector::MetricsCollector;
 pub use core::analyzer::*;
-pub use api::{analyze, repair, test_cmd, cargo_run};
+pub use api::{
+    analyze, analyze_cancellable, cargo_run, cargo_run_capture, repair, repair_with_progress,
+    test_cmd,
+};
+pub use events::ProgressEvent;
+pub use error::TraeError;
+pub use tokio_util::sync::CancellationToken;
@@ -2,3 +2,4 @@
 #![doc = ""]
 #![doc = " Sistema de recolección y reporte de métricas"]
 pub mod collector;
+pub mod perf_history;
@@ -0,0 +1,176 @@
+#![doc = " # Perf History - Build stability across runs"]
+#![doc = ""]
+#![doc = " Historial acotado de estabilidad FFT y operaciones lentas por ejecución"]
+use crate::performance_patterns::MetricsCollector;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+/// Location of the history file, relative to the project root.
+pub const PERF_HISTORY_FILE: &str = ".trae/perf_history.json";
+/// Oldest runs are dropped beyond this many entries.
+pub const MAX_ENTRIES: usize = 100;
+/// Same cut-off `trae build` uses to call a run unstable.
+pub const STABILITY_THRESHOLD: f64 = 0.7;
+/// Consecutive unstable runs needed to flag a regression.
+pub const REGRESSION_RUNS: usize = 3;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[doc = " Operación lenta registrada en una ejecución"]
+pub struct SlowOperation {
+    pub name: String,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[doc = " Resultado de rendimiento de una ejecución"]
+pub struct PerfRun {
+    pub timestamp: DateTime<Utc>,
+    pub command: String,
+    pub stability: f64,
+    pub success_rate: f64,
+    pub total_ms: u64,
+    pub slowest: Vec<SlowOperation>,
+}
+impl PerfRun {
+    /// Snapshot of `metrics` keeping its three slowest operations.
+    pub fn from_collector(command: &str, metrics: &MetricsCollector) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            command: command.to_string(),
+            stability: metrics.fft_pattern_analysis(),
+            success_rate: metrics.success_rate(),
+            total_ms: metrics.total_duration().as_millis() as u64,
+            slowest: metrics
+                .slowest_operations(3)
+                .into_iter()
+                .map(|op| SlowOperation {
+                    name: op._name.clone(),
+                    duration_ms: op.duration.as_millis() as u64,
+                    success: op.success,
+                })
+                .collect(),
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc = " Dirección de la estabilidad en las últimas ejecuciones"]
+pub enum TrendDirection {
+    Improving,
+    Degrading,
+    Flat,
+}
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[doc = " Historial persistido en `.trae/perf_history.json`"]
+pub struct PerfHistory {
+    pub runs: Vec<PerfRun>,
+}
+impl PerfHistory {
+    /// Reads the history, returning an empty one when missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+    /// Writes the history to `path`, creating its directory.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    /// Appends a run, keeping only the last [`MAX_ENTRIES`].
+    pub fn push(&mut self, run: PerfRun) {
+        self.runs.push(run);
+        if self.runs.len() > MAX_ENTRIES {
+            let excess = self.runs.len() - MAX_ENTRIES;
+            self.runs.drain(..excess);
+        }
+    }
+    /// The last `count` runs, oldest first (all of them when there are fewer).
+    pub fn last(&self, count: usize) -> &[PerfRun] {
+        &self.runs[self.runs.len().saturating_sub(count)..]
+    }
+    /// Least-squares slope of stability over the last `count` runs.
+    pub fn trend(&self, count: usize) -> TrendDirection {
+        let runs = self.last(count);
+        if runs.len() < 2 {
+            return TrendDirection::Flat;
+        }
+        let n = runs.len() as f64;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = runs.iter().map(|r| r.stability).sum::<f64>() / n;
+        let (num, den) = runs
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(num, den), (i, run)| {
+                let dx = i as f64 - mean_x;
+                (num + dx * (run.stability - mean_y), den + dx * dx)
+            });
+        let slope = num / den;
+        if slope > 0.01 {
+            TrendDirection::Improving
+        } else if slope < -0.01 {
+            TrendDirection::Degrading
+        } else {
+            TrendDirection::Flat
+        }
+    }
+    /// The runs of `command` (any if `None`) recorded at or after `since`
+    /// (any time if `None`), as a history of their own.
+    pub fn window(&self, since: Option<DateTime<Utc>>, command: Option<&str>) -> Self {
+        let runs = self
+            .runs
+            .iter()
+            .filter(|run| since.is_none_or(|since| run.timestamp >= since))
+            .filter(|run| command.is_none_or(|command| run.command == command))
+            .cloned()
+            .collect();
+        Self { runs }
+    }
+    /// Mean `total_ms` and stability of `runs`; `None` when empty.
+    pub fn averages(runs: &[PerfRun]) -> Option<(f64, f64)> {
+        if runs.is_empty() {
+            return None;
+        }
+        let n = runs.len() as f64;
+        let total_ms = runs.iter().map(|run| run.total_ms as f64).sum::<f64>() / n;
+        let stability = runs.iter().map(|run| run.stability).sum::<f64>() / n;
+        Some((total_ms, stability))
+    }
+    /// True when the most recent [`REGRESSION_RUNS`] runs are all below [`STABILITY_THRESHOLD`].
+    pub fn regression(&self) -> bool {
+        self.runs.len() >= REGRESSION_RUNS
+            && self
+                .last(REGRESSION_RUNS)
+                .iter()
+                .all(|run| run.stability < STABILITY_THRESHOLD)
+    }
+}
+/// Appends `run` to the project history file.
+pub fn record_run(run: PerfRun) -> Result<()> {
+    let path = Path::new(PERF_HISTORY_FILE);
+    let mut history = PerfHistory::load(path);
+    history.push(run);
+    history.save(path)
+}
+/// Parses `--since` durations: a whole number followed by `m` (minutes), `h`,
+/// `d` or `w`, e.g. `24h`, `7d`, `30d`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("Duración inválida '{s}' (usa p. ej. 30m, 24h, 7d o 4w)");
+    let s = s.trim();
+    let unit = s.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = s[..s.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    let duration = match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    };
+    duration.ok_or_else(invalid)
+}
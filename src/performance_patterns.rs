@@ -21,7 +21,7 @@ impl Default for PerformanceConfig {
     #[doc = "Method documentation added by AI refactor"]
     fn default() -> Self {
         Self {
-            thread_count: num_cpus::get(),
+            thread_count: rayon::current_num_threads(),
             cache_size: 1000,
             batch_size: 100,
             timeout_ms: 5000,
@@ -32,7 +32,7 @@ impl Default for PerformanceConfig {
 impl PerformanceConfig {
     #[doc = " Auto-configuración basada en características del sistema con PSO"]
     pub fn auto_tune() -> Self {
-        let cpu_count = num_cpus::get();
+        let cpu_count = rayon::current_num_threads();
         let base_config = Self {
             thread_count: cpu_count,
             cache_size: cpu_count * 200,
@@ -274,7 +274,7 @@ impl MetricsCollector {
     #[doc = "Method documentation added by AI refactor"]
     pub fn slowest_operations(&self, count: usize) -> Vec<&OperationMetric> {
         let mut ops = self.operations.iter().collect::<Vec<_>>();
-        ops.sort_by(|a, b| b.duration.cmp(&a.duration));
+        ops.sort_by_key(|op| std::cmp::Reverse(op.duration));
         ops.into_iter().take(count).collect()
     }
     #[doc = "Method documentation added by AI refactor"]
@@ -0,0 +1,36 @@
+#![doc = " # ANSI Utils - Terminal escape sequences"]
+#![doc = ""]
+#![doc = " Quita colores y demás secuencias de escape del texto capturado de cargo"]
+/// `text` without ANSI escape sequences: CSI (`ESC [ ... m`, colors and
+/// cursor movement), OSC (`ESC ] ... BEL`, e.g. the hyperlinks cargo prints)
+/// and two-byte `ESC x` sequences. Everything else is kept as is.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // Parameters and intermediates up to the final byte `@`..`~`
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // Terminated by BEL or by the string terminator `ESC \`
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,102 @@
+#![doc = " # Diagnostics - LSP-style line output"]
+#![doc = ""]
+#![doc = " Un objeto JSON por issue (`uri`, `range`, `severity`, `code`, `message`, `source`),"]
+#![doc = " el formato que consumen clientes LSP genéricos como efm-langserver"]
+use crate::utils::snippet::Snippet;
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+/// LSP `DiagnosticSeverity` values.
+pub const ERROR: u8 = 1;
+pub const WARNING: u8 = 2;
+pub const INFORMATION: u8 = 3;
+pub const HINT: u8 = 4;
+/// Zero-based line/character position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub character: usize,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[doc = " Rango semiabierto `[start, end)`"]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[doc = " Un diagnóstico en formato LSP"]
+pub struct Diagnostic {
+    pub uri: String,
+    pub range: Range,
+    pub severity: u8,
+    pub code: String,
+    pub message: String,
+    pub source: &'static str,
+    /// Confidence and, with `--diff-context`, source context of the finding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<DiagnosticData>,
+}
+/// LSP `data`: payload clients pass back untouched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DiagnosticData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Snippet>,
+    /// How likely the finding is real, from 0 to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+impl Diagnostic {
+    /// `line` is 1-based as trae reports it; the range covers the whole line.
+    /// Findings without a file are attached to the project's `Cargo.toml`.
+    pub fn new(
+        file: Option<&str>,
+        line: Option<usize>,
+        severity: u8,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        let line = line.map_or(0, |l| l.saturating_sub(1));
+        Self {
+            uri: file_uri(Path::new(file.unwrap_or("Cargo.toml"))),
+            range: Range {
+                start: Position { line, character: 0 },
+                end: Position {
+                    line: line + 1,
+                    character: 0,
+                },
+            },
+            severity,
+            code: code.into(),
+            message: message.into(),
+            source: "trae",
+            data: None,
+        }
+    }
+    pub fn with_snippet(mut self, snippet: Option<Snippet>) -> Self {
+        if let Some(snippet) = snippet {
+            self.data.get_or_insert_with(DiagnosticData::default).snippet = Some(snippet);
+        }
+        self
+    }
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.data.get_or_insert_with(DiagnosticData::default).confidence = Some(confidence);
+        self
+    }
+}
+/// `file://` URI for `path`, resolved against the working directory.
+pub fn file_uri(path: &Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    let absolute = std::env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path));
+    reqwest::Url::from_file_path(&absolute)
+        .map_or_else(|()| format!("file://{}", absolute.display()), String::from)
+}
+/// Writes one diagnostic per line on stdout; a closed pipe is an error, not a panic.
+pub fn emit(diagnostics: impl IntoIterator<Item = Diagnostic>) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    for diagnostic in diagnostics {
+        serde_json::to_writer(&mut out, &diagnostic)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![doc = " # HTTP Utils - Outbound HTTP client"]
+#![doc = ""]
+#![doc = " Cliente HTTP común: User-Agent, headers de `[http]`, proxy del entorno y TLS de JARVIX"]
+use crate::build_info::LONG_VERSION;
+use crate::config::{HttpConfig, TraeConfig};
+use crate::jarvix::client::{parse_report_header, VERSION_HEADER};
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Certificate, Client, ClientBuilder, NoProxy, Proxy};
+use std::sync::Once;
+/// `User-Agent` of every request unless `[http] user_agent` replaces it.
+pub const DEFAULT_USER_AGENT: &str = concat!("trae-cli/", env!("CARGO_PKG_VERSION"));
+/// Proxy variables read from the environment, most specific first. The
+/// lowercase spelling is used when the uppercase one is unset.
+const PROXY_VARS: [(&str, &str); 3] = [
+    ("HTTPS_PROXY", "https"),
+    ("HTTP_PROXY", "http"),
+    ("ALL_PROXY", "all"),
+];
+/// `[jarvix] insecure` is announced once per process.
+static INSECURE_WARNING: Once = Once::new();
+/// Builder every outbound client starts from, configured by `[http]` of
+/// the config in effect. Callers add their own timeout.
+pub fn client_builder() -> Result<ClientBuilder> {
+    builder_with(&TraeConfig::load().http)
+}
+/// A client from [`client_builder`] with nothing else set.
+pub fn client() -> Result<Client> {
+    Ok(client_builder()?.build()?)
+}
+/// Builder with the headers of `config` and the proxies of the
+/// environment (`HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY`, skipping the hosts
+/// in `NO_PROXY`). A proxy variable that isn't a URL is an error rather
+/// than being silently ignored.
+pub fn builder_with(config: &HttpConfig) -> Result<ClientBuilder> {
+    let mut builder = Client::builder().default_headers(default_headers(config)?).no_proxy();
+    for (var, scheme) in PROXY_VARS {
+        let Some(url) = std::env::var(var)
+            .or_else(|_| std::env::var(var.to_lowercase()))
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+        else {
+            continue;
+        };
+        let proxy = match scheme {
+            "https" => Proxy::https(url.as_str()),
+            "http" => Proxy::http(url.as_str()),
+            _ => Proxy::all(url.as_str()),
+        }
+        .with_context(|| format!("{var}=`{url}` no es una URL de proxy válida"))?;
+        builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+    }
+    Ok(builder)
+}
+/// Headers sent on every request: `User-Agent`, `x-trae-version` and the
+/// `key:value` lines of `[http] headers`, which may override either.
+pub fn default_headers(config: &HttpConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    let user_agent = config.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+    let user_agent = HeaderValue::from_str(user_agent)
+        .with_context(|| format!("[http] user_agent `{user_agent}` no es un header válido"))?;
+    headers.insert(USER_AGENT, user_agent);
+    headers.insert(VERSION_HEADER, HeaderValue::from_static(LONG_VERSION));
+    for line in &config.headers {
+        let (name, value) = parse_report_header(line)
+            .map_err(|e| anyhow::anyhow!("[http] headers: {e}"))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+/// Builder for requests to JARVIXSERVER: [`client_builder`] plus the TLS
+/// settings of `[jarvix]`.
+pub fn jarvix_builder() -> Result<ClientBuilder> {
+    jarvix_builder_with(&TraeConfig::load())
+}
+/// A client from [`jarvix_builder`] with nothing else set.
+pub fn jarvix_client() -> Result<Client> {
+    Ok(jarvix_builder()?.build()?)
+}
+/// [`builder_with`] the `[http]` of `config`, also trusting the CAs of
+/// `[jarvix] ca_cert`. A `ca_cert` that can't be read or holds no
+/// certificate is an error. `[jarvix] insecure` turns certificate checks
+/// off, with a warning the first time.
+pub fn jarvix_builder_with(config: &TraeConfig) -> Result<ClientBuilder> {
+    let mut builder = builder_with(&config.http)?;
+    if let Some(path) = &config.jarvix.ca_cert {
+        let describe = || format!("[jarvix] ca_cert {}", path.display());
+        let pem = std::fs::read(path).with_context(|| format!("{}: no se pudo leer", describe()))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("{}: certificado PEM inválido", describe()))?;
+        if certs.is_empty() {
+            anyhow::bail!("{}: no contiene ningún certificado PEM", describe());
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if config.jarvix.insecure {
+        INSECURE_WARNING.call_once(|| {
+            eprintln!(
+                "⚠️  [jarvix] insecure = true: la verificación TLS de JARVIXSERVER está \
+                 DESACTIVADA. Solo para desarrollo; nunca en producción."
+            );
+        });
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
@@ -0,0 +1,138 @@
+#![doc = " # Markdown - Reports for PR comments and docs"]
+#![doc = ""]
+#![doc = " Documento Markdown con una tabla resumen y una sección plegable por categoría,"]
+#![doc = " listo para publicarse como comentario de un PR"]
+use std::collections::BTreeMap;
+use std::fmt::Display;
+/// One row of a [`Report`]: what it is about, where and what it says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub category: String,
+    /// Severity name as the command reports it (`critical`, `High`, ...).
+    pub severity: String,
+    /// A path, `path:line` or command; rendered as code.
+    pub location: Option<String>,
+    pub message: String,
+}
+impl Finding {
+    /// `file:line` (or just `file`) as the location of a finding.
+    pub fn location(file: Option<&str>, line: Option<usize>) -> Option<String> {
+        file.map(|file| match line {
+            Some(line) => format!("{file}:{line}"),
+            None => file.to_string(),
+        })
+    }
+}
+/// A Markdown document: title, summary table, findings grouped by category
+/// and closing notes.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    title: String,
+    summary: Vec<(String, String)>,
+    findings: Vec<Finding>,
+    notes: Vec<String>,
+}
+impl Report {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), ..Self::default() }
+    }
+    /// Adds a row to the summary table.
+    pub fn summary(&mut self, label: impl Into<String>, value: impl Display) -> &mut Self {
+        self.summary.push((label.into(), value.to_string()));
+        self
+    }
+    pub fn finding(&mut self, finding: Finding) -> &mut Self {
+        self.findings.push(finding);
+        self
+    }
+    /// A paragraph after the findings (suggestions, truncation notices...).
+    pub fn note(&mut self, text: impl Into<String>) -> &mut Self {
+        self.notes.push(text.into());
+        self
+    }
+    /// The document. Categories are sorted by name, and each one keeps its
+    /// findings in the order they were added. Text from the findings is
+    /// escaped, so it can't break the tables or inject markup.
+    pub fn render(&self) -> String {
+        let mut out = format!("## {}\n\n", escape(&self.title));
+        if !self.summary.is_empty() {
+            out.push_str("| Metric | Value |\n|---|---:|\n");
+            for (label, value) in &self.summary {
+                out.push_str(&format!("| {} | {} |\n", escape(label), escape(value)));
+            }
+            out.push('\n');
+        }
+        let mut categories: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+        for finding in &self.findings {
+            categories.entry(&finding.category).or_default().push(finding);
+        }
+        if categories.is_empty() {
+            out.push_str("✅ No findings.\n\n");
+        }
+        for (category, findings) in categories {
+            out.push_str(&format!(
+                "<details>\n<summary><strong>{}</strong> ({})</summary>\n\n",
+                html_escape(category),
+                findings.len()
+            ));
+            out.push_str("| Severity | Location | Message |\n|---|---|---|\n");
+            for finding in findings {
+                let location = finding.location.as_deref().map(code).unwrap_or_default();
+                out.push_str(&format!(
+                    "| {} {} | {location} | {} |\n",
+                    badge(&finding.severity),
+                    escape(&finding.severity),
+                    escape(&finding.message)
+                ));
+            }
+            out.push_str("\n</details>\n\n");
+        }
+        for note in &self.notes {
+            out.push_str(&format!("> {}\n\n", escape(note)));
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+}
+/// Colored circle for a severity name, whatever the command calls it.
+pub fn badge(severity: &str) -> &'static str {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" | "error" => "🔴",
+        "high" => "🟠",
+        "warning" | "medium" => "🟡",
+        _ => "🔵",
+    }
+}
+/// `text` as plain text inside a table cell: Markdown punctuation and pipes
+/// backslash-escaped, line breaks turned into spaces.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' | '\r' => out.push(' '),
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' | '~' | '!' | '&' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+/// `text` as an inline code span that survives backticks in it and sits in
+/// a table cell (pipes escaped, as GFM requires even inside code).
+pub fn code(text: &str) -> String {
+    let text = text.replace(['\n', '\r'], " ").replace('|', "\\|");
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    let pad = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+    format!("{fence}{pad}{text}{pad}{fence}")
+}
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -1,6 +1,13 @@
 #![doc = " # Utils Module - Utility functions"]
 #![doc = ""]
 #![doc = " Funciones de utilidad y helpers"]
+pub mod ansi;
+pub mod diagnostics;
 pub mod docs;
+pub mod http;
+pub mod markdown;
+pub mod path;
 pub mod progress;
+pub mod snippet;
+pub mod source;
 pub mod ui;
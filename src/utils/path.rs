@@ -0,0 +1,136 @@
+#![doc = " # Path Utils - Project root handling"]
+#![doc = ""]
+#![doc = " Validación de rutas y cambio temporal del directorio de trabajo"]
+use crate::config::RunOptions;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use walkdir::{DirEntry, WalkDir};
+/// Clap value parser: accepts only paths that exist.
+pub fn validate_path(s: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(s);
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(format!("La ruta '{}' no existe", s))
+    }
+}
+/// `path` as reports print it: `/` between components on every platform,
+/// so `.\src\lib.rs` becomes `./src/lib.rs` and editors, CI annotations and
+/// baselines read the same file whichever OS produced the report.
+pub fn portable(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+/// Whether `entry` is a dotfile or dot-directory below the walk root; the
+/// root itself (`.` included) never is.
+pub fn is_hidden(entry: &DirEntry) -> bool {
+    entry.depth() > 0 && entry.file_name().to_str().is_some_and(|name| name.starts_with('.'))
+}
+/// Entries under `dir`, at most `max_depth` levels below it, that scanners
+/// look at: hidden entries and everything below them are skipped unless
+/// `options` include them (`--include-hidden`).
+pub fn walk(
+    dir: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> impl Iterator<Item = DirEntry> {
+    let mut walker = WalkDir::new(dir);
+    if let Some(depth) = max_depth {
+        walker = walker.max_depth(depth);
+    }
+    let include_hidden = options.include_hidden;
+    walker
+        .into_iter()
+        .filter_entry(move |e| include_hidden || !is_hidden(e))
+        .filter_map(|e| e.ok())
+}
+/// `.rs` files under `dir`, at most `max_depth` levels below it (`Some(1)` is
+/// only `dir/*.rs`); `None` walks the whole tree. See [`walk`]. Files over
+/// the size limit of `options` are left out (see [`within_size_limit`]).
+pub fn rust_files(
+    dir: &Path,
+    max_depth: Option<usize>,
+    options: &RunOptions,
+) -> impl Iterator<Item = DirEntry> {
+    let limit = options.max_file_size();
+    let oversized = options.oversized.clone();
+    walk(dir, max_depth, options)
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter(move |e| within_size_limit(e.path(), limit, &oversized))
+}
+/// Files a run skipped for their size, each with its size and the limit it
+/// went over. Clones share the list, so the copies of a run's
+/// [`RunOptions`] handed to scanners all record into the one
+/// [`Self::warn`] reports.
+#[derive(Debug, Clone, Default)]
+pub struct Oversized(Arc<Mutex<BTreeMap<PathBuf, (u64, u64)>>>);
+impl Oversized {
+    /// Files skipped since the last call, by path: each with its size and
+    /// the limit it went over.
+    pub fn take(&self) -> Vec<(PathBuf, u64, u64)> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+            .into_iter()
+            .map(|(path, (size, limit))| (path, size, limit))
+            .collect()
+    }
+    /// Lists on stderr the files skipped for their size, if any.
+    pub fn warn(&self) {
+        let skipped = self.take();
+        let Some(limit) = skipped.iter().map(|(_, _, limit)| *limit).min() else {
+            return;
+        };
+        eprintln!(
+            "⚠️  {} archivo(s) omitidos por superar {limit} bytes (--max-file-size):",
+            skipped.len()
+        );
+        for (path, size, _) in skipped {
+            eprintln!("   - {} ({size} bytes)", path.display());
+        }
+    }
+}
+/// Whether the file at `path` is at most `limit` bytes (see
+/// [`RunOptions::max_file_size`]). Larger files (generated tables, vendored
+/// bundles) would make regex scans crawl, so scanners skip them and record
+/// them in `oversized`.
+pub fn within_size_limit(path: &Path, limit: u64, oversized: &Oversized) -> bool {
+    let Ok(size) = std::fs::metadata(path).map(|m| m.len()) else {
+        return true;
+    };
+    if size <= limit {
+        return true;
+    }
+    let path = path.strip_prefix(".").unwrap_or(path).to_path_buf();
+    oversized.0.lock().unwrap_or_else(|e| e.into_inner()).insert(path, (size, limit));
+    false
+}
+/// Total size in bytes of the regular files under `dir`, recursively.
+/// Symlinks are not followed, so nothing is counted twice.
+pub fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+/// Makes `dir` the working directory until dropped, so file walks and cargo
+/// invocations resolve against it and findings stay relative to it.
+pub struct WorkingDirGuard {
+    previous: PathBuf,
+}
+impl WorkingDirGuard {
+    /// Enters `dir`; fails if it cannot be made the current directory.
+    pub fn enter(dir: &Path) -> Result<Self> {
+        let previous = std::env::current_dir()?;
+        std::env::set_current_dir(dir)
+            .map_err(|e| anyhow::anyhow!("No se pudo usar '{}' como raíz: {e}", dir.display()))?;
+        Ok(Self { previous })
+    }
+}
+impl Drop for WorkingDirGuard {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.previous);
+    }
+}
@@ -1,12 +1,54 @@
 use anyhow::Result;
-use cargo_metadata::MetadataCommand;
+use crate::config::RunOptions;
+use crate::core::cargo::metadata_command;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+/// Env var choosing how spinners and bars look: `dots` (the default),
+/// `ascii` for terminals without Unicode, or `none` to hide them.
+pub const PROGRESS_STYLE_ENV: &str = "TRAE_PROGRESS_STYLE";
+const SPINNER: &str = "{spinner:.green} {msg}";
+const TIMED_SPINNER: &str = "{spinner:.green} {msg} · {elapsed}";
+const BAR: &str = "{spinner:.green} {pos}/{len} {wide_bar:.cyan/blue} {msg}";
+const ETA_BAR: &str =
+    "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {percent:>3}% ({eta}) {msg}";
+/// Spinner that is never drawn in CI or quiet mode.
+pub fn spinner(options: &RunOptions) -> ProgressBar {
+    styled(ProgressBar::new_spinner(), SPINNER, options)
+}
+/// [`spinner`] that also shows the time elapsed, for remote waits.
+pub fn timed_spinner(options: &RunOptions) -> ProgressBar {
+    styled(ProgressBar::new_spinner(), TIMED_SPINNER, options)
+}
+/// Progress bar of `len` steps that is never drawn in CI or quiet mode.
+pub fn bar(len: u64, options: &RunOptions) -> ProgressBar {
+    styled(ProgressBar::new(len), BAR, options)
+}
+/// [`bar`] showing the percentage and the estimated time left.
+pub fn eta_bar(len: u64, options: &RunOptions) -> ProgressBar {
+    styled(ProgressBar::new(len), ETA_BAR, options)
+}
+/// `progress` drawn with `template` and the frames of [`PROGRESS_STYLE_ENV`],
+/// or hidden in CI, quiet mode and with the `none` style.
+fn styled(progress: ProgressBar, template: &str, options: &RunOptions) -> ProgressBar {
+    let kind = std::env::var(PROGRESS_STYLE_ENV).unwrap_or_default();
+    if options.hides_progress() || kind == "none" {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+        return progress;
+    }
+    let style = ProgressStyle::with_template(template).expect("progress template");
+    let style = match kind.as_str() {
+        "ascii" => style.tick_chars("|/-\\ ").progress_chars("#>-"),
+        _ => style.tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏ ").progress_chars("█▉▊▋▌▍▎▏  "),
+    };
+    progress.set_style(style);
+    progress
+}
 #[doc = "Function documentation added by AI refactor"]
-pub fn estimate_cargo_units() -> usize {
-    estimate_cargo_units_inner().unwrap_or(100)
+pub fn estimate_cargo_units(options: &RunOptions) -> usize {
+    estimate_cargo_units_inner(options).unwrap_or(100)
 }
 #[doc = "Function documentation added by AI refactor"]
-fn estimate_cargo_units_inner() -> Result<usize> {
-    let metadata = MetadataCommand::new().no_deps().exec()?;
+fn estimate_cargo_units_inner(options: &RunOptions) -> Result<usize> {
+    let metadata = metadata_command(options).no_deps().exec()?;
     if let Some(resolve) = metadata.resolve {
         if !resolve.nodes.is_empty() {
             return Ok(resolve.nodes.len());
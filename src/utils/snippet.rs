@@ -0,0 +1,123 @@
+#![doc = " # Snippet - Source context around findings"]
+#![doc = ""]
+#![doc = " Las líneas alrededor del `archivo:línea` de cada hallazgo (`--diff-context`), con un"]
+#![doc = " tope total para que un escaneo con miles de hallazgos no inunde el log"]
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+/// Most lines `--diff-context` shows on each side of a finding.
+pub const MAX_CONTEXT: usize = 10;
+/// Snippet lines one output carries at most; findings past it get none.
+pub const MAX_TOTAL_LINES: usize = 200;
+/// `--diff-context` value: 0 (the line alone) to [`MAX_CONTEXT`].
+pub fn parse_context(s: &str) -> Result<usize, String> {
+    let n: usize = s.parse().map_err(|_| format!("'{s}' no es un número de líneas"))?;
+    if n > MAX_CONTEXT {
+        return Err(format!("como máximo {MAX_CONTEXT} líneas de contexto"));
+    }
+    Ok(n)
+}
+/// Lines `start..start + lines.len()` of a file; `line` is the finding's.
+/// Line numbers are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub start: usize,
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+impl Snippet {
+    /// `context` lines each side of line `line` of `content`; `None` past the end.
+    pub fn around(content: &str, line: usize, context: usize) -> Option<Self> {
+        let all: Vec<&str> = content.lines().collect();
+        if line == 0 || line > all.len() {
+            return None;
+        }
+        let start = line.saturating_sub(context).max(1);
+        let end = (line + context).min(all.len());
+        Some(Self {
+            start,
+            line,
+            lines: all[start - 1..end].iter().map(|l| l.to_string()).collect(),
+        })
+    }
+    /// Numbered lines for the terminal, the finding's marked with `>`.
+    pub fn render(&self) -> Vec<String> {
+        let width = (self.start + self.lines.len()).to_string().len();
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let number = self.start + i;
+                if number == self.line {
+                    format!("  > {number:>width$} │ {}", text.red().bold())
+                } else {
+                    format!("    {} {}", format!("{number:>width$} │").bright_black(), text)
+                }
+            })
+            .collect()
+    }
+    /// Prints [`Self::render`], unless `options` are quiet.
+    pub fn print(&self, options: &crate::config::RunOptions) {
+        if options.quiet {
+            return;
+        }
+        for line in self.render() {
+            println!("{line}");
+        }
+    }
+}
+/// Snippets for one output, within [`MAX_TOTAL_LINES`]. Files are read once.
+#[derive(Debug, Default)]
+pub struct Snippets {
+    context: usize,
+    used: usize,
+    skipped: usize,
+    files: HashMap<String, Option<String>>,
+}
+impl Snippets {
+    /// `None` without `--diff-context`.
+    pub fn new(context: Option<usize>) -> Option<Self> {
+        context.map(|context| Self { context, ..Self::default() })
+    }
+    /// The snippet of a finding at `file:line`, if it has both, the file
+    /// reads and the budget allows it.
+    pub fn take(&mut self, file: Option<&str>, line: Option<usize>) -> Option<Snippet> {
+        let (file, line) = (file?, line?);
+        let content = self
+            .files
+            .entry(file.to_string())
+            .or_insert_with(|| std::fs::read_to_string(file).ok())
+            .as_deref()?;
+        let snippet = Snippet::around(content, line, self.context)?;
+        if self.used + snippet.lines.len() > MAX_TOTAL_LINES {
+            self.skipped += 1;
+            return None;
+        }
+        self.used += snippet.lines.len();
+        Some(snippet)
+    }
+    /// Says how many findings went without a snippet once the cap was hit.
+    pub fn notice(&self) -> Option<String> {
+        (self.skipped > 0).then(|| {
+            format!(
+                "✂️ {} hallazgos sin snippet: tope de {MAX_TOTAL_LINES} líneas de contexto",
+                self.skipped
+            )
+        })
+    }
+}
+/// `items` as a JSON array, each object with its `snippet` when it has one.
+pub fn with_snippets<T: Serialize>(
+    items: &[T],
+    snippets: &[Option<Snippet>],
+) -> serde_json::Result<serde_json::Value> {
+    let mut values = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let mut value = serde_json::to_value(item)?;
+        if let (Some(Some(snippet)), Some(object)) = (snippets.get(i), value.as_object_mut()) {
+            object.insert("snippet".to_string(), serde_json::to_value(snippet)?);
+        }
+        values.push(value);
+    }
+    Ok(values.into())
+}
@@ -0,0 +1,269 @@
+#![doc = " # Source Utils - Minimal Rust lexing for line scanners"]
+#![doc = ""]
+#![doc = " Enmascara literales raw (`r#\"...\"#`) y de bytes (`b\"...\"`, `br\"...\"`) para que"]
+#![doc = " los escáneres por línea no reporten TODOs o secretos embebidos en ellos."]
+/// Returns `content` with the bodies of raw and byte string literals replaced
+/// by spaces. Newlines are kept, so line numbers still match the original;
+/// comments, plain strings and char literals are copied through untouched.
+pub fn mask_raw_and_byte_strings(content: &str) -> String {
+    mask(content, Blank { comments: false, strings: false, code: false })
+}
+/// Returns `content` with every comment and string or char literal replaced
+/// by spaces, newlines kept: only code is left for a scanner to match.
+pub fn mask_comments_and_strings(content: &str) -> String {
+    mask(content, Blank { comments: true, strings: true, code: false })
+}
+/// Returns `content` with code and string or char literals replaced by
+/// spaces, newlines kept: only the text of `//` and `/* */` comments is left.
+pub fn mask_code_and_strings(content: &str) -> String {
+    mask(content, Blank { comments: false, strings: true, code: true })
+}
+/// What [`mask`] blanks besides raw and byte string bodies, which it
+/// always does.
+#[derive(Clone, Copy)]
+struct Blank {
+    comments: bool,
+    strings: bool,
+    code: bool,
+}
+fn mask(content: &str, blank: Blank) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let after_ident = i > 0 && is_ident_char(chars[i - 1]);
+        match c {
+            '/' if next == Some('/') => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|&c| c == '\n')
+                    .map_or(chars.len(), |p| i + p);
+                copy(&mut out, &chars[i..end], blank.comments);
+                i = end;
+            }
+            '/' if next == Some('*') => {
+                let end = find_block_comment_end(&chars, i + 2);
+                copy(&mut out, &chars[i..end], blank.comments);
+                i = end;
+            }
+            '"' => {
+                let end = find_quote_end(&chars, i + 1);
+                copy(&mut out, &chars[i..end], blank.strings);
+                i = end;
+            }
+            '\'' => match char_literal_end(&chars, i) {
+                Some(end) => {
+                    copy(&mut out, &chars[i..end], blank.strings);
+                    i = end;
+                }
+                // A lifetime
+                None => {
+                    copy(&mut out, &chars[i..=i], blank.code);
+                    i += 1;
+                }
+            },
+            'b' | 'r' if !after_ident => match literal_at(&chars, i) {
+                Some((body_start, body_end, end)) => {
+                    copy(&mut out, &chars[i..body_start], blank.strings);
+                    copy(&mut out, &chars[body_start..body_end], true);
+                    copy(&mut out, &chars[body_end..end], blank.strings);
+                    i = end;
+                }
+                None => {
+                    copy(&mut out, &chars[i..=i], blank.code);
+                    i += 1;
+                }
+            },
+            _ => {
+                copy(&mut out, &chars[i..=i], blank.code);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+/// Appends `chars` to `out`, as spaces (newlines kept) when `blank`.
+fn copy(out: &mut String, chars: &[char], blank: bool) {
+    if blank {
+        out.extend(chars.iter().map(|&c| if c == '\n' { '\n' } else { ' ' }));
+    } else {
+        out.extend(chars);
+    }
+}
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+/// Recognises `r"`, `r#"`, `b"`, `br"`, `br#"` at `start`; returns the body
+/// span and the index just past the closing delimiter.
+fn literal_at(chars: &[char], start: usize) -> Option<(usize, usize, usize)> {
+    let mut i = start;
+    let byte = chars[i] == 'b';
+    if byte {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'r') {
+        i += 1;
+        let hashes = chars[i..].iter().take_while(|&&c| c == '#').count();
+        i += hashes;
+        if chars.get(i) != Some(&'"') {
+            return None;
+        }
+        let body_start = i + 1;
+        let mut j = body_start;
+        while j < chars.len() {
+            if chars[j] == '"'
+                && chars[j + 1..]
+                    .iter()
+                    .take(hashes)
+                    .filter(|&&c| c == '#')
+                    .count()
+                    == hashes
+            {
+                return Some((body_start, j, j + 1 + hashes));
+            }
+            j += 1;
+        }
+        return Some((body_start, chars.len(), chars.len()));
+    }
+    if byte && chars.get(i) == Some(&'"') {
+        let end = find_quote_end(chars, i + 1);
+        let body_end = if chars.get(end - 1) == Some(&'"') && end - 1 > i {
+            end - 1
+        } else {
+            end
+        };
+        return Some((i + 1, body_end, end));
+    }
+    None
+}
+/// Index just past the closing `"` of a plain (escapable) string body.
+fn find_quote_end(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+fn find_block_comment_end(chars: &[char], mut i: usize) -> usize {
+    while i + 1 < chars.len() {
+        if chars[i] == '*' && chars[i + 1] == '/' {
+            return i + 2;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+/// `TODO`/`FIXME`/`BUG`/`HACK` comments as `(line, "[TAG] text")`, ignoring
+/// markers that only appear inside raw or byte string literals.
+pub fn find_todos(content: &str) -> Vec<(usize, String)> {
+    let todo_pattern =
+        regex::Regex::new(r"//\s*(TODO|FIXME|BUG|HACK):\s*(.+)").expect("valid regex");
+    mask_raw_and_byte_strings(content)
+        .lines()
+        .enumerate()
+        .filter_map(|(line_num, line)| {
+            let caps = todo_pattern.captures(line)?;
+            Some((line_num + 1, format!("[{}] {}", &caps[1], &caps[2])))
+        })
+        .collect()
+}
+/// Panicking call found by [`panicking_call`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicCall {
+    /// `unwrap()`.
+    Unwrap,
+    /// `expect()`/`expect("")`: nothing explains the panic either.
+    EmptyExpect,
+    /// `expect(...)` with a message: a non-empty literal, an expression, or
+    /// arguments continued on the next line.
+    Expect,
+}
+/// The worst panicking call on `line`. `unwrap_or`, `unwrap_or_else` and
+/// `unwrap_or_default` cannot panic and are never reported.
+pub fn panicking_call(line: &str) -> Option<PanicCall> {
+    if line.contains("unwrap()") {
+        return Some(PanicCall::Unwrap);
+    }
+    let mut found = None;
+    for (idx, _) in line.match_indices(".expect(") {
+        let args = line[idx + ".expect(".len()..].trim_start();
+        let args = args.strip_prefix("\"\"").map_or(args, str::trim_start);
+        if args.starts_with(')') {
+            return Some(PanicCall::EmptyExpect);
+        }
+        found = Some(PanicCall::Expect);
+    }
+    found
+}
+/// For each line of `content`, whether it belongs to a `#[cfg(test)]` item
+/// (brace-matched, line based). The attribute line itself is not included.
+pub fn cfg_test_lines(content: &str) -> Vec<bool> {
+    let mut gated = Vec::new();
+    let mut pending_cfg_test = false;
+    let mut gated_depth: Option<i64> = None;
+    for line in content.lines() {
+        let code = line.split("//").next().unwrap_or_default();
+        if code.trim_start().starts_with("#[cfg(test)]") {
+            pending_cfg_test = true;
+        }
+        let opens = code.matches('{').count() as i64;
+        let closes = code.matches('}').count() as i64;
+        let in_test = match gated_depth.as_mut() {
+            Some(depth) => {
+                *depth += opens - closes;
+                if *depth <= 0 {
+                    gated_depth = None;
+                }
+                true
+            }
+            None if pending_cfg_test && (opens > 0 || code.trim_end().ends_with(';')) => {
+                pending_cfg_test = false;
+                if opens > closes {
+                    gated_depth = Some(opens - closes);
+                }
+                true
+            }
+            None => false,
+        };
+        gated.push(in_test);
+    }
+    gated
+}
+/// 1-based lines whose code names a mock (`mock`, `Mock`, `MOCK` anywhere in
+/// an identifier), leaving out comments, string literals and `#[cfg(test)]`
+/// items, where mocks are expected.
+pub fn mock_lines(content: &str) -> Vec<usize> {
+    let test_lines = cfg_test_lines(content);
+    mask_comments_and_strings(content)
+        .lines()
+        .enumerate()
+        .filter(|(i, line)| {
+            !test_lines.get(*i).copied().unwrap_or(false)
+                && line.to_ascii_lowercase().contains("mock")
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+/// Whether `path` is test or bench code by location: a `tests` or `benches`
+/// directory anywhere in it.
+pub fn is_test_path(path: &std::path::Path) -> bool {
+    path.components().any(|c| matches!(c.as_os_str().to_str(), Some("tests" | "benches")))
+}
+/// `'x'` or `'\n'`-style char literal at `start`; `None` for lifetimes.
+fn char_literal_end(chars: &[char], start: usize) -> Option<usize> {
+    match chars.get(start + 1)? {
+        // Skip the escaped char so `'\''` closes at the right quote.
+        '\\' => chars
+            .get(start + 3..)?
+            .iter()
+            .position(|&c| c == '\'')
+            .map(|p| start + 4 + p),
+        _ if chars.get(start + 2) == Some(&'\'') => Some(start + 3),
+        _ => None,
+    }
+}
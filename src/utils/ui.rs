@@ -34,6 +34,24 @@ impl StepSummary {
             state: StepState::Skipped,
         }
     }
+    /// `{ name, status, duration_ms }` with status `passed`, `failed` or
+    /// `skipped`; failed steps add `error`, skipped ones have no duration.
+    pub fn to_json(&self) -> serde_json::Value {
+        let (status, duration, error) = match &self.state {
+            StepState::Success(dur) => ("passed", Some(dur), None),
+            StepState::Failed(dur, msg) => ("failed", Some(dur), Some(msg)),
+            StepState::Skipped => ("skipped", None, None),
+        };
+        let mut step = serde_json::json!({
+            "name": self.label,
+            "status": status,
+            "duration_ms": duration.map(|d| d.as_millis() as u64),
+        });
+        if let Some(error) = error {
+            step["error"] = serde_json::json!(error);
+        }
+        step
+    }
 }
 #[doc = "Function documentation added by AI refactor"]
 pub fn print_step_table(title: &str, steps: &[StepSummary], total: Duration) {
@@ -67,6 +85,47 @@ pub fn print_step_table(title: &str, steps: &[StepSummary], total: Duration) {
     println!("{} Total {:>27.2}s", "│".dimmed(), total.as_secs_f64());
     println!("{}", "└───────────────────────────────┘".dimmed());
 }
+/// A stage of a multi-command pipeline (`trae auto`): its own outcome plus
+/// the steps it reported, if any.
+#[derive(Debug, Clone)]
+pub struct StageSummary {
+    pub step: StepSummary,
+    pub steps: Vec<StepSummary>,
+}
+impl StageSummary {
+    /// Success or failure of a stage that ran for `duration`.
+    pub fn new<E: std::fmt::Display>(
+        label: &str,
+        duration: Duration,
+        outcome: &std::result::Result<(), E>,
+    ) -> Self {
+        let step = match outcome {
+            Ok(()) => StepSummary::success(label, duration),
+            Err(e) => StepSummary::failed(label, duration, e.to_string()),
+        };
+        Self { step, steps: Vec::new() }
+    }
+    /// Whether the stage itself failed.
+    pub fn failed(&self) -> bool {
+        matches!(self.step.state, StepState::Failed(..))
+    }
+}
+/// Rows of [`print_overall_summary`]: each stage, then its steps indented.
+pub fn overall_rows(stages: &[StageSummary]) -> Vec<StepSummary> {
+    stages
+        .iter()
+        .flat_map(|stage| {
+            std::iter::once(stage.step.clone()).chain(stage.steps.iter().map(|step| StepSummary {
+                label: format!("  {}", step.label),
+                state: step.state.clone(),
+            }))
+        })
+        .collect()
+}
+/// One [`print_step_table`] for a whole pipeline instead of a report per stage.
+pub fn print_overall_summary(title: &str, stages: &[StageSummary], total: Duration) {
+    print_step_table(title, &overall_rows(stages), total);
+}
 #[doc = "Function documentation added by AI refactor"]
 fn truncate(value: &str, max: usize) -> String {
     if value.chars().count() <= max {
@@ -79,3 +138,31 @@ fn truncate(value: &str, max: usize) -> String {
             + "…"
     }
 }
+/// Prints a colored unified diff for a single file and returns the number of
+/// changed lines (insertions + deletions).
+pub fn print_file_diff(path: &str, old: &str, new: &str) -> usize {
+    use similar::{ChangeTag, TextDiff};
+    let diff = TextDiff::from_lines(old, new);
+    let mut changed = 0;
+    println!("{}", format!("--- a/{path}").red().bold());
+    println!("{}", format!("+++ b/{path}").green().bold());
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        println!("{}", hunk.header().to_string().cyan());
+        for change in hunk.iter_changes() {
+            let line = change.to_string_lossy();
+            let line = line.trim_end_matches('\n');
+            match change.tag() {
+                ChangeTag::Delete => {
+                    changed += 1;
+                    println!("{}", format!("-{line}").red());
+                }
+                ChangeTag::Insert => {
+                    changed += 1;
+                    println!("{}", format!("+{line}").green());
+                }
+                ChangeTag::Equal => println!(" {line}"),
+            }
+        }
+    }
+    changed
+}
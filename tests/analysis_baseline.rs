@@ -0,0 +1,39 @@
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+use trae_cli::core::baseline::{load_issues, BaselineDiff};
+use uuid::Uuid;
+
+fn issue(severity: IssueSeverity, file: &str, line: usize, code: &str) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Safety".to_string(),
+        description: format!("unwrap() en línea {line}: {code}"),
+        severity,
+        file: Some(file.to_string()),
+        line: Some(line),
+        confidence: 1.0,
+    }
+}
+
+#[test]
+fn issues_are_matched_by_fingerprint_not_line() {
+    let baseline = vec![
+        issue(IssueSeverity::Warning, "./src/lib.rs", 10, "let a = v.unwrap();"),
+        issue(IssueSeverity::Warning, "./src/lib.rs", 20, "let b = w.unwrap();"),
+    ];
+    let path = std::env::temp_dir().join(format!("trae_baseline_{}.json", Uuid::new_v4()));
+    let report = serde_json::json!({ "total_issues": 2, "issues": baseline, "suggestions": [] });
+    std::fs::write(&path, report.to_string()).unwrap();
+    let baseline = load_issues(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let current = vec![
+        issue(IssueSeverity::Warning, "src/lib.rs", 14, "let a = v.unwrap();"),
+        issue(IssueSeverity::Critical, "src/lib.rs", 30, "let c = x.unwrap();"),
+    ];
+    let diff = BaselineDiff::new(baseline, current);
+    assert_eq!(diff.unchanged, 1);
+    assert_eq!(diff.new.len(), 1);
+    assert_eq!(diff.new[0].line, Some(30));
+    assert_eq!(diff.new_critical(), 1);
+    assert_eq!(diff.fixed.len(), 1);
+    assert_eq!(diff.fixed[0].line, Some(20));
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=trae", "-c", "user.email=trae@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn analyze(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "analyze"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn compare_reports_the_delta_against_a_ref_and_removes_its_worktree() {
+    let dir = std::env::temp_dir().join(format!("trae_compare_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    git(&dir, &["init", "-q", "-b", "main"]);
+    std::fs::write(dir.join("src/lib.rs"), "pub fn a() -> u8 {\n    1\n}\n").unwrap();
+    std::fs::write(dir.join("src/old.rs"), "pub unsafe fn old() {}\n").unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "base"]);
+    // The PR: old.rs is gone and ffi.rs brings a new critical issue
+    std::fs::remove_file(dir.join("src/old.rs")).unwrap();
+    std::fs::write(dir.join("src/ffi.rs"), "pub unsafe fn fresh() {}\n").unwrap();
+    let status_before = git(&dir, &["status", "--porcelain"]);
+
+    let compare = analyze(&dir, &["--compare", "main"]);
+    let unknown = analyze(&dir, &["--compare", "no-such-branch"]);
+    let worktrees = git(&dir, &["worktree", "list"]);
+    let status_after = git(&dir, &["status", "--porcelain"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let stdout = String::from_utf8_lossy(&compare.stdout);
+    assert_eq!(compare.status.code(), Some(2), "{stdout}");
+    assert!(stdout.contains("Comparación con `main`"), "{stdout}");
+    assert!(stdout.contains("1 nuevos, 1 corregidos"), "{stdout}");
+    let unsafe_in = |file: &str| format!("Uso de código unsafe detectado (./src/{file}:1)");
+    assert!(stdout.contains(&format!("+ [Critical] Security: {}", unsafe_in("ffi.rs"))));
+    assert!(stdout.contains(&format!("- [Critical] Security: {}", unsafe_in("old.rs"))));
+
+    assert!(!unknown.status.success());
+    let stderr = String::from_utf8_lossy(&unknown.stderr);
+    assert!(stderr.contains("`no-such-branch` no es una rama, tag o commit"), "{stderr}");
+
+    // Only the main worktree is left, and the working tree is as it was
+    assert_eq!(worktrees.lines().count(), 1, "{worktrees}");
+    assert_eq!(status_before, status_after);
+}
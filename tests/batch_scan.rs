@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::config::RunOptions;
+use trae_cli::core::fleet::discover_projects;
+use uuid::Uuid;
+
+fn write(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn package(name: &str) -> String {
+    format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n")
+}
+
+#[test]
+fn scans_every_project_and_reports_failures_in_the_table() {
+    let dir = std::env::temp_dir().join(format!("trae_fleet_{}", Uuid::new_v4()));
+    write(&dir.join("clean/Cargo.toml"), &package("clean"));
+    write(&dir.join("clean/src/lib.rs"), "pub fn answer() -> u8 {\n    42\n}\n");
+    write(&dir.join("dirty/Cargo.toml"), &package("dirty"));
+    write(&dir.join("dirty/src/lib.rs"), "pub fn raw() {\n    // FIXME: handle errors\n}\n");
+    write(&dir.join("dirty/target/debug/build/x/Cargo.toml"), &package("x"));
+    write(&dir.join("ws/Cargo.toml"), "[workspace]\nmembers = [\"member\"]\n");
+    write(&dir.join("ws/member/Cargo.toml"), &package("member"));
+    write(&dir.join("ws/member/src/lib.rs"), "pub fn member() {}\n");
+    write(&dir.join("broken/Cargo.toml"), &package("broken"));
+    write(&dir.join("broken/.trae/multilang.toml"), "[[py]]\npattern = \"(\"\nmessage = \"x\"\n");
+
+    let projects = discover_projects(&dir, &RunOptions::default());
+    let names: Vec<_> = projects.iter().map(|p| p.strip_prefix(&dir).unwrap()).collect();
+    let expected = ["broken", "clean", "dirty", "ws"].map(Path::new);
+    assert_eq!(names, expected);
+
+    let export = dir.join("fleet.json");
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--multilang", "--max-parallel", "2", "--projects", "."])
+        .arg("--export")
+        .arg(&export)
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("./broken  ❌ Reglas inválidas"), "{stdout}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 de 4 proyectos"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+    let rows = report["projects"].as_array().unwrap();
+    let row = |name: &str| rows.iter().find(|r| r["project"] == name).unwrap().clone();
+    assert!(row("./broken")["error"].as_str().unwrap().contains("Patrón inválido"));
+    assert_eq!(row("./clean")["critical"], 0);
+    assert_eq!(row("./clean")["quality_score"], 97.0);
+    assert_eq!(row("./dirty")["critical"], 1);
+    assert!(row("./ws")["error"].is_null());
+
+    // Without the broken project only the critical issue decides the exit code
+    std::fs::remove_dir_all(dir.join("broken")).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--projects", "."])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_dir_all(&dir).ok();
+}
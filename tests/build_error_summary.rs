@@ -0,0 +1,28 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::core::build_errors::{parse, summary_lines};
+use uuid::Uuid;
+
+#[test]
+fn failed_build_errors_are_grouped_with_counts() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/broken_crate");
+    let target = std::env::temp_dir().join(format!("trae_broken_crate_{}", Uuid::new_v4()));
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--message-format=json", "--target-dir"])
+        .arg(&target)
+        .current_dir(&fixture)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&target).ok();
+    assert!(!output.status.success());
+
+    let errors = parse(&String::from_utf8_lossy(&output.stdout));
+    assert_eq!(
+        summary_lines(&errors, 10),
+        [
+            "[E0425] cannot find value `missing_value` in this scope (src/lib.rs:2) ×2",
+            "[E0308] mismatched types (src/lib.rs:10)",
+        ]
+    );
+    assert_eq!(summary_lines(&errors, 1).last().unwrap(), "... y 1 más");
+}
@@ -0,0 +1,27 @@
+use trae_cli::commands::build::{profile_args, resolve_profile};
+use trae_cli::exit::UsageError;
+use uuid::Uuid;
+
+#[test]
+fn profiles_resolve_from_flags_and_the_workspace_manifest() {
+    let root = std::env::temp_dir().join(format!("trae_build_profiles_{}", Uuid::new_v4()));
+    let member = root.join("crates/app");
+    std::fs::create_dir_all(&member).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/app\"]\n\n[profile.ci]\ninherits = \"dev\"\n",
+    )
+    .unwrap();
+    std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+
+    assert_eq!(resolve_profile(&member, false, None).unwrap(), None);
+    assert_eq!(resolve_profile(&member, true, None).unwrap().as_deref(), Some("release"));
+    assert_eq!(resolve_profile(&member, true, Some("release")).unwrap().as_deref(), Some("release"));
+    assert_eq!(resolve_profile(&member, false, Some("ci")).unwrap().as_deref(), Some("ci"));
+    assert_eq!(profile_args(Some("ci")), ["--profile", "ci"]);
+    for (release, profile) in [(true, "ci"), (false, "dist")] {
+        let err = resolve_profile(&member, release, Some(profile)).unwrap_err();
+        assert!(err.downcast_ref::<UsageError>().is_some(), "{err}");
+    }
+    std::fs::remove_dir_all(&root).ok();
+}
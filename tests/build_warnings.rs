@@ -0,0 +1,39 @@
+use std::path::Path;
+use std::process::Command;
+use uuid::Uuid;
+
+#[test]
+fn fail_on_warning_turns_compiler_warnings_into_findings() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/warning_crate");
+    let dir = std::env::temp_dir().join(format!("trae_warnings_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    for file in ["Cargo.toml", "src/lib.rs"] {
+        std::fs::copy(fixture.join(file), dir.join(file)).unwrap();
+    }
+    let trae = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "--target-dir"])
+            .arg(dir.join("target"))
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    };
+
+    // Permissive by default
+    let output = trae(&["build"]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // Warnings replayed from the cached build still count
+    let output = trae(&["build", "--fail-on-warning"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{stdout}");
+    assert!(stdout.contains("unused_variables"), "{stdout}");
+    assert!(stdout.contains("dead_code"), "{stdout}");
+    assert!(stdout.contains("2 warnings"), "{stdout}");
+
+    let output = trae(&["check", "--fail-on-warning"]);
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use trae_cli::commands::repair::RepairOptions;
+use trae_cli::{CancellationToken, TraeError};
+use uuid::Uuid;
+
+// Single test: both calls change the process working directory.
+#[tokio::test]
+async fn cancelled_token_stops_analyze_and_repair() {
+    let dir = std::env::temp_dir().join(format!("trae_cancel_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).expect("create project");
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .expect("write manifest");
+    fs::write(dir.join("src").join("lib.rs"), "pub fn id(x: u8) -> u8 {\n    x\n}\n")
+        .expect("write lib.rs");
+    let orig = std::env::current_dir().expect("cwd");
+    std::env::set_current_dir(&dir).expect("enter project");
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let analyzed = trae_cli::analyze_cancellable(
+        false, false, false, true, None, true, None, cancel.clone(),
+    )
+    .await;
+    assert!(matches!(analyzed, Err(TraeError::Cancelled)), "{analyzed:?}");
+
+    let repaired = trae_cli::repair(RepairOptions {
+        clippy: true,
+        dry_run: true,
+        no_jarvix: true,
+        cancel: Some(cancel),
+        ..Default::default()
+    })
+    .await;
+    assert!(matches!(repaired, Err(TraeError::Cancelled)), "{repaired:?}");
+
+    std::env::set_current_dir(orig).expect("restore cwd");
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::process::Command;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn capture_returns_the_exit_code_and_output_of_a_failing_cargo() {
+    let dir = std::env::temp_dir().join(format!("trae_capture_{}", Uuid::new_v4()));
+    let target = dir.with_extension("target");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"cap\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src/lib.rs"), "pub fn broken() -> u8 { \"no\" }\n").unwrap();
+
+    // The CLI keeps streaming and also writes the plain output to the file
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "cargo", "check", "--capture-output", "check.log"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .env("CARGO_NET_OFFLINE", "true")
+        .env("CARGO_TARGET_DIR", &target)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let log = fs::read_to_string(dir.join("check.log")).unwrap();
+    assert!(log.contains("error[E0308]: mismatched types"), "{log}");
+    assert!(!log.contains('\x1b'), "{log}");
+
+    let orig = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+    std::env::set_var("CARGO_TARGET_DIR", &target);
+    std::env::set_var("CARGO_NET_OFFLINE", "true");
+    let failed = trae_cli::cargo_run_capture("check", &[], false).await;
+    fs::write(dir.join("src/lib.rs"), "pub fn fixed() -> u8 { 1 }\n").unwrap();
+    let passed = trae_cli::cargo_run_capture("check", &["--quiet".to_string()], false).await;
+    std::env::set_current_dir(orig).unwrap();
+    fs::remove_dir_all(&dir).ok();
+    fs::remove_dir_all(&target).ok();
+
+    let failed = failed.expect("a failing cargo is still a captured run");
+    assert_eq!(failed.code, Some(101));
+    assert!(!failed.success());
+    assert!(failed.output.contains("error[E0308]"), "{}", failed.output);
+    assert!(!failed.output.contains('\x1b'), "{}", failed.output);
+    let passed = passed.unwrap();
+    assert!(passed.success(), "{}", passed.output);
+}
@@ -0,0 +1,26 @@
+use trae_cli::cli::TraeCli;
+use trae_cli::commands::cargo::cargo_args;
+use trae_cli::exit::ExitCode;
+
+#[test]
+fn color_is_injected_before_the_double_dash() {
+    let args = ["--release", "--", "--nocapture"].map(String::from);
+    assert_eq!(
+        cargo_args("test", &args),
+        ["test", "--release", "--color=always", "--", "--nocapture"]
+    );
+    let args = ["--color=never".to_string()];
+    assert_eq!(cargo_args("build", &args), ["build", "--color=never"]);
+}
+
+#[tokio::test]
+async fn dry_run_validates_without_running() {
+    assert_eq!(
+        TraeCli::run_from(["trae", "--dry-run", "cargo", "build", "--release"]).await,
+        ExitCode::Success
+    );
+    assert_eq!(
+        TraeCli::run_from(["trae", "--dry-run", "cargo", "frobnicate"]).await,
+        ExitCode::UsageError
+    );
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+/// `trae args` in `dir` with no cargo on PATH, and `dir/cargo` as the only
+/// CARGO_HOME and `dir` as HOME.
+fn without_cargo(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(args)
+        .current_dir(dir)
+        .env("PATH", dir.join("bin"))
+        .env("CARGO_HOME", dir.join("cargo"))
+        .env("HOME", dir)
+        .env_remove("CARGO")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn cargo_commands_fail_up_front_without_cargo() {
+    let dir = std::env::temp_dir().join(format!("trae_no_cargo_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("bin")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n")
+        .unwrap();
+    let runs: Vec<_> = [
+        &["--no-jarvix", "check"][..],
+        &["--no-jarvix", "preflight"],
+        &["--no-jarvix", "repair", "--fmt"],
+        &["--no-jarvix", "build"],
+        &["--no-jarvix", "nextest"],
+    ]
+    .iter()
+    .map(|args| (args.to_vec(), without_cargo(&dir, args)))
+    .collect();
+    let version = without_cargo(&dir, &["version"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    for (args, output) in runs {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "{args:?}: {stderr}");
+        assert!(stderr.contains("https://www.rust-lang.org/tools/install"), "{args:?}: {stderr}");
+        assert!(!stderr.contains("No such file"), "{args:?}: {stderr}");
+    }
+    // Commands that don't need cargo still run
+    assert!(version.status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn cargo_is_found_in_cargo_home() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("trae_cargo_home_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("bin")).unwrap();
+    std::fs::create_dir_all(dir.join("cargo/bin")).unwrap();
+    let cargo = dir.join("cargo/bin/cargo");
+    // Just enough of a cargo for `--dry-run`, which asks `cargo --list`
+    std::fs::write(&cargo, "#!/bin/sh\necho 'Installed Commands:'\necho '    build'\n").unwrap();
+    std::fs::set_permissions(&cargo, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let output = without_cargo(&dir, &["--no-jarvix", "--dry-run", "cargo", "build"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout.contains("cargo build"), "{stdout}");
+}
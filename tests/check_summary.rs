@@ -0,0 +1,38 @@
+use clap::Parser;
+use std::path::Path;
+use std::process::Command;
+use trae_cli::cli::{Commands, TraeCli};
+use trae_cli::core::lints::LintSummary;
+use uuid::Uuid;
+
+#[test]
+fn check_all_targets_counts_diagnostics_by_code() {
+    let cli = ["trae", "check", "--all-targets", "-p", "broken_crate", "--", "-q"];
+    let cli = TraeCli::try_parse_from(cli).unwrap();
+    let Commands::Check(check) = cli.command else {
+        panic!("check no parseado");
+    };
+    let args = check.check_args();
+    assert_eq!(
+        args,
+        ["check", "--message-format=json", "--all-targets", "--package", "broken_crate", "-q"]
+    );
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/broken_crate");
+    let target = std::env::temp_dir().join(format!("trae_check_{}", Uuid::new_v4()));
+    let output = Command::new(env!("CARGO"))
+        .args(&args[..3])
+        .arg("--target-dir")
+        .arg(&target)
+        .current_dir(&fixture)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&target).ok();
+    assert!(!output.status.success());
+
+    let summary = LintSummary::parse(&String::from_utf8_lossy(&output.stdout));
+    // lib and its unit tests report the same errors once
+    assert_eq!(summary.lints["E0425"], 2);
+    assert_eq!(summary.lints["E0308"], 1);
+    assert_eq!((summary.warnings(), summary.denied()), (0, 3));
+}
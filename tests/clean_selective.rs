@@ -0,0 +1,69 @@
+use trae_cli::commands::clean::{CleanCommand, WorkspacePackage};
+use uuid::Uuid;
+
+#[test]
+fn selection_maps_to_cargo_clean_and_sizes_only_what_it_deletes() {
+    let packages = vec![WorkspacePackage {
+        name: "my-app".to_string(),
+        artifact_names: vec!["my-app".to_string(), "my_app".to_string()],
+    }];
+    let release = CleanCommand {
+        profile: Some("release".into()),
+        ..Default::default()
+    };
+    assert_eq!(release.cargo_args(&[]), ["clean", "--release"]);
+    let doc = CleanCommand {
+        doc: true,
+        ..Default::default()
+    };
+    assert_eq!(doc.cargo_args(&[]), ["clean", "--doc"]);
+    let keep_deps = CleanCommand {
+        profile: Some("dev".into()),
+        keep_deps: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        keep_deps.cargo_args(&packages),
+        ["clean", "--profile", "dev", "-p", "my-app"]
+    );
+
+    let target = std::env::temp_dir().join(format!("trae_clean_{}", Uuid::new_v4()));
+    let deps = target.join("debug/deps");
+    let fingerprint = target.join("debug/.fingerprint");
+    std::fs::create_dir_all(&deps).unwrap();
+    std::fs::create_dir_all(fingerprint.join("my-app-77aa")).unwrap();
+    std::fs::create_dir_all(fingerprint.join("my-app-macros-88bb")).unwrap();
+    std::fs::create_dir_all(target.join("doc")).unwrap();
+    std::fs::write(deps.join("my_app-1a2b.rlib"), [0u8; 100]).unwrap();
+    std::fs::write(deps.join("libserde-9f8e.rlib"), [0u8; 1000]).unwrap();
+    std::fs::write(target.join("debug/my-app"), [0u8; 20]).unwrap();
+    std::fs::write(fingerprint.join("my-app-77aa/lib-my_app"), [0u8; 3]).unwrap();
+    // A dependency whose name starts with the package's is not the package
+    std::fs::write(fingerprint.join("my-app-macros-88bb/lib-my_app_macros"), [0u8; 5]).unwrap();
+    std::fs::write(deps.join("libmy_app_macros-88bb.so"), [0u8; 7]).unwrap();
+    std::fs::write(target.join("doc/index.html"), [0u8; 10]).unwrap();
+
+    assert_eq!(keep_deps.reclaimable(&target, &packages), 123);
+    assert_eq!(doc.reclaimable(&target, &[]), 10);
+    assert_eq!(CleanCommand::default().reclaimable(&target, &[]), 1145);
+    std::fs::remove_dir_all(&target).unwrap();
+}
+
+#[test]
+fn keep_deps_fails_instead_of_cleaning_everything_without_metadata() {
+    let dir = std::env::temp_dir().join(format!("trae_clean_nometa_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("target/debug/deps")).unwrap();
+    std::fs::write(dir.join("target/debug/deps/libserde-9f8e.rlib"), [0u8; 10]).unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "clean", "--keep-deps"])
+        .env("NO_COLOR", "1")
+        .env_remove("CARGO_TARGET_DIR")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("--keep-deps necesita cargo metadata"), "{stderr}");
+    assert!(dir.join("target/debug/deps/libserde-9f8e.rlib").exists());
+    std::fs::remove_dir_all(&dir).ok();
+}
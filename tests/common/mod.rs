@@ -0,0 +1,30 @@
+//! `.trae/config.toml` setup shared by the integration tests.
+#![allow(dead_code)]
+
+use std::path::Path;
+
+/// The smallest valid `.trae/config.toml`: every key `[jarvix]`,
+/// `[analysis]` and `[repair]` require.
+pub const CONFIG: &str = "[jarvix]\nenabled = false\nserver_url = \"http://localhost:8080\"\n\
+    timeout = 30\n\n[analysis]\nauto_analysis = true\nperformance_analysis = false\n\
+    security_analysis = false\n\n[repair]\nauto_repair = false\n\
+    backup_before_repair = true\nclippy_auto_fix = true\n";
+
+/// [`CONFIG`] with `keys` added to `[section]`, which is appended when
+/// `CONFIG` doesn't have it.
+pub fn config_with(section: &str, keys: &str) -> String {
+    let header = format!("[{section}]\n");
+    match CONFIG.find(&header) {
+        Some(at) => {
+            let (head, tail) = CONFIG.split_at(at + header.len());
+            format!("{head}{keys}{tail}")
+        }
+        None => format!("{CONFIG}\n{header}{keys}"),
+    }
+}
+
+/// Writes `toml` as the `.trae/config.toml` of `dir`.
+pub fn write_config(dir: &Path, toml: &str) {
+    std::fs::create_dir_all(dir.join(".trae")).unwrap();
+    std::fs::write(dir.join(".trae/config.toml"), toml).unwrap();
+}
@@ -0,0 +1,107 @@
+use std::process::Command;
+use trae_cli::core::analyzer::{parse_confidence, sort_issues, AnalysisIssue, IssueSeverity};
+use uuid::Uuid;
+
+const SOURCE: &str = r#"// TODO: wire the cache
+pub const HELP: &str = "TODO: shown to users";
+pub fn doc() -> &'static str {
+    "call .unwrap() here"
+}
+pub fn first(v: &[u8]) -> u8 {
+    *v.first().unwrap()
+}
+"#;
+
+fn issue(severity: IssueSeverity, line: usize, confidence: f64) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Safety".to_string(),
+        description: format!("línea {line}"),
+        severity,
+        file: Some("./src/lib.rs".to_string()),
+        line: Some(line),
+        confidence,
+    }
+}
+
+/// `(message prefix, confidence)` of each LSP diagnostic `trae` prints.
+fn diagnostics(dir: &std::path::Path, args: &[&str]) -> Vec<(String, f64)> {
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .arg("--no-jarvix")
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let json: serde_json::Value = serde_json::from_str(line).unwrap();
+            let message = json["message"].as_str().unwrap();
+            let prefix = message.split(" en línea").next().unwrap().to_string();
+            (prefix, json["data"]["confidence"].as_f64().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn confidence_orders_issues_within_a_severity_only() {
+    let mut issues = vec![
+        issue(IssueSeverity::Warning, 1, 0.3),
+        issue(IssueSeverity::Info, 2, 0.9),
+        issue(IssueSeverity::Warning, 9, 0.9),
+    ];
+    sort_issues(&mut issues);
+    let order: Vec<_> = issues.iter().map(|i| (i.line.unwrap(), i.confidence)).collect();
+    assert_eq!(order, [(9, 0.9), (1, 0.3), (2, 0.9)]);
+
+    assert_eq!(parse_confidence("0.75"), Ok(0.75));
+    assert!(parse_confidence("1.5").is_err());
+    assert!(parse_confidence("alta").is_err());
+
+    // Exports written before confidence existed are never filtered out
+    let old = r#"{"category":"Safety","description":"x","severity":"Info",
+                  "file":null,"line":null}"#;
+    let old: AnalysisIssue = serde_json::from_str(old).unwrap();
+    assert_eq!(old.confidence, 1.0);
+}
+
+#[test]
+fn scan_rates_matches_in_strings_low_and_min_confidence_drops_them() {
+    let dir = std::env::temp_dir().join(format!("trae_confidence_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), SOURCE).unwrap();
+    let all = diagnostics(&dir, &["scan", "--format", "lsp"]);
+    let confident = diagnostics(&dir, &["scan", "--format", "lsp", "--min-confidence", "0.8"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    // The unwrap() call outranks the one mentioned in a string despite its later line
+    let expected = [("unwrap()", 0.9), ("unwrap()", 0.3), ("TODO", 0.9), ("TODO", 0.5)];
+    let all: Vec<_> = all.iter().map(|(m, c)| (m.as_str(), *c)).collect();
+    assert_eq!(all, expected);
+    let confident: Vec<_> = confident.iter().map(|(m, c)| (m.as_str(), *c)).collect();
+    assert_eq!(confident, [("unwrap()", 0.9), ("TODO", 0.9)]);
+}
+
+#[test]
+fn security_rates_secrets_by_entropy() {
+    let dir = std::env::temp_dir().join(format!("trae_confidence_sec_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("src/lib.rs"),
+        "pub fn creds() {\n    let password = \"aaaaaaaa\";\n    \
+         let token = \"Zx9qL2vT8mWp4RkB7nYc\";\n}\n",
+    )
+    .unwrap();
+    let args = ["security", "--secrets", "--format", "lsp"];
+    let all = diagnostics(&dir, &args);
+    let confident = diagnostics(&dir, &[&args[..], &["--min-confidence", "0.8"]].concat());
+    std::fs::remove_dir_all(&dir).ok();
+
+    let rated: Vec<_> = all.iter().map(|(m, c)| (m.as_str(), *c)).collect();
+    assert_eq!(
+        rated,
+        [("Password hardcodeado detectado", 0.3), ("Token hardcodeado detectado", 0.9)]
+    );
+    assert_eq!(confident.len(), 1);
+    assert_eq!(confident[0].0, "Token hardcodeado detectado");
+}
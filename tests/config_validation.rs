@@ -0,0 +1,111 @@
+use trae_cli::config::TraeConfig;
+
+const VALID: &str = r#"
+[jarvix]
+enabled = true
+server_url = "http://localhost:8080"
+timeout = 30
+
+[analysis]
+auto_analysis = true
+performance_analysis = false
+security_analysis = false
+
+[repair]
+auto_repair = false
+backup_before_repair = true
+clippy_auto_fix = true
+"#;
+
+#[test]
+fn valid_config_parses() {
+    let config = TraeConfig::parse(VALID).expect("valid config");
+    assert!(!config.jarvix.offload);
+    assert_eq!(config.jarvix.offload_timeout, 120);
+    assert_eq!(config.analysis.max_file_size, 2_000_000);
+}
+
+#[test]
+fn misspelled_key_reports_line_and_valid_keys() {
+    let content = VALID.replace("auto_repair = false", "auto_repiar = false");
+    let err = TraeConfig::parse(&content)
+        .expect_err("typo must be rejected")
+        .to_string();
+    assert!(err.contains("auto_repiar"), "{err}");
+    assert!(err.contains("línea 13: auto_repiar = false"), "{err}");
+    assert!(
+        err.contains("Claves válidas en [repair]: auto_repair, backup_before_repair, clippy_auto_fix"),
+        "{err}"
+    );
+}
+
+#[test]
+fn wrong_type_is_rejected() {
+    let content = VALID.replace("timeout = 30", "timeout = \"30s\"");
+    let err = TraeConfig::parse(&content).expect_err("type error").to_string();
+    assert!(err.contains("Claves válidas en [jarvix]"), "{err}");
+}
+
+#[test]
+fn offload_settings_come_from_jarvix_section() {
+    use std::time::Duration;
+    use trae_cli::commands::cargo::RemoteOffload;
+
+    let default = RemoteOffload::default();
+    assert_eq!(default.poll_interval, Duration::from_secs(2));
+    assert_eq!(default.max_poll_interval, Duration::from_secs(30));
+    assert_eq!(RemoteOffload::from_config(&TraeConfig::parse(VALID).unwrap()), None);
+
+    let content = VALID.replace(
+        "timeout = 30",
+        "timeout = 30\noffload = true\noffload_timeout = 900\noffload_max_poll_interval = 60",
+    );
+    let offload = RemoteOffload::from_config(&TraeConfig::parse(&content).unwrap());
+    assert_eq!(
+        offload,
+        Some(RemoteOffload {
+            timeout: Duration::from_secs(900),
+            max_poll_interval: Duration::from_secs(60),
+            ..default
+        })
+    );
+}
+
+/// The valid keys `parse` lists for `[section]`, from the error on an unknown one.
+fn listed_keys(defaults: &toml::Value, section: &str) -> Vec<String> {
+    let mut config = defaults.clone();
+    config[section].as_table_mut().unwrap().insert("no_such_key".into(), 1.into());
+    let err = TraeConfig::parse(&toml::to_string(&config).unwrap()).unwrap_err().to_string();
+    assert!(err.contains(": no_such_key = 1\n"), "{err}");
+    let prefix = format!("Claves válidas en [{section}]: ");
+    let (_, keys) = err.split_once(&prefix).unwrap_or_else(|| panic!("{err}"));
+    keys.lines().next().unwrap().split(", ").map(str::to_string).collect()
+}
+
+#[test]
+fn listed_keys_match_the_config_sections() {
+    let defaults = toml::Value::try_from(TraeConfig::default()).unwrap();
+    let sections = defaults.as_table().unwrap();
+    assert_eq!(
+        sections.keys().collect::<Vec<_>>(),
+        ["analysis", "http", "jarvix", "repair", "score", "status"]
+    );
+    for (section, table) in sections {
+        let listed = listed_keys(&defaults, section);
+        // Every key the defaults write is listed...
+        for key in table.as_table().unwrap().keys() {
+            assert!(listed.contains(key), "[{section}] {key} missing from {listed:?}");
+        }
+        // ...and every listed key, optional ones included, is a real field
+        for key in &listed {
+            let mut config = defaults.clone();
+            config[section.as_str()]
+                .as_table_mut()
+                .unwrap()
+                .insert(key.clone(), toml::Value::Array(vec![]));
+            let parsed = TraeConfig::parse(&toml::to_string(&config).unwrap());
+            let err = parsed.err().map(|e| e.to_string()).unwrap_or_default();
+            assert!(!err.contains("unknown field"), "[{section}] {key}: {err}");
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+use trae_cli::config::RunOptions;
+use trae_cli::core::coverage::{count_source, estimate_test_coverage};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/coverage_overflow");
+
+#[test]
+fn cfg_test_functions_are_excluded_from_denominator() {
+    let content = std::fs::read_to_string(Path::new(FIXTURE).join("src/lib.rs")).expect("fixture");
+    let counts = count_source(&content);
+    assert_eq!(counts.functions, 5);
+    assert_eq!(counts.test_gated_functions, 3);
+    assert_eq!(counts.tests, 2);
+}
+
+#[test]
+fn estimate_includes_integration_tests_and_is_clamped() {
+    let estimate = estimate_test_coverage(Path::new(FIXTURE), None, &RunOptions::default());
+    assert_eq!(estimate.production_functions, 2);
+    assert_eq!(estimate.tests, 5);
+    // Naive tests/production functions would be 250%.
+    assert!(estimate.tests as f64 / estimate.production_functions as f64 > 1.0);
+    assert_eq!(estimate.percent, 100.0);
+}
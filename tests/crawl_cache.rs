@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+use trae_cli::config::RunOptions;
+use trae_cli::core::crawl_cache::{CrawlCache, CRAWL_CACHE_FILE};
+use trae_cli::core::crawler::extract_functions;
+use uuid::Uuid;
+
+#[test]
+fn unchanged_files_are_reused_and_edited_or_deleted_ones_invalidated() {
+    let root = std::env::temp_dir().join(format!("trae_crawl_cache_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub fn kept() {}\n").unwrap();
+    std::fs::write(root.join("src/edited.rs"), "fn before() {}\n").unwrap();
+    let files = |names: &[&str]| -> Vec<(String, PathBuf)> {
+        names
+            .iter()
+            .map(|name| (format!("src/{name}"), root.join("src").join(name)))
+            .collect()
+    };
+    let names = |symbols: Vec<trae_cli::core::crawler::FileSymbols>| {
+        let mut names: Vec<_> = symbols
+            .into_iter()
+            .flat_map(|file| file.functions)
+            .map(|f| f.name)
+            .collect();
+        names.sort();
+        names
+    };
+
+    let mut cache = CrawlCache::default();
+    let (symbols, changed) = cache.refresh(&files(&["lib.rs", "edited.rs"]), true);
+    assert!(changed);
+    assert_eq!(names(symbols), ["before", "kept"]);
+    let (_, changed) = cache.refresh(&files(&["lib.rs", "edited.rs"]), true);
+    assert!(!changed, "nothing changed on disk");
+
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    std::fs::write(root.join("src/edited.rs"), "fn after() {}\n").unwrap();
+    let (symbols, changed) = cache.refresh(&files(&["lib.rs", "edited.rs"]), true);
+    assert!(changed);
+    assert_eq!(names(symbols), ["after", "kept"]);
+
+    // A depth-limited crawl keeps entries it didn't see; a full one drops them.
+    let (_, changed) = cache.refresh(&files(&["lib.rs"]), false);
+    assert!(!changed);
+    let (_, changed) = cache.refresh(&files(&["lib.rs"]), true);
+    assert!(changed);
+    assert_eq!(cache.files().collect::<Vec<_>>(), ["src/lib.rs"]);
+
+    // The crawler persists its cache next to the project.
+    assert_eq!(extract_functions(&root, None, &RunOptions::default()).len(), 2);
+    let saved = CrawlCache::load(&root.join(CRAWL_CACHE_FILE));
+    assert_eq!(saved.files().collect::<Vec<_>>(), ["src/edited.rs", "src/lib.rs"]);
+    std::fs::remove_dir_all(&root).unwrap();
+}
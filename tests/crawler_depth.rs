@@ -0,0 +1,28 @@
+use std::fs;
+use trae_cli::config::RunOptions;
+use trae_cli::utils::path::rust_files;
+use uuid::Uuid;
+
+#[test]
+fn max_depth_excludes_deeper_files() {
+    let root = std::env::temp_dir().join(format!("trae_depth_{}", Uuid::new_v4()));
+    let src = root.join("src");
+    fs::create_dir_all(src.join("a").join("b")).expect("create dirs");
+    fs::write(src.join("lib.rs"), "pub mod a;").expect("write");
+    fs::write(src.join("a").join("mod.rs"), "pub mod b;").expect("write");
+    fs::write(src.join("a").join("b").join("deep.rs"), "pub fn deep() {}").expect("write");
+
+    let names = |depth| {
+        let mut names: Vec<_> = rust_files(&src, depth, &RunOptions::default())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names(Some(1)), ["lib.rs"]);
+    // deep.rs sits at depth 3, so n = 2 must exclude it
+    assert_eq!(names(Some(2)), ["lib.rs", "mod.rs"]);
+    assert_eq!(names(None), ["deep.rs", "lib.rs", "mod.rs"]);
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,32 @@
+use std::fs;
+use trae_cli::config::RunOptions;
+use trae_cli::core::crawler::{macro_generated, EXPANDED_FILE};
+use uuid::Uuid;
+
+#[test]
+fn expanded_source_separates_macro_generated_items() {
+    let root = std::env::temp_dir().join(format!("trae_expand_{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).expect("create dirs");
+    fs::write(
+        root.join("src/main.rs"),
+        "#[derive(Debug, Clone)]\npub struct Config {\n    port: u16,\n}\n\nimpl std::fmt::Display for Config {\n    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }\n}\n\n#[tokio::main]\nasync fn main() {\n}\n",
+    )
+    .expect("write");
+    // Shape of `cargo expand` output for the file above
+    let expanded = "pub struct Config {\n    port: u16,\n}\n\
+        #[automatically_derived]\nimpl ::core::fmt::Debug for Config {\n    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {\n    }\n}\n\
+        #[automatically_derived]\nimpl ::core::clone::Clone for Config {\n    fn clone(&self) -> Config {\n    }\n}\n\
+        impl std::fmt::Display for Config {\n    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }\n}\n\
+        fn main() {\n    let body = async {};\n}\n";
+
+    let generated = macro_generated(&root, expanded, &RunOptions::default());
+    fs::remove_dir_all(&root).ok();
+
+    assert_eq!(generated.impls, ["Debug for Config", "Clone for Config"]);
+    // One `fmt` is hand-written, so only the derived one is counted
+    let mut functions: Vec<_> = generated.functions.iter().map(|f| f.name.as_str()).collect();
+    functions.sort_unstable();
+    assert_eq!(functions, ["clone", "fmt"]);
+    assert!(generated.functions.iter().all(|f| f.file == EXPANDED_FILE));
+    assert!(generated.structs.is_empty());
+}
@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+use trae_cli::config::RunOptions;
+use trae_cli::core::crawler::{
+    calculate_metrics, extract_functions, extract_structs, extract_traits, project_metrics,
+    scan_deadcode, ProjectMetrics,
+};
+use uuid::Uuid;
+
+/// A fresh copy of `tests/fixtures/crawler_sample`, since the crawl cache
+/// writes `.trae/` into the project.
+fn sample() -> PathBuf {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/crawler_sample");
+    let root = std::env::temp_dir().join(format!("trae_crawler_fixture_{}", Uuid::new_v4()));
+    for entry in walkdir::WalkDir::new(&fixture) {
+        let entry = entry.unwrap();
+        let target = root.join(entry.path().strip_prefix(&fixture).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    root
+}
+
+#[test]
+fn functions_include_multiline_restricted_and_async_signatures() {
+    let root = sample();
+    let mut functions: Vec<_> = extract_functions(&root, None, &RunOptions::default())
+        .into_iter()
+        .map(|f| (f.file, f.line, f.name, f.is_pub, f.params.join(", "), f.return_type))
+        .collect();
+    functions.sort();
+    let expected = [
+        ("src/lib.rs", 18, "area", true, "width: u32, height: u32", "u32"),
+        ("src/lib.rs", 22, "fetch", true, "url: &str", "Result<String, String>"),
+        ("src/lib.rs", 26, "private_helper", false, "", "()"),
+        ("src/lib.rs", 31, "legacy", false, "", "()"),
+        ("src/lib.rs", 37, "computes_area", false, "", "()"),
+        ("src/shapes/mod.rs", 10, "area", false, "&self", "f64"),
+        ("src/shapes/mod.rs", 13, "name", false, "&self", "String"),
+        ("src/shapes/polygon.rs", 8, "regular", true, "sides: u32", "Polygon"),
+        ("src/shapes/polygon.rs", 15, "area", false, "&self", "f64"),
+        ("src/shapes/polygon.rs", 18, "name", false, "&self", "String"),
+        ("src/util.rs", 3, "clamp", true, "value: i64, min: i64, max: i64", "i64"),
+        ("src/util.rs", 8, "double", false, "value: i64", "i64"),
+    ];
+    let mut expected: Vec<_> = expected
+        .iter()
+        .map(|(file, line, name, is_pub, params, ret)| {
+            let (params, ret) = (params.to_string(), ret.to_string());
+            (file.to_string(), *line, name.to_string(), *is_pub, params, ret)
+        })
+        .collect();
+    expected.sort();
+    assert_eq!(functions, expected);
+    // Trait declarations without a body aren't functions
+    assert!(!functions.iter().any(|f| f.2 == "render" || f.2 == "width"));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn structs_and_same_named_traits_keep_their_files() {
+    let root = sample();
+    let mut structs: Vec<_> = extract_structs(&root, None, &RunOptions::default())
+        .into_iter()
+        .map(|s| (s.file, s.line, s.name, s.is_pub, s.fields))
+        .collect();
+    structs.sort();
+    let fields = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        structs,
+        vec![
+            ("src/lib.rs".into(), 7, "Config".into(), true, fields(&["name", "retries"])),
+            ("src/lib.rs".into(), 9, "Marker".into(), false, fields(&[])),
+            ("src/shapes/mod.rs".into(), 5, "Circle".into(), true, fields(&[])),
+            ("src/shapes/polygon.rs".into(), 6, "Polygon".into(), true, fields(&["sides"])),
+        ]
+    );
+
+    let mut traits: Vec<_> = extract_traits(&root, None, &RunOptions::default())
+        .into_iter()
+        .map(|t| (t.file, t.line, t.name, t.methods))
+        .collect();
+    traits.sort();
+    assert_eq!(
+        traits,
+        vec![
+            ("src/shapes/mod.rs".into(), 3, "Shape".into(), fields(&["area", "name"])),
+            ("src/shapes/polygon.rs".into(), 4, "Render".into(), fields(&["render"])),
+            ("src/util.rs".into(), 1, "Render".into(), fields(&["render", "width"])),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn deadcode_honours_attributes_on_the_previous_line() {
+    let root = sample();
+    let mut items: Vec<_> = scan_deadcode(&root, None, &RunOptions::default())
+        .into_iter()
+        .map(|d| (d.file, d.line, d.item_type, d.name, d.is_pub))
+        .collect();
+    items.sort();
+    let expected = [
+        ("src/lib.rs", 7, "struct", "Config", true),
+        ("src/lib.rs", 9, "struct", "Marker", false),
+        ("src/lib.rs", 11, "enum", "Mode", true),
+        ("src/lib.rs", 16, "const", "MAX_RETRIES", true),
+        ("src/lib.rs", 18, "function", "area", true),
+        ("src/lib.rs", 22, "function", "fetch", true),
+        ("src/lib.rs", 26, "function", "private_helper", false),
+        ("src/shapes/mod.rs", 5, "struct", "Circle", true),
+        ("src/shapes/mod.rs", 10, "function", "area", false),
+        ("src/shapes/mod.rs", 13, "function", "name", false),
+        ("src/shapes/polygon.rs", 6, "struct", "Polygon", true),
+        ("src/shapes/polygon.rs", 8, "function", "regular", true),
+        ("src/shapes/polygon.rs", 15, "function", "area", false),
+        ("src/shapes/polygon.rs", 18, "function", "name", false),
+        ("src/util.rs", 3, "function", "clamp", true),
+        ("src/util.rs", 8, "function", "double", false),
+    ];
+    let mut expected: Vec<_> = expected
+        .iter()
+        .map(|(file, line, kind, name, is_pub)| {
+            (file.to_string(), *line, kind.to_string(), name.to_string(), *is_pub)
+        })
+        .collect();
+    expected.sort();
+    assert_eq!(items, expected);
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn metrics_skip_hidden_directories_and_non_files() {
+    let root = sample();
+    let expected = ProjectMetrics {
+        total_lines: 85,
+        total_functions: 17,
+        total_structs: 4,
+        total_traits: 3,
+        total_tests: 3,
+        code_files: 4,
+        test_coverage_estimate: 18.75,
+    };
+    assert_eq!(calculate_metrics(&root, None, &RunOptions::default()), expected);
+    assert_eq!(project_metrics(&root, None, &RunOptions::default()), expected);
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::Path;
+use trae_cli::config::RunOptions;
+use trae_cli::core::crawler::{advanced_project_crawler, Location, SymbolKind};
+use uuid::Uuid;
+
+#[test]
+fn symbols_defined_in_two_modules_return_every_location() {
+    let root = std::env::temp_dir().join(format!("trae_symbols_{}", Uuid::new_v4()));
+    let src = root.join("src");
+    fs::create_dir_all(src.join("net")).expect("create dirs");
+    fs::write(src.join("lib.rs"), "pub mod net;\n\npub struct Config {\n    port: u16,\n}\n\npub fn connect() {\n}\n").expect("write");
+    fs::write(src.join("net").join("mod.rs"), "pub trait Config {\n    fn port(&self) -> u16;\n}\n\nfn connect(addr: &str) {\n}\n").expect("write");
+
+    let index = advanced_project_crawler(&root, None, &RunOptions::default());
+    let mut connect = index.find_symbol("connect");
+    connect.sort_by(|a, b| a.file.cmp(&b.file));
+    let loc = |file: &str, line, kind| Location { file: Path::new(file).display().to_string(), line, kind };
+    assert_eq!(
+        connect,
+        [loc("src/lib.rs", 7, SymbolKind::Function), loc("src/net/mod.rs", 5, SymbolKind::Function)]
+    );
+    let mut config: Vec<_> = index.find_symbol("Config").into_iter().map(|l| (l.kind, l.line)).collect();
+    config.sort_by_key(|(_, line)| *line);
+    assert_eq!(config, [(SymbolKind::Trait, 1), (SymbolKind::Struct, 3)]);
+
+    let in_lib: Vec<_> = index
+        .symbols_in_file(Path::new("./src/lib.rs"))
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(in_lib, ["connect", "Config"]);
+    assert!(index.find_symbol("missing").is_empty());
+
+    let _ = fs::remove_dir_all(root);
+}
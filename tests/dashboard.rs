@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use uuid::Uuid;
+
+fn dashboard(dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--offline", "--no-jarvix", "dashboard"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .env("CARGO_NET_OFFLINE", "true")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn dashboard_snapshot_survives_a_failing_sub_check() {
+    let dir = std::env::temp_dir().join(format!("trae_dashboard_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::create_dir_all(dir.join(".trae")).unwrap();
+    let manifest = "[package]\nname = \"dash\"\nversion = \"0.1.0\"\nedition = \"2021\"\n";
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    fs::write(dir.join("src/lib.rs"), "pub fn a() -> u8 {\n    // FIXME: later\n    1\n}\n")
+        .unwrap();
+    fs::write(
+        dir.join(".trae/perf_history.json"),
+        r#"{"runs":[{"timestamp":"2026-01-02T03:04:05Z","command":"build","stability":0.9,
+            "success_rate":1.0,"total_ms":4200,"slowest":[]}]}"#,
+    )
+    .unwrap();
+
+    let output = dashboard(&dir, &["--json"]);
+    assert!(output.status.success(), "{output:?}");
+    let snapshot: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(snapshot["project"], dir.file_name().unwrap().to_str().unwrap());
+    assert_eq!(snapshot["issues"]["critical"], 1, "{snapshot}");
+    assert!(snapshot["quality_score"].is_number(), "{snapshot}");
+    assert_eq!(snapshot["metrics"]["total_lines"], 4);
+    assert_eq!(snapshot["dependencies"]["checked"], 0, "{snapshot}");
+    assert!(snapshot["security"]["high"].is_number(), "{snapshot}");
+    assert_eq!(snapshot["last_build"]["total_ms"], 4200);
+    assert!(snapshot["unavailable"].get("dependencies").is_none(), "{snapshot}");
+
+    // A dependency cargo can't resolve only takes the dependency check down
+    let broken = format!("{manifest}\n[dependencies]\nnope = {{ path = \"../nope\" }}\n");
+    fs::write(dir.join("Cargo.toml"), broken).unwrap();
+    let output = dashboard(&dir, &["--json"]);
+    let text = dashboard(&dir, &[]);
+    fs::remove_dir_all(&dir).ok();
+    assert!(output.status.success(), "{output:?}");
+    let snapshot: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(snapshot["dependencies"].is_null(), "{snapshot}");
+    let reason = snapshot["unavailable"]["dependencies"].as_str().unwrap();
+    assert!(reason.contains("cargo metadata falló"), "{reason}");
+    assert_eq!(snapshot["issues"]["critical"], 1, "{snapshot}");
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(text.status.success());
+    assert!(stdout.contains("📦 Dependencias: no disponible (cargo metadata falló"), "{stdout}");
+    assert!(stdout.contains("🏗️ Último build: 2026-01-02 03:04 · 4200ms · 100% ok"), "{stdout}");
+}
@@ -0,0 +1,52 @@
+use globset::Glob;
+use trae_cli::commands::deadcode::DeadcodeCommand;
+use trae_cli::core::crawler::DeadCodeItem;
+
+fn item(item_type: &str, name: &str, is_pub: bool) -> DeadCodeItem {
+    DeadCodeItem {
+        item_type: item_type.to_string(),
+        name: name.to_string(),
+        file: "src/lib.rs".to_string(),
+        line: 1,
+        is_pub,
+    }
+}
+
+#[test]
+fn name_glob_visibility_and_kind_filters_must_all_match() {
+    let items = vec![
+        item("function", "test_helper", false),
+        item("function", "test_fixture", true),
+        item("struct", "RequestHandler", true),
+        item("struct", "LegacyHandler", false),
+        item("function", "parse", true),
+    ];
+    let kept = |cmd: DeadcodeCommand| {
+        let mut items = items.clone();
+        cmd.retain_matching(&mut items);
+        items.into_iter().map(|i| i.name).collect::<Vec<_>>()
+    };
+
+    let public_handlers = DeadcodeCommand {
+        filter: Some(Glob::new("*Handler").unwrap()),
+        public_only: true,
+        ..Default::default()
+    };
+    assert_eq!(kept(public_handlers), ["RequestHandler"]);
+
+    let private_test_fns = DeadcodeCommand {
+        filter: Some(Glob::new("test_*").unwrap()),
+        private_only: true,
+        functions: true,
+        ..Default::default()
+    };
+    assert_eq!(kept(private_test_fns), ["test_helper"]);
+
+    let public_structs = DeadcodeCommand {
+        structs: true,
+        public_only: true,
+        ..Default::default()
+    };
+    assert_eq!(kept(public_structs), ["RequestHandler"]);
+    assert_eq!(kept(DeadcodeCommand::default()).len(), 5);
+}
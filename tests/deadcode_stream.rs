@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+const LIB: &str = "// TODO: split this module\npub mod util;\n\npub struct Config {\n    \
+    pub name: String,\n}\n\npub trait Render {\n    fn render(&self) -> String;\n}\n\n\
+    fn helper(x: u32) -> u32 {\n    x + 1\n}\n";
+const UTIL: &str = "pub fn parse(input: &str) -> usize {\n    input.len()\n}\n\n\
+    enum Mode {\n    Fast,\n}\n";
+
+fn deadcode(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .arg("--no-jarvix")
+        .arg("deadcode")
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn lines(output: &Output) -> Vec<serde_json::Value> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn stream_emits_one_tagged_item_per_line_in_file_and_line_order() {
+    let dir = std::env::temp_dir().join(format!("trae_deadcode_stream_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), LIB).unwrap();
+    std::fs::write(dir.join("src/util.rs"), UTIL).unwrap();
+    let stream = deadcode(&dir, &["--no-cache", "--format", "json", "--stream"]);
+    let structs_only = deadcode(&dir, &["--no-cache", "--format", "json", "--stream", "--structs"]);
+    let batch = deadcode(&dir, &["--no-cache", "--format", "json"]);
+    let text_stream = deadcode(&dir, &["--stream"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(stream.status.success(), "{}", String::from_utf8_lossy(&stream.stderr));
+    let items = lines(&stream);
+    let tagged: Vec<_> = items
+        .iter()
+        .map(|i| {
+            let file = i["file"].as_str().unwrap();
+            (file, i["line"].as_u64().unwrap(), i["type"].as_str().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        tagged,
+        [
+            ("src/lib.rs", 1, "todo"),
+            ("src/lib.rs", 4, "struct"),
+            ("src/lib.rs", 4, "deadcode"),
+            ("src/lib.rs", 8, "trait"),
+            ("src/lib.rs", 9, "deadcode"),
+            ("src/lib.rs", 12, "function"),
+            ("src/lib.rs", 12, "deadcode"),
+            ("src/util.rs", 1, "function"),
+            ("src/util.rs", 1, "deadcode"),
+            ("src/util.rs", 5, "deadcode"),
+        ]
+    );
+    assert_eq!(items[5]["name"], "helper");
+    assert_eq!(items[5]["params"], serde_json::json!(["x: u32"]));
+
+    // Dead-code filters apply to the `deadcode` lines only
+    let filtered: Vec<_> = lines(&structs_only)
+        .into_iter()
+        .filter(|i| i["type"] == "deadcode")
+        .map(|i| i["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(filtered, ["Config"]);
+
+    // The batch object holds the same items
+    let batch: serde_json::Value = serde_json::from_slice(&batch.stdout).unwrap();
+    assert_eq!(batch["functions"].as_array().unwrap().len(), 2);
+    assert_eq!(batch["dead_code"].as_array().unwrap().len(), 5);
+    assert_eq!(batch["todos"][0]["line"], 1);
+
+    assert!(!text_stream.status.success());
+    let stderr = String::from_utf8_lossy(&text_stream.stderr);
+    assert!(stderr.contains("--stream solo está disponible con --format json"), "{stderr}");
+}
@@ -0,0 +1,99 @@
+use chrono::{Duration as Days, Utc};
+use std::process::Command;
+use std::time::Duration;
+use trae_cli::core::freshness::{DependencyAge, ReleaseCache, Releases};
+use uuid::Uuid;
+
+fn fixture() -> serde_json::Value {
+    let content = include_str!("fixtures/crates_io/anyhow.json");
+    serde_json::from_str(content).unwrap()
+}
+
+#[test]
+fn crates_io_releases_give_the_gap_to_the_latest_stable_version() {
+    let releases = Releases::from_crates_io(&fixture(), Utc::now()).unwrap();
+    assert_eq!(releases.latest, "1.0.80");
+    assert_eq!(releases.published.len(), 4);
+
+    let current = DependencyAge::new("anyhow", "1.0.80", &releases);
+    assert_eq!(current.gap_days(), Some(0));
+    let recent = DependencyAge::new("anyhow", "1.0.70", &releases);
+    assert_eq!(recent.gap_days(), Some(349));
+    assert!(!recent.is_stale(365) && recent.is_stale(300));
+    // Yanked releases keep their date
+    let old = DependencyAge::new("anyhow", "1.0.40", &releases);
+    assert!(old.is_stale(365));
+    let unknown = DependencyAge::new("anyhow", "1.0.1", &releases);
+    assert_eq!(unknown.gap_days(), None);
+    assert!(!unknown.is_stale(0));
+    assert!(Releases::from_crates_io(&serde_json::json!({ "errors": [] }), Utc::now()).is_none());
+}
+
+#[test]
+fn cached_releases_expire_unless_any_age_is_accepted() {
+    let dir = std::env::temp_dir().join(format!("trae_age_{}", Uuid::new_v4()));
+    let cache = ReleaseCache::new(&dir);
+    assert!(cache.load("anyhow", None).is_none());
+    let stale = Releases::from_crates_io(&fixture(), Utc::now() - Days::days(2)).unwrap();
+    cache.save("anyhow", &stale).unwrap();
+    assert!(dir.join(".trae/crates-io/anyhow.json").exists());
+    assert!(cache.load("anyhow", Some(Duration::from_secs(24 * 60 * 60))).is_none());
+    assert_eq!(cache.load("anyhow", None), Some(stale));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn offline_age_report_uses_the_cache_and_skips_path_dependencies() {
+    let dir = std::env::temp_dir().join(format!("trae_age_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("app/src")).unwrap();
+    std::fs::create_dir_all(dir.join("local/src")).unwrap();
+    std::fs::write(
+        dir.join("app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n[workspace]\n\n\
+         [dependencies]\nserde = \"1\"\nlocal = { path = \"../local\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("local/Cargo.toml"),
+        "[package]\nname = \"local\"\nversion = \"0.2.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("app/src/lib.rs"), "").unwrap();
+    std::fs::write(dir.join("local/src/lib.rs"), "").unwrap();
+    let app = dir.join("app");
+    let run = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--offline", "deps", "--age", "--max-age-days", "100"])
+            .current_dir(&app)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let stdout = run();
+    assert!(stdout.contains("local 0.2.0: dependencia path"), "{stdout}");
+    assert!(stdout.contains("serde (sin caché en modo offline)"), "{stdout}");
+
+    // Seed the cache with the version the lockfile resolved, released 200 days before 9.9.9
+    let lock = std::fs::read_to_string(app.join("Cargo.lock")).unwrap();
+    let version = lock
+        .split("[[package]]")
+        .find(|entry| entry.contains("name = \"serde\""))
+        .and_then(|entry| entry.lines().find_map(|l| l.strip_prefix("version = ")))
+        .unwrap()
+        .trim_matches('"')
+        .to_string();
+    let now = Utc::now();
+    let releases = Releases {
+        fetched_at: now - Days::days(30),
+        latest: "9.9.9".to_string(),
+        published: [(version.clone(), now - Days::days(200)), ("9.9.9".to_string(), now)].into(),
+    };
+    ReleaseCache::new(&app).save("serde", &releases).unwrap();
+    let stdout = run();
+    assert!(stdout.contains(&format!("serde {version}")), "{stdout}");
+    assert!(stdout.contains("→ 9.9.9") && stdout.contains("200 días"), "{stdout}");
+    assert!(stdout.contains("1 dependencias más de 100 días por detrás"), "{stdout}");
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,30 @@
+use std::path::Path;
+use trae_cli::core::crawler::{extract_dependencies, extract_dependency_details, Dependency, DependencyKind};
+
+#[test]
+fn every_dependency_table_is_read_and_tagged_with_its_kind() {
+    let fixture = Path::new("tests/fixtures/dependency_tables");
+    let dep = |name: &str, kind, target: Option<&str>| Dependency {
+        name: name.to_string(),
+        kind,
+        target: target.map(str::to_string),
+    };
+    assert_eq!(
+        extract_dependency_details(fixture),
+        [
+            dep("anyhow", DependencyKind::Normal, None),
+            dep("serde", DependencyKind::Normal, None),
+            dep("tokio", DependencyKind::Normal, None),
+            dep("serde", DependencyKind::Dev, None),
+            dep("tempfile", DependencyKind::Dev, None),
+            dep("cc", DependencyKind::Build, None),
+            // Target tables come in name order
+            dep("nix", DependencyKind::Dev, Some("cfg(unix)")),
+            dep("winapi", DependencyKind::Normal, Some("cfg(windows)")),
+        ]
+    );
+    assert_eq!(
+        extract_dependencies(fixture),
+        ["anyhow", "serde", "tokio", "tempfile", "cc", "nix", "winapi"]
+    );
+}
@@ -0,0 +1,68 @@
+use std::process::Command;
+use trae_cli::utils::snippet::{Snippet, Snippets, MAX_TOTAL_LINES};
+use uuid::Uuid;
+
+#[test]
+fn snippets_clip_at_the_file_edges_and_stop_at_the_total_cap() {
+    let snippet = Snippet::around("a\nb\nc\nd\n", 2, 2).unwrap();
+    assert_eq!((snippet.start, snippet.line), (1, 2));
+    assert_eq!(snippet.lines, ["a", "b", "c", "d"]);
+    assert!(Snippet::around("a\n", 3, 1).is_none());
+
+    let dir = std::env::temp_dir().join(format!("trae_snippets_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("long.rs");
+    std::fs::write(&file, "x\n".repeat(MAX_TOTAL_LINES * 2)).unwrap();
+    let file = file.to_string_lossy();
+    let mut snippets = Snippets::new(Some(10)).unwrap();
+    // 21 lines each: the line and 10 on each side
+    let taken = (0..20).filter(|_| snippets.take(Some(&file), Some(100)).is_some()).count();
+    assert_eq!(taken, MAX_TOTAL_LINES / 21);
+    assert!(snippets.notice().unwrap().starts_with(&format!("✂️ {} hallazgos", 20 - taken)));
+    assert!(Snippets::new(None).is_none());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn scan_shows_context_in_the_report_and_the_export() {
+    let dir = std::env::temp_dir().join(format!("trae_diff_context_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"ctx\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("src/lib.rs"),
+        "pub fn a() {}\n// FIXME: first\npub fn b() {}\n// FIXME: second\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .arg("--no-jarvix")
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let export = dir.join("scan.json");
+    let export_arg = export.to_string_lossy();
+    let args = ["scan", "--diff-context", "1", "--max-issues", "1", "--export", &export_arg];
+    let stdout = run(&args);
+    assert!(stdout.contains("  > 2 │ // FIXME: first\n    3 │ pub fn b() {}"), "{stdout}");
+    assert!(!stdout.contains("// FIXME: second"), "{stdout}");
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    let lines = ["pub fn a() {}", "// FIXME: first", "pub fn b() {}"];
+    assert_eq!(issues[0]["snippet"], serde_json::json!({"start": 1, "line": 2, "lines": lines}));
+
+    let quiet = run(&["--quiet", "scan", "--diff-context", "1"]);
+    assert!(quiet.contains("FIXME en línea 2"), "{quiet}");
+    assert!(!quiet.contains('│'), "{quiet}");
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::commands::repair::dirty_paths;
+use uuid::Uuid;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=trae", "-c", "user.email=trae@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("git");
+    assert!(status.status.success(), "git {args:?}: {status:?}");
+}
+
+#[test]
+fn dirty_paths_lists_uncommitted_changes_but_not_trae_dir() {
+    let dir = std::env::temp_dir().join(format!("trae_dirty_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    assert!(dirty_paths(&dir).is_empty(), "outside a repository nothing is dirty");
+
+    git(&dir, &["init", "-q"]);
+    std::fs::write(dir.join("lib.rs"), "fn main() {}\n").unwrap();
+    assert_eq!(dirty_paths(&dir), ["lib.rs"]);
+
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-qm", "init"]);
+    std::fs::create_dir_all(dir.join(".trae")).unwrap();
+    std::fs::write(dir.join(".trae/cache.json"), "{}").unwrap();
+    assert!(dirty_paths(&dir).is_empty(), ".trae must not count as dirty");
+
+    std::fs::write(dir.join("lib.rs"), "fn main() { }\n").unwrap();
+    assert_eq!(dirty_paths(&dir), ["lib.rs"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
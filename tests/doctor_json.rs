@@ -0,0 +1,34 @@
+use trae_cli::config::RunOptions;
+use trae_cli::core::doctor::{collect_checks, CheckStatus};
+
+#[tokio::test]
+async fn doctor_report_serializes_every_check_and_ok_tracks_required_ones() {
+    let report = collect_checks(&RunOptions { offline: true, ..Default::default() }).await;
+    let required_failed = report
+        .checks
+        .iter()
+        .any(|check| check.required && check.status == CheckStatus::Fail);
+    assert_eq!(report.ok, !required_failed);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["ok"], report.ok);
+    let names: Vec<_> = json["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|check| {
+            for key in ["status", "required", "detail", "remediation"] {
+                assert!(check.get(key).is_some(), "{check} sin {key}");
+            }
+            check["name"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(
+        names,
+        ["rustc", "cargo", "clippy", "rustfmt", "cargo-audit", "jarvix"]
+    );
+    // Offline, JARVIXSERVER is skipped: a warning, never a failure of `ok`.
+    let jarvix = &json["checks"][5];
+    assert_eq!(jarvix["status"], "warn");
+    assert_eq!(jarvix["required"], false);
+}
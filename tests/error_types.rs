@@ -0,0 +1,22 @@
+use anyhow::Context;
+use trae_cli::TraeError;
+
+#[test]
+fn anyhow_errors_keep_their_kind_at_the_api_boundary() {
+    let io = std::fs::read_to_string("/definitely/not/here")
+        .context("leyendo config")
+        .unwrap_err();
+    assert!(matches!(TraeError::from(io), TraeError::Io(_)));
+
+    let cargo: anyhow::Error = TraeError::CargoNotFound.into();
+    assert!(matches!(TraeError::from(cargo), TraeError::CargoNotFound));
+
+    let gate = trae_cli::exit::findings(2, "issues críticos detectados");
+    assert!(trae_cli::exit::is_findings(&gate));
+    let gate = TraeError::from(gate);
+    assert!(matches!(gate, TraeError::QualityGateFailed(_)));
+    assert!(trae_cli::exit::is_findings(&gate.into()));
+
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    assert!(matches!(TraeError::from(anyhow::Error::from(json)), TraeError::Parse(_)));
+}
@@ -0,0 +1,40 @@
+use std::fs;
+use std::path::PathBuf;
+use trae_cli::cli::TraeCli;
+use trae_cli::exit::ExitCode;
+use uuid::Uuid;
+
+fn project(lib_rs: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_exit_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).expect("create project");
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .expect("write manifest");
+    fs::write(dir.join("src").join("lib.rs"), lib_rs).expect("write lib.rs");
+    dir
+}
+
+async fn security_exit(dir: &std::path::Path) -> ExitCode {
+    let path = dir.to_string_lossy().into_owned();
+    TraeCli::run_from(["trae", "--no-jarvix", "security", "--secrets", "--path", &path]).await
+}
+
+// Single test: `--path` changes the process working directory.
+#[tokio::test]
+async fn security_exit_codes_distinguish_findings_from_clean_and_usage() {
+    let critical = project("pub fn login() -> bool {\n    let password = \"hunter2\";\n    !password.is_empty()\n}\n");
+    let clean = project("pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n");
+
+    assert_eq!(security_exit(&critical).await, ExitCode::FindingsPresent);
+    assert_eq!(security_exit(&clean).await, ExitCode::Success);
+    assert_eq!(
+        TraeCli::run_from(["trae", "security", "--no-such-flag"]).await,
+        ExitCode::UsageError
+    );
+    assert_eq!(ExitCode::FindingsPresent.code(), 2);
+
+    let _ = fs::remove_dir_all(critical);
+    let _ = fs::remove_dir_all(clean);
+}
@@ -0,0 +1,29 @@
+use trae_cli::core::build_errors::{explainable_codes, rustc_code, BuildError};
+
+fn error(code: Option<&str>) -> BuildError {
+    BuildError {
+        code: code.map(str::to_string),
+        message: "m".to_string(),
+        location: None,
+        count: 1,
+    }
+}
+
+#[test]
+fn error_codes_are_normalized_and_lints_are_not_explained() {
+    assert_eq!(rustc_code("E0308").as_deref(), Some("E0308"));
+    assert_eq!(rustc_code(" e308 ").as_deref(), Some("E0308"));
+    assert_eq!(rustc_code("425").as_deref(), Some("E0425"));
+    for invalid in ["", "E", "E03080", "unused_variables", "E03x8"] {
+        assert_eq!(rustc_code(invalid), None, "{invalid}");
+    }
+
+    let errors = [
+        error(Some("E0425")),
+        error(Some("unused_variables")),
+        error(None),
+        error(Some("E0308")),
+        error(Some("E0425")),
+    ];
+    assert_eq!(explainable_codes(&errors), ["E0425", "E0308"]);
+}
@@ -0,0 +1,104 @@
+use clap::Parser;
+use std::collections::BTreeSet;
+use trae_cli::cli::{Commands, TraeCli};
+use trae_cli::core::features::{mask_inactive, ActiveFeatures};
+use trae_cli::core::manifest::Manifest;
+
+const SOURCE: &str = r#"pub fn always() {}
+#[cfg(feature = "json")]
+pub fn to_json() -> String {
+    String::new()
+}
+#[cfg(not(feature = "json"))]
+pub fn no_json() {}
+#[cfg(all(feature = "json", feature = "yaml"))]
+#[derive(Debug)]
+pub struct Both {
+    a: u8,
+}
+#[cfg(any(unix, feature = "yaml"))]
+pub fn maybe() {}
+#[cfg(feature = "yaml")]
+mod yaml;
+pub struct Config {
+    #[cfg(feature = "yaml")]
+    pub yaml: bool,
+    pub name: String,
+}
+"#;
+
+fn only(features: &[&str]) -> ActiveFeatures {
+    ActiveFeatures::Only(features.iter().map(|f| f.to_string()).collect::<BTreeSet<_>>())
+}
+
+#[test]
+fn disabled_feature_code_is_blanked_keeping_line_numbers() {
+    let masked = mask_inactive(SOURCE, &only(&["default", "json"]));
+    assert_eq!(masked.lines().count(), SOURCE.lines().count());
+    let kept: Vec<&str> = masked.lines().filter(|l| !l.is_empty()).collect();
+    assert_eq!(
+        kept,
+        [
+            "pub fn always() {}",
+            "#[cfg(feature = \"json\")]",
+            "pub fn to_json() -> String {",
+            "    String::new()",
+            "}",
+            "#[cfg(any(unix, feature = \"yaml\"))]",
+            "pub fn maybe() {}",
+            "pub struct Config {",
+            "    pub name: String,",
+            "}",
+        ]
+    );
+    assert_eq!(masked.lines().nth(16), Some("pub struct Config {"));
+
+    let all = mask_inactive(SOURCE, &ActiveFeatures::All);
+    assert!(all.contains("pub struct Both"), "{all}");
+    assert!(!all.contains("no_json"), "{all}");
+    assert!(mask_inactive("#![cfg(feature = \"json\")]\nfn f() {}\n", &only(&[]))
+        .lines()
+        .all(str::is_empty));
+}
+
+#[test]
+fn feature_flags_expand_through_the_manifest_and_reach_cargo() {
+    let manifest = Manifest::parse(
+        r#"
+[package]
+name = "gated"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", optional = true }
+
+[features]
+default = ["json"]
+json = ["serde/derive"]
+yaml = []
+full = ["json", "yaml"]
+"#,
+    )
+    .unwrap();
+    let parse = |args: &[&str]| {
+        let cli = TraeCli::try_parse_from([&["trae", "check"], args].concat()).unwrap();
+        let Commands::Check(check) = cli.command else {
+            panic!("check no parseado");
+        };
+        check
+    };
+
+    let check = parse(&[]);
+    assert_eq!(check.features.active(Some(&manifest)), only(&["default", "json", "serde"]));
+
+    let check = parse(&["--no-default-features", "--features", "gated/yaml"]);
+    assert_eq!(check.features.active(Some(&manifest)), only(&["yaml"]));
+    assert_eq!(
+        check.check_args(),
+        ["check", "--message-format=json", "--features", "gated/yaml", "--no-default-features"]
+    );
+
+    let check = parse(&["-F", "full yaml", "--all-features"]);
+    assert_eq!(check.features.active(Some(&manifest)), ActiveFeatures::All);
+    assert_eq!(check.features.cargo_args(), ["--features", "full,yaml", "--all-features"]);
+}
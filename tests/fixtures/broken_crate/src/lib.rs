@@ -0,0 +1,11 @@
+pub fn first() -> u32 {
+    missing_value
+}
+
+pub fn second() -> u32 {
+    missing_value
+}
+
+pub fn wrong_type() -> u32 {
+    "not a number"
+}
@@ -0,0 +1,26 @@
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn double(a: i32) -> i32 {
+    add(a, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> i32 {
+        2
+    }
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(fixture(), 2), 4);
+    }
+
+    #[test]
+    fn doubles() {
+        assert_eq!(double(fixture()), 4);
+    }
+}
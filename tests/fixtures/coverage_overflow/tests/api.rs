@@ -0,0 +1,14 @@
+#[test]
+fn add_is_commutative() {
+    assert_eq!(coverage_overflow::add(1, 2), coverage_overflow::add(2, 1));
+}
+
+#[test]
+fn double_matches_add() {
+    assert_eq!(coverage_overflow::double(3), coverage_overflow::add(3, 3));
+}
+
+#[tokio::test]
+async fn works_async() {
+    assert_eq!(coverage_overflow::double(0), 0);
+}
@@ -0,0 +1 @@
+pub fn hidden_backup() {}
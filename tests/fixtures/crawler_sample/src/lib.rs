@@ -0,0 +1,40 @@
+//! Sample crate for the crawler tests: one item of each kind per line.
+pub mod shapes;
+mod util;
+
+pub use shapes::Shape;
+
+pub struct Config { pub name: String, pub retries: u32 }
+
+struct Marker;
+
+pub enum Mode {
+    Fast,
+    Safe,
+}
+
+pub const MAX_RETRIES: u32 = 3;
+
+pub fn area(width: u32, height: u32) -> u32 {
+    width * height
+}
+
+pub async fn fetch(url: &str) -> Result<String, String> {
+    Ok(url.to_string())
+}
+
+fn private_helper() {
+}
+
+// TODO: read retries from the environment
+#[allow(dead_code)]
+fn legacy() {
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn computes_area() {
+        assert_eq!(super::area(2, 3), 6);
+    }
+}
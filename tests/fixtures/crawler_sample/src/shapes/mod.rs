@@ -0,0 +1,16 @@
+pub mod polygon;
+
+pub trait Shape { fn area(&self) -> f64; fn name(&self) -> String; }
+
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        3.14 * self.radius * self.radius
+    }
+    fn name(&self) -> String {
+        "circle".to_string()
+    }
+}
@@ -0,0 +1,21 @@
+use super::Shape;
+
+/// Same name as `util::Render`: both must be reported.
+pub trait Render { fn render(&self) -> String; }
+
+pub struct Polygon { sides: u32 }
+
+pub fn regular(
+    sides: u32,
+) -> Polygon {
+    Polygon { sides }
+}
+
+impl Shape for Polygon {
+    fn area(&self) -> f64 {
+        f64::from(self.sides)
+    }
+    fn name(&self) -> String {
+        format!("{}-gon", self.sides)
+    }
+}
@@ -0,0 +1,8 @@
+pub trait Render { fn render(&self) -> String; fn width(&self) -> usize; }
+
+pub(crate) fn clamp(value: i64, min: i64, max: i64) -> i64 {
+    value.max(min).min(max)
+}
+
+// FIXME: handle overflow
+fn double(value: i64) -> i64 { value * 2 }
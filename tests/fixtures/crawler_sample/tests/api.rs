@@ -0,0 +1,9 @@
+#[test]
+fn area_of_a_square() {
+    assert_eq!(crawler_sample::area(2, 2), 4);
+}
+
+#[test]
+fn polygon_sides() {
+    let _ = crawler_sample::shapes::polygon::regular(5);
+}
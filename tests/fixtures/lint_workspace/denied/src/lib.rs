@@ -0,0 +1,5 @@
+#![deny(clippy::needless_return)]
+
+pub fn answer() -> u32 {
+    return 42;
+}
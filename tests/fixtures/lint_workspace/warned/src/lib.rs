@@ -0,0 +1,8 @@
+pub fn total(values: &[u32]) -> u32 {
+    let unused = 1;
+    values.iter().sum()
+}
+
+pub fn answer() -> u32 {
+    return 42;
+}
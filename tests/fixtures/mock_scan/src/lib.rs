@@ -0,0 +1,24 @@
+// A mock in a comment is not flagged.
+pub const HELP: &str = "pass --mock to fake the server";
+
+pub struct MockClient;
+
+impl MockClient {
+    pub fn new() -> Self {
+        MockClient
+    }
+}
+
+pub fn connect() {
+    let _client = MockClient::new();
+}
+
+#[cfg(test)]
+mod tests {
+    struct MockServer;
+
+    #[test]
+    fn uses_a_mock() {
+        let _server = MockServer;
+    }
+}
@@ -0,0 +1,6 @@
+struct MockServer;
+
+#[test]
+fn talks_to_a_mock_server() {
+    let _server = MockServer;
+}
@@ -0,0 +1,207 @@
+// Generated lookup table; regenerate instead of editing by hand.
+// FIXME: regenerate from the upstream table
+pub fn lookup(code: u32) -> &'static str {
+    match code {
+        0 => "entry 0000",
+        1 => "entry 0001",
+        2 => "entry 0002",
+        3 => "entry 0003",
+        4 => "entry 0004",
+        5 => "entry 0005",
+        6 => "entry 0006",
+        7 => "entry 0007",
+        8 => "entry 0008",
+        9 => "entry 0009",
+        10 => "entry 0010",
+        11 => "entry 0011",
+        12 => "entry 0012",
+        13 => "entry 0013",
+        14 => "entry 0014",
+        15 => "entry 0015",
+        16 => "entry 0016",
+        17 => "entry 0017",
+        18 => "entry 0018",
+        19 => "entry 0019",
+        20 => "entry 0020",
+        21 => "entry 0021",
+        22 => "entry 0022",
+        23 => "entry 0023",
+        24 => "entry 0024",
+        25 => "entry 0025",
+        26 => "entry 0026",
+        27 => "entry 0027",
+        28 => "entry 0028",
+        29 => "entry 0029",
+        30 => "entry 0030",
+        31 => "entry 0031",
+        32 => "entry 0032",
+        33 => "entry 0033",
+        34 => "entry 0034",
+        35 => "entry 0035",
+        36 => "entry 0036",
+        37 => "entry 0037",
+        38 => "entry 0038",
+        39 => "entry 0039",
+        40 => "entry 0040",
+        41 => "entry 0041",
+        42 => "entry 0042",
+        43 => "entry 0043",
+        44 => "entry 0044",
+        45 => "entry 0045",
+        46 => "entry 0046",
+        47 => "entry 0047",
+        48 => "entry 0048",
+        49 => "entry 0049",
+        50 => "entry 0050",
+        51 => "entry 0051",
+        52 => "entry 0052",
+        53 => "entry 0053",
+        54 => "entry 0054",
+        55 => "entry 0055",
+        56 => "entry 0056",
+        57 => "entry 0057",
+        58 => "entry 0058",
+        59 => "entry 0059",
+        60 => "entry 0060",
+        61 => "entry 0061",
+        62 => "entry 0062",
+        63 => "entry 0063",
+        64 => "entry 0064",
+        65 => "entry 0065",
+        66 => "entry 0066",
+        67 => "entry 0067",
+        68 => "entry 0068",
+        69 => "entry 0069",
+        70 => "entry 0070",
+        71 => "entry 0071",
+        72 => "entry 0072",
+        73 => "entry 0073",
+        74 => "entry 0074",
+        75 => "entry 0075",
+        76 => "entry 0076",
+        77 => "entry 0077",
+        78 => "entry 0078",
+        79 => "entry 0079",
+        80 => "entry 0080",
+        81 => "entry 0081",
+        82 => "entry 0082",
+        83 => "entry 0083",
+        84 => "entry 0084",
+        85 => "entry 0085",
+        86 => "entry 0086",
+        87 => "entry 0087",
+        88 => "entry 0088",
+        89 => "entry 0089",
+        90 => "entry 0090",
+        91 => "entry 0091",
+        92 => "entry 0092",
+        93 => "entry 0093",
+        94 => "entry 0094",
+        95 => "entry 0095",
+        96 => "entry 0096",
+        97 => "entry 0097",
+        98 => "entry 0098",
+        99 => "entry 0099",
+        100 => "entry 0100",
+        101 => "entry 0101",
+        102 => "entry 0102",
+        103 => "entry 0103",
+        104 => "entry 0104",
+        105 => "entry 0105",
+        106 => "entry 0106",
+        107 => "entry 0107",
+        108 => "entry 0108",
+        109 => "entry 0109",
+        110 => "entry 0110",
+        111 => "entry 0111",
+        112 => "entry 0112",
+        113 => "entry 0113",
+        114 => "entry 0114",
+        115 => "entry 0115",
+        116 => "entry 0116",
+        117 => "entry 0117",
+        118 => "entry 0118",
+        119 => "entry 0119",
+        120 => "entry 0120",
+        121 => "entry 0121",
+        122 => "entry 0122",
+        123 => "entry 0123",
+        124 => "entry 0124",
+        125 => "entry 0125",
+        126 => "entry 0126",
+        127 => "entry 0127",
+        128 => "entry 0128",
+        129 => "entry 0129",
+        130 => "entry 0130",
+        131 => "entry 0131",
+        132 => "entry 0132",
+        133 => "entry 0133",
+        134 => "entry 0134",
+        135 => "entry 0135",
+        136 => "entry 0136",
+        137 => "entry 0137",
+        138 => "entry 0138",
+        139 => "entry 0139",
+        140 => "entry 0140",
+        141 => "entry 0141",
+        142 => "entry 0142",
+        143 => "entry 0143",
+        144 => "entry 0144",
+        145 => "entry 0145",
+        146 => "entry 0146",
+        147 => "entry 0147",
+        148 => "entry 0148",
+        149 => "entry 0149",
+        150 => "entry 0150",
+        151 => "entry 0151",
+        152 => "entry 0152",
+        153 => "entry 0153",
+        154 => "entry 0154",
+        155 => "entry 0155",
+        156 => "entry 0156",
+        157 => "entry 0157",
+        158 => "entry 0158",
+        159 => "entry 0159",
+        160 => "entry 0160",
+        161 => "entry 0161",
+        162 => "entry 0162",
+        163 => "entry 0163",
+        164 => "entry 0164",
+        165 => "entry 0165",
+        166 => "entry 0166",
+        167 => "entry 0167",
+        168 => "entry 0168",
+        169 => "entry 0169",
+        170 => "entry 0170",
+        171 => "entry 0171",
+        172 => "entry 0172",
+        173 => "entry 0173",
+        174 => "entry 0174",
+        175 => "entry 0175",
+        176 => "entry 0176",
+        177 => "entry 0177",
+        178 => "entry 0178",
+        179 => "entry 0179",
+        180 => "entry 0180",
+        181 => "entry 0181",
+        182 => "entry 0182",
+        183 => "entry 0183",
+        184 => "entry 0184",
+        185 => "entry 0185",
+        186 => "entry 0186",
+        187 => "entry 0187",
+        188 => "entry 0188",
+        189 => "entry 0189",
+        190 => "entry 0190",
+        191 => "entry 0191",
+        192 => "entry 0192",
+        193 => "entry 0193",
+        194 => "entry 0194",
+        195 => "entry 0195",
+        196 => "entry 0196",
+        197 => "entry 0197",
+        198 => "entry 0198",
+        199 => "entry 0199",
+        _ => "unknown",
+    }
+}
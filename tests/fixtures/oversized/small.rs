@@ -0,0 +1,4 @@
+// FIXME: handle the error case
+pub fn small() -> u32 {
+    1
+}
@@ -0,0 +1,12 @@
+let bare = value.unwrap();
+let explained = value.expect("config was validated at startup");
+let from_const = value.expect(MISSING_CONFIG);
+let empty = value.expect("");
+let nothing = value.expect();
+let or = value.unwrap_or(0);
+let or_else = value.unwrap_or_else(|| 0);
+let or_default = value.unwrap_or_default();
+let continued = value.expect(
+    "message on the next line",
+);
+let both = value.expect("ok").unwrap();
@@ -0,0 +1,12 @@
+// TODO: real marker that must be reported
+pub const SAMPLE: &str = r#"
+    // FIXME: sample code embedded in a raw string
+    let password = "hunter2";
+"#;
+
+pub const BYTES: &[u8] = b"// HACK: inside a byte string";
+pub const RAW_BYTES: &[u8] = br"// BUG: inside a raw byte string";
+
+pub fn quote() -> char {
+    '"' // FIXME: after a char literal that contains a quote
+}
@@ -0,0 +1,3 @@
+fn main() {
+    cc::Build::new().file("src/shim.c").compile("shim");
+}
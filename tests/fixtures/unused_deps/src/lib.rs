@@ -0,0 +1,11 @@
+#[cfg(unix)]
+extern crate libc;
+use serde::Serialize;
+// regex::Regex would be nicer here
+#[derive(Serialize)]
+pub struct Version(String);
+pub fn parse(raw: &str) -> anyhow::Result<json::Value> {
+    let _ = tokio_util::sync::CancellationToken::new();
+    let _ = semver::Version::parse(raw);
+    Ok(json::from_str(raw)?)
+}
@@ -0,0 +1,5 @@
+#[test]
+fn writes() {
+    let dir = tempfile::tempdir().unwrap();
+    log::info!("{}", dir.path().display());
+}
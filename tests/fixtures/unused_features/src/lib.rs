@@ -0,0 +1,4 @@
+#[cfg(feature = "tracing")]
+pub fn trace() {}
+
+pub const DOC: &str = r#"#[cfg(feature = "experimental")]"#;
@@ -0,0 +1,6 @@
+pub fn answer() -> u32 {
+    let unused = 1;
+    42
+}
+
+fn helper() {}
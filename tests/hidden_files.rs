@@ -0,0 +1,31 @@
+use clap::Parser;
+use trae_cli::cli::TraeCli;
+use trae_cli::config::RunOptions;
+use trae_cli::utils::path::rust_files;
+use uuid::Uuid;
+
+#[test]
+fn hidden_entries_are_skipped_unless_included() {
+    let dir = std::env::temp_dir().join(format!("trae_hidden_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::create_dir_all(dir.join(".hidden")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+    std::fs::write(dir.join("src/.scratch.rs"), "fn g() {}\n").unwrap();
+    std::fs::write(dir.join(".hidden/foo.rs"), "fn h() {}\n").unwrap();
+
+    let found = |dir: &std::path::Path, options: &RunOptions| {
+        let mut files: Vec<String> = rust_files(dir, None, options)
+            .map(|e| e.path().strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        files.sort();
+        files
+    };
+    assert_eq!(found(&dir, &RunOptions::default()), ["src/lib.rs"]);
+
+    let cli = TraeCli::try_parse_from(["trae", "--include-hidden", "scan"]).unwrap();
+    assert!(cli.include_hidden);
+    let options = RunOptions { include_hidden: true, ..RunOptions::default() };
+    assert_eq!(found(&dir, &options), [".hidden/foo.rs", "src/.scratch.rs", "src/lib.rs"]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::mpsc;
+use trae_cli::config::HttpConfig;
+use trae_cli::utils::http::{default_headers, DEFAULT_USER_AGENT};
+use uuid::Uuid;
+
+/// A proxy that answers 200 to everything and sends back each request head.
+fn recording_proxy() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut head = [0u8; 4096];
+            let read = stream.read(&mut head).unwrap_or(0);
+            let _ = tx.send(String::from_utf8_lossy(&head[..read]).to_string());
+            let body = r#"{"version":"9.9.9"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    (url, rx)
+}
+
+fn status(dir: &Path, env: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["status", "--remote", "--json"])
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("JARVIX_URL", "http://jarvix.invalid:5051")
+        .env("NO_PROXY", "server.invalid")
+        .envs(env.iter().copied())
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn project() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_http_{}", Uuid::new_v4()));
+    let status = common::config_with("status", "server_url = \"http://server.invalid:3001\"\n");
+    common::write_config(&dir, &format!("{status}\n[http]\nheaders = [\"X-Team: core\"]\n"));
+    dir
+}
+
+#[test]
+fn every_request_names_trae_and_carries_the_config_headers() {
+    let headers = default_headers(&HttpConfig::default()).unwrap();
+    assert_eq!(headers["user-agent"], DEFAULT_USER_AGENT);
+    assert!(DEFAULT_USER_AGENT.starts_with("trae-cli/"));
+    assert!(headers.contains_key("x-trae-version"));
+
+    let config = HttpConfig {
+        user_agent: Some("acme-ci".to_string()),
+        headers: vec!["X-Team: core".to_string()],
+    };
+    let headers = default_headers(&config).unwrap();
+    assert_eq!(headers["user-agent"], "acme-ci");
+    assert_eq!(headers["x-team"], "core");
+    let bad = HttpConfig { headers: vec!["sin dos puntos".to_string()], ..config };
+    assert!(default_headers(&bad).is_err());
+}
+
+#[test]
+fn requests_go_through_the_env_proxy_except_no_proxy_hosts() {
+    let dir = project();
+    let (proxy, requests) = recording_proxy();
+    let output = status(&dir, &[("HTTP_PROXY", &proxy)]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let up = |name: &str| {
+        let components = report["components"].as_array().unwrap();
+        components.iter().find(|c| c["name"] == name).unwrap()["up"].as_bool().unwrap()
+    };
+    // Reached through the proxy; server.invalid is in NO_PROXY and doesn't resolve
+    assert!(up("jarvix"), "{report}");
+    assert!(!up("server"), "{report}");
+    let seen: Vec<String> = requests.try_iter().collect();
+    assert_eq!(seen.len(), 1, "{seen:?}");
+    let head = seen[0].to_lowercase();
+    assert!(head.starts_with("get http://jarvix.invalid:5051/healthz"), "{head}");
+    assert!(head.contains(&format!("user-agent: {}", DEFAULT_USER_AGENT)), "{head}");
+    assert!(head.contains("x-team: core"), "{head}");
+}
+
+#[test]
+fn a_malformed_proxy_variable_is_reported() {
+    let dir = project();
+    let output = status(&dir, &[("HTTP_PROXY", "http://[no-es-url")]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let text = report.to_string();
+    let message = "HTTP_PROXY=`http://[no-es-url` no es una URL de proxy válida";
+    assert!(text.contains(message), "{text}");
+}
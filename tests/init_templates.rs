@@ -0,0 +1,39 @@
+use trae_cli::config::{template_files, write_template, TraeConfig, INIT_TEMPLATES};
+use trae_cli::core::multilang::MultilangRules;
+use uuid::Uuid;
+
+#[test]
+fn every_template_writes_a_valid_config_and_refuses_to_overwrite() {
+    for template in INIT_TEMPLATES {
+        let files = template_files(template).unwrap();
+        let config = TraeConfig::parse(&files[0].1).unwrap();
+        match template {
+            "ci" | "minimal" => assert!(!config.jarvix.enabled, "{template}"),
+            _ => assert!(config.analysis.security_analysis, "{template}"),
+        }
+        for (path, content) in &files[1..] {
+            toml::from_str::<toml::Value>(content).unwrap_or_else(|e| panic!("{path}: {e}"));
+        }
+    }
+    assert!(template_files("enterprise").is_none());
+    let full = template_files("full").unwrap();
+    let (_, rules) = full.iter().find(|(path, _)| path.ends_with("multilang.toml")).unwrap();
+    assert!(MultilangRules::parse(rules).unwrap().covers("py"));
+
+    let dir = std::env::temp_dir().join(format!("trae_init_{}", Uuid::new_v4()));
+    let written = write_template(&dir, "security", false).unwrap();
+    assert_eq!(written.len(), 3);
+    assert!(written.iter().all(|path| path.starts_with(dir.join(".trae")) && path.exists()));
+
+    // Every file both templates share is listed, and nothing is written
+    let err = write_template(&dir, "full", false).unwrap_err().to_string();
+    assert!(err.contains(".trae/config.toml, .trae/bans.toml, .trae/secrets.toml"), "{err}");
+    assert!(!dir.join(".trae/multilang.toml").exists());
+    let err = write_template(&dir, "ci", false).unwrap_err().to_string();
+    assert!(err.contains(".trae/config.toml") && !err.contains("bans"), "{err}");
+
+    write_template(&dir, "ci", true).unwrap();
+    let config = std::fs::read_to_string(dir.join(".trae/config.toml")).unwrap();
+    assert!(config.starts_with("# Generado por `trae init --template ci`"));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use serde_json::Value;
 use std::thread;
 use tiny_http::{Response, Server};
+use trae_cli::config::RunOptions;
 use trae_cli::jarvix::client::JarvixClient;
 use trae_cli::metrics::collector::MetricsCollector;
 use trae_cli::commands::analyze::AnalyzeCommand;
@@ -44,7 +45,10 @@ async fn jarvix_client_reports_scan_metrics_to_local_server() {
     metrics.add_custom_metric("foo".to_string(), 42);
 
     // Create client and report
-    let client = JarvixClient::new().expect("client new").expect("client present");
+    let client = JarvixClient::new(&RunOptions::default())
+        .await
+        .expect("client new")
+        .expect("client present");
     let res = client.report_scan_metrics(metrics).await;
     assert!(res.is_ok(), "report_scan_metrics failed: {:?}", res.err());
 
@@ -66,5 +70,10 @@ async fn jarvix_client_reports_scan_metrics_to_local_server() {
 async fn analyze_command_run_simple_executes() {
     // Should run without requiring JARVIX
     let res = AnalyzeCommand::run_simple(false, false, false, true, None, false, None).await;
-    assert!(res.is_ok(), "Analyze run_simple failed: {:?}", res.err());
+    // Critical issues are a completed run with findings, not a failure
+    let completed = match &res {
+        Ok(()) => true,
+        Err(e) => trae_cli::exit::is_findings(e),
+    };
+    assert!(completed, "Analyze run_simple failed: {:?}", res.err());
 }
@@ -0,0 +1,86 @@
+use std::process::Command;
+use trae_cli::core::analyzer::{sort_issues, AnalysisIssue, IssueSeverity};
+use uuid::Uuid;
+
+fn issue(severity: IssueSeverity, file: &str, line: usize, description: &str) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Code Quality".to_string(),
+        description: description.to_string(),
+        severity,
+        file: Some(file.to_string()),
+        line: Some(line),
+        confidence: 1.0,
+    }
+}
+
+#[test]
+fn sorts_by_severity_then_file_line_and_message() {
+    let mut issues = vec![
+        issue(IssueSeverity::Info, "./src/a.rs", 1, "todo"),
+        issue(IssueSeverity::Critical, "./src/b.rs", 9, "fixme"),
+        issue(IssueSeverity::Warning, "./src/a.rs", 3, "xxx"),
+        issue(IssueSeverity::Critical, "./src/a.rs", 7, "panic"),
+        issue(IssueSeverity::Critical, "./src/a.rs", 7, "fixme"),
+    ];
+    sort_issues(&mut issues);
+    let order: Vec<_> = issues
+        .iter()
+        .map(|i| (i.file.as_deref().unwrap(), i.line.unwrap(), i.description.as_str()))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            ("./src/a.rs", 7, "fixme"),
+            ("./src/a.rs", 7, "panic"),
+            ("./src/b.rs", 9, "fixme"),
+            ("./src/a.rs", 3, "xxx"),
+            ("./src/a.rs", 1, "todo"),
+        ]
+    );
+}
+
+#[test]
+fn two_runs_over_the_same_tree_print_the_same_output() {
+    let dir = std::env::temp_dir().join(format!("trae_order_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"ordered\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    for (i, name) in ["zeta", "alpha", "nested/mid", "beta", "nested/omega"].iter().enumerate() {
+        let source = format!(
+            "// TODO: later {i}\n// XXX: hack\n// FIXME: broken {i}\n\
+             pub fn raw() {{\n    unsafe {{}}\n}}\n"
+        );
+        std::fs::write(dir.join(format!("src/{name}.rs")), source).unwrap();
+    }
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .arg("--no-jarvix")
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let first = run(&["scan", "--format", "lsp"]);
+    assert_eq!(first, run(&["scan", "--format", "lsp"]));
+    let diagnostics: Vec<serde_json::Value> =
+        first.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(diagnostics.len(), 15, "{first}");
+    // Critical findings come first, in file order
+    let uris: Vec<_> = diagnostics[..5].iter().map(|d| d["uri"].as_str().unwrap()).collect();
+    assert!(diagnostics[..5].iter().all(|d| d["severity"] == 1), "{first}");
+    let mut sorted = uris.clone();
+    sorted.sort_unstable();
+    assert_eq!(uris, sorted);
+
+    let security = ["security", "--code", "--secrets", "--level", "low", "--format", "lsp"];
+    let first = run(&security);
+    assert_eq!(first.lines().count(), 5, "{first}");
+    assert_eq!(first, run(&security));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,27 @@
+use std::thread;
+use tiny_http::{Response, Server};
+use trae_cli::jarvix::client::discover;
+
+#[tokio::test]
+async fn discovery_skips_dead_ports_and_picks_the_first_healthy_server() {
+    let dead = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let dead_url = format!("http://{}", dead.local_addr().unwrap());
+    drop(dead);
+
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let live_url = format!("http://{}", server.server_addr());
+    let handle = thread::spawn(move || {
+        let request = server.recv().unwrap();
+        let status = if request.url() == "/healthz" { 200 } else { 404 };
+        let agent = request.headers().iter().find(|h| h.field.equiv("User-Agent"));
+        let agent = agent.map(|h| h.value.to_string()).unwrap_or_default();
+        let _ = request.respond(Response::from_string("ok").with_status_code(status));
+        agent
+    });
+
+    let candidates = [dead_url.clone(), live_url.clone()];
+    assert_eq!(discover(&candidates).await, Some(live_url));
+    // Probes go through the shared JARVIXSERVER client
+    assert!(handle.join().unwrap().starts_with("trae-cli/"));
+    assert_eq!(discover(&[dead_url]).await, None);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output};
+use trae_cli::config::TraeConfig;
+use trae_cli::utils::http::jarvix_builder_with;
+use uuid::Uuid;
+
+fn config(jarvix: &str) -> String {
+    common::config_with("jarvix", jarvix)
+}
+
+/// `trae status --remote --json` against an unreachable JARVIXSERVER.
+fn status(dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["status", "--remote", "--json"])
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("JARVIX_URL", "https://127.0.0.1:9")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn jarvix_detail(output: &Output) -> String {
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let components = report["components"].as_array().unwrap();
+    let jarvix = components.iter().find(|c| c["name"] == "jarvix").unwrap();
+    jarvix["detail"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn an_unusable_ca_cert_is_an_error() {
+    let dir = std::env::temp_dir().join(format!("trae_jarvix_tls_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let missing = dir.join("missing.pem");
+    let not_pem = dir.join("notes.txt");
+    std::fs::write(&not_pem, "not a certificate\n").unwrap();
+    let garbage = dir.join("garbage.pem");
+    std::fs::write(&garbage, "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n")
+        .unwrap();
+
+    let error = |path: &Path| {
+        let toml = config(&format!("ca_cert = {:?}\n", path.display().to_string()));
+        let config = TraeConfig::parse(&toml).unwrap();
+        format!("{:#}", jarvix_builder_with(&config).expect_err("ca_cert error"))
+    };
+    let missing_error = error(&missing);
+    let not_pem_error = error(&not_pem);
+    let garbage_error = error(&garbage);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(missing_error.contains("missing.pem: no se pudo leer"), "{missing_error}");
+    assert!(not_pem_error.contains("no contiene ningún certificado PEM"), "{not_pem_error}");
+    assert!(garbage_error.contains("certificado PEM inválido"), "{garbage_error}");
+    let plain = TraeConfig::parse(&config("")).unwrap();
+    assert!(jarvix_builder_with(&plain).is_ok());
+}
+
+#[test]
+fn status_names_a_missing_ca_and_warns_about_insecure() {
+    let dir = std::env::temp_dir().join(format!("trae_jarvix_tls_cli_{}", Uuid::new_v4()));
+    common::write_config(&dir, &config("ca_cert = \"/no/such/ca.pem\"\n"));
+    let missing = status(&dir);
+    common::write_config(&dir, &config("insecure = true\n"));
+    let insecure = status(&dir);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let detail = jarvix_detail(&missing);
+    assert!(detail.contains("[jarvix] ca_cert /no/such/ca.pem: no se pudo leer"), "{detail}");
+    let stderr = String::from_utf8_lossy(&insecure.stderr);
+    assert!(stderr.contains("verificación TLS de JARVIXSERVER está DESACTIVADA"), "{stderr}");
+    assert!(stderr.contains("nunca en producción"), "{stderr}");
+}
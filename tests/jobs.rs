@@ -0,0 +1,18 @@
+use clap::Parser;
+use trae_cli::cli::TraeCli;
+use trae_cli::config::RunOptions;
+
+#[test]
+fn jobs_caps_cargo_and_the_scanner_pool() {
+    let cli = TraeCli::try_parse_from(["trae", "doctor", "--jobs", "3"]).unwrap();
+    let jobs = cli.jobs.unwrap();
+    assert_eq!(jobs.get(), 3);
+    assert!(TraeCli::try_parse_from(["trae", "-j", "0", "doctor"]).is_err());
+
+    let options = RunOptions { jobs: Some(jobs), ..RunOptions::default() };
+    assert!(options.cargo_env().contains(&("CARGO_BUILD_JOBS", "3".into())));
+    trae_cli::config::use_jobs(jobs);
+    assert_eq!(rayon::current_num_threads(), 3);
+    let config = trae_cli::performance_patterns::PerformanceConfig::default();
+    assert_eq!(config.thread_count, 3);
+}
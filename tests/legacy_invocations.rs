@@ -0,0 +1,64 @@
+use clap::Parser;
+use std::process::Command;
+use trae_cli::cli::{Commands, TraeCli};
+use uuid::Uuid;
+
+fn parse(args: &[&str]) -> TraeCli {
+    TraeCli::try_parse_from(std::iter::once("trae").chain(args.iter().copied())).unwrap()
+}
+
+#[test]
+fn old_binary_invocations_parse_on_the_unified_cli() {
+    let cli = parse(&["--project", ".", "--no-report", "deadcode", "--max-depth", "2", "--verbose"]);
+    assert!(cli.no_jarvix && cli.verbose && cli.project.is_some());
+    assert!(matches!(cli.command, Commands::Deadcode(ref cmd) if cmd.max_depth == Some(2)));
+
+    assert!(matches!(parse(&["b", "--release"]).command, Commands::Build(_)));
+    assert!(matches!(parse(&["t"]).command, Commands::Test(_)));
+    assert!(matches!(parse(&["lint"]).command, Commands::Clippy(_)));
+    assert!(matches!(parse(&["c"]).command, Commands::External(ref args) if args == &["c"]));
+    assert!(matches!(parse(&["custom", "check", "--quiet"]).command, Commands::Custom { ref args } if args == &["check", "--quiet"]));
+    assert!(matches!(parse(&["preflight"]).command, Commands::Preflight(_)));
+    assert!(matches!(parse(&["modules", "--tree", "-d", "1"]).command, Commands::Modules(_)));
+
+    let Commands::WebSearch(search) = parse(&["--jarvix", "http://localhost:5051", "web-search", "tokio", "--crates"]).command else {
+        panic!("web-search no parseado");
+    };
+    assert_eq!(search.request()["query"], "tokio site:crates.io");
+}
+
+#[test]
+fn old_init_doc_and_repair_invocations_keep_their_meaning() {
+    let Commands::Init { path, lib, .. } = parse(&["init", "--lib", "mycrate"]).command else {
+        panic!("init no parseado");
+    };
+    assert!(lib && path.is_some_and(|p| p.ends_with("mycrate")));
+    assert!(TraeCli::try_parse_from(["trae", "init", "mycrate", "--force"]).is_err());
+
+    let cli = parse(&["doc", "--document-private-items", "--no-deps", "-j", "2"]);
+    assert_eq!(cli.jobs.map(|jobs| jobs.get()), Some(2));
+    assert!(matches!(cli.command, Commands::Doc(ref doc) if doc.private && doc.no_deps));
+
+    let plain = |args: &[&str]| match parse(args).command {
+        Commands::Repair(cmd) => cmd.selects_nothing(),
+        _ => panic!("repair no parseado"),
+    };
+    assert!(plain(&["repair"]) && plain(&["repair", "--allow-dirty-repair"]));
+    assert!(!plain(&["repair", "--fmt"]) && !plain(&["repair", "--edition", "2024"]));
+}
+
+#[test]
+fn init_with_a_path_creates_a_crate_instead_of_the_config() {
+    let dir = std::env::temp_dir().join(format!("trae_legacy_init_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "init", "--lib", "mycrate"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.join("mycrate/src/lib.rs").exists());
+    assert!(!dir.join(".trae").exists());
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,28 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::core::lints::{CrateLints, LintSummary};
+use uuid::Uuid;
+
+#[test]
+fn clippy_findings_are_counted_per_lint_and_per_crate() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/lint_workspace");
+    let target = std::env::temp_dir().join(format!("trae_lint_workspace_{}", Uuid::new_v4()));
+    let output = Command::new(env!("CARGO"))
+        .args(["clippy", "--all-targets", "--workspace", "--keep-going"])
+        .arg("--message-format=json")
+        .arg("--target-dir")
+        .arg(&target)
+        .current_dir(&fixture)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&target).ok();
+    assert!(!output.status.success());
+
+    let summary = LintSummary::parse(&String::from_utf8_lossy(&output.stdout));
+    // --all-targets is the trae default: lib and lib-test findings count once
+    assert_eq!(summary.crates["denied"], CrateLints { warnings: 0, denied: 1 });
+    assert_eq!(summary.crates["warned"], CrateLints { warnings: 2, denied: 0 });
+    assert_eq!(summary.lints["clippy::needless_return"], 2);
+    assert_eq!(summary.lints["unused_variables"], 1);
+    assert_eq!((summary.warnings(), summary.denied()), (2, 1));
+}
@@ -0,0 +1,35 @@
+use trae_cli::commands::security::SecuritySeverity;
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+use trae_cli::utils::diagnostics::{Diagnostic, ERROR, HINT, INFORMATION, WARNING};
+
+#[test]
+fn issues_become_zero_based_lsp_diagnostics() {
+    let issue = AnalysisIssue {
+        category: "Code Quality".to_string(),
+        description: "FIXME en línea 3".to_string(),
+        severity: IssueSeverity::Warning,
+        file: Some("./src/lib.rs".to_string()),
+        line: Some(3),
+        confidence: 1.0,
+    };
+    let json = serde_json::to_value(issue.to_diagnostic()).unwrap();
+    assert!(json["uri"].as_str().unwrap().starts_with("file:///"));
+    assert!(json["uri"].as_str().unwrap().ends_with("/src/lib.rs"));
+    assert_eq!(json["range"]["start"], serde_json::json!({"line": 2, "character": 0}));
+    assert_eq!(json["range"]["end"], serde_json::json!({"line": 3, "character": 0}));
+    assert_eq!(json["severity"], WARNING);
+    assert_eq!(json["code"], "Code Quality");
+    assert_eq!(json["message"], "FIXME en línea 3");
+    assert_eq!(json["source"], "trae");
+
+    let project_wide = Diagnostic::new(None, None, ERROR, "Dependencies", "x");
+    assert!(project_wide.uri.ends_with("/Cargo.toml"));
+    assert_eq!(project_wide.range.start.line, 0);
+
+    assert_eq!(IssueSeverity::Critical.lsp_severity(), ERROR);
+    assert_eq!(IssueSeverity::Info.lsp_severity(), INFORMATION);
+    assert_eq!(SecuritySeverity::High.lsp_severity(), ERROR);
+    assert_eq!(SecuritySeverity::Medium.lsp_severity(), WARNING);
+    assert_eq!(SecuritySeverity::Low.lsp_severity(), INFORMATION);
+    assert_eq!(SecuritySeverity::Info.lsp_severity(), HINT);
+}
@@ -0,0 +1,34 @@
+use trae_cli::core::manifest::{declaration_line, Manifest};
+
+#[test]
+fn manifest_reads_table_forms_sources_and_profiles() {
+    let content = r#"
+[workspace]
+members = ["crates/*"]
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+local = { path = "../local" }
+shared = { workspace = true }
+
+[dependencies.forked]
+git = "https://example.com/forked.git"
+
+[profile.release]
+panic = "abort"
+"#;
+    let manifest = Manifest::parse(content).unwrap();
+    assert!(manifest.package.is_none() && manifest.workspace.is_some());
+    assert!(manifest.depends_on("forked"));
+    let mut unversioned: Vec<_> = manifest
+        .specs()
+        .filter(|(_, spec)| spec.is_unversioned_source())
+        .map(|(dep, _)| dep.name)
+        .collect();
+    unversioned.sort();
+    assert_eq!(unversioned, ["forked", "local"]);
+    assert_eq!(manifest.profile["release"].panic.as_deref(), Some("abort"));
+    assert_eq!(declaration_line(content, "local"), Some(7));
+    assert_eq!(declaration_line(content, "forked"), Some(10));
+    assert!(Manifest::parse("[package\nname = 1").is_err());
+}
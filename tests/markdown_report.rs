@@ -0,0 +1,66 @@
+use std::process::Command;
+use trae_cli::utils::markdown::{code, Finding, Report};
+use uuid::Uuid;
+
+#[test]
+fn findings_are_grouped_by_category_and_escaped() {
+    let mut report = Report::new("trae scan");
+    report.summary("Critical", 1).note("✂️ truncated");
+    for (category, location, message) in [
+        ("Safety", "src/b.rs:2", "unwrap() on `a|b`"),
+        ("Code <Quality>", "src/a.rs:1", "TODO: *fix*\n<script>"),
+    ] {
+        report.finding(Finding {
+            category: category.to_string(),
+            severity: "Critical".to_string(),
+            location: Some(location.to_string()),
+            message: message.to_string(),
+        });
+    }
+    let rendered = report.render();
+    let quality = rendered.find("<summary><strong>Code &lt;Quality&gt;</strong> (1)").unwrap();
+    assert!(quality < rendered.find("<strong>Safety</strong>").unwrap(), "{rendered}");
+    assert!(rendered.contains("| Critical | 1 |"), "{rendered}");
+    assert!(rendered.contains("| 🔴 Critical | `src/a.rs:1` | TODO: \\*fix\\* \\<script\\> |"));
+    assert!(rendered.contains("unwrap() on \\`a\\|b\\`"), "{rendered}");
+    assert!(rendered.ends_with("> ✂️ truncated\n"), "{rendered}");
+    assert_eq!(code("a`b|c"), "``a`b\\|c``");
+    assert!(Report::new("empty").render().contains("✅ No findings."));
+}
+
+#[test]
+fn scan_and_security_print_markdown_from_the_same_findings() {
+    let dir = std::env::temp_dir().join(format!("trae_markdown_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"md\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "// FIXME: later\npub fn raw() {\n    unsafe {}\n}\n")
+        .unwrap();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_trae"))
+            .arg("--no-jarvix")
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["scan", "--format", "markdown"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{stdout}");
+    assert!(stdout.starts_with("## 🔍 trae scan\n"), "{stdout}");
+    assert!(stdout.contains("| 🔴 Critical | 1 |"), "{stdout}");
+    assert!(stdout.contains("<summary><strong>Code Quality</strong> (1)</summary>"), "{stdout}");
+    assert!(stdout.contains("`./src/lib.rs:1`"), "{stdout}");
+
+    let output = run(&["security", "--code", "--format", "markdown"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.starts_with("## 🔒 trae security\n"), "{stdout}");
+    assert!(stdout.contains("| 🟡 Medium | `src/lib.rs:3` | CWE-119:"), "{stdout}");
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output};
+use trae_cli::config::RunOptions;
+use trae_cli::utils::path::rust_files;
+use uuid::Uuid;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/oversized");
+
+fn scan(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix"])
+        .args(args)
+        .args(["scan", "--path", ".", "--count-only", "--json"])
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+fn critical(output: &Output) -> u64 {
+    let counts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    counts["critical"].as_u64().unwrap()
+}
+
+#[test]
+fn files_over_the_limit_are_skipped_and_listed() {
+    // Both fixture files carry a FIXME; generated.rs is ~6KB.
+    let full = scan(Path::new(FIXTURE), &[]);
+    assert_eq!(critical(&full), 2, "{full:?}");
+
+    let limited = scan(Path::new(FIXTURE), &["--max-file-size", "1024"]);
+    assert_eq!(critical(&limited), 1, "{limited:?}");
+    let stderr = String::from_utf8_lossy(&limited.stderr);
+    assert!(stderr.contains("1 archivo(s) omitidos por superar 1024 bytes"), "{stderr}");
+    assert!(stderr.contains("generated.rs"), "{stderr}");
+
+    // The limit can also come from the project's .trae/config.toml.
+    let dir = std::env::temp_dir().join(format!("trae_max_size_{}", Uuid::new_v4()));
+    common::write_config(&dir, &common::config_with("analysis", "max_file_size = 1024\n"));
+    for file in ["generated.rs", "small.rs"] {
+        std::fs::copy(Path::new(FIXTURE).join(file), dir.join(file)).unwrap();
+    }
+    let configured = scan(&dir, &[]);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(critical(&configured), 1, "{configured:?}");
+    assert!(String::from_utf8_lossy(&configured.stderr).contains("generated.rs"));
+}
+
+#[test]
+fn each_run_keeps_its_own_skipped_files() {
+    let run = RunOptions { max_file_size: Some(1024), ..RunOptions::default() };
+    let other = RunOptions { max_file_size: Some(4096), ..RunOptions::default() };
+    // A clone handed to a scanner records into the run it came from
+    assert_eq!(rust_files(Path::new(FIXTURE), None, &run.clone()).count(), 1);
+    assert_eq!(rust_files(Path::new(FIXTURE), None, &other).count(), 1);
+
+    for (options, limit) in [(&run, 1024), (&other, 4096)] {
+        let skipped = options.oversized.take();
+        assert_eq!(skipped.len(), 1, "{skipped:?}");
+        assert!(skipped[0].0.ends_with("generated.rs"), "{skipped:?}");
+        assert_eq!(skipped[0].2, limit);
+    }
+    assert!(run.oversized.take().is_empty());
+}
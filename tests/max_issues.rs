@@ -0,0 +1,50 @@
+use std::process::Command;
+use uuid::Uuid;
+
+#[test]
+fn caps_issues_per_severity_and_reports_the_truncation() {
+    let dir = std::env::temp_dir().join(format!("trae_max_issues_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"noisy\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    let mut lib = String::from("// FIXME: one\n// FIXME: two\n");
+    for i in 0..8 {
+        lib.push_str(&format!("// TODO: generated {i}\n"));
+    }
+    std::fs::write(dir.join("src/lib.rs"), lib).unwrap();
+    let trae = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "scan"])
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    };
+
+    let output = trae(&["--count-only", "--json", "--max-issues", "3"]);
+    let line = String::from_utf8_lossy(&output.stdout).lines().last().unwrap().to_string();
+    let counts: serde_json::Value = serde_json::from_str(&line).unwrap();
+    // Info findings don't crowd out the critical ones
+    assert_eq!(counts["critical"], 2);
+    assert_eq!(counts["info"], 3);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("truncated at 3 issues per severity"), "{stderr}");
+
+    let output = trae(&["--count-only", "--json"]);
+    let line = String::from_utf8_lossy(&output.stdout).lines().last().unwrap().to_string();
+    let counts: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(counts["info"], 8);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("truncated"));
+
+    let output = trae(&["--max-issues", "3", "--export", "report.json"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("truncated at 3"));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("report.json")).unwrap()).unwrap();
+    assert_eq!(report["truncated_at"], 3);
+    assert_eq!(report["issues"].as_array().unwrap().len(), 5);
+    std::fs::remove_dir_all(&dir).ok();
+}
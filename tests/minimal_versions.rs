@@ -0,0 +1,56 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+fn minimal_versions(dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "deps", "--minimal-versions"])
+        .env("NO_COLOR", "1")
+        .env_remove("CARGO_TARGET_DIR")
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn minimal_versions_never_writes_the_real_lockfile() {
+    let nightly = Command::new("cargo").args(["+nightly", "--version"]).output();
+    if !nightly.is_ok_and(|output| output.status.success()) {
+        eprintln!("skipped: --minimal-versions needs the nightly toolchain");
+        return;
+    }
+    let dir = std::env::temp_dir().join(format!("trae_minimal_versions_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::create_dir_all(dir.join("util/src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"mvapp\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+         [dependencies]\nutil = { path = \"util\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("util/Cargo.toml"),
+        "[package]\nname = \"util\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("util/src/lib.rs"), "pub fn id(x: u8) -> u8 {\n    x\n}\n").unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "pub fn one() -> u8 {\n    util::id(1)\n}\n").unwrap();
+    // Hand-edited, so any rewrite by cargo would show
+    let lock = "# keep me\nversion = 3\n\n[[package]]\nname = \"mvapp\"\nversion = \"0.1.0\"\n\
+                dependencies = [\n \"util\",\n]\n\n[[package]]\nname = \"util\"\n\
+                version = \"0.1.0\"\n";
+    std::fs::write(dir.join("Cargo.lock"), lock).unwrap();
+
+    let passing = minimal_versions(&dir);
+    assert!(passing.status.success(), "{}", String::from_utf8_lossy(&passing.stderr));
+    assert_eq!(std::fs::read_to_string(dir.join("Cargo.lock")).unwrap(), lock);
+    assert!(dir.join("target/minimal-versions").is_dir());
+    assert!(!dir.join("target/debug").exists());
+
+    std::fs::write(dir.join("src/lib.rs"), "pub fn one() -> u8 {\n    util::id(\"1\")\n}\n")
+        .unwrap();
+    let failing = minimal_versions(&dir);
+    assert_eq!(failing.status.code(), Some(2), "{}", String::from_utf8_lossy(&failing.stderr));
+    assert_eq!(std::fs::read_to_string(dir.join("Cargo.lock")).unwrap(), lock);
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::utils::source::{is_test_path, mask_comments_and_strings, mock_lines};
+use uuid::Uuid;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mock_scan");
+
+#[test]
+fn mocks_in_comments_strings_and_test_code_are_not_lines_to_flag() {
+    let masked = mask_comments_and_strings("let a = \"mock\"; // Mock\nlet b = 'm';\n");
+    assert_eq!(masked, "let a =       ;        \nlet b =    ;\n");
+    let content = std::fs::read_to_string(Path::new(FIXTURE).join("src/lib.rs")).unwrap();
+    assert_eq!(mock_lines(&content), [4, 6, 8, 13]);
+    assert!(is_test_path(Path::new("crate/tests/api.rs")));
+    assert!(!is_test_path(Path::new("crate/src/testsuite.rs")));
+}
+
+#[test]
+fn dead_code_scan_flags_only_production_mocks_unless_disabled() {
+    let mock_issues = |extra: &[&str]| {
+        let export = std::env::temp_dir().join(format!("trae_mocks_{}.json", Uuid::new_v4()));
+        let status = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "scan", "--dead-code", "--path", "."])
+            .args(extra)
+            .arg("--export")
+            .arg(&export)
+            .current_dir(FIXTURE)
+            .env("NO_COLOR", "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+        std::fs::remove_file(&export).ok();
+        report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|issue| issue["description"].as_str().unwrap().contains("mock"))
+            .map(|issue| (issue["file"].as_str().unwrap().to_string(), issue["line"].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let flagged = mock_issues(&[]);
+    assert_eq!(flagged.len(), 4, "{flagged:?}");
+    assert!(flagged.iter().all(|(file, _)| file.ends_with("src/lib.rs")), "{flagged:?}");
+    assert!(flagged.iter().any(|(_, line)| line == 13), "{flagged:?}");
+    assert!(mock_issues(&["--no-mock-check"]).is_empty());
+}
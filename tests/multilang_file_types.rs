@@ -0,0 +1,111 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+use trae_cli::core::analyzer::IssueSeverity;
+use trae_cli::core::multilang::MultilangRules;
+use uuid::Uuid;
+
+#[test]
+fn shell_dockerfile_and_sql_are_checked_by_file_type() {
+    let rules = MultilangRules::builtin();
+    for (path, ty) in [
+        ("./deploy.sh", Some("sh")),
+        ("./scripts/run.bash", Some("sh")),
+        ("./Dockerfile", Some("dockerfile")),
+        ("./docker/Dockerfile.prod", Some("dockerfile")),
+        ("./api.dockerfile", Some("dockerfile")),
+        ("./Containerfile", Some("dockerfile")),
+        ("./schema.sql", Some("sql")),
+        ("./template.rs.in", None),
+        ("./notes.txt", None),
+    ] {
+        assert_eq!(rules.file_type(Path::new(path)), ty, "{path}");
+    }
+
+    let script = "#!/bin/sh\nset -euxo pipefail\n# TODO: quitar\nset -e\necho x\n";
+    let issues = rules.check("sh", "./deploy.sh", script);
+    let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+    assert_eq!(lines, [Some(2), Some(3)], "{issues:#?}");
+    assert!(matches!(issues[0].severity, IssueSeverity::Warning));
+
+    let dockerfile = "FROM rust:latest AS build\n# FROM alpine:latest\nFROM debian:12\nUSER root\n";
+    let issues = rules.check("dockerfile", "./Dockerfile", dockerfile);
+    let lines: Vec<_> = issues.iter().map(|i| i.line).collect();
+    assert_eq!(lines, [Some(1), Some(4)], "{issues:#?}");
+
+    let issues = rules.check("sql", "./q.sql", "select * from users;\n-- SELECT * FROM t\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].description, "SELECT * en SQL línea 1: select * from users;");
+}
+
+#[test]
+fn files_globs_and_overrides_are_configurable() {
+    let rules = MultilangRules::parse(
+        r#"
+[files]
+"*.rs.in" = "rs"
+"Dockerfile.*" = "sh"
+
+[overrides]
+select-star = "off"
+latest-tag = "critical"
+
+[[rs]]
+pattern = 'dbg!\('
+message = "dbg! en plantilla"
+"#,
+    )
+    .unwrap();
+    assert_eq!(rules.file_type(Path::new("./template.rs.in")), Some("rs"));
+    assert_eq!(rules.file_type(Path::new("./main.rs")), Some("rs"));
+    // User globs come before the built-in ones
+    assert_eq!(rules.file_type(Path::new("./Dockerfile.dev")), Some("sh"));
+    assert_eq!(rules.file_type(Path::new("./Dockerfile")), Some("dockerfile"));
+    // No rules left for SQL
+    assert_eq!(rules.file_type(Path::new("./q.sql")), None);
+    assert!(!rules.covers("sql"));
+    let issues = rules.check("dockerfile", "./Dockerfile", "FROM node:latest\n");
+    assert!(matches!(issues[0].severity, IssueSeverity::Critical));
+
+    let err = MultilangRules::parse("[overrides]\nno-such-rule = 'off'\n").unwrap_err();
+    assert!(format!("{err:#}").contains("`no-such-rule` no es una regla integrada"), "{err:#}");
+    let err = MultilangRules::parse("[overrides]\nprint = 'loud'\n").unwrap_err();
+    assert!(format!("{err:#}").contains("Severidad inválida `loud`"), "{err:#}");
+    let err = MultilangRules::parse("[files]\n\"[*.x\" = 'sh'\n").unwrap_err();
+    assert!(format!("{err:#}").contains("[files] glob inválido"), "{err:#}");
+}
+
+#[test]
+fn scan_multilang_finds_dockerfiles_and_registered_extensions() {
+    let dir = std::env::temp_dir().join(format!("trae_multilang_types_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("docker")).unwrap();
+    common::write_config(&dir, common::CONFIG);
+    std::fs::write(
+        dir.join(".trae/multilang.toml"),
+        "[files]\n\"*.sh.tpl\" = \"sh\"\n\n[overrides]\ntodo = \"off\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("docker/Dockerfile.prod"), "FROM alpine:latest\n").unwrap();
+    std::fs::write(dir.join("entry.sh.tpl"), "set -x\n# TODO: algo\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--multilang", "--export", "out.json"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let report = std::fs::read_to_string(dir.join("out.json"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report: serde_json::Value = serde_json::from_str(&report.unwrap()).unwrap();
+    let mut found: Vec<_> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| (issue["file"].as_str().unwrap(), issue["line"].as_u64().unwrap()))
+        .collect();
+    found.sort();
+    assert_eq!(found, [("./docker/Dockerfile.prod", 1), ("./entry.sh.tpl", 1)], "{stdout}");
+}
@@ -0,0 +1,40 @@
+use trae_cli::core::analyzer::IssueSeverity;
+use trae_cli::core::multilang::MultilangRules;
+
+#[test]
+fn user_rules_extend_builtin_ones_and_are_validated() {
+    let builtin = MultilangRules::builtin();
+    for ext in ["js", "py", "go", "rb", "java", "c", "cpp"] {
+        assert!(builtin.covers(ext), "missing built-in rules for {ext}");
+    }
+    let issues = builtin.check("js", "./a.js", "console.log(1)\n// console.log(2)\ndebugger;\n");
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].description, "console.log en archivo JS línea 1: console.log(1)");
+    assert_eq!(issues[1].line, Some(3));
+    assert!(matches!(issues[1].severity, IssueSeverity::Warning));
+
+    let rules = MultilangRules::parse(
+        r#"
+[[js]]
+pattern = 'alert\('
+message = "alert en JS"
+severity = "warning"
+
+[[kt]]
+pattern = 'println\('
+message = "println en Kotlin"
+"#,
+    )
+    .unwrap();
+    let issues = rules.check("js", "./a.js", "console.log(1)\nalert(2)\n");
+    assert_eq!(issues.len(), 2, "user rules are added to the built-in ones");
+    assert_eq!(issues[1].description, "alert en JS línea 2: alert(2)");
+    let issues = rules.check("kt", "./Main.kt", "  println(\"x\")\n// println(\"y\")\n");
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(issues[0].severity, IssueSeverity::Info));
+
+    let err = MultilangRules::parse("[[js]]\npattern = 'alert('\nmessage = 'x'\n").unwrap_err();
+    assert!(format!("{err:#}").contains("Regla 1 de [js]"), "{err:#}");
+    let err = MultilangRules::parse("[[js]]\npattern = 'a'\nmessage = 'x'\nseverity = 'fatal'\n");
+    assert!(err.is_err());
+}
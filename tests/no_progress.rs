@@ -0,0 +1,34 @@
+use clap::Parser;
+use std::process::Command;
+use trae_cli::cli::TraeCli;
+use uuid::Uuid;
+
+#[test]
+fn no_progress_hides_spinners_but_keeps_the_result() {
+    let cli = TraeCli::try_parse_from(["trae", "modules", "--no-progress"]).unwrap();
+    assert!(cli.quiet);
+
+    let dir = std::env::temp_dir().join(format!("trae_no_progress_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src/net")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"quiet\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "mod net;\n").unwrap();
+    std::fs::write(dir.join("src/net/mod.rs"), "pub fn ping() {}\n").unwrap();
+    let runs = [("--no-progress", "dots"), ("--verbose", "ascii"), ("--verbose", "none")];
+    for (flag, style) in runs {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", flag, "modules"])
+            .current_dir(&dir)
+            .env("TRAE_PROGRESS_STYLE", style)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{style}: {stdout}");
+        assert!(stdout.contains("✓ net - 1 archivos"), "{style}: {stdout}");
+    }
+    std::fs::remove_dir_all(&dir).ok();
+}
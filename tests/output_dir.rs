@@ -0,0 +1,48 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+fn trae(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .arg("--no-jarvix")
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn project() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_output_dir_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "// TODO: wire the cache\npub fn f() {}\n").unwrap();
+    dir
+}
+
+#[test]
+fn reports_land_in_the_output_dir_unless_exported_elsewhere() {
+    let dir = project();
+    let scan = trae(&dir, &["--output-dir", "out/reports", "scan"]);
+    let stdout = String::from_utf8_lossy(&scan.stdout).to_string();
+    let report = dir.join("out/reports/scan-report.json").canonicalize();
+    let security = trae(&dir, &["--output-dir", "out/reports", "security", "--secrets"]);
+    let explicit = trae(&dir, &["--output-dir", "out/other", "scan", "--export", "mine.json"]);
+    let in_other = dir.join("out/other/scan-report.json").exists();
+    let mine = dir.join("mine.json").exists();
+    let security_report = std::fs::read_to_string(dir.join("out/reports/security-report.json"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    let report = report.expect("scan-report.json in the created --output-dir");
+    assert!(stdout.contains(&report.display().to_string()), "{stdout}");
+    assert!(security.status.success() || security.status.code() == Some(2));
+    let security: serde_json::Value = serde_json::from_str(&security_report.unwrap()).unwrap();
+    assert!(security["findings"].is_array(), "{security}");
+    // An explicit --export wins over --output-dir
+    assert!(explicit.status.success());
+    assert!(mine && !in_other);
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+use trae_cli::utils::ui::{overall_rows, StageSummary, StepState, StepSummary};
+
+#[test]
+fn pipeline_stages_fold_into_one_table_with_their_steps_nested() {
+    let ok: Result<(), String> = Ok(());
+    let failed: Result<(), String> = Err("2 tests fallaron".into());
+    let mut repair = StageSummary::new("Repair", Duration::from_secs(3), &ok);
+    repair.steps = vec![
+        StepSummary::success("cargo fmt", Duration::from_secs(1)),
+        StepSummary::skipped("Jarvix report"),
+    ];
+    let stages = [
+        StageSummary::new("Analyze", Duration::from_secs(2), &ok),
+        repair,
+        StageSummary::new("Test", Duration::from_secs(5), &failed),
+    ];
+    assert_eq!(
+        stages.iter().map(StageSummary::failed).collect::<Vec<_>>(),
+        [false, false, true]
+    );
+
+    let rows = overall_rows(&stages);
+    let labels: Vec<_> = rows.iter().map(|row| row.label.as_str()).collect();
+    assert_eq!(
+        labels,
+        ["Analyze", "Repair", "  cargo fmt", "  Jarvix report", "Test"]
+    );
+    assert!(matches!(&rows[4].state, StepState::Failed(d, msg)
+        if *d == Duration::from_secs(5) && msg == "2 tests fallaron"));
+}
@@ -0,0 +1,25 @@
+use trae_cli::utils::source::{panicking_call, PanicCall};
+
+const FIXTURE: &str = include_str!("fixtures/panic_calls.rs");
+
+#[test]
+fn expect_with_message_is_told_apart_from_bare_unwrap() {
+    let calls: Vec<_> = FIXTURE.lines().map(panicking_call).collect();
+    assert_eq!(
+        calls,
+        vec![
+            Some(PanicCall::Unwrap),
+            Some(PanicCall::Expect),
+            Some(PanicCall::Expect),
+            Some(PanicCall::EmptyExpect),
+            Some(PanicCall::EmptyExpect),
+            None,
+            None,
+            None,
+            Some(PanicCall::Expect),
+            None,
+            None,
+            Some(PanicCall::Unwrap),
+        ]
+    );
+}
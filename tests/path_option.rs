@@ -0,0 +1,46 @@
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+fn trae(args: &[&str]) -> Output {
+    // Run from the system temp dir so only --path can point at the project
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .arg("--no-jarvix")
+        .args(args)
+        .current_dir(std::env::temp_dir())
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn deadcode_and_modules_work_on_the_given_path() {
+    let dir = std::env::temp_dir().join(format!("trae_path_option_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src/parser")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"pathopt\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "mod parser;\nfn unused_helper() {}\n").unwrap();
+    std::fs::write(dir.join("src/parser/mod.rs"), "pub fn parse() {}\n").unwrap();
+    let path = dir.to_string_lossy().into_owned();
+
+    let deadcode = trae(&["deadcode", "--no-cache", "--format", "json", "--path", &path]);
+    assert!(deadcode.status.success(), "{}", String::from_utf8_lossy(&deadcode.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&deadcode.stdout).unwrap();
+    let dead: Vec<&str> =
+        json["dead_code"].as_array().unwrap().iter().filter_map(|d| d["name"].as_str()).collect();
+    assert!(dead.contains(&"unused_helper"), "{json}");
+
+    let modules = trae(&["modules", "--path", &path]);
+    let stdout = String::from_utf8_lossy(&modules.stdout);
+    assert!(modules.status.success(), "{}", String::from_utf8_lossy(&modules.stderr));
+    assert!(stdout.contains("parser - 1 archivos"), "{stdout}");
+    assert!(stdout.contains("pathopt v0.1.0"), "{stdout}");
+
+    let missing = dir.join("missing").to_string_lossy().into_owned();
+    for command in ["deadcode", "modules"] {
+        assert_eq!(trae(&[command, "--path", &missing]).status.code(), Some(3));
+    }
+    std::fs::remove_dir_all(&dir).ok();
+}
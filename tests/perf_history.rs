@@ -0,0 +1,77 @@
+use chrono::{Duration, Utc};
+use trae_cli::metrics::perf_history::{
+    parse_duration, PerfHistory, PerfRun, TrendDirection, MAX_ENTRIES,
+};
+
+fn run(stability: f64) -> PerfRun {
+    PerfRun {
+        timestamp: Utc::now(),
+        command: "build".to_string(),
+        stability,
+        success_rate: 1.0,
+        total_ms: 100,
+        slowest: Vec::new(),
+    }
+}
+
+#[test]
+fn history_is_bounded_and_round_trips() {
+    let mut history = PerfHistory::default();
+    for i in 0..MAX_ENTRIES + 5 {
+        history.push(run(i as f64 / 1000.0));
+    }
+    assert_eq!(history.runs.len(), MAX_ENTRIES);
+    assert_eq!(history.runs[0].stability, 0.005);
+
+    let dir = std::env::temp_dir().join(format!("trae_perf_history_{}", uuid::Uuid::new_v4()));
+    let path = dir.join(".trae").join("perf_history.json");
+    history.save(&path).expect("save");
+    assert_eq!(PerfHistory::load(&path).runs.len(), MAX_ENTRIES);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn consecutive_unstable_runs_flag_regression() {
+    let mut history = PerfHistory::default();
+    for stability in [0.95, 0.9, 0.6, 0.5] {
+        history.push(run(stability));
+    }
+    assert_eq!(history.trend(10), TrendDirection::Degrading);
+    assert!(!history.regression());
+    history.push(run(0.4));
+    assert!(history.regression());
+    history.push(run(0.9));
+    assert!(!history.regression());
+}
+
+#[test]
+fn since_window_filters_by_age_and_command() {
+    assert_eq!(parse_duration("24h"), Ok(Duration::hours(24)));
+    assert_eq!(parse_duration("7d"), Ok(Duration::days(7)));
+    assert_eq!(parse_duration("30m"), Ok(Duration::minutes(30)));
+    assert_eq!(parse_duration("2w"), Ok(Duration::weeks(2)));
+    for invalid in ["", "7", "d", "0d", "-3d", "7x", "1.5h"] {
+        assert!(parse_duration(invalid).is_err(), "{invalid}");
+    }
+
+    let mut history = PerfHistory::default();
+    let runs = [(40, "build", 1000), (3, "build", 3000), (1, "test", 500)];
+    for (days_ago, command, total_ms) in runs {
+        history.push(PerfRun {
+            timestamp: Utc::now() - Duration::days(days_ago),
+            command: command.to_string(),
+            total_ms,
+            ..run(0.8)
+        });
+    }
+    history.push(PerfRun { total_ms: 5000, ..run(0.4) });
+    let since = Some(Utc::now() - parse_duration("7d").unwrap());
+    let builds = history.window(since, Some("build"));
+    assert_eq!(builds.runs.iter().map(|r| r.total_ms).collect::<Vec<_>>(), [3000, 5000]);
+    let (avg_ms, avg_stability) = PerfHistory::averages(&builds.runs).unwrap();
+    assert_eq!(avg_ms, 4000.0);
+    assert!((avg_stability - 0.6).abs() < 1e-9);
+    assert_eq!(history.window(since, None).runs.len(), 3);
+    assert_eq!(history.window(None, Some("build")).runs.len(), 3);
+    assert!(PerfHistory::averages(&history.window(since, Some("clippy")).runs).is_none());
+}
@@ -0,0 +1,34 @@
+use std::process::Command;
+use uuid::Uuid;
+
+#[test]
+fn fmt_failure_reports_ok_false_and_exits_non_zero() {
+    let dir = std::env::temp_dir().join(format!("trae_preflight_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"unformatted\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "pub fn  answer( )->u32{42}\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "preflight", "--json"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["ok"], false);
+    let steps = report["steps"].as_array().unwrap();
+    assert_eq!(steps.len(), 4);
+    assert_eq!(steps[0]["name"], "Fmt check");
+    assert_eq!(steps[0]["status"], "failed");
+    assert!(steps[0]["duration_ms"].is_u64());
+    for step in &steps[1..] {
+        assert_eq!(step["status"], "skipped");
+        assert!(step["duration_ms"].is_null());
+    }
+}
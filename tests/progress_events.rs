@@ -0,0 +1,43 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+use trae_cli::commands::repair::RepairOptions;
+use trae_cli::ProgressEvent;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn repair_with_progress_reports_steps_and_metrics() {
+    let dir = std::env::temp_dir().join(format!("trae_events_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).expect("create project");
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .expect("write manifest");
+    fs::write(dir.join("src").join("lib.rs"), "pub fn id(x: u8) -> u8 {   x }\n")
+        .expect("write lib.rs");
+    let orig = std::env::current_dir().expect("cwd");
+    std::env::set_current_dir(&dir).expect("enter project");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let opts = RepairOptions {
+        fmt: true,
+        no_jarvix: true,
+        ..Default::default()
+    };
+    let result = trae_cli::repair_with_progress(opts, move |event| {
+        sink.lock().expect("events lock").push(event)
+    })
+    .await;
+    std::env::set_current_dir(orig).expect("restore cwd");
+    let _ = fs::remove_dir_all(dir);
+
+    assert!(result.is_ok(), "{result:?}");
+    let events = events.lock().expect("events lock");
+    assert!(matches!(
+        events.first(),
+        Some(ProgressEvent::StepStarted { label }) if label.starts_with("Detecci")
+    ));
+    assert!(events.iter().any(|e| matches!(e, ProgressEvent::StepFinished(step) if step.label == "Jarvix report")));
+    assert!(events.iter().any(|e| matches!(e, ProgressEvent::Metric { name, .. } if name == "total_repairs")));
+}
@@ -0,0 +1,27 @@
+use trae_cli::config::RunOptions;
+use trae_cli::core::crawler::{advanced_project_crawler, project_metrics};
+use uuid::Uuid;
+
+#[test]
+fn metrics_fast_path_matches_full_crawl() {
+    let root = std::env::temp_dir().join(format!("trae_project_metrics_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(root.join("src/nested/deeper")).unwrap();
+    std::fs::create_dir_all(root.join("tests")).unwrap();
+    std::fs::write(
+        root.join("src/lib.rs"),
+        "pub struct Config;\npub trait Run {}\npub fn run() {}\n#[test]\nfn runs() {}\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src/nested/mod.rs"), "fn helper() {}\nstruct Inner;\n").unwrap();
+    std::fs::write(root.join("src/nested/deeper/leaf.rs"), "fn leaf() {}\n").unwrap();
+    std::fs::write(root.join("tests/it.rs"), "#[test]\nfn it() {}\n").unwrap();
+
+    for max_depth in [None, Some(1), Some(2)] {
+        let full = advanced_project_crawler(&root, max_depth, &RunOptions::default()).metrics;
+        let fast = project_metrics(&root, max_depth, &RunOptions::default());
+        assert_eq!(fast, full, "max_depth {max_depth:?}");
+    }
+    let all = trae_cli::api::project_metrics(&root);
+    assert_eq!((all.code_files, all.total_functions, all.total_structs), (3, 4, 2));
+    std::fs::remove_dir_all(&root).ok();
+}
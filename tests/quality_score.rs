@@ -0,0 +1,88 @@
+mod common;
+
+use std::process::Command;
+use trae_cli::config::TraeConfig;
+use trae_cli::core::score::{duplication_factor, ScoreBreakdown, ScoreWeights};
+use uuid::Uuid;
+
+#[test]
+fn score_is_100_minus_penalties_plus_the_size_bonus() {
+    let defaults = ScoreWeights::default();
+    let breakdown =
+        ScoreBreakdown::compute(6, 2, 4000, 50.0, duplication_factor(4000, 6), &defaults);
+    assert_eq!(breakdown.issues_per_1k_lines, 0.5);
+    assert_eq!(breakdown.issue_penalty, 2.5);
+    assert_eq!(breakdown.dpmo, 500.0);
+    assert_eq!(breakdown.dpmo_penalty, 0.5);
+    assert_eq!(breakdown.complexity_penalty, 5.0);
+    assert_eq!(breakdown.duplication, 0.3);
+    assert_eq!(breakdown.size_bonus, 5.0);
+    let penalties = breakdown.issue_penalty
+        + breakdown.dpmo_penalty
+        + breakdown.complexity_penalty
+        + breakdown.duplication_penalty;
+    assert!((breakdown.score - (100.0 - penalties + 5.0)).abs() < 1e-9);
+    assert!((breakdown.score - 88.0).abs() < 1e-9);
+
+    // Capped penalties, clamped score
+    let noisy = ScoreBreakdown::compute(2, 50, 1000, 500.0, 0.1, &defaults);
+    assert_eq!((noisy.dpmo_penalty, noisy.complexity_penalty, noisy.size_bonus), (20.0, 15.0, 0.0));
+    assert_eq!(noisy.score, 0.0);
+    assert_eq!(ScoreBreakdown::compute(0, 3, 100, 1.0, 0.1, &defaults), ScoreBreakdown::default());
+    assert!(breakdown.lines().contains(&"Puntuación: 88.0/100".to_string()));
+}
+
+#[test]
+fn score_coefficients_come_from_the_config_and_are_validated() {
+    assert_eq!(TraeConfig::parse(common::CONFIG).unwrap().score, ScoreWeights::default());
+
+    let custom = common::config_with("score", "issues_per_1k = 1.0\nsize_bonus = 0\n");
+    let config = TraeConfig::parse(&custom).unwrap();
+    assert_eq!(config.score.issues_per_1k, 1.0);
+    assert_eq!(config.score.dpmo_cap, 20.0);
+    let lenient = ScoreBreakdown::compute(6, 2, 4000, 50.0, 0.3, &config.score);
+    assert_eq!((lenient.issue_penalty, lenient.size_bonus), (0.5, 0.0));
+    assert!((lenient.score - 85.0).abs() < 1e-9);
+    assert!(lenient.lines().last().unwrap().contains("issues/1k ×1,"));
+
+    for (score, expected) in [
+        ("dpmo_divisor = 0", "dpmo_divisor debe ser mayor que 0"),
+        ("duplication = -2.0", "duplication debe ser un número >= 0"),
+        ("size_bonus = 150", "size_bonus no puede superar 100"),
+        ("unwrap_weight = 3", "Claves válidas en [score]: issues_per_1k"),
+    ] {
+        let config = common::config_with("score", &format!("{score}\n"));
+        let err = TraeConfig::parse(&config).unwrap_err();
+        assert!(err.to_string().contains(expected), "{score}: {err}");
+    }
+}
+
+#[test]
+fn analyze_explain_prints_and_caches_the_breakdown() {
+    let dir = std::env::temp_dir().join(format!("trae_score_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"scored\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "pub fn answer() -> u8 {\n    42\n}\n").unwrap();
+    let run = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "analyze", "--explain"])
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    for cached in [false, true] {
+        let stdout = run();
+        assert_eq!(stdout.contains("Usando cache"), cached, "{stdout}");
+        assert!(stdout.contains("Issues/1k líneas: 0.00 → -0.00"), "{stdout}");
+        assert!(stdout.contains("Duplicación: 0.10 → -3.00"), "{stdout}");
+        assert!(stdout.contains("Puntuación: 97.0/100"), "{stdout}");
+    }
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+fn release(dir: &Path, target: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "release", "--no-tests"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .env("CARGO_NET_OFFLINE", "true")
+        .env("CARGO_TARGET_DIR", target)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn resume_skips_passed_stages_until_the_sources_change() {
+    let dir = std::env::temp_dir().join(format!("trae_release_{}", Uuid::new_v4()));
+    let target = dir.with_extension("target");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::create_dir_all(dir.join("helper/src")).unwrap();
+    // A path dependency without a version: fmt and clippy pass, `cargo package` fails
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"rr\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+         [dependencies]\nhelper = { path = \"helper\" }\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("helper/Cargo.toml"),
+        "[package]\nname = \"helper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("helper/src/lib.rs"), "pub fn one() -> u8 {\n    1\n}\n").unwrap();
+    fs::write(dir.join("src/lib.rs"), "pub fn two() -> u8 {\n    helper::one() + 1\n}\n").unwrap();
+
+    let first = release(&dir, &target, &[]);
+    let stdout = String::from_utf8_lossy(&first.stdout);
+    assert!(!first.status.success(), "{stdout}");
+    assert!(stdout.contains("--resume"), "{stdout}");
+    let state = fs::read_to_string(dir.join(".trae/release-state.json")).unwrap();
+    assert!(state.contains("fmt -- --check") && !state.contains("package"), "{state}");
+
+    let resumed = release(&dir, &target, &["--resume"]);
+    let stdout = String::from_utf8_lossy(&resumed.stdout);
+    assert!(!resumed.status.success(), "{stdout}");
+    assert!(stdout.contains("Fmt check (reanudado)"), "{stdout}");
+    assert!(stdout.contains("2 etapas omitidas por --resume"), "{stdout}");
+
+    let forced = release(&dir, &target, &["--resume", "--force-all"]);
+    let stdout = String::from_utf8_lossy(&forced.stdout);
+    assert!(!stdout.contains("reanudado"), "{stdout}");
+
+    fs::write(dir.join("src/lib.rs"), "pub fn two() -> u8 {\n    helper::one() * 2\n}\n").unwrap();
+    let changed = release(&dir, &target, &["--resume"]);
+    let stdout = String::from_utf8_lossy(&changed.stdout);
+    assert!(stdout.contains("Checkpoint invalidado"), "{stdout}");
+    assert!(!stdout.contains("reanudado"), "{stdout}");
+
+    fs::remove_dir_all(&dir).ok();
+    fs::remove_dir_all(&target).ok();
+}
@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Method, Response, Server};
+use trae_cli::config::RunOptions;
+use trae_cli::jarvix::client::JarvixClient;
+use trae_cli::jarvix::jobs::{JobManager, JobSpec, JobStatus};
+
+/// Jobs finish on their third poll, except `broken`, which fails, and
+/// `slow`, which never ends.
+/// Returns the most jobs that were ever running at once.
+fn fake_server() -> (String, Arc<Mutex<usize>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr());
+    let peak = Arc::new(Mutex::new(0));
+    let seen = Arc::clone(&peak);
+    thread::spawn(move || {
+        let mut running = HashSet::new();
+        let mut polls: HashMap<String, u32> = HashMap::new();
+        for mut request in server.incoming_requests() {
+            let body = if *request.method() == Method::Post {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let job: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let id = job["type"].as_str().unwrap().to_string();
+                running.insert(id.clone());
+                let mut peak = seen.lock().unwrap();
+                *peak = (*peak).max(running.len());
+                serde_json::json!({ "id": id })
+            } else {
+                let id = request.url().trim_start_matches("/jobs/").to_string();
+                let count = polls.entry(id.clone()).or_default();
+                *count += 1;
+                if id == "broken" {
+                    running.remove(&id);
+                    serde_json::json!({ "status": "failed", "error": "worker crashed" })
+                } else if *count >= 3 && id != "slow" {
+                    running.remove(&id);
+                    serde_json::json!({ "status": "finished", "result": [{ "description": id }] })
+                } else {
+                    serde_json::json!({ "status": "running" })
+                }
+            };
+            let _ = request.respond(Response::from_string(body.to_string()));
+        }
+    });
+    (url, peak)
+}
+
+fn jobs(names: &[&str]) -> Vec<JobSpec> {
+    names
+        .iter()
+        .map(|name| JobSpec {
+            name: name.to_string(),
+            job_type: name.to_string(),
+            data: serde_json::json!({}),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn jobs_respect_the_in_flight_limit_and_report_each_outcome() {
+    let (url, peak) = fake_server();
+    let options = RunOptions { jarvix_endpoint: Some(url), ..RunOptions::default() };
+    let client = JarvixClient::new(&options).await.unwrap().unwrap();
+
+    let manager = JobManager::new(&client, Duration::from_secs(10), Duration::from_millis(200))
+        .max_in_flight(1);
+    let mut reported = Vec::new();
+    let outcomes = manager.run(jobs(&["deps", "dead_code", "broken"]), |o| {
+        reported.push(o.name.clone())
+    });
+    let outcomes = outcomes.await;
+    assert_eq!(*peak.lock().unwrap(), 1);
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(reported, outcomes.iter().map(|o| o.name.clone()).collect::<Vec<_>>());
+    for outcome in &outcomes {
+        match outcome.name.as_str() {
+            "broken" => {
+                assert!(matches!(&outcome.status, JobStatus::Failed(e) if e.contains("crashed")))
+            }
+            name => {
+                let expected = serde_json::json!([{ "description": name }]);
+                assert_eq!(outcome.result(), Some(&expected));
+            }
+        }
+    }
+    // With one slot, whichever job ran last waited for the other two
+    assert!(outcomes.iter().any(|o| o.queued >= Duration::from_millis(400)), "{outcomes:?}");
+
+    *peak.lock().unwrap() = 0;
+    let manager = JobManager::new(&client, Duration::from_secs(10), Duration::from_millis(200))
+        .max_in_flight(2);
+    let outcomes = manager.run(jobs(&["a", "b", "c"]), |_| {}).await;
+    assert_eq!(*peak.lock().unwrap(), 2);
+    assert!(outcomes.iter().all(|o| o.result().is_some()), "{outcomes:?}");
+
+    let manager = JobManager::new(&client, Duration::from_millis(300), Duration::from_millis(100));
+    let outcomes = manager.run(jobs(&["slow"]), |_| {}).await;
+    assert_eq!(outcomes[0].status, JobStatus::TimedOut);
+    assert!(outcomes[0].to_string().starts_with("slow sin respuesta a tiempo en 0.3"));
+}
@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tiny_http::{Response, Server};
+use trae_cli::config::RunOptions;
+use trae_cli::jarvix::client::JarvixClient;
+
+#[tokio::test]
+async fn stalled_job_gives_up_at_the_timeout_and_finished_job_returns_its_result() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", server.server_addr());
+    let slow_polls = Arc::new(AtomicUsize::new(0));
+    let polls = Arc::clone(&slow_polls);
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            if request.url() == "/jobs/slow" {
+                polls.fetch_add(1, Ordering::SeqCst);
+            }
+            let body = if request.url() == "/jobs/done" {
+                r#"{"status":"finished","result":{"ok":true}}"#
+            } else {
+                r#"{"status":"running"}"#
+            };
+            let _ = request.respond(Response::from_string(body));
+        }
+    });
+    let options = RunOptions { jarvix_endpoint: Some(endpoint), ..RunOptions::default() };
+    let client = JarvixClient::new(&options).await.unwrap().unwrap();
+
+    let start = Instant::now();
+    let stalled = client.wait_for_job("stalled", ms(600), ms(100), ms(100)).await;
+    assert_eq!(stalled, None);
+    assert!(start.elapsed() < Duration::from_secs(3), "took {:?}", start.elapsed());
+
+    let done = client
+        .wait_for_job("done", Duration::from_secs(5), ms(100), ms(100))
+        .await;
+    assert_eq!(done, Some(serde_json::json!({"ok": true})));
+
+    // Waits double from 50 ms up to 400 ms: ~7 polls in 1.5 s instead of 30
+    let slow = client.wait_for_job("slow", ms(1500), ms(50), ms(400)).await;
+    assert_eq!(slow, None);
+    let polls = slow_polls.load(Ordering::SeqCst);
+    assert!((5..=10).contains(&polls), "{polls} polls");
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
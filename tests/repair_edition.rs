@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+const LIB: &str = "pub fn doubled() -> Vec<i32> {
+    let a = [1, 2];
+    a.into_iter().map(|x| x * 2).collect()
+}
+";
+
+fn repair(dir: &Path, target: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "repair", "--edition", "2021"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .env("CARGO_NET_OFFLINE", "true")
+        .env("CARGO_TARGET_DIR", target)
+        .output()
+        .unwrap()
+}
+
+fn crate_dir(build_script: Option<&str>) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_edition_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"ed\"\nversion = \"0.1.0\"\nedition = \"2018\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src/lib.rs"), LIB).unwrap();
+    if let Some(script) = build_script {
+        fs::write(dir.join("build.rs"), script).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn edition_migration_previews_applies_and_rolls_back() {
+    let dir = crate_dir(None);
+    let target = dir.with_extension("target");
+
+    let preview = repair(&dir, &target, &["--preview"]);
+    let stdout = String::from_utf8_lossy(&preview.stdout);
+    assert!(stdout.contains("+edition = \"2021\""), "{stdout}");
+    assert!(stdout.contains("+    a.iter().map(|x| x * 2).collect()"), "{stdout}");
+    assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), LIB);
+
+    let applied = repair(&dir, &target, &[]);
+    let stdout = String::from_utf8_lossy(&applied.stdout);
+    assert!(applied.status.success(), "{stdout}");
+    assert!(stdout.contains("Edición 2018 → 2021 en [package]"), "{stdout}");
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("edition = \"2021\""), "{manifest}");
+    let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("a.iter().map"), "{lib}");
+    // Already on the edition: nothing to migrate
+    let again = repair(&dir, &target, &[]);
+    assert!(String::from_utf8_lossy(&again.stdout).contains("No se encontraron issues"));
+    fs::remove_dir_all(&dir).ok();
+
+    // The build breaks once Cargo.toml says 2021: the idioms pass fails after
+    // the sources and manifest were migrated, and --rollback puts them back
+    let breaks = r#"fn main() {
+    let manifest = std::fs::read_to_string("Cargo.toml").unwrap();
+    assert!(!manifest.contains("2021"), "no 2021");
+}
+"#;
+    let dir = crate_dir(Some(breaks));
+    let failed = repair(&dir, &target, &["--rollback"]);
+    let stdout = String::from_utf8_lossy(&failed.stdout);
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    fs::remove_dir_all(&dir).ok();
+    fs::remove_dir_all(&target).ok();
+    assert!(stdout.contains("cargo fix --edition-idioms (2021)"), "{stdout}");
+    assert!(stdout.contains("revertidos 2 archivos (--rollback)"), "{stdout}");
+    assert!(manifest.contains("edition = \"2018\""), "{manifest}");
+    assert_eq!(lib, LIB);
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use trae_cli::commands::repair::git_error;
+use uuid::Uuid;
+
+const UNFORMATTED: &str = "pub fn id(x: u8) -> u8 {   x }\n";
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=trae", "-c", "user.email=trae@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn repair(dir: &Path, target: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "repair", "--fmt"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .env("CARGO_NET_OFFLINE", "true")
+        .env("CARGO_TARGET_DIR", target)
+        .env("GIT_AUTHOR_NAME", "trae")
+        .env("GIT_AUTHOR_EMAIL", "trae@example.com")
+        .env("GIT_COMMITTER_NAME", "trae")
+        .env("GIT_COMMITTER_EMAIL", "trae@example.com")
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn git_steps_preview_refuse_existing_branches_and_skip_dirty_commits() {
+    assert_eq!(
+        git_error(b"warning: ignored\nfatal: a branch named 'fix' already exists\n"),
+        "a branch named 'fix' already exists"
+    );
+    assert_eq!(git_error(b"\n  something odd\n"), "something odd");
+
+    let dir = std::env::temp_dir().join(format!("trae_repair_git_{}", Uuid::new_v4()));
+    let target = dir.with_extension("target");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"rg\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::write(dir.join(".gitignore"), "Cargo.lock\n").unwrap();
+    fs::write(dir.join("src/lib.rs"), UNFORMATTED).unwrap();
+    git(&dir, &["init", "-q", "-b", "main"]);
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-qm", "init"]);
+
+    // --dry-run prints the git commands and runs none of them
+    let args = ["--dry-run", "--git-branch", "fix", "--git-commit", "fmt it"];
+    let stdout = repair(&dir, &target, &args);
+    assert!(stdout.contains("[dry-run] git checkout -b fix"), "{stdout}");
+    assert!(stdout.contains("[dry-run] git commit -m \"fmt it\""), "{stdout}");
+    assert!(git(&dir, &["branch", "--list", "fix"]).is_empty());
+
+    // An existing branch is an error, and nothing gets committed
+    git(&dir, &["branch", "fix"]);
+    let stdout = repair(&dir, &target, &["--git-branch", "fix", "--git-commit", "fmt it"]);
+    assert!(stdout.contains("usa --git-branch-force"), "{stdout}");
+    assert_eq!(git(&dir, &["rev-list", "--count", "HEAD"]).trim(), "1");
+    git(&dir, &["checkout", "-q", "--", "."]);
+
+    let args = ["--git-branch", "fix", "--git-branch-force", "--git-commit", "fmt it"];
+    let stdout = repair(&dir, &target, &args);
+    assert_eq!(git(&dir, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(), "fix", "{stdout}");
+    assert_eq!(git(&dir, &["log", "-1", "--format=%s"]).trim(), "fmt it", "{stdout}");
+
+    // Changes the user had before the repair are not swept into its commit
+    fs::write(dir.join("src/lib.rs"), UNFORMATTED).unwrap();
+    git(&dir, &["commit", "-qam", "unformat"]);
+    fs::write(dir.join("notes.txt"), "wip\n").unwrap();
+    let args = ["--allow-dirty-repair", "--git-commit", "fmt again"];
+    let stdout = repair(&dir, &target, &args);
+    assert!(stdout.contains("(notes.txt); usa --git-allow-dirty"), "{stdout}");
+    assert_eq!(git(&dir, &["log", "-1", "--format=%s"]).trim(), "unformat");
+    git(&dir, &["checkout", "-q", "--", "."]);
+
+    let args = ["--allow-dirty-repair", "--git-commit", "fmt again", "--git-allow-dirty"];
+    let stdout = repair(&dir, &target, &args);
+    assert_eq!(git(&dir, &["log", "-1", "--format=%s"]).trim(), "fmt again", "{stdout}");
+
+    fs::remove_dir_all(&dir).ok();
+    fs::remove_dir_all(&target).ok();
+}
@@ -0,0 +1,44 @@
+use trae_cli::core::workspace::sandbox;
+use uuid::Uuid;
+
+#[test]
+fn sandbox_copies_the_workspace_and_keeps_external_path_deps() {
+    let base = std::env::temp_dir().join(format!("trae_preview_{}", Uuid::new_v4()));
+    let root = base.join("app");
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::create_dir_all(root.join("crates/inner/src")).unwrap();
+    std::fs::create_dir_all(root.join("target/debug")).unwrap();
+    std::fs::create_dir_all(base.join("shared/src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\n\
+         shared = { path = \"../shared\" }\ninner = { path = \"crates/inner\" }\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub fn app() {}\n").unwrap();
+    std::fs::write(root.join("crates/inner/Cargo.toml"), "[package]\nname = \"inner\"\n").unwrap();
+    std::fs::write(root.join("target/debug/big.rlib"), "artifact").unwrap();
+    std::fs::write(base.join("shared/Cargo.toml"), "[package]\nname = \"shared\"\n").unwrap();
+    // A link back to the root must not send the copy into a loop
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&root, root.join("src/loop")).unwrap();
+
+    let sandbox = sandbox(&root).unwrap();
+    let copy = sandbox.path().to_path_buf();
+    let manifest = std::fs::read_to_string(copy.join("Cargo.toml")).unwrap();
+    let shared = base.join("shared").canonicalize().unwrap();
+    let shared = shared.to_string_lossy().replace('\\', "/");
+
+    assert!(manifest.contains(&format!("shared = {{ path = \"{shared}\" }}")), "{manifest}");
+    assert!(manifest.contains("inner = { path = \"crates/inner\" }"), "{manifest}");
+    assert!(copy.join("src/lib.rs").is_file());
+    assert!(copy.join("crates/inner/Cargo.toml").is_file());
+    assert!(!copy.join("target").exists());
+    assert!(!copy.join("src/loop").exists());
+    // The real tree is untouched and the copy goes away with the guard
+    let original = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(original.contains("path = \"../shared\""));
+    drop(sandbox);
+    assert!(!copy.exists());
+    let _ = std::fs::remove_dir_all(&base);
+}
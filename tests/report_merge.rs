@@ -0,0 +1,116 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::process::Command;
+use trae_cli::core::report::{ExportedReport, MergedReport, SCHEMA_VERSION};
+use trae_cli::core::severity::SeverityFilter;
+use uuid::Uuid;
+
+fn issue(severity: &str, file: &str, line: usize, code: &str) -> Value {
+    json!({
+        "category": "Safety",
+        "description": format!("unwrap() en línea {line}: {code}"),
+        "severity": severity,
+        "file": file,
+        "line": line,
+        "confidence": 0.9,
+    })
+}
+
+fn report(source: &str, json: Value) -> ExportedReport {
+    ExportedReport::from_json(source, json).unwrap()
+}
+
+#[test]
+fn merging_dedupes_by_fingerprint_and_recounts() {
+    let shared = issue("Critical", "./src/lib.rs", 3, "a.unwrap()");
+    let a = json!({
+        "schema_version": 1,
+        "issues": [shared, issue("Warning", "./src/x.rs", 8, "b.unwrap()")],
+        "suggestions": [{"description": "Usar ?", "impact": "High", "effort": "Low"}],
+    });
+    // Older export without schema_version: the same critical issue, moved down
+    // a line, plus two identical unwraps that both stay
+    let b = json!({
+        "issues": [
+            issue("Critical", r".\src\lib.rs", 4, "a.unwrap()"),
+            issue("Info", "./src/y.rs", 1, "c.unwrap()"),
+            issue("Info", "./src/y.rs", 2, "c.unwrap()"),
+        ],
+        "suggestions": [{"description": "Usar ?", "impact": "High", "effort": "Low"}],
+    });
+    let merged = MergedReport::new(vec![report("a.json", a), report("b.json", b)]).unwrap();
+    let json = merged.to_json(&SeverityFilter::default());
+
+    assert_eq!(json["schema_version"], SCHEMA_VERSION);
+    assert_eq!(json["merged_from"], json!(["a.json", "b.json"]));
+    assert_eq!(json["total_issues"], 4);
+    assert_eq!(json["critical_issues"], 1);
+    assert_eq!(json["warning_issues"], 1);
+    assert_eq!(json["info_issues"], 2);
+    assert_eq!(json["duplicates_removed"], 2);
+    assert_eq!(json["suggestions"].as_array().unwrap().len(), 1);
+    // Most severe first; the first report's copy is the one kept
+    assert_eq!(json["issues"][0]["file"], "./src/lib.rs");
+    assert_eq!(json["issues"][0]["line"], 3);
+
+    let mut severity = SeverityFilter::new(Some(trae_cli::core::analyzer::IssueSeverity::Warning));
+    let mut merged = merged;
+    merged.retain_severity(&mut severity);
+    let json = merged.to_json(&severity);
+    assert_eq!((json["total_issues"].clone(), json["suppressed"].clone()), (json!(2), json!(2)));
+    assert_eq!(json["severity_threshold"], "warning");
+}
+
+#[test]
+fn incompatible_or_foreign_reports_are_rejected() {
+    let newer = ExportedReport::from_json("new.json", json!({"schema_version": 99, "issues": []}));
+    let message = format!("{:#}", newer.unwrap_err());
+    assert!(message.contains("new.json") && message.contains("schema_version 99"), "{message}");
+
+    let findings = ExportedReport::from_json("security.json", json!({"findings": []}));
+    let message = format!("{:#}", findings.unwrap_err());
+    assert!(message.contains("security.json") && message.contains("`issues`"), "{message}");
+
+    // A version this build still reads, but not the one of the other report
+    let old = report("old.json", json!({"schema_version": 0, "issues": []}));
+    let current = report("current.json", json!({"schema_version": 1, "issues": []}));
+    let message = format!("{:#}", MergedReport::new(vec![old, current]).unwrap_err());
+    assert!(message.contains("old.json") && message.contains("current.json"), "{message}");
+}
+
+#[test]
+fn cli_writes_the_merged_report_and_applies_the_gate() {
+    let dir = std::env::temp_dir().join(format!("trae_report_merge_{}", Uuid::new_v4()));
+    common::write_config(&dir, common::CONFIG);
+    let shard = |issues: Value| json!({"schema_version": 1, "issues": issues, "suggestions": []});
+    let a = shard(json!([issue("Critical", "./crates/a/src/lib.rs", 1, "x.unwrap()")]));
+    let b = shard(json!([issue("Warning", "./crates/b/src/lib.rs", 2, "y.unwrap()")]));
+    std::fs::write(dir.join("a.json"), a.to_string()).unwrap();
+    std::fs::write(dir.join("b.json"), b.to_string()).unwrap();
+    let trae = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(args)
+            .current_dir(&dir)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    };
+
+    let gated = trae(&["report", "--merge", "a.json", "b.json", "--output", "out.json", "--gate"]);
+    let merged = std::fs::read_to_string(dir.join("out.json"));
+    let stdout = trae(&["report", "--merge", "a.json", "b.json"]);
+    let passing = trae(&["--severity", "critical", "report", "--merge", "b.json", "--gate"]);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(gated.status.code(), Some(2), "{}", String::from_utf8_lossy(&gated.stderr));
+    let merged: Value = serde_json::from_str(&merged.unwrap()).unwrap();
+    assert_eq!(merged["total_issues"], 2);
+    assert_eq!(merged["critical_issues"], 1);
+
+    assert!(stdout.status.success(), "{}", String::from_utf8_lossy(&stdout.stderr));
+    let printed: Value = serde_json::from_slice(&stdout.stdout).unwrap();
+    assert_eq!(printed["issues"], merged["issues"]);
+
+    assert!(passing.status.success(), "{}", String::from_utf8_lossy(&passing.stderr));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+use trae_cli::core::baseline::fingerprint;
+use trae_cli::utils::path::portable;
+use uuid::Uuid;
+
+#[test]
+fn paths_use_forward_slashes_on_every_platform() {
+    assert_eq!(portable(Path::new(r".\src\nested\deep.rs")), "./src/nested/deep.rs");
+    assert_eq!(portable(Path::new(r"C:\work\app\src\lib.rs")), "C:/work/app/src/lib.rs");
+    assert_eq!(portable(Path::new("./src/lib.rs")), "./src/lib.rs");
+
+    // A baseline exported on Windows before paths were normalized still matches
+    let issue = |file: &str| AnalysisIssue {
+        category: "Safety".to_string(),
+        description: "unwrap() en línea 3: x.unwrap()".to_string(),
+        severity: IssueSeverity::Warning,
+        file: Some(file.to_string()),
+        line: Some(3),
+        confidence: 1.0,
+    };
+    assert_eq!(fingerprint(&issue(r".\src\lib.rs")), fingerprint(&issue("./src/lib.rs")));
+}
+
+#[test]
+fn exported_scan_has_no_backslashes() {
+    let dir = std::env::temp_dir().join(format!("trae_report_paths_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+    common::write_config(&dir, common::CONFIG);
+    std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"paths\"\nversion = \"0.1.0\"\n")
+        .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "pub mod nested;\n").unwrap();
+    std::fs::write(dir.join("src/nested/mod.rs"), "// FIXME: split this module\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--export", "out.json"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let report = std::fs::read_to_string(dir.join("out.json"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report = report.unwrap();
+    assert!(!report.contains('\\'), "{report}");
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    let files: Vec<_> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|issue| issue["file"].as_str())
+        .collect();
+    assert!(files.contains(&"./src/nested/mod.rs"), "{stdout}\n{files:?}");
+}
@@ -0,0 +1,58 @@
+use std::sync::mpsc;
+use std::thread;
+use tiny_http::{Response, Server};
+use trae_cli::config::RunOptions;
+use trae_cli::jarvix::client::JarvixClient;
+use trae_cli::metrics::collector::MetricsCollector;
+
+#[tokio::test]
+async fn metrics_go_to_the_report_url_with_headers_and_retries() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for (n, mut request) in server.incoming_requests().enumerate() {
+            let header = |name: &'static str| {
+                request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv(name))
+                    .map(|h| h.value.to_string())
+            };
+            let seen = (
+                request.url().to_string(),
+                header("Authorization"),
+                header("X-Team"),
+                header("X-TRAE-Version").is_some(),
+            );
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            tx.send((seen, body)).unwrap();
+            // The first attempt fails with a 5xx to exercise the retry.
+            let status = if n == 0 { 503 } else { 200 };
+            let _ = request.respond(Response::empty(status));
+        }
+    });
+
+    let options = RunOptions {
+        jarvix_endpoint: Some("http://127.0.0.1:9".into()),
+        report_url: Some(format!("http://{addr}/ingest")),
+        report_headers: vec!["Authorization: Token abc".into(), "X-Team:infra".into()],
+        ..RunOptions::default()
+    };
+    let client = JarvixClient::new(&options).await.unwrap().unwrap();
+    client
+        .report_build_metrics(MetricsCollector::new("build".into()))
+        .await
+        .unwrap();
+
+    let attempts: Vec<_> = rx.try_iter().collect();
+    assert_eq!(attempts.len(), 2);
+    let ((url, auth, team, versioned), body) = &attempts[1];
+    assert_eq!(url, "/ingest");
+    assert_eq!(auth.as_deref(), Some("Token abc"));
+    assert_eq!(team.as_deref(), Some("infra"));
+    assert!(versioned);
+    let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(payload["type"], "build_metrics");
+}
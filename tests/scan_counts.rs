@@ -0,0 +1,34 @@
+use trae_cli::cli::{ScanCounts, TraeCli};
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+use trae_cli::exit::ExitCode;
+
+fn issue(severity: IssueSeverity) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Code Quality".to_string(),
+        description: "FIXME".to_string(),
+        severity,
+        file: None,
+        line: None,
+        confidence: 1.0,
+    }
+}
+
+#[tokio::test]
+async fn count_only_summary_is_one_line_and_needs_count_only_for_json() {
+    let issues = [
+        issue(IssueSeverity::Critical),
+        issue(IssueSeverity::Warning),
+        issue(IssueSeverity::Warning),
+        issue(IssueSeverity::Info),
+    ];
+    let counts = ScanCounts::of(&issues, &[]);
+    assert_eq!(counts.to_string(), "critical=1 warning=2 info=1 suggestions=0");
+    assert_eq!(
+        serde_json::to_value(counts).unwrap(),
+        serde_json::json!({ "critical": 1, "warning": 2, "info": 1, "suggestions": 0 })
+    );
+    assert_eq!(
+        TraeCli::run_from(["trae", "scan", "--json"]).await,
+        ExitCode::UsageError
+    );
+}
@@ -0,0 +1,77 @@
+use std::process::Command;
+use trae_cli::core::analyzer::{group_issues, sort_issues, AnalysisIssue, IssueSeverity};
+use uuid::Uuid;
+
+fn issue(severity: IssueSeverity, category: &str, file: Option<&str>) -> AnalysisIssue {
+    AnalysisIssue {
+        category: category.to_string(),
+        description: "x".to_string(),
+        severity,
+        file: file.map(str::to_string),
+        line: Some(1),
+        confidence: 1.0,
+    }
+}
+
+/// `(heading, size)` of each group.
+fn sizes(groups: &[(String, Vec<usize>)]) -> Vec<(&str, usize)> {
+    groups.iter().map(|(heading, members)| (heading.as_str(), members.len())).collect()
+}
+
+#[test]
+fn groups_lead_with_the_most_severe_or_the_largest() {
+    let mut issues = vec![
+        issue(IssueSeverity::Info, "Code Quality", Some("./src/a.rs")),
+        issue(IssueSeverity::Warning, "Safety", Some("./src/b.rs")),
+        issue(IssueSeverity::Info, "Code Quality", Some("./src/b.rs")),
+        issue(IssueSeverity::Critical, "Code Quality", None),
+    ];
+    sort_issues(&mut issues);
+    let severity = group_issues(&issues, "severity");
+    assert_eq!(sizes(&severity), [("Critical", 1), ("Warning", 1), ("Info", 2)]);
+    let category = group_issues(&issues, "category");
+    assert_eq!(sizes(&category), [("Code Quality", 3), ("Safety", 1)]);
+    let file = group_issues(&issues, "file");
+    assert_eq!(sizes(&file), [("src/b.rs", 2), ("(sin archivo)", 1), ("src/a.rs", 1)]);
+    // Within a group issues keep the severity order
+    assert_eq!(file[0].1.iter().map(|&i| issues[i].severity.rank()).collect::<Vec<_>>(), [1, 0]);
+}
+
+#[test]
+fn scan_group_by_file_nests_the_export() {
+    let dir = std::env::temp_dir().join(format!("trae_group_by_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("src/lib.rs"),
+        "// TODO: cache\npub fn f(v: Option<u8>) -> u8 {\n    v.unwrap()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/main.rs"), "// XXX: args\nfn main() {}\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--group-by", "file", "--export", "out.json"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let report = std::fs::read_to_string(dir.join("out.json"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lib = stdout.find("📂 src/lib.rs (2)").expect(&stdout);
+    let main = stdout.find("📂 src/main.rs (1)").expect(&stdout);
+    assert!(lib < main, "{stdout}");
+    assert!(stdout.contains("🔵 Code Quality: TODO en línea 1"), "{stdout}");
+
+    let report: serde_json::Value = serde_json::from_str(&report.unwrap()).unwrap();
+    assert_eq!(report["group_by"], "file");
+    let groups: Vec<_> = report["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| (g["group"].as_str().unwrap(), g["count"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(groups, [("src/lib.rs", 2), ("src/main.rs", 1)]);
+    assert_eq!(report["groups"][0]["issues"][0]["category"], "Safety");
+    // The flat list stays for --baseline
+    assert_eq!(report["issues"].as_array().unwrap().len(), 3);
+}
@@ -0,0 +1,38 @@
+use std::path::Path;
+use trae_cli::cli::IssueDiff;
+use trae_cli::commands::watch::gitignore_matcher;
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+
+fn fixme(line: usize, text: &str) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Code Quality".to_string(),
+        description: format!("FIXME en línea {line}: // FIXME: {text}"),
+        severity: IssueSeverity::Critical,
+        file: Some("./src/lib.rs".to_string()),
+        line: Some(line),
+        confidence: 1.0,
+    }
+}
+
+#[test]
+fn watch_reports_new_and_resolved_issues_and_skips_ignored_paths() {
+    let before = [fixme(2, "roto"), fixme(3, "lento")];
+    // "roto" only moved down a line; "lento" was fixed and "nuevo" added
+    let after = [fixme(3, "roto"), fixme(7, "nuevo")];
+    let diff = IssueDiff::between(&before, &after);
+    assert_eq!(diff.introduced.len(), 1);
+    assert!(diff.introduced[0].description.contains("nuevo"));
+    assert_eq!(diff.resolved.len(), 1);
+    assert!(diff.resolved[0].description.contains("lento"));
+
+    let gitignore = std::env::temp_dir().join(format!("trae_gitignore_{}", uuid::Uuid::new_v4()));
+    std::fs::write(&gitignore, "# build output\n/dist\n*.log\nnode_modules/\n").unwrap();
+    let ignored = gitignore_matcher(&gitignore).unwrap();
+    std::fs::remove_file(&gitignore).unwrap();
+    for path in ["target/debug/trae", ".trae/cache/x.json", "dist/app.js", "logs/run.log", "web/node_modules/a.js"] {
+        assert!(ignored.is_match(Path::new(path)), "{path} should be ignored");
+    }
+    for path in ["src/lib.rs", "web/dist/app.js", "Cargo.toml"] {
+        assert!(!ignored.is_match(Path::new(path)), "{path} should be watched");
+    }
+}
@@ -0,0 +1,37 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use trae_cli::cli::TraeCli;
+use trae_cli::exit::ExitCode;
+use uuid::Uuid;
+
+fn project(lib_rs: &str, secrets_toml: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_secrets_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).expect("create project");
+    fs::create_dir_all(dir.join(".trae")).expect("create .trae");
+    fs::write(dir.join("src").join("lib.rs"), lib_rs).expect("write lib.rs");
+    fs::write(dir.join(".trae").join("secrets.toml"), secrets_toml).expect("write secrets.toml");
+    dir
+}
+
+async fn secrets_exit(dir: &Path) -> ExitCode {
+    let path = dir.to_string_lossy().into_owned();
+    TraeCli::run_from(["trae", "--no-jarvix", "security", "--secrets", "--path", &path]).await
+}
+
+// Single test: `--path` changes the process working directory.
+#[tokio::test]
+async fn secrets_file_adds_patterns_and_disables_builtins() {
+    let custom = "[[pattern]]\nname = \"Token interno ACME\"\nregex = 'acme_[0-9a-f]{8}'\nseverity = \"critical\"\n";
+    let internal = project("pub const T: &str = \"acme_deadbeef\";\n", custom);
+    assert_eq!(secrets_exit(&internal).await, ExitCode::FindingsPresent);
+
+    let disabled = project("pub fn p() { let password = \"hunter2\"; }\n", "disable = [\"password\"]\n");
+    assert_eq!(secrets_exit(&disabled).await, ExitCode::Success);
+
+    let invalid = project("", "[[pattern]]\nname = \"roto\"\nregex = '(['\nseverity = \"high\"\n");
+    assert_eq!(secrets_exit(&invalid).await, ExitCode::ToolError);
+
+    for dir in [internal, disabled, invalid] {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
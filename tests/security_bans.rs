@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+/// A fresh copy of `tests/fixtures/banned_deps`, since cargo metadata writes
+/// its Cargo.lock.
+fn fixture() -> PathBuf {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/banned_deps");
+    let root = std::env::temp_dir().join(format!("trae_bans_{}", Uuid::new_v4()));
+    for entry in walkdir::WalkDir::new(&fixture) {
+        let entry = entry.unwrap();
+        let target = root.join(entry.path().strip_prefix(&fixture).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    root
+}
+
+#[test]
+fn fail_on_ban_names_the_banned_crates_in_range() {
+    let root = fixture();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "security", "--bans", "--fail-on-ban", "--format", "lsp"])
+        .env("NO_COLOR", "1")
+        .current_dir(&root)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "{stdout}\n{stderr}");
+    // old-time 0.2.0 is outside its banned range
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+    assert!(
+        stdout.contains("left-pad 1.3.0 (unmaintained (no longer receives fixes)) vía"),
+        "{stdout}"
+    );
+    assert!(
+        stderr.contains("1 crates prohibidos en el árbol de dependencias: left-pad\n"),
+        "{stderr}"
+    );
+    std::fs::remove_dir_all(&root).ok();
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::process::Command;
+use uuid::Uuid;
+
+fn project(files: &[(&str, &str)]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("trae_secsupp_{}", Uuid::new_v4()));
+    for (file, content) in files {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"secsupp\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn diagnostics(dir: &Path, args: &[&str]) -> Vec<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "security", "--level", "low", "--format", "lsp"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let mut found: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let json: serde_json::Value = serde_json::from_str(line).unwrap();
+            let file = json["uri"].as_str().unwrap().rsplit('/').next().unwrap().to_string();
+            format!("{file}:{}", json["message"].as_str().unwrap())
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn ignore_markers_suppress_with_reason_and_are_reported_without() {
+    let dir = project(&[(
+        "src/lib.rs",
+        "// trae-security-ignore: FFI boundary, checked by the caller\n\
+         pub fn a() { unsafe {} }\n\
+         pub fn b() {} // trae-security-ignore\n\
+         pub fn c(x: Option<u8>) -> u8 { x.unwrap() } // trae-security-ignore:\n",
+    )]);
+    let reasonless = |line: usize| {
+        format!(
+            "lib.rs:La supresión en línea {line} no indica motivo; \
+             use `// trae-security-ignore: <motivo>`"
+        )
+    };
+    let expected = [
+        reasonless(3),
+        reasonless(4),
+        "lib.rs:Uso de unwrap() que puede causar panics en línea 4".to_string(),
+    ];
+    assert_eq!(diagnostics(&dir, &["--code"]), expected);
+    // Reported once when both scans run, and by the secrets scan on its own
+    assert_eq!(diagnostics(&dir, &["--code", "--secrets"]), expected);
+    assert_eq!(diagnostics(&dir, &["--secrets"]), expected[..2]);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "security", "--code", "--level", "low", "--report"])
+        .env("NO_COLOR", "1")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 hallazgos suprimidos con trae-security-ignore"), "{stdout}");
+    let note = "src/lib.rs:2 Uso de código unsafe — FFI boundary, checked by the caller";
+    assert!(stdout.contains(note), "{stdout}");
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn scan_paths_and_excludes_choose_the_scanned_files() {
+    let unsafe_fn = "pub fn f() { unsafe {} }\n";
+    let dir = project(&[
+        ("src/lib.rs", unsafe_fn),
+        ("src/generated/gen.rs", unsafe_fn),
+        ("tools/tool.rs", unsafe_fn),
+    ]);
+    let found = |args: &[&str]| -> Vec<String> {
+        let mut args = args.to_vec();
+        args.push("--code");
+        diagnostics(&dir, &args).iter().map(|d| d.split(':').next().unwrap().to_string()).collect()
+    };
+    assert_eq!(found(&[]), ["gen.rs", "lib.rs"]);
+    assert_eq!(found(&["--exclude", "src/generated/**"]), ["lib.rs"]);
+    assert_eq!(
+        found(&["--scan-path", "src", "--scan-path", "tools", "--exclude", "src/generated/**"]),
+        ["lib.rs", "tool.rs"]
+    );
+    assert_eq!(found(&["--scan-path", "tools"]), ["tool.rs"]);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "security", "--code", "--exclude", "src/[oops"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::utils::source::{cfg_test_lines, is_test_path};
+use uuid::Uuid;
+
+#[test]
+fn cfg_test_items_and_test_directories_are_test_context() {
+    let source = "fn a() {}\n#[cfg(test)]\nmod tests {\n    fn t() {\n    }\n}\nfn b() {}\n\
+                  #[cfg(test)]\nuse std::fs;\n";
+    assert_eq!(
+        cfg_test_lines(source),
+        [false, false, true, true, true, true, false, false, true]
+    );
+    assert!(is_test_path(Path::new("tests/it.rs")));
+    assert!(is_test_path(Path::new("crates/core/benches/parse.rs")));
+    assert!(!is_test_path(Path::new("src/testing.rs")));
+}
+
+#[test]
+fn ignore_tests_skips_low_findings_in_test_code_but_not_secrets() {
+    let dir = std::env::temp_dir().join(format!("trae_sectests_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::create_dir_all(dir.join("tests")).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"sectests\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("src/lib.rs"),
+        "pub fn a(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n#[cfg(test)]\nmod tests {\n    \
+         #[test]\n    fn t() {\n        \
+         super::a(Some(1)).to_string().parse::<u8>().unwrap();\n        unsafe {}\n    }\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("tests/it.rs"),
+        "const password = \"hunter22\";\n#[test]\n\
+         fn it() { std::process::Command::new(\"ls\").output().unwrap(); }\n",
+    )
+    .unwrap();
+    let diagnostics = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "security", "--code", "--secrets", "--level", "low"])
+            .args(["--format", "lsp"])
+            .args(extra)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let mut found: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| {
+                let json: serde_json::Value = serde_json::from_str(line).unwrap();
+                let file = json["uri"].as_str().unwrap().rsplit('/').next().unwrap().to_string();
+                format!("{file}:{}", json["message"].as_str().unwrap())
+            })
+            .collect();
+        found.sort();
+        found
+    };
+    let default = diagnostics(&[]);
+    assert_eq!(
+        default,
+        [
+            "it.rs:Password hardcodeado detectado en línea 1",
+            "lib.rs:Uso de código unsafe en línea 9",
+            "lib.rs:Uso de unwrap() que puede causar panics en línea 2",
+        ]
+    );
+    assert_eq!(diagnostics(&["--ignore-tests"]), default);
+
+    let all = diagnostics(&["--ignore-tests=false"]);
+    assert_eq!(all.len(), 6, "{all:#?}");
+    assert!(all.contains(&"lib.rs:Uso de unwrap() que puede causar panics en línea 8".to_string()));
+    assert!(all.contains(&"it.rs:Ejecución de comandos del sistema en línea 3".to_string()));
+    std::fs::remove_dir_all(&dir).ok();
+}
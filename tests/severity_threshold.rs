@@ -0,0 +1,130 @@
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output};
+use trae_cli::commands::security::SecuritySeverity;
+use trae_cli::config::RunOptions;
+use trae_cli::core::analyzer::{AnalysisIssue, IssueSeverity};
+use trae_cli::core::severity::SeverityFilter;
+use uuid::Uuid;
+
+
+/// One warning (`HACK`) and two infos (`TODO`), nothing critical.
+const SOURCE: &str =
+    "// HACK: until the cache lands\n// TODO: docs\n// TODO: tests\npub fn a() {}\n";
+
+fn issue(severity: IssueSeverity) -> AnalysisIssue {
+    AnalysisIssue {
+        category: "Code Quality".to_string(),
+        description: "x".to_string(),
+        severity,
+        file: None,
+        line: None,
+        confidence: 1.0,
+    }
+}
+
+fn scan(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan"])
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn threshold_filters_and_decides_what_fails() {
+    let options = RunOptions { severity: Some(IssueSeverity::Warning), ..RunOptions::default() };
+    let mut filter = SeverityFilter::from_options(&options);
+    let issues = filter.filter(vec![
+        issue(IssueSeverity::Info),
+        issue(IssueSeverity::Warning),
+        issue(IssueSeverity::Critical),
+        issue(IssueSeverity::Info),
+    ]);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(filter.suppressed(), 2);
+    assert_eq!(filter.failing(issues.iter().map(|i| &i.severity)), 2);
+    assert_eq!(
+        filter.notice().as_deref(),
+        Some("🔕 2 hallazgo(s) por debajo de --severity warning ocultos")
+    );
+    let what = filter.describe_failing("críticos", "issues");
+    assert_eq!(what, "issues de severidad warning o mayor");
+
+    // Without a threshold nothing is hidden and only critical issues fail
+    let mut none = SeverityFilter::default();
+    let issues = none.filter(vec![issue(IssueSeverity::Info), issue(IssueSeverity::Critical)]);
+    assert_eq!((issues.len(), none.suppressed(), none.notice()), (2, 0, None));
+    assert_eq!(none.failing(issues.iter().map(|i| &i.severity)), 1);
+
+    let mapped: Vec<_> = [
+        SecuritySeverity::Critical,
+        SecuritySeverity::High,
+        SecuritySeverity::Medium,
+        SecuritySeverity::Low,
+        SecuritySeverity::Info,
+    ]
+    .iter()
+    .map(|s| s.issue_severity().name())
+    .collect();
+    assert_eq!(mapped, ["critical", "critical", "warning", "info", "info"]);
+    assert_eq!(IssueSeverity::from_name("WARNING").map(|s| s.name()), Some("warning"));
+    assert!(IssueSeverity::from_name("high").is_none());
+}
+
+#[test]
+fn scan_hides_findings_below_severity_and_fails_on_the_rest() {
+    let dir = std::env::temp_dir().join(format!("trae_severity_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    common::write_config(&dir, &common::config_with("markers", "HACK = \"warning\"\n"));
+    std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n")
+        .unwrap();
+    std::fs::write(dir.join("src/lib.rs"), SOURCE).unwrap();
+
+    let all = scan(&dir, &["--count-only", "--json"]);
+    let warning = scan(&dir, &["--severity", "warning", "--count-only", "--json"]);
+    let critical = scan(&dir, &["--count-only", "--severity", "critical"]);
+    let exported = scan(&dir, &["--severity", "warning", "--export", "out.json"]);
+    let report = std::fs::read_to_string(dir.join("out.json"));
+    let bad = scan(&dir, &["--severity", "high"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let counts = |output: &Output| -> serde_json::Value {
+        serde_json::from_slice(&output.stdout).expect("counts JSON")
+    };
+    assert_eq!(all.status.code(), Some(0), "{all:?}");
+    let all = counts(&all);
+    assert_eq!((&all["warning"], &all["info"]), (&1.into(), &2.into()), "{all}");
+    assert!(all.get("suppressed").is_none(), "{all}");
+
+    // Warnings now fail the scan
+    assert_eq!(warning.status.code(), Some(2), "{warning:?}");
+    let notice = "🔕 2 hallazgo(s) por debajo de --severity warning ocultos";
+    let stderr = String::from_utf8_lossy(&warning.stderr);
+    assert!(stderr.contains(notice), "{stderr}");
+    let warning = counts(&warning);
+    assert_eq!(
+        (&warning["warning"], &warning["info"], &warning["suppressed"]),
+        (&1.into(), &0.into(), &2.into())
+    );
+
+    assert_eq!(critical.status.code(), Some(0), "{critical:?}");
+    let stdout = String::from_utf8_lossy(&critical.stdout);
+    assert_eq!(stdout.trim(), "critical=0 warning=0 info=0 suggestions=0 suppressed=3");
+
+    assert_eq!(exported.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&exported.stdout);
+    assert!(stdout.contains(notice), "{stdout}");
+    let report: serde_json::Value = serde_json::from_str(&report.unwrap()).unwrap();
+    assert_eq!(report["total_issues"], 1);
+    assert_eq!(report["info_issues"], 0);
+    assert_eq!(report["severity_threshold"], "warning");
+    assert_eq!(report["suppressed"], 2);
+
+    assert_eq!(bad.status.code(), Some(3), "usage error");
+    let stderr = String::from_utf8_lossy(&bad.stderr);
+    assert!(stderr.contains("[possible values: info, warning, critical]"), "{stderr}");
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+use uuid::Uuid;
+
+/// Answers every request with a 200 and a `/health` body carrying a version.
+fn health_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let _ = stream.read(&mut [0u8; 1024]);
+            let body = r#"{"success":true,"data":{"status":"healthy","version":"9.9.9"}}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    url
+}
+
+/// A URL nothing listens on.
+fn closed_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+fn status(dir: &Path, jarvix: &str, args: &[&str]) -> (Output, serde_json::Value) {
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["status", "--json"])
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("JARVIX_URL", jarvix)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let json = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    (output, json)
+}
+
+/// `(name, up)` of each component.
+fn components(report: &serde_json::Value) -> Vec<(String, bool)> {
+    report["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| (c["name"].as_str().unwrap().to_string(), c["up"].as_bool().unwrap()))
+        .collect()
+}
+
+#[test]
+fn status_checks_server_daemon_and_jarvix() {
+    let dir = std::env::temp_dir().join(format!("trae_status_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("config/trae")).unwrap();
+    let server = health_server();
+    let keys = format!("server_url = \"{server}\"\nrequired = [\"server\", \"daemon\"]\n");
+    let config = common::config_with("status", &keys);
+    common::write_config(&dir, &config);
+    let pid_file = dir.join("config/trae/daemon.pid");
+    let record = |pid: u32| {
+        let record = serde_json::json!({
+            "pid": pid, "port": 3001, "binary": "trae-server",
+            "version": "9.9.9", "started_at": "2026-01-01T00:00:00Z"
+        });
+        std::fs::write(&pid_file, record.to_string()).unwrap();
+    };
+
+    // This test process stands in for a running daemon
+    record(std::process::id());
+    let jarvix = closed_url();
+    let (local, local_json) = status(&dir, &jarvix, &[]);
+    let (remote, remote_json) = status(&dir, &jarvix, &["--remote"]);
+    // JARVIX down only fails the run once it is required
+    common::write_config(&dir, &config.replace("\"daemon\"]", "\"daemon\", \"jarvix\"]"));
+    let (strict, _) = status(&dir, &jarvix, &["--remote"]);
+    record(u32::MAX - 1);
+    let (stopped, stopped_json) = status(&dir, &jarvix, &[]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(local.status.code(), Some(0), "{local:?}");
+    let up = |name: &str| (name.to_string(), true);
+    assert_eq!(components(&local_json), [up("server"), up("daemon")]);
+    let server_status = &local_json["components"][0];
+    assert_eq!(server_status["version"], "9.9.9");
+    assert_eq!(server_status["url"], format!("{server}/health"));
+    assert!(server_status["latency_ms"].is_u64());
+
+    assert_eq!(remote.status.code(), Some(0), "{remote:?}");
+    let jarvix_status = &remote_json["components"][2];
+    assert_eq!(jarvix_status["name"], "jarvix");
+    assert_eq!(jarvix_status["up"], false);
+    assert_eq!(jarvix_status["required"], false);
+    assert_eq!(remote_json["ok"], true);
+
+    assert_eq!(strict.status.code(), Some(2), "{strict:?}");
+
+    assert_eq!(stopped.status.code(), Some(2), "{stopped:?}");
+    assert_eq!(components(&stopped_json), [up("server"), ("daemon".to_string(), false)]);
+    assert_eq!(stopped_json["ok"], false);
+}
+
+#[test]
+fn offline_status_sends_nothing_to_jarvix() {
+    let dir = std::env::temp_dir().join(format!("trae_status_offline_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // JARVIXSERVER is up, so only --offline can leave it unchecked
+    let jarvix = health_server();
+    let (_, online) = status(&dir, &jarvix, &["--remote"]);
+    let (_, offline) = status(&dir, &jarvix, &["--remote", "--offline"]);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let jarvix_status = |report: &serde_json::Value| report["components"][2].clone();
+    assert_eq!(jarvix_status(&online)["up"], true, "{online}");
+    let offline = jarvix_status(&offline);
+    assert_eq!(offline["name"], "jarvix");
+    assert_eq!(offline["up"], false);
+    assert_eq!(offline["detail"], "offline");
+    assert!(offline["latency_ms"].is_null());
+}
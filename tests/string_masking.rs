@@ -0,0 +1,27 @@
+use trae_cli::utils::source::{find_todos, mask_raw_and_byte_strings};
+
+const FIXTURE: &str = include_str!("fixtures/raw_strings.rs");
+
+#[test]
+fn todos_inside_raw_and_byte_strings_are_not_reported() {
+    let todos = find_todos(FIXTURE);
+    assert_eq!(
+        todos,
+        vec![
+            (1, "[TODO] real marker that must be reported".to_string()),
+            (
+                11,
+                "[FIXME] after a char literal that contains a quote".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn masking_keeps_line_numbers_and_plain_code() {
+    let masked = mask_raw_and_byte_strings(FIXTURE);
+    assert_eq!(masked.lines().count(), FIXTURE.lines().count());
+    assert!(!masked.contains("password"));
+    assert!(masked.contains("pub const SAMPLE: &str = r#\""));
+    assert!(masked.contains("'\"' // FIXME"));
+}
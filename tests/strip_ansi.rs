@@ -0,0 +1,27 @@
+use trae_cli::core::cargo::CargoOutput;
+use trae_cli::utils::ansi::strip_ansi;
+
+#[test]
+fn strips_colors_cursor_moves_and_hyperlinks() {
+    let colored = "\x1b[1m\x1b[32m   Compiling\x1b[0m demo v0.1.0\x1b[K";
+    assert_eq!(strip_ansi(colored), "   Compiling demo v0.1.0");
+    let link = "see \x1b]8;;https://doc.rust-lang.org\x1b\\E0308\x1b]8;;\x07 for more";
+    assert_eq!(strip_ansi(link), "see E0308 for more");
+    assert_eq!(strip_ansi("sin color: ñ ✅"), "sin color: ñ ✅");
+}
+
+#[test]
+fn serialized_cargo_output_has_no_escape_codes() {
+    let run = CargoOutput {
+        code: Some(101),
+        output: "\x1b[0m\x1b[1m\x1b[38;5;9merror[E0308]\x1b[0m: mismatched types\n".to_string(),
+    };
+    let json = serde_json::to_string(&run).unwrap();
+    assert!(!json.contains('\x1b') && !json.contains("\\u001b"), "{json}");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+        serde_json::json!({"code": 101, "output": "error[E0308]: mismatched types\n"})
+    );
+    // The terminal keeps its colors
+    assert!(run.output.contains('\x1b'));
+}
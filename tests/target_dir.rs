@@ -0,0 +1,21 @@
+use std::fs;
+use trae_cli::config::{writable_target_dir, RunOptions};
+use uuid::Uuid;
+
+#[test]
+fn target_dir_override_is_validated_and_passed_to_cargo() {
+    let base = std::env::temp_dir().join(format!("trae_target_{}", Uuid::new_v4()));
+    let dir = writable_target_dir(&base.join("build")).expect("writable target dir");
+    assert!(dir.is_absolute() && dir.is_dir());
+    let options = RunOptions { target_dir: Some(dir.clone()), ..RunOptions::default() };
+    assert_eq!(options.target_dir(), dir);
+    let env = options.cargo_env();
+    assert!(env.iter().any(|(key, value)| *key == "CARGO_TARGET_DIR" && *value == dir));
+
+    // A path below a regular file cannot be created
+    let file = base.join("not-a-dir");
+    fs::write(&file, "").expect("write file");
+    assert!(writable_target_dir(&file.join("target")).is_err());
+
+    let _ = fs::remove_dir_all(base);
+}
@@ -0,0 +1,27 @@
+use std::fs;
+use trae_cli::utils::path::dir_size;
+use uuid::Uuid;
+
+#[test]
+fn dir_size_sums_files_in_nested_target_tree() {
+    let target = std::env::temp_dir().join(format!("trae_target_size_{}", Uuid::new_v4()));
+    let files = [
+        ("CACHEDIR.TAG", 177),
+        ("debug/trae", 4_096),
+        ("debug/deps/libfoo-1234.rlib", 10_000),
+        ("debug/deps/libfoo-1234.rmeta", 2_500),
+        ("debug/incremental/foo-abc/s-xyz/query-cache.bin", 65_537),
+        ("release/build/foo-5678/out/generated.rs", 1),
+    ];
+    for (path, len) in files {
+        let path = target.join(path);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        fs::write(&path, vec![0u8; len]).expect("write file");
+    }
+    fs::create_dir_all(target.join("debug/empty")).expect("create empty dir");
+
+    let expected: u64 = files.iter().map(|(_, len)| *len as u64).sum();
+    assert_eq!(dir_size(&target), expected);
+
+    let _ = fs::remove_dir_all(target);
+}
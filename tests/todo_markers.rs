@@ -0,0 +1,81 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::process::Command;
+use trae_cli::core::markers::TodoMarkers;
+use trae_cli::utils::source::mask_code_and_strings;
+use uuid::Uuid;
+
+const MARKERS: &str = "HACK = \"warning\"\nSAFETY = \"info\"\nXXX = \"off\"\n";
+
+const SOURCE: &str = r#"// HACK: until the cache lands
+pub fn a() {} /* TODO */
+/// TODO-style markers are prose here
+pub fn b() {} // XXX: turned off
+// FIXME(ana): leaks the handle
+pub fn c() {}
+"#;
+
+fn config(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+/// `MARKER Severity` found on each line of `SOURCE` by `markers`.
+fn found(markers: &TodoMarkers) -> Vec<Option<String>> {
+    let comments = mask_code_and_strings(SOURCE);
+    SOURCE
+        .lines()
+        .zip(comments.lines())
+        .map(|(line, comment)| {
+            let found = markers.find(line, comment);
+            found.map(|(marker, severity, _)| format!("{marker} {severity:?}"))
+        })
+        .collect()
+}
+
+#[test]
+fn markers_come_from_the_config() {
+    let defaults = TodoMarkers::default();
+    assert_eq!(defaults.names().collect::<Vec<_>>(), ["FIXME", "XXX", "TODO"]);
+    assert_eq!(
+        found(&defaults),
+        [
+            None,
+            Some("TODO Info".to_string()),
+            None,
+            Some("XXX Warning".to_string()),
+            Some("FIXME Critical".to_string()),
+            None,
+        ]
+    );
+
+    let custom = TodoMarkers::from_config(&config(&[("HACK", "warning"), ("XXX", "off")])).unwrap();
+    assert_eq!(custom.names().collect::<Vec<_>>(), ["FIXME", "HACK", "TODO"]);
+    let custom = found(&custom);
+    assert_eq!(custom[0], Some("HACK Warning".to_string()));
+    assert_eq!(custom[3], None);
+
+    assert!(TodoMarkers::from_config(&config(&[("HACK", "urgent")])).is_err());
+    assert!(TodoMarkers::from_config(&config(&[("NO TE", "info")])).is_err());
+}
+
+#[test]
+fn scan_reports_configured_markers_with_counts() {
+    let dir = std::env::temp_dir().join(format!("trae_markers_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    common::write_config(&dir, &common::config_with("markers", MARKERS));
+    std::fs::write(dir.join("src/lib.rs"), SOURCE).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--export", "out.json"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let report = std::fs::read_to_string(dir.join("out.json"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("📝 Marcadores: FIXME 1 · HACK 1 · TODO 1"), "{stdout}");
+    let report: serde_json::Value = serde_json::from_str(&report.unwrap()).unwrap();
+    assert_eq!(report["markers"], serde_json::json!({"FIXME": 1, "HACK": 1, "TODO": 1}));
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::config::RunOptions;
+use trae_cli::core::manifest::DependencyKind;
+use trae_cli::core::unused_deps::{references, unused_dependencies};
+
+#[test]
+fn each_table_is_matched_against_the_code_that_can_use_it() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/unused_deps");
+    let unused = unused_dependencies(&fixture, &RunOptions::default()).unwrap();
+    let found: Vec<_> = unused.iter().map(|d| (d.name.as_str(), d.kind, d.line)).collect();
+    assert_eq!(
+        found,
+        [
+            ("log", DependencyKind::Normal, Some(9)),
+            ("regex", DependencyKind::Normal, Some(10)),
+            ("criterion", DependencyKind::Dev, Some(15)),
+            ("semver", DependencyKind::Build, Some(20)),
+        ]
+    );
+    assert!(references("use ::tokio_util as tu;", "tokio_util"));
+    assert!(!references("mod inner { use crate::serde::X; }", "serde"));
+    assert!(!references(r#"let s = r"regex::Regex";"#, "regex"));
+
+    let trae = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_trae"))
+            .args(["--no-jarvix", "deps", "--unused"])
+            .args(args)
+            .current_dir(&fixture)
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    };
+    let output = trae(&[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(stdout.contains("semver [build-dependencies] sin uso"), "{stdout}");
+    assert!(stdout.contains("4 dependencias posiblemente sin uso"), "{stdout}");
+    assert_eq!(trae(&["--fail-on-unused"]).status.code(), Some(2));
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+use trae_cli::config::RunOptions;
+use trae_cli::core::features::{cfg_features, unused_features, UnusedFeature};
+
+#[test]
+fn features_nothing_gates_enables_or_forwards_are_reported_with_their_line() {
+    let fixture = Path::new("tests/fixtures/unused_features");
+    let unused = unused_features(fixture, &RunOptions::default()).unwrap();
+    assert_eq!(
+        unused,
+        [
+            UnusedFeature {
+                name: "experimental".to_string(),
+                line: Some(15),
+            },
+            UnusedFeature {
+                name: "legacy-api".to_string(),
+                line: Some(14),
+            },
+        ]
+    );
+    let gated = cfg_features(r#"#[cfg_attr(feature = "a", derive(Debug))] fn f() { cfg!(feature="b"); }"#);
+    assert_eq!(gated.into_iter().collect::<Vec<_>>(), ["a", "b"]);
+}
@@ -0,0 +1,65 @@
+use std::fs;
+use std::process::Command;
+use trae_cli::core::crawler::imports_in;
+use uuid::Uuid;
+
+const SOURCE: &str = "\
+use std::collections::{HashMap, BTreeMap as Sorted};
+use std::io::{self, Write};
+use std::fmt::*;
+pub use std::path::PathBuf;
+use crate::net::{
+    Client,
+    server::Server as Listener,
+};
+use serde_json::json;
+
+fn build() -> Sorted<u8, u8> {
+    let _ = json!({});
+    let _: io::Result<()> = Ok(());
+    Sorted::new()
+}
+";
+
+#[test]
+fn flags_imported_names_that_never_appear_outside_use_statements() {
+    let imports = imports_in(SOURCE, "src/lib.rs");
+    let found: Vec<_> = imports
+        .iter()
+        .map(|i| (i.path.as_str(), i.name.as_str(), i.line, i.used))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("std::collections::HashMap", "HashMap", 1, false),
+            ("std::collections::BTreeMap", "Sorted", 1, true),
+            ("std::io", "io", 2, true),
+            ("std::io::Write", "Write", 2, false),
+            ("std::path::PathBuf", "PathBuf", 4, false),
+            ("crate::net::Client", "Client", 5, false),
+            ("crate::net::server::Server", "Listener", 5, false),
+            ("serde_json::json", "json", 9, true),
+        ]
+    );
+    assert!(imports.iter().all(|i| i.is_pub == (i.name == "PathBuf")));
+
+    // Re-exports and method-only traits like `Write` are not reported
+    let dir = std::env::temp_dir().join(format!("trae_imports_{}", Uuid::new_v4()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/lib.rs"), SOURCE).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "deadcode", "--imports", "--no-cache"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    let reported: Vec<_> = stdout.lines().filter(|l| l.contains('✗')).collect();
+    assert_eq!(reported.len(), 3, "{stdout}");
+    for path in ["std::collections::HashMap", "crate::net::Client", "crate::net::server::Server"] {
+        assert!(stdout.contains(&format!("{path} (src/lib.rs:")), "{stdout}");
+    }
+    assert!(stdout.contains("Heurístico"), "{stdout}");
+    fs::remove_dir_all(&dir).ok();
+}
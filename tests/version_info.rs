@@ -0,0 +1,29 @@
+use clap::CommandFactory;
+use trae_cli::build_info::{BUILD_DATE, GIT_HASH, LONG_VERSION, VERSION};
+use trae_cli::cli::TraeCli;
+
+#[test]
+fn version_reports_commit_and_build_date() {
+    assert_eq!(
+        TraeCli::command().render_version(),
+        format!("trae {VERSION} ({GIT_HASH} {BUILD_DATE})\n")
+    );
+    assert!(LONG_VERSION.starts_with(env!("CARGO_PKG_VERSION")));
+    assert!(GIT_HASH == "unknown" || GIT_HASH.chars().all(|c| c.is_ascii_hexdigit()));
+    let date = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    assert!(date.is_match(BUILD_DATE), "{BUILD_DATE}");
+}
+
+#[tokio::test]
+async fn version_json_lists_build_metadata() {
+    let info = trae_cli::build_info::to_json();
+    for key in ["version", "git_hash", "build_date", "rustc_version", "features"] {
+        assert!(info.get(key).is_some(), "falta {key}");
+    }
+    assert!(info["rustc_version"].as_str().unwrap().starts_with("rustc "));
+    assert!(info["features"].is_array());
+    assert_eq!(
+        TraeCli::run_from(["trae", "version", "--json"]).await,
+        trae_cli::exit::ExitCode::Success
+    );
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+use trae_cli::commands::watch::{ChangeWatcher, WatchMode};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn poll_mode_reports_mtime_changes() {
+    let dir = std::env::temp_dir().join(format!("trae_watch_poll_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("lib.rs");
+    std::fs::write(&file, "fn a() {}\n").unwrap();
+
+    let interval = Duration::from_millis(100);
+    let paths = std::slice::from_ref(&dir);
+    let mut changes = ChangeWatcher::new(paths, Duration::from_millis(50), Some(interval)).unwrap();
+    assert_eq!(changes.mode(), WatchMode::Poll(interval));
+    assert_eq!(changes.mode().to_string(), "polling cada 100ms");
+
+    // mtime granularity can be a full second on some filesystems
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    std::fs::write(&file, "fn a() {}\nfn b() {}\n").unwrap();
+    let changed = tokio::time::timeout(Duration::from_secs(10), changes.changed())
+        .await
+        .expect("polling no detectó el cambio");
+    assert!(changed.contains(&file), "{changed:?}");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+use std::process::Command;
+use trae_cli::core::workspace::find_root_from;
+use uuid::Uuid;
+
+fn write(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn package(name: &str) -> String {
+    format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n")
+}
+
+#[test]
+fn nested_package_resolves_to_the_workspace_above_it() {
+    let dir = std::env::temp_dir().join(format!("trae_ws_nested_{}", Uuid::new_v4()));
+    write(&dir.join("Cargo.toml"), &(package("app") + "\n[workspace]\nmembers = [\"crates/*\"]\n"));
+    write(&dir.join("crates/util/Cargo.toml"), &package("util"));
+    write(&dir.join("crates/util/src/lib.rs"), "");
+    write(&dir.join("standalone/Cargo.toml"), &package("standalone"));
+    let root = dir.canonicalize().unwrap();
+
+    assert_eq!(find_root_from(&dir.join("crates/util/src")), Some(root.clone()));
+    assert_eq!(find_root_from(&dir), Some(root.clone()));
+    // Not a member, but still below the topmost [workspace]
+    assert_eq!(find_root_from(&dir.join("standalone")), Some(root));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn standalone_package_is_its_own_root() {
+    let dir = std::env::temp_dir().join(format!("trae_ws_single_{}", Uuid::new_v4()));
+    write(&dir.join("lib/Cargo.toml"), &package("lib"));
+    write(&dir.join("lib/src/lib.rs"), "");
+
+    let root = dir.join("lib").canonicalize().unwrap();
+    assert_eq!(find_root_from(&dir.join("lib/src")), Some(root));
+    assert_eq!(find_root_from(&dir), None);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn scan_from_a_member_of_a_virtual_workspace_covers_every_member() {
+    let dir = std::env::temp_dir().join(format!("trae_ws_virtual_{}", Uuid::new_v4()));
+    write(&dir.join("Cargo.toml"), "[workspace]\nmembers = [\"a\", \"b\"]\nresolver = \"2\"\n");
+    for member in ["a", "b"] {
+        write(&dir.join(member).join("Cargo.toml"), &package(member));
+        write(&dir.join(member).join("src/lib.rs"), "// FIXME: unfinished\n");
+    }
+    assert_eq!(find_root_from(&dir.join("a/src")), Some(dir.canonicalize().unwrap()));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trae"))
+        .args(["--no-jarvix", "scan", "--count-only", "--json"])
+        .current_dir(dir.join("a/src"))
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    let counts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(counts["critical"], 2, "{output:?}");
+}